@@ -1,4 +1,8 @@
-use crate::{currency::BalanceLike, defi::CurrencyPair, prelude::*};
+use crate::{
+	currency::BalanceLike,
+	defi::{CurrencyPair, Rate},
+	prelude::*,
+};
 
 use frame_support::{
 	ensure,
@@ -161,6 +165,14 @@ impl<AssetId, Balance> AssetAmount<AssetId, Balance> {
 	pub fn new(asset_id: AssetId, amount: Balance) -> Self {
 		Self { asset_id, amount }
 	}
+
+	pub fn map<A, B>(
+		self,
+		asset: impl Fn(AssetId) -> A,
+		balance: impl Fn(Balance) -> B,
+	) -> AssetAmount<A, B> {
+		AssetAmount { asset_id: asset(self.asset_id), amount: balance(self.amount) }
+	}
 }
 
 /// The (expected or executed) result of a swap operation.
@@ -297,6 +309,40 @@ pub trait Amm {
 	) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError>;
 }
 
+/// Enumeration of the pools of an [`Amm`], e.g. to discover routes between assets.
+pub trait AmmPools: Amm {
+	/// Ids of at most `limit` of the existing pools.
+	fn pools(limit: u32) -> Vec<Self::PoolId>;
+
+	/// Reserves of each asset of `pool_id`.
+	fn balances(
+		pool_id: Self::PoolId,
+	) -> Result<BTreeMap<Self::AssetId, Self::Balance>, DispatchError>;
+
+	/// Amount of `in_asset_id` to be sent to `pool_id` to receive exactly `out_asset`, the
+	/// counterpart of [`Amm::spot_price`] for buys.
+	fn buy_price(
+		pool_id: Self::PoolId,
+		in_asset_id: Self::AssetId,
+		out_asset: AssetAmount<Self::AssetId, Self::Balance>,
+		calculate_with_fees: bool,
+	) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError>;
+}
+
+/// Time weighted average prices of the pools of an [`Amm`], resistant to manipulations within a
+/// block.
+pub trait AmmTwap: Amm {
+	/// Timestamps the averages are computed over.
+	type Moment;
+
+	/// Average price of each asset of `pool_id`, in the other asset, over at least the last
+	/// `window`. Fails if the pool has not been observed for long enough.
+	fn twap(
+		pool_id: Self::PoolId,
+		window: Self::Moment,
+	) -> Result<BTreeMap<Self::AssetId, Rate>, DispatchError>;
+}
+
 pub const REWARD_PERCENTAGE: u32 = 10;
 
 /// Pool Fees
@@ -452,6 +498,69 @@ pub struct PriceAggregate<PoolId, AssetId, Balance> {
 	pub spot_price: Balance, // prices based on any other stat such as TWAP goes here..
 }
 
+/// Simulated outcome of trading through a route of pools.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RouteQuote<PoolId, AssetId, Balance> {
+	/// Amount given to the first pool of the route.
+	pub in_asset: AssetAmount<AssetId, Balance>,
+	/// Amount received from the last pool of the route.
+	pub out_asset: AssetAmount<AssetId, Balance>,
+	/// Trades against each pool of the route, in order.
+	pub hops: Vec<RouteHop<PoolId, AssetId, Balance>>,
+}
+
+impl<PoolId: Clone, AssetId, Balance> RouteQuote<PoolId, AssetId, Balance> {
+	/// Pools traded against, in order.
+	pub fn route(&self) -> Vec<PoolId> {
+		self.hops.iter().map(|hop| hop.pool_id.clone()).collect()
+	}
+}
+
+impl<PoolId, AssetId, Balance> RouteQuote<PoolId, AssetId, Balance> {
+	pub fn map<P, A, B>(
+		self,
+		pool: impl Fn(PoolId) -> P,
+		asset: impl Fn(AssetId) -> A,
+		balance: impl Fn(Balance) -> B,
+	) -> RouteQuote<P, A, B> {
+		RouteQuote {
+			in_asset: self.in_asset.map(&asset, &balance),
+			out_asset: self.out_asset.map(&asset, &balance),
+			hops: self.hops.into_iter().map(|hop| hop.map(&pool, &asset, &balance)).collect(),
+		}
+	}
+}
+
+/// Simulated trade against a single pool of a route.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RouteHop<PoolId, AssetId, Balance> {
+	pub pool_id: PoolId,
+	/// Amount given to the pool.
+	pub in_asset: AssetAmount<AssetId, Balance>,
+	/// Amount received from the pool.
+	pub out_asset: AssetAmount<AssetId, Balance>,
+	/// Fee charged by the pool.
+	pub fee: AssetAmount<AssetId, Balance>,
+}
+
+impl<PoolId, AssetId, Balance> RouteHop<PoolId, AssetId, Balance> {
+	pub fn map<P, A, B>(
+		self,
+		pool: impl Fn(PoolId) -> P,
+		asset: impl Fn(AssetId) -> A,
+		balance: impl Fn(Balance) -> B,
+	) -> RouteHop<P, A, B> {
+		RouteHop {
+			pool_id: pool(self.pool_id),
+			in_asset: self.in_asset.map(&asset, &balance),
+			out_asset: self.out_asset.map(&asset, &balance),
+			fee: self.fee.map(&asset, &balance),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::dex::{Fee, FeeConfig};
@@ -55,6 +55,7 @@ pub mod defi;
 pub mod dex;
 pub mod fnft;
 pub mod governance;
+pub mod liquid_staking;
 pub mod oracle;
 pub mod prelude;
 pub mod privilege;
@@ -0,0 +1,173 @@
+use frame_support::pallet_prelude::*;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Era of the relaychain staking.
+pub type EraIndex = u32;
+
+/// Staking currency becoming free at the start of `era`.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Unlocking<Balance> {
+	pub value: Balance,
+	pub era: EraIndex,
+}
+
+/// Liquid staking position of an account.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LiquidStakingPosition<Balance> {
+	/// Liquid currency held by the account.
+	pub liquid_balance: Balance,
+	/// Value of `liquid_balance` in the staking currency at the current exchange rate.
+	pub staked_value: Balance,
+	/// Staking currency unstaked through the relaychain, claimable once its era is reached.
+	pub unlockings: Vec<Unlocking<Balance>>,
+	/// Liquid currency waiting to be matched through the matching pool.
+	pub fast_unstake_request: Balance,
+}
+
+/// Ledger of a derivative account on the relaychain.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DerivativeLedger<Balance> {
+	pub derivative_index: u16,
+	/// `active` plus all the `unlocking` balances.
+	pub total: Balance,
+	/// Bonded and earning rewards.
+	pub active: Balance,
+	pub unlocking: Vec<Unlocking<Balance>>,
+}
+
+/// Outcome of staking an amount of the staking currency.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StakeSimulation<Balance> {
+	/// Liquid currency minted to the staker.
+	pub liquid_amount: Balance,
+	/// Staking currency kept as reserves.
+	pub reserves: Balance,
+	/// Staking currency paid for the XCM fees.
+	pub xcm_fees: Balance,
+}
+
+/// Outcome of unstaking an amount of the liquid currency.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct UnstakeSimulation<Balance> {
+	/// Liquid currency burned.
+	pub liquid_amount: Balance,
+	/// Staking currency claimable at `unlock_era` when unstaking through the relaychain.
+	pub staking_amount: Balance,
+	pub unlock_era: EraIndex,
+	/// Liquid currency charged when the whole amount is matched through the matching pool.
+	pub fast_unstake_fee: Balance,
+}
+
+/// Kind of an XCM request sent to the relaychain for a derivative account.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum XcmRequestKind {
+	Bond,
+	BondExtra,
+	Unbond,
+	Rebond,
+	WithdrawUnbonded,
+	Nominate,
+	Chill,
+	SetPayee,
+}
+
+/// Progress of an XCM request sent to the relaychain.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum XcmRequestState<BlockNumber> {
+	/// Waiting for its response.
+	Flying,
+	/// Failed, sent again at the block.
+	RetryAt(BlockNumber),
+	/// Timed out, or failed without timeout for its kind, waiting for its response or to be
+	/// resolved by governance.
+	Stuck,
+}
+
+/// XCM request waiting for its response or to be resolved.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PendingXcmRequest<Balance, BlockNumber> {
+	pub query_id: u64,
+	pub kind: XcmRequestKind,
+	pub derivative_index: u16,
+	/// Staking currency bonded, unbonded or rebonded, zero for the other kinds.
+	pub amount: Balance,
+	/// Times the request was sent.
+	pub attempts: u32,
+	/// Block at which the request was last sent.
+	pub sent_at: BlockNumber,
+	pub state: XcmRequestState<BlockNumber>,
+}
+
+impl<Balance> Unlocking<Balance> {
+	pub fn map<B>(self, f: impl Fn(Balance) -> B) -> Unlocking<B> {
+		Unlocking { value: f(self.value), era: self.era }
+	}
+}
+
+impl<Balance> LiquidStakingPosition<Balance> {
+	pub fn map<B>(self, f: impl Fn(Balance) -> B) -> LiquidStakingPosition<B> {
+		LiquidStakingPosition {
+			liquid_balance: f(self.liquid_balance),
+			staked_value: f(self.staked_value),
+			unlockings: self.unlockings.into_iter().map(|unlocking| unlocking.map(&f)).collect(),
+			fast_unstake_request: f(self.fast_unstake_request),
+		}
+	}
+}
+
+impl<Balance> DerivativeLedger<Balance> {
+	pub fn map<B>(self, f: impl Fn(Balance) -> B) -> DerivativeLedger<B> {
+		DerivativeLedger {
+			derivative_index: self.derivative_index,
+			total: f(self.total),
+			active: f(self.active),
+			unlocking: self.unlocking.into_iter().map(|unlocking| unlocking.map(&f)).collect(),
+		}
+	}
+}
+
+impl<Balance> StakeSimulation<Balance> {
+	pub fn map<B>(self, f: impl Fn(Balance) -> B) -> StakeSimulation<B> {
+		StakeSimulation {
+			liquid_amount: f(self.liquid_amount),
+			reserves: f(self.reserves),
+			xcm_fees: f(self.xcm_fees),
+		}
+	}
+}
+
+impl<Balance> UnstakeSimulation<Balance> {
+	pub fn map<B>(self, f: impl Fn(Balance) -> B) -> UnstakeSimulation<B> {
+		UnstakeSimulation {
+			liquid_amount: f(self.liquid_amount),
+			staking_amount: f(self.staking_amount),
+			unlock_era: self.unlock_era,
+			fast_unstake_fee: f(self.fast_unstake_fee),
+		}
+	}
+}
+
+impl<Balance, BlockNumber> PendingXcmRequest<Balance, BlockNumber> {
+	pub fn map<B>(self, f: impl Fn(Balance) -> B) -> PendingXcmRequest<B, BlockNumber> {
+		PendingXcmRequest {
+			query_id: self.query_id,
+			kind: self.kind,
+			derivative_index: self.derivative_index,
+			amount: f(self.amount),
+			attempts: self.attempts,
+			sent_at: self.sent_at,
+			state: self.state,
+		}
+	}
+}
@@ -3,9 +3,14 @@ use crate::{
 	defi::{CurrencyPair, Ratio},
 };
 use frame_support::{dispatch::DispatchError, pallet_prelude::*};
+use sp_runtime::Permill;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 // block timestamped value
 #[derive(Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Price<PriceValue, BlockNumber> {
 	/// value
 	pub price: PriceValue,
@@ -27,6 +32,50 @@ pub struct RewardTracker<Balance, Timestamp> {
 	pub total_reward_weight: Balance,
 }
 
+/// Stake and rewards of an oracle, keyed by its signing key.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OracleStatus<AccountId, Balance, BlockNumber> {
+	/// Controller which set the signing key, if it is still set.
+	pub controller: Option<AccountId>,
+	pub stake: Balance,
+	/// Stake at risk of being slashed for answers not aggregated yet.
+	pub answer_in_transit: Balance,
+	/// Stake declared for withdrawal and the block from which it can be reclaimed.
+	pub withdrawal: Option<(Balance, BlockNumber)>,
+	/// Total rewards paid for the answers of the signing key.
+	pub rewards: Balance,
+	/// Agreement of the answers with the aggregated prices, scaled by participation and averaged
+	/// over the assets answered for.
+	pub reputation: Permill,
+}
+
+/// `OracleStatus` served by version 1 of the oracle runtime API, before reputations were tracked.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, TypeInfo, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OracleStatusV1<AccountId, Balance, BlockNumber> {
+	pub controller: Option<AccountId>,
+	pub stake: Balance,
+	pub answer_in_transit: Balance,
+	pub withdrawal: Option<(Balance, BlockNumber)>,
+	pub rewards: Balance,
+}
+
+impl<AccountId, Balance, BlockNumber> From<OracleStatusV1<AccountId, Balance, BlockNumber>>
+	for OracleStatus<AccountId, Balance, BlockNumber>
+{
+	fn from(status: OracleStatusV1<AccountId, Balance, BlockNumber>) -> Self {
+		Self {
+			controller: status.controller,
+			stake: status.stake,
+			answer_in_transit: status.answer_in_transit,
+			withdrawal: status.withdrawal,
+			rewards: status.rewards,
+			reputation: Permill::zero(),
+		}
+	}
+}
+
 /// An object that is able to provide an asset price.
 /// Important: the current price-feed is providing prices in USDT only.
 pub trait Oracle {
@@ -118,3 +167,17 @@ pub trait Oracle {
 		amount: Self::Balance,
 	) -> Result<Self::Balance, DispatchError>;
 }
+
+/// Source of the rates at which derivative assets, e.g. liquid staking vouchers, redeem for their
+/// underlying assets.
+pub trait ExchangeRateProvider<AssetId> {
+	/// The underlying asset of `derivative` and the amount of it one unit of `derivative` is
+	/// worth, if `derivative` has an exchange rate.
+	fn exchange_rate(derivative: AssetId) -> Option<(AssetId, Ratio)>;
+}
+
+impl<AssetId> ExchangeRateProvider<AssetId> for () {
+	fn exchange_rate(_derivative: AssetId) -> Option<(AssetId, Ratio)> {
+		None
+	}
+}
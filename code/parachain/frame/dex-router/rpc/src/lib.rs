@@ -0,0 +1,176 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::{
+	defi::CurrencyPair,
+	dex::{AssetAmount, RouteQuote},
+};
+use core::{fmt::Display, str::FromStr};
+use dex_router_runtime_api::DexRouterRuntimeApi;
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use sp_std::{sync::Arc, vec::Vec};
+
+#[rpc(client, server)]
+pub trait DexRouterApi<BlockHash, PoolId, AssetId, Balance>
+where
+	PoolId: FromStr + Display,
+	AssetId: FromStr + Display,
+	Balance: FromStr + Display,
+{
+	#[method(name = "dexRouter_getRoute")]
+	fn get_route(
+		&self,
+		asset_pair: CurrencyPair<SafeRpcWrapper<AssetId>>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<(Vec<SafeRpcWrapper<PoolId>>, bool)>>;
+
+	#[method(name = "dexRouter_allRoutes")]
+	fn all_routes(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(CurrencyPair<SafeRpcWrapper<AssetId>>, Vec<SafeRpcWrapper<PoolId>>)>>;
+
+	#[method(name = "dexRouter_quoteSwap")]
+	fn quote_swap(
+		&self,
+		in_asset: AssetAmount<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		out_asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<
+		Option<
+			RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		>,
+	>;
+
+	#[method(name = "dexRouter_quoteBuy")]
+	fn quote_buy(
+		&self,
+		in_asset_id: SafeRpcWrapper<AssetId>,
+		out_asset: AssetAmount<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		at: Option<BlockHash>,
+	) -> RpcResult<
+		Option<
+			RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		>,
+	>;
+}
+
+pub struct DexRouter<C, Block> {
+	client: Arc<C>,
+	_marker: sp_std::marker::PhantomData<Block>,
+}
+
+impl<C, M> DexRouter<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, PoolId, AssetId, Balance>
+	DexRouterApiServer<<Block as BlockT>::Hash, PoolId, AssetId, Balance>
+	for DexRouter<C, (Block, PoolId, AssetId, Balance)>
+where
+	Block: BlockT,
+	PoolId: Send + Sync + 'static + Codec + FromStr + Display,
+	AssetId: Send + Sync + 'static + Codec + FromStr + Display,
+	Balance: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: DexRouterRuntimeApi<Block, PoolId, AssetId, Balance>,
+{
+	fn get_route(
+		&self,
+		asset_pair: CurrencyPair<SafeRpcWrapper<AssetId>>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<(Vec<SafeRpcWrapper<PoolId>>, bool)>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.get_route(at, asset_pair);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn all_routes(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(CurrencyPair<SafeRpcWrapper<AssetId>>, Vec<SafeRpcWrapper<PoolId>>)>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.all_routes(at);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn quote_swap(
+		&self,
+		in_asset: AssetAmount<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		out_asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<
+		Option<
+			RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		>,
+	> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.quote_swap(at, in_asset, out_asset_id);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn quote_buy(
+		&self,
+		in_asset_id: SafeRpcWrapper<AssetId>,
+		out_asset: AssetAmount<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<
+		Option<
+			RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		>,
+	> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.quote_buy(at, in_asset_id, out_asset);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+}
@@ -0,0 +1,45 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::{
+	defi::CurrencyPair,
+	dex::{AssetAmount, RouteQuote},
+};
+use sp_std::vec::Vec;
+
+// DEX Router Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	pub trait DexRouterRuntimeApi<PoolId, AssetId, Balance>
+	where
+		PoolId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Route pinned for `asset_pair` and whether it has to be used in reverse direction.
+		fn get_route(
+			asset_pair: CurrencyPair<SafeRpcWrapper<AssetId>>,
+		) -> Option<(Vec<SafeRpcWrapper<PoolId>>, bool)>;
+
+		/// All routes pinned by governance, keyed by the pair they were set for.
+		fn all_routes() -> Vec<(CurrencyPair<SafeRpcWrapper<AssetId>>, Vec<SafeRpcWrapper<PoolId>>)>;
+
+		/// Simulates swapping `in_asset` for `out_asset_id` through the route the router would
+		/// use, without executing it. `None` if there is no route between the assets.
+		fn quote_swap(
+			in_asset: AssetAmount<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+			out_asset_id: SafeRpcWrapper<AssetId>,
+		) -> Option<RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>>;
+
+		/// Simulates buying exactly `out_asset` with `in_asset_id` through the route the router
+		/// would use, quoting the input it requires fees included, without executing it. `None` if
+		/// there is no route between the assets.
+		fn quote_buy(
+			in_asset_id: SafeRpcWrapper<AssetId>,
+			out_asset: AssetAmount<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
+		) -> Option<RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>>;
+	}
+}
@@ -196,6 +196,43 @@ where
 	(currency_pair, dex_route)
 }
 
+/// Creates `pools` funded pools over a cycle of four assets, starting at asset 200, so that
+/// routes between them branch at every hop.
+fn create_cycle_pools<T>(pools: u32)
+where
+	T: pallet_dex_router::Config + pallet_pablo::Config,
+	<T as pallet_pablo::Config>::Balance: From<u128>,
+	<T as pallet_pablo::Config>::AssetId: From<u128>,
+{
+	let unit = 1_000_000_000_000_u128;
+	let owner: <T as frame_system::Config>::AccountId = whitelisted_caller();
+	for pool in 0..pools {
+		let x: <T as pallet_pablo::Config>::AssetId = (200 + (pool % 4) as u128).into();
+		let y: <T as pallet_pablo::Config>::AssetId = (200 + ((pool + 1) % 4) as u128).into();
+		let config = PoolInitConfiguration::DualAssetConstantProduct {
+			owner: owner.clone(),
+			fee: Permill::zero(),
+			assets_weights: dual_asset_pool_weights::<T>(x, Permill::from_percent(50), y),
+		};
+		let pool_id =
+			pallet_pablo::Pallet::<T>::do_create_pool(config, Some((2000 + pool as u128).into()))
+				.expect("create pool failed");
+		let amount = 1000 * unit;
+		<T as pallet_pablo::Config>::Assets::mint_into(x, &owner, amount.into())
+			.expect("mint x failed");
+		<T as pallet_pablo::Config>::Assets::mint_into(y, &owner, amount.into())
+			.expect("mint y failed");
+		<pallet_pablo::Pallet<T> as Amm>::add_liquidity(
+			&owner,
+			pool_id,
+			BTreeMap::from([(x, amount.into()), (y, amount.into())]),
+			0_u128.into(),
+			false,
+		)
+		.expect("add_liquidity failed");
+	}
+}
+
 benchmarks! {
 	impl_benchmark_test_suite!(DexRouter, crate::mock::new_test_ext(), crate::mock::Test);
 
@@ -230,6 +267,20 @@ benchmarks! {
 		// exchange 1000 PICA via route
 	} : _(origin, AssetAmount::new(pica_, (1000_u128 * unit).into()), AssetAmount::new(usdt, 0_u128.into()))
 
+	swap_split {
+		let p in 1 .. T::MaxSplitParts::get();
+		let unit = 1_000_000_000_000_u128;
+		let pica_amount =  2000_u128 * unit;
+		let _ = create_pools_route::<T>();
+		let owner : <T as frame_system::Config>::AccountId= whitelisted_caller();
+		let origin = RawOrigin::Signed(owner.clone());
+		let pica : <T as pallet_pablo::Config>::AssetId = 100_u128.into();
+		let pica_ : <T as pallet::Config>::AssetId = 100_u128.into();
+		let usdt : <T as pallet::Config>::AssetId = 104_u128.into();
+		<T as pallet_pablo::Config>::Assets::mint_into(pica, &owner, pica_amount.into()).expect("Mint pica failed");
+		// exchange 1000 PICA in `p` parts via computed routes
+	} : _(origin, AssetAmount::new(pica_, (1000_u128 * unit).into()), AssetAmount::new(usdt, 0_u128.into()), p)
+
 	buy {
 		let unit = 1_000_000_000_000_u128;
 		let usdc_amount =  20_u128 * 100 * unit; // 1 pica = 20 usdc based on liquidity added while pool creation
@@ -267,4 +318,35 @@ benchmarks! {
 		pallet_dex_router::Pallet::<T>::add_liquidity(origin.clone().into(), BTreeMap::from([(currency_pair.base, usdc_amount.into()), (currency_pair.quote, usdt_amount.into())]), 0_u128.into(), false).expect("add_liquidity failed");
 		// remove 1 lp_token
 	} : _(origin, 1_u128.into(), BTreeMap::from([(currency_pair.base, 0.into()), (currency_pair.quote, 0.into())]))
+
+	zap_in {
+		let unit = 1_000_000_000_000_u128;
+		let _ = create_pools_route::<T>();
+		let owner : <T as frame_system::Config>::AccountId= whitelisted_caller();
+		let origin = RawOrigin::Signed(owner.clone());
+		let usdt : <T as pallet_pablo::Config>::AssetId = 104_u128.into();
+		let usdt_ : <T as pallet::Config>::AssetId = 104_u128.into();
+		<T as pallet_pablo::Config>::Assets::mint_into(usdt, &owner, (100_u128 * unit).into()).expect("Mint usdt failed");
+		// add 100 USDT as liquidity to the PICA/KSM pool, routed through all the other pools
+	} : _(origin, 0_u128.into(), AssetAmount::new(usdt_, (100_u128 * unit).into()), 0_u128.into(), false)
+
+	// route search over `p` pools pricing `c` hops, the asset searched for being held by no pool
+	route_search {
+		let p in 2 .. T::MaxRoutingPools::get();
+		let c in 1 .. T::MaxRouteCandidates::get();
+		let unit = 1_000_000_000_000_u128;
+		create_cycle_pools::<T>(p);
+		let in_asset = AssetAmount::new(200_u128.into(), unit.into());
+	} : {
+		let _ = DexRouter::<T>::best_swap_path_within(in_asset, 299_u128.into(), c);
+	}
+
+	zap_out {
+		let unit = 1_000_000_000_000_u128;
+		let _ = create_pools_route::<T>();
+		let owner : <T as frame_system::Config>::AccountId= whitelisted_caller();
+		let origin = RawOrigin::Signed(owner);
+		let usdt : <T as pallet::Config>::AssetId = 104_u128.into();
+		// remove 1 lp_token of the PICA/KSM pool into USDT, routed through all the other pools
+	} : _(origin, 0_u128.into(), unit.into(), AssetAmount::new(usdt, 0_u128.into()), false)
 }
@@ -1,7 +1,8 @@
 //! # DEX Router Pallet
 //!
-//! Is used to add route to DEX for given asset_id's pair.
-//! It is required to have permissioned approval of routes.
+//! Is used to route trades of given asset_id's pair through DEX pools.
+//! Routes are computed over all existing pools, unless a route for the pair was pinned through
+//! permissioned approval.
 #![allow(clippy::let_unit_value)]
 #![cfg_attr(not(test), warn(clippy::disallowed_methods, clippy::indexing_slicing))] // allow in tests
 #![warn(clippy::unseparated_literal_suffix, clippy::disallowed_types)]
@@ -10,13 +11,13 @@
 use composable_traits::defi::CurrencyPair;
 pub use pallet::*;
 
-// #[cfg(test)]
-// mod mock;
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
-// #[cfg(any(feature = "runtime-benchmarks", test))]
-// mod benchmarking;
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
 
 pub mod weights;
 
@@ -29,12 +30,12 @@ pub mod pallet {
 	use composable_support::math::safe::SafeArithmetic;
 	use composable_traits::{
 		defi::CurrencyPair,
-		dex::{Amm, AssetAmount, DexRoute, DexRouter, SwapResult},
+		dex::{Amm, AmmPools, AssetAmount, DexRoute, DexRouter, RouteHop, RouteQuote, SwapResult},
 	};
 	use core::fmt::Debug;
 	use frame_support::{pallet_prelude::*, transactional, PalletId};
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
-	use sp_arithmetic::Permill;
+	use sp_arithmetic::{helpers_128bit::multiply_by_rational_with_rounding, Permill, Rounding};
 	use sp_runtime::{
 		traits::{CheckedAdd, One, Zero},
 		DispatchResult,
@@ -64,10 +65,21 @@ pub mod pallet {
 			+ Copy
 			+ Zero
 			+ Ord
-			+ SafeArithmetic;
+			+ SafeArithmetic
+			+ From<u32>
+			+ Into<u128>;
 		/// The maximum hops in the route.
 		#[pallet::constant]
 		type MaxHopsInRoute: Get<u32> + MaxEncodedLen + TypeInfo;
+		/// The maximum number of parts a split swap can be divided in.
+		#[pallet::constant]
+		type MaxSplitParts: Get<u32>;
+		/// The maximum number of pools considered when searching a route.
+		#[pallet::constant]
+		type MaxRoutingPools: Get<u32>;
+		/// The maximum number of pool hops priced when searching a route.
+		#[pallet::constant]
+		type MaxRouteCandidates: Get<u32>;
 		type PoolId: FullCodec
 			+ MaxEncodedLen
 			+ Default
@@ -81,11 +93,11 @@ pub mod pallet {
 			+ Zero
 			+ One;
 		type Pablo: Amm<
-			AssetId = Self::AssetId,
-			Balance = Self::Balance,
-			AccountId = Self::AccountId,
-			PoolId = Self::PoolId,
-		>;
+				AssetId = Self::AssetId,
+				Balance = Self::Balance,
+				AccountId = Self::AccountId,
+				PoolId = Self::PoolId,
+			> + AmmPools;
 
 		/// Required origin to update route operations.
 		type UpdateRouteOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -100,6 +112,10 @@ pub mod pallet {
 	#[pallet::generate_store(trait Store)]
 	pub struct Pallet<T>(_);
 
+	/// Number of quotes of the bisection searching how much of a `zap_in` deposit to swap to the
+	/// other asset of the pool.
+	pub(crate) const ZAP_IN_SEARCH_STEPS: u32 = 32;
+
 	#[pallet::storage]
 	pub type DexRoutes<T: Config> = StorageDoubleMap<
 		_,
@@ -127,6 +143,8 @@ pub mod pallet {
 		LoopSuspectedInRouteUpdate,
 		/// Only dual asset pools supported
 		OnlyDualAssetPoolsSupported,
+		/// Split swap must have at least one and at most `MaxSplitParts` parts.
+		InvalidSplitParts,
 	}
 
 	#[pallet::event]
@@ -148,6 +166,28 @@ pub mod pallet {
 			old_route: Vec<T::PoolId>,
 			updated_route: Vec<T::PoolId>,
 		},
+		/// Swap was split across routes.
+		SplitSwapped {
+			who: T::AccountId,
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			out_asset: AssetAmount<T::AssetId, T::Balance>,
+			/// Routes taken along with the amount of `in_asset` swapped through each of them.
+			routes: Vec<(Vec<T::PoolId>, T::Balance)>,
+		},
+		/// Liquidity was added to a pool from a single asset.
+		ZappedIn {
+			who: T::AccountId,
+			pool_id: T::PoolId,
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			minted_lp: T::Balance,
+		},
+		/// Liquidity was removed from a pool into a single asset.
+		ZappedOut {
+			who: T::AccountId,
+			pool_id: T::PoolId,
+			lp_amount: T::Balance,
+			out_asset: AssetAmount<T::AssetId, T::Balance>,
+		},
 	}
 
 	#[pallet::call]
@@ -171,8 +211,9 @@ pub mod pallet {
 		}
 
 		/// Exchange `amount` of quote asset for `asset_pair` via route found in router.
+		/// Uses the route pinned for the pair if any, otherwise the best route over all pools.
 		/// On successful underlying DEX pallets will emit appropriate event
-		#[pallet::weight(T::WeightInfo::swap())]
+		#[pallet::weight(T::WeightInfo::swap().saturating_add(Pallet::<T>::route_search_weight()))]
 		pub fn swap(
 			origin: OriginFor<T>,
 			in_asset: AssetAmount<T::AssetId, T::Balance>,
@@ -189,9 +230,29 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Exchange `in_asset` for at least `min_receive`, splitting it in `parts` chunks.
+		/// Each chunk goes through the best route given the state of the pools left by the previous
+		/// ones, spreading large trades over parallel pools.
+		/// A route is searched for every chunk, so the weight grows with `parts`.
+		/// On successful underlying DEX pallets will emit appropriate events and `SplitSwapped` is
+		/// emitted.
+		#[pallet::weight(T::WeightInfo::swap_split(*parts)
+			.saturating_add(Pallet::<T>::route_search_weight().saturating_mul(*parts as u64)))]
+		pub fn swap_split(
+			origin: OriginFor<T>,
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			min_receive: AssetAmount<T::AssetId, T::Balance>,
+			parts: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_split(&who, in_asset, min_receive, parts, false)?;
+			Ok(())
+		}
+
 		/// Buy `amount` of quote asset for `asset_pair` via route found in router.
+		/// Uses the route pinned for the pair if any, otherwise the cheapest route over all pools.
 		/// On successful underlying DEX pallets will emit appropriate event.
-		#[pallet::weight(T::WeightInfo::buy())]
+		#[pallet::weight(T::WeightInfo::buy().saturating_add(Pallet::<T>::route_search_weight()))]
 		pub fn buy(
 			origin: OriginFor<T>,
 			in_asset_id: T::AssetId,
@@ -209,7 +270,7 @@ pub mod pallet {
 		}
 
 		/// Add liquidity to the underlying pablo pool.
-		/// Works only for single pool route.
+		/// Works only for single pool route, see `zap_in` to add liquidity from any asset.
 		#[pallet::weight(T::WeightInfo::add_liquidity())]
 		pub fn add_liquidity(
 			origin: OriginFor<T>,
@@ -229,7 +290,7 @@ pub mod pallet {
 		}
 
 		/// Remove liquidity from the underlying pablo pool.
-		/// Works only for single pool route.
+		/// Works only for single pool route, see `zap_out` to receive any asset.
 		#[pallet::weight(T::WeightInfo::remove_liquidity())]
 		pub fn remove_liquidity(
 			origin: OriginFor<T>,
@@ -249,9 +310,60 @@ pub mod pallet {
 			<Self as Amm>::remove_liquidity(&who, asset_pair, lp_amount, min_receive)?;
 			Ok(())
 		}
+
+		/// Add liquidity to the pablo pool `pool_id` from `in_asset` only, which is swapped through
+		/// its route to one of the pool assets if it is not one of them, then partly to the other
+		/// pool asset so that both are deposited in the pool ratio.
+		/// Fails if less than `min_mint_amount` LP tokens are minted.
+		/// On successful emits `ZappedIn`.
+		#[pallet::weight(Pallet::<T>::zap_in_weight())]
+		pub fn zap_in(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			min_mint_amount: T::Balance,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_zap_in(&who, pool_id, in_asset, min_mint_amount, keep_alive)?;
+			Ok(())
+		}
+
+		/// Remove `lp_amount` of liquidity from the pablo pool `pool_id`, swapping the redeemed
+		/// assets through their routes to `min_receive.asset_id`.
+		/// Fails if less than `min_receive` is received in total.
+		/// On successful emits `ZappedOut`.
+		#[pallet::weight(T::WeightInfo::zap_out().saturating_add(
+			Pallet::<T>::route_search_weight().saturating_mul(2)
+		))]
+		pub fn zap_out(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			lp_amount: T::Balance,
+			min_receive: AssetAmount<T::AssetId, T::Balance>,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_zap_out(&who, pool_id, lp_amount, min_receive, keep_alive)?;
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Upper bound of the weight of a route search, which is charged even if a route is
+		/// pinned for the pair.
+		pub(crate) fn route_search_weight() -> Weight {
+			T::WeightInfo::route_search(T::MaxRoutingPools::get(), T::MaxRouteCandidates::get())
+		}
+
+		/// Weight of `zap_in`, which searches a route to each asset of the pool to pick the one to
+		/// enter with, and prices the swap to the pool ratio at every step of its bisection.
+		pub(crate) fn zap_in_weight() -> Weight {
+			T::WeightInfo::zap_in()
+				.saturating_add(Self::route_search_weight().saturating_mul(2))
+				.saturating_add(T::WeightInfo::route_search(0, ZAP_IN_SEARCH_STEPS))
+		}
+
 		fn validate_route(
 			asset_pair: CurrencyPair<T::AssetId>,
 			route: &BoundedVec<T::PoolId, T::MaxHopsInRoute>,
@@ -323,6 +435,534 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Route pinned by governance to trade `in_asset_id` for `out_asset_id`, ordered from the
+		/// pool taking `in_asset_id`.
+		fn pinned_path(
+			in_asset_id: T::AssetId,
+			out_asset_id: T::AssetId,
+		) -> Option<Vec<T::PoolId>> {
+			let (route, reverse) = Self::get_route(CurrencyPair::new(out_asset_id, in_asset_id))?;
+			Some(if reverse { route.into_iter().rev().collect() } else { route })
+		}
+
+		/// Pools used to swap `in_asset` for `out_asset_id`, ordered from the pool taking
+		/// `in_asset`.
+		pub fn swap_path(
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			out_asset_id: T::AssetId,
+		) -> Result<Vec<T::PoolId>, DispatchError> {
+			match Self::pinned_path(in_asset.asset_id, out_asset_id) {
+				Some(path) => Ok(path),
+				None => Self::best_swap_path(in_asset, out_asset_id).map(|(path, _)| path),
+			}
+		}
+
+		/// Pools used to buy `out_asset` with `in_asset_id`, ordered from the pool taking
+		/// `in_asset_id`.
+		pub fn buy_path(
+			in_asset_id: T::AssetId,
+			out_asset: AssetAmount<T::AssetId, T::Balance>,
+		) -> Result<Vec<T::PoolId>, DispatchError> {
+			match Self::pinned_path(in_asset_id, out_asset.asset_id) {
+				Some(path) => Ok(path),
+				None => Self::best_buy_path(in_asset_id, out_asset).map(|(path, _)| path),
+			}
+		}
+
+		/// Route over all pools yielding the most of `out_asset_id` for `in_asset`, along with the
+		/// simulated result of the swap.
+		pub fn best_swap_path(
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			out_asset_id: T::AssetId,
+		) -> Result<(Vec<T::PoolId>, SwapResult<T::AssetId, T::Balance>), DispatchError> {
+			Self::best_swap_path_within(in_asset, out_asset_id, T::MaxRouteCandidates::get())
+		}
+
+		/// Same as `best_swap_path`, pricing at most `max_candidates` hops.
+		pub(crate) fn best_swap_path_within(
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			out_asset_id: T::AssetId,
+			max_candidates: u32,
+		) -> Result<(Vec<T::PoolId>, SwapResult<T::AssetId, T::Balance>), DispatchError> {
+			Self::search_path(
+				in_asset,
+				out_asset_id,
+				max_candidates,
+				|pool_id, held, next_asset_id| {
+					T::Pablo::spot_price(pool_id, held, next_asset_id, true)
+				},
+				|new, best| new > best,
+			)
+		}
+
+		/// Route over all pools requiring the least of `in_asset_id` to buy `out_asset`, along with
+		/// the amount of `in_asset_id` to be spent, fees included.
+		pub fn best_buy_path(
+			in_asset_id: T::AssetId,
+			out_asset: AssetAmount<T::AssetId, T::Balance>,
+		) -> Result<(Vec<T::PoolId>, SwapResult<T::AssetId, T::Balance>), DispatchError> {
+			// walk from `out_asset` back to `in_asset_id`, pricing at each hop the amount to send
+			// to the pool to receive what the next hop requires
+			Self::search_path(
+				out_asset,
+				in_asset_id,
+				T::MaxRouteCandidates::get(),
+				|pool_id, held, next_asset_id| {
+					T::Pablo::buy_price(pool_id, next_asset_id, held, true)
+				},
+				|new, best| new < best,
+			)
+			.map(|(path, result)| (path.into_iter().rev().collect(), result))
+		}
+
+		/// Depth first search over the loop free paths of at most `MaxHopsInRoute` pools, out of
+		/// the first `MaxRoutingPools` pools, leading from `from` to `to_asset_id`.
+		///
+		/// Each hop is priced with `quote`, at most `max_candidates` times so that the search
+		/// stays within its weight, and the path whose priced amount is `better` is kept.
+		fn search_path(
+			from: AssetAmount<T::AssetId, T::Balance>,
+			to_asset_id: T::AssetId,
+			max_candidates: u32,
+			quote: impl Fn(
+				T::PoolId,
+				AssetAmount<T::AssetId, T::Balance>,
+				T::AssetId,
+			) -> Result<SwapResult<T::AssetId, T::Balance>, DispatchError>,
+			better: impl Fn(T::Balance, T::Balance) -> bool,
+		) -> Result<(Vec<T::PoolId>, SwapResult<T::AssetId, T::Balance>), DispatchError> {
+			ensure!(from.asset_id != to_asset_id, Error::<T>::NoRouteFound);
+			let pools = Self::dual_asset_pools();
+			let max_hops = T::MaxHopsInRoute::get() as usize;
+			let mut candidates = max_candidates;
+			let mut best: Option<(Vec<T::PoolId>, SwapResult<T::AssetId, T::Balance>)> = None;
+			let mut stack = Vec::from([(Vec::new(), BTreeSet::from([from.asset_id]), from)]);
+			'search: while let Some((path, visited, held)) = stack.pop() {
+				if path.len() >= max_hops {
+					continue
+				}
+				for (pool_id, (x, y)) in pools.iter() {
+					let next_asset_id = if *x == held.asset_id {
+						*y
+					} else if *y == held.asset_id {
+						*x
+					} else {
+						continue
+					};
+					if visited.contains(&next_asset_id) {
+						continue
+					}
+					if candidates.is_zero() {
+						break 'search
+					}
+					candidates -= 1;
+					let result = match quote(*pool_id, held, next_asset_id) {
+						Ok(result) if !result.value.amount.is_zero() => result,
+						_ => continue,
+					};
+					let mut next_path = path.clone();
+					next_path.push(*pool_id);
+					if next_asset_id == to_asset_id {
+						if best.as_ref().map_or(true, |(_, best)| {
+							better(result.value.amount, best.value.amount)
+						}) {
+							best = Some((next_path, result));
+						}
+					} else {
+						let mut next_visited = visited.clone();
+						next_visited.insert(next_asset_id);
+						stack.push((next_path, next_visited, result.value));
+					}
+				}
+			}
+			best.ok_or_else(|| Error::<T>::NoRouteFound.into())
+		}
+
+		/// First `MaxRoutingPools` pools made of exactly two assets, the only ones routes can go
+		/// through.
+		fn dual_asset_pools() -> Vec<(T::PoolId, (T::AssetId, T::AssetId))> {
+			T::Pablo::pools(T::MaxRoutingPools::get())
+				.into_iter()
+				.filter_map(|pool_id| {
+					let mut assets = T::Pablo::assets(pool_id).ok()?.into_keys();
+					match (assets.next(), assets.next(), assets.next()) {
+						(Some(x), Some(y), None) => Some((pool_id, (x, y))),
+						_ => None,
+					}
+				})
+				.collect()
+		}
+
+		/// Swaps `in_asset` through the pools of `path`.
+		fn swap_through(
+			who: &T::AccountId,
+			path: &[T::PoolId],
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			keep_alive: bool,
+		) -> Result<SwapResult<T::AssetId, T::Balance>, DispatchError> {
+			// Iterate and swap until we obtain the asset of the last pool
+			path.iter().try_fold(
+				SwapResult {
+					value: in_asset,
+					fee: AssetAmount::new(in_asset.asset_id, T::Balance::zero()),
+				},
+				|swap_result, pool_id| {
+					let out_asset_id = Self::other_asset(*pool_id, swap_result.value.asset_id)?;
+					T::Pablo::do_swap(
+						who,
+						*pool_id,
+						swap_result.value,
+						AssetAmount::new(out_asset_id, T::Balance::zero()),
+						keep_alive,
+					)
+				},
+			)
+		}
+
+		/// Swaps `in_asset` for `min_receive.asset_id` in `parts` equal chunks, each of them
+		/// through the best route at the time it is swapped.
+		///
+		/// As every chunk moves the prices of the pools it goes through, the following chunks
+		/// naturally flow to the routes which became better, which approximates the split
+		/// maximizing the output. Only the total received is checked against `min_receive`.
+		#[transactional]
+		pub fn do_swap_split(
+			who: &T::AccountId,
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			min_receive: AssetAmount<T::AssetId, T::Balance>,
+			parts: u32,
+			keep_alive: bool,
+		) -> Result<SwapResult<T::AssetId, T::Balance>, DispatchError> {
+			ensure!(parts > 0 && parts <= T::MaxSplitParts::get(), Error::<T>::InvalidSplitParts);
+			let chunk = in_asset.amount.safe_div(&parts.into())?;
+			// the last chunk also takes the remainder of the division
+			let last_chunk = in_asset.amount.safe_sub(&chunk.safe_mul(&(parts - 1).into())?)?;
+			let mut routes = BTreeMap::<Vec<T::PoolId>, T::Balance>::new();
+			let mut received = T::Balance::zero();
+			let mut fee = T::Balance::zero();
+			for part in 1..=parts {
+				let amount = if part == parts { last_chunk } else { chunk };
+				if amount.is_zero() {
+					continue
+				}
+				let chunk_asset = AssetAmount::new(in_asset.asset_id, amount);
+				let (route, _) = Self::best_swap_path(chunk_asset, min_receive.asset_id)?;
+				let swap_result = Self::swap_through(who, &route, chunk_asset, keep_alive)?;
+				received = received.safe_add(&swap_result.value.amount)?;
+				fee = fee.safe_add(&swap_result.fee.amount)?;
+				let swapped = routes.entry(route).or_insert_with(T::Balance::zero);
+				*swapped = swapped.safe_add(&amount)?;
+			}
+			ensure!(received >= min_receive.amount, Error::<T>::CanNotRespectMinAmountRequested);
+			let out_asset = AssetAmount::new(min_receive.asset_id, received);
+			Self::deposit_event(Event::<T>::SplitSwapped {
+				who: who.clone(),
+				in_asset,
+				out_asset,
+				routes: routes.into_iter().collect(),
+			});
+			// fees of each chunk are charged in the asset entering its last pool, which for
+			// multi hop routes is not `in_asset`; same limitation as `do_swap`
+			Ok(SwapResult { value: out_asset, fee: AssetAmount::new(in_asset.asset_id, fee) })
+		}
+
+		/// Route and simulated result of swapping `in_asset` for `out_asset_id`, through the
+		/// route `swap` would take.
+		fn quote_swap_route(
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			out_asset_id: T::AssetId,
+		) -> Result<(Vec<T::PoolId>, SwapResult<T::AssetId, T::Balance>), DispatchError> {
+			match Self::pinned_path(in_asset.asset_id, out_asset_id) {
+				Some(path) => {
+					let result = Self::simulate_swap_path(&path, in_asset, true)?;
+					Ok((path, result))
+				},
+				None => Self::best_swap_path(in_asset, out_asset_id),
+			}
+		}
+
+		/// Amount of `deposit` to swap to `other_asset_id` in `pool_id` so that what is kept and
+		/// what is received are in the ratio of the pool reserves after the swap.
+		///
+		/// The more is swapped, the less is kept and the more is received, so the amount is found
+		/// by bisection over `ZAP_IN_SEARCH_STEPS` quotes of the pool.
+		fn zap_in_swap_amount(
+			pool_id: T::PoolId,
+			deposit: AssetAmount<T::AssetId, T::Balance>,
+			other_asset_id: T::AssetId,
+		) -> Result<T::Balance, DispatchError> {
+			let balances = T::Pablo::balances(pool_id)?;
+			let reserve_of = |asset_id| -> Result<u128, DispatchError> {
+				balances
+					.get(&asset_id)
+					.map(|balance| (*balance).into())
+					.ok_or_else(|| Error::<T>::NoRouteFound.into())
+			};
+			let deposit_reserve = reserve_of(deposit.asset_id)?;
+			let other_reserve = reserve_of(other_asset_id)?;
+			let (mut low, mut high) = (T::Balance::zero(), deposit.amount);
+			for _ in 0..ZAP_IN_SEARCH_STEPS {
+				let swapped = low.safe_add(&high.safe_sub(&low)?.safe_div(&2_u32.into())?)?;
+				if swapped == low {
+					break
+				}
+				let received: u128 = T::Pablo::spot_price(
+					pool_id,
+					AssetAmount::new(deposit.asset_id, swapped),
+					other_asset_id,
+					true,
+				)?
+				.value
+				.amount
+				.into();
+				let kept: u128 = deposit.amount.safe_sub(&swapped)?.into();
+				// `received` valued in the deposited asset at the reserves after the swap
+				let received_value = multiply_by_rational_with_rounding(
+					received,
+					deposit_reserve.saturating_add(swapped.into()),
+					other_reserve.saturating_sub(received),
+					Rounding::Down,
+				)
+				.unwrap_or(u128::MAX);
+				if kept > received_value {
+					low = swapped;
+				} else {
+					high = swapped;
+				}
+			}
+			Ok(low)
+		}
+
+		/// Adds `in_asset` as liquidity to `pool_id`, entering the pool through the asset of the
+		/// pool quoted to be worth the most, then swapping part of it to the other asset of the
+		/// pool to deposit both in the pool ratio.
+		#[transactional]
+		pub fn do_zap_in(
+			who: &T::AccountId,
+			pool_id: T::PoolId,
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			min_mint_amount: T::Balance,
+			keep_alive: bool,
+		) -> Result<T::Balance, DispatchError> {
+			let balances = T::Pablo::balances(pool_id)?;
+			ensure!(balances.len() == 2, Error::<T>::OnlyDualAssetPoolsSupported);
+			let deposit = if balances.contains_key(&in_asset.asset_id) {
+				in_asset
+			} else {
+				// what each route yields is valued in the first asset of the pool at its current
+				// price, so that routes ending in different assets compare
+				let (value_asset_id, value_reserve) =
+					balances.iter().next().ok_or(Error::<T>::OnlyDualAssetPoolsSupported)?;
+				let (route, _) = balances
+					.iter()
+					.filter_map(|(asset_id, reserve)| {
+						let (route, result) = Self::quote_swap_route(in_asset, *asset_id).ok()?;
+						let value: u128 = if asset_id == value_asset_id {
+							result.value.amount.into()
+						} else {
+							multiply_by_rational_with_rounding(
+								result.value.amount.into(),
+								(*value_reserve).into(),
+								(*reserve).into(),
+								Rounding::Down,
+							)?
+						};
+						Some((route, value))
+					})
+					.max_by_key(|(_, value)| *value)
+					.ok_or(Error::<T>::NoRouteFound)?;
+				Self::swap_through(who, &route, in_asset, keep_alive)?.value
+			};
+			let other_asset_id = Self::other_asset(pool_id, deposit.asset_id)?;
+			let swapped = Self::zap_in_swap_amount(pool_id, deposit, other_asset_id)?;
+			let assets = if swapped.is_zero() {
+				// too small to be split, pablo takes single asset deposits as well
+				BTreeMap::from([(deposit.asset_id, deposit.amount)])
+			} else {
+				let received = T::Pablo::do_swap(
+					who,
+					pool_id,
+					AssetAmount::new(deposit.asset_id, swapped),
+					AssetAmount::new(other_asset_id, T::Balance::zero()),
+					keep_alive,
+				)?
+				.value;
+				BTreeMap::from([
+					(deposit.asset_id, deposit.amount.safe_sub(&swapped)?),
+					(received.asset_id, received.amount),
+				])
+			};
+			let minted_lp =
+				T::Pablo::add_liquidity(who, pool_id, assets, min_mint_amount, keep_alive)?;
+			Self::deposit_event(Event::<T>::ZappedIn {
+				who: who.clone(),
+				pool_id,
+				in_asset,
+				minted_lp,
+			});
+			Ok(minted_lp)
+		}
+
+		/// Removes `lp_amount` of liquidity from `pool_id` and swaps all the redeemed assets to
+		/// `min_receive.asset_id`.
+		#[transactional]
+		pub fn do_zap_out(
+			who: &T::AccountId,
+			pool_id: T::PoolId,
+			lp_amount: T::Balance,
+			min_receive: AssetAmount<T::AssetId, T::Balance>,
+			keep_alive: bool,
+		) -> Result<AssetAmount<T::AssetId, T::Balance>, DispatchError> {
+			let pool_assets = T::Pablo::assets(pool_id)?;
+			ensure!(pool_assets.len() == 2, Error::<T>::OnlyDualAssetPoolsSupported);
+			let redeemed = T::Pablo::remove_liquidity(
+				who,
+				pool_id,
+				lp_amount,
+				pool_assets.into_keys().map(|asset_id| (asset_id, Zero::zero())).collect(),
+			)?;
+			let mut received = T::Balance::zero();
+			for (asset_id, amount) in redeemed {
+				let amount = if asset_id == min_receive.asset_id || amount.is_zero() {
+					amount
+				} else {
+					let in_asset = AssetAmount::new(asset_id, amount);
+					let route = Self::swap_path(in_asset, min_receive.asset_id)?;
+					Self::swap_through(who, &route, in_asset, keep_alive)?.value.amount
+				};
+				received = received.safe_add(&amount)?;
+			}
+			ensure!(received >= min_receive.amount, Error::<T>::CanNotRespectMinAmountRequested);
+			let out_asset = AssetAmount::new(min_receive.asset_id, received);
+			Self::deposit_event(Event::<T>::ZappedOut {
+				who: who.clone(),
+				pool_id,
+				lp_amount,
+				out_asset,
+			});
+			Ok(out_asset)
+		}
+
+		/// Simulates swapping `in_asset` through `path`, without executing it.
+		fn simulate_swap_path(
+			path: &[T::PoolId],
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			calculate_with_fees: bool,
+		) -> Result<SwapResult<T::AssetId, T::Balance>, DispatchError> {
+			path.iter().try_fold(
+				SwapResult {
+					value: in_asset,
+					fee: AssetAmount::new(in_asset.asset_id, T::Balance::zero()),
+				},
+				|swap_result, pool_id| {
+					let out_asset_id = Self::other_asset(*pool_id, swap_result.value.asset_id)?;
+					T::Pablo::spot_price(
+						*pool_id,
+						swap_result.value,
+						out_asset_id,
+						calculate_with_fees,
+					)
+				},
+			)
+		}
+
+		/// Asset of the dual asset pool `pool_id` which is not `asset_id`.
+		fn other_asset(
+			pool_id: T::PoolId,
+			asset_id: T::AssetId,
+		) -> Result<T::AssetId, DispatchError> {
+			// We only allow dual asset pools in routes, therefore taking the remaining asset
+			// other than `asset_id` gives us the out asset
+			T::Pablo::assets(pool_id)?
+				.keys()
+				.copied()
+				.find(|a| *a != asset_id)
+				.ok_or_else(|| Error::<T>::NoRouteFound.into())
+		}
+
+		/// Quotes swapping `in_asset` for `out_asset_id` through the route `swap` would take,
+		/// without executing it.
+		pub fn quote_swap(
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+			out_asset_id: T::AssetId,
+		) -> Result<RouteQuote<T::PoolId, T::AssetId, T::Balance>, DispatchError> {
+			let route = Self::swap_path(in_asset, out_asset_id)?;
+			Self::quote_path(&route, in_asset)
+		}
+
+		/// Simulates buying exactly `out_asset` with `in_asset_id` through the route `buy` would
+		/// use, quoting the input it requires fees included.
+		pub fn quote_buy(
+			in_asset_id: T::AssetId,
+			out_asset: AssetAmount<T::AssetId, T::Balance>,
+		) -> Result<RouteQuote<T::PoolId, T::AssetId, T::Balance>, DispatchError> {
+			let route = Self::buy_path(in_asset_id, out_asset)?;
+			let hops = Self::buy_hops(&route, out_asset)?;
+			let in_asset = hops.first().map(|hop| hop.in_asset).ok_or(Error::<T>::NoRouteFound)?;
+			Ok(RouteQuote { in_asset, out_asset, hops })
+		}
+
+		/// All routes pinned by governance, keyed by the pair they were set for.
+		pub fn all_routes() -> Vec<(CurrencyPair<T::AssetId>, Vec<T::PoolId>)> {
+			DexRoutes::<T>::iter()
+				.map(|(base, quote, DexRoute::Direct(route))| {
+					(CurrencyPair::new(base, quote), route.into_inner())
+				})
+				.collect()
+		}
+
+		/// Simulates trading `in_asset` through `path`, recording the trade against each pool.
+		fn quote_path(
+			path: &[T::PoolId],
+			in_asset: AssetAmount<T::AssetId, T::Balance>,
+		) -> Result<RouteQuote<T::PoolId, T::AssetId, T::Balance>, DispatchError> {
+			let mut held = in_asset;
+			let hops = path
+				.iter()
+				.map(|pool_id| {
+					let out_asset_id = Self::other_asset(*pool_id, held.asset_id)?;
+					let result = T::Pablo::spot_price(*pool_id, held, out_asset_id, true)?;
+					let hop = RouteHop {
+						pool_id: *pool_id,
+						in_asset: held,
+						out_asset: result.value,
+						fee: result.fee,
+					};
+					held = result.value;
+					Ok(hop)
+				})
+				.collect::<Result<Vec<_>, DispatchError>>()?;
+			ensure!(!hops.is_empty(), Error::<T>::NoRouteFound);
+			Ok(RouteQuote { in_asset, out_asset: held, hops })
+		}
+
+		/// Iterates `route` backward, pricing the amount each pool requires, fees included, to
+		/// give exactly what the next pool requires, and the last one `out_asset`.
+		fn buy_hops(
+			route: &[T::PoolId],
+			out_asset: AssetAmount<T::AssetId, T::Balance>,
+		) -> Result<Vec<RouteHop<T::PoolId, T::AssetId, T::Balance>>, DispatchError> {
+			let mut held = out_asset;
+			let mut hops = route
+				.iter()
+				.rev()
+				.map(|pool_id| {
+					let in_asset_id = Self::other_asset(*pool_id, held.asset_id)?;
+					let result = T::Pablo::buy_price(*pool_id, in_asset_id, held, true)?;
+					let hop = RouteHop {
+						pool_id: *pool_id,
+						in_asset: result.value,
+						out_asset: held,
+						fee: result.fee,
+					};
+					held = result.value;
+					Ok(hop)
+				})
+				.collect::<Result<Vec<_>, DispatchError>>()?;
+			hops.reverse();
+			Ok(hops)
+		}
+
 		fn do_delete_route(asset_pair: CurrencyPair<T::AssetId>) -> Result<(), DispatchError> {
 			let mut base_asset = asset_pair.base;
 			let mut quote_asset = asset_pair.quote;
@@ -403,11 +1043,14 @@ pub mod pallet {
 			quote_asset_id: Self::AssetId,
 			calculate_with_fees: bool,
 		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
-			let (route, _reverse) = Self::get_route(pool_id).ok_or(Error::<T>::NoRouteFound)?;
-			match route[..] {
-				[pool_id] =>
-					T::Pablo::spot_price(pool_id, base_asset, quote_asset_id, calculate_with_fees),
-				_ => Err(Error::<T>::UnsupportedOperation.into()),
+			match Self::get_route(pool_id).map(|(route, _reverse)| route).as_deref() {
+				Some([pool_id]) =>
+					T::Pablo::spot_price(*pool_id, base_asset, quote_asset_id, calculate_with_fees),
+				_ => Self::simulate_swap_path(
+					&Self::swap_path(base_asset, quote_asset_id)?,
+					base_asset,
+					calculate_with_fees,
+				),
 			}
 		}
 
@@ -456,44 +1099,8 @@ pub mod pallet {
 			min_receive: AssetAmount<Self::AssetId, Self::Balance>,
 			keep_alive: bool,
 		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
-			let currency_pair = CurrencyPair::new(min_receive.asset_id, in_asset.asset_id);
-			let (route, reverse) =
-				Self::get_route(currency_pair).ok_or(Error::<T>::NoRouteFound)?;
-			let mut forward_iter;
-			let mut backward_iter;
-			// Iterate forward or backward depending on the reverse flag to find the pools to swap
-			// with.
-			let route_iter: &mut dyn Iterator<Item = &T::PoolId> = if !reverse {
-				forward_iter = route.iter();
-				&mut forward_iter
-			} else {
-				backward_iter = route.iter().rev();
-				&mut backward_iter
-			};
-			// Iterate and swap until we obtain the required asset in the `min_receive.asset_id`
-			let mut in_asset_itr = in_asset;
-			let mut swap_result: SwapResult<T::AssetId, T::Balance> = SwapResult {
-				value: in_asset_itr,
-				fee: AssetAmount { asset_id: in_asset_itr.asset_id, amount: T::Balance::zero() },
-			};
-			for pool_id in route_iter {
-				let assets = T::Pablo::assets(*pool_id)?;
-				// We only allow dual asset pools in routes, therefore taking the remaining asset
-				// other than `in_asset_itr.asset_id` gives us the out_asset_id
-				let out_asset_id = assets
-					.keys()
-					.copied()
-					.find(|a| *a != in_asset_itr.asset_id)
-					.ok_or(Error::<T>::NoRouteFound)?;
-				swap_result = T::Pablo::do_swap(
-					who,
-					*pool_id,
-					in_asset_itr,
-					AssetAmount::new(out_asset_id, T::Balance::zero()),
-					keep_alive,
-				)?;
-				in_asset_itr = swap_result.value;
-			}
+			let route = Self::swap_path(in_asset, min_receive.asset_id)?;
+			let swap_result = Self::swap_through(who, &route, in_asset, keep_alive)?;
 			ensure!(
 				swap_result.value.amount >= min_receive.amount,
 				Error::<T>::CanNotRespectMinAmountRequested
@@ -511,65 +1118,23 @@ pub mod pallet {
 			out_asset: AssetAmount<Self::AssetId, Self::Balance>,
 			keep_alive: bool,
 		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
-			let currency_pair = CurrencyPair::new(out_asset.asset_id, in_asset_id);
-			let (route, reverse) =
-				Self::get_route(currency_pair).ok_or(Error::<T>::NoRouteFound)?;
-
-			// Iterate and calculate spot price until we reach the `in_asset` amount required
-			let mut forward_iter;
-			let mut backward_iter;
-			let route_iter: &mut dyn Iterator<Item = &T::PoolId> = if !reverse {
-				backward_iter = route.iter().rev();
-				&mut backward_iter
-			} else {
-				forward_iter = route.iter();
-				&mut forward_iter
-			};
-			let mut in_asset_itr = out_asset;
-			let mut in_asset: SwapResult<T::AssetId, T::Balance> = SwapResult {
-				value: in_asset_itr,
-				fee: AssetAmount { asset_id: in_asset_itr.asset_id, amount: T::Balance::zero() },
-			};
-			for pool_id in route_iter {
-				let assets = T::Pablo::assets(*pool_id)?;
-				// We only allow dual asset pools in routes, therefore taking the remaining asset
-				// other than `in_asset_itr.asset_id` gives us the out_asset_id
-				let quote_asset_id = assets
-					.keys()
-					.copied()
-					.find(|a| *a != in_asset_itr.asset_id)
-					.ok_or(Error::<T>::NoRouteFound)?;
-				in_asset = T::Pablo::spot_price(*pool_id, in_asset_itr, quote_asset_id, false)?;
-				in_asset_itr = in_asset.value;
-			}
-
-			// Iterate and swap until we reach the out_asset amount required
-			let route_iter: &mut dyn Iterator<Item = &T::PoolId> = if !reverse {
-				forward_iter = route.iter();
-				&mut forward_iter
-			} else {
-				backward_iter = route.iter().rev();
-				&mut backward_iter
-			};
-			let mut out_asset_itr = in_asset;
-			for pool_id in route_iter {
-				let assets = T::Pablo::assets(*pool_id)?;
-				let out_asset_id = assets
-					.keys()
-					.copied()
-					.find(|a| *a != out_asset_itr.value.asset_id)
-					.ok_or(Error::<T>::NoRouteFound)?;
-				out_asset_itr = T::Pablo::do_swap(
+			let route = Self::buy_path(in_asset_id, out_asset)?;
+			// routes do not go twice through a pool, so each buy leaves the price of the
+			// following hops as quoted
+			let hops = Self::buy_hops(&route, out_asset)?;
+			let mut result = None;
+			for hop in hops {
+				result = Some(T::Pablo::do_buy(
 					who,
-					*pool_id,
-					out_asset_itr.value,
-					AssetAmount::new(out_asset_id, T::Balance::zero()),
+					hop.pool_id,
+					hop.in_asset.asset_id,
+					hop.out_asset,
 					keep_alive,
-				)?;
+				)?);
 			}
 			// TODO (vim): Final fee amount is not correct as the fee need to be incremented with
 			//  each swap fee when iterating.
-			Ok(out_asset_itr)
+			result.ok_or_else(|| Error::<T>::NoRouteFound.into())
 		}
 
 		#[transactional]
@@ -1,9 +1,13 @@
 use crate as dex_router;
-use crate::mock_fnft::MockFnft;
-use composable_traits::governance::SignedRawOrigin;
-use frame_support::{parameter_types, traits::Everything, PalletId};
+use frame_support::{
+	ord_parameter_types,
+	pallet_prelude::ConstU32,
+	parameter_types,
+	traits::{EitherOfDiverse, Everything},
+	PalletId,
+};
 use frame_system as system;
-use orml_traits::{parameter_type_with_key, LockIdentifier};
+use orml_traits::parameter_type_with_key;
 use primitives::currency::ForeignAssetId;
 use scale_info::TypeInfo;
 use sp_arithmetic::traits::Zero;
@@ -13,7 +17,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
 	Permill,
 };
-use system::{EnsureRoot, EnsureSigned};
+use system::{EnsureRoot, EnsureSignedBy};
 
 pub type Balance = u128;
 pub type AssetId = u128;
@@ -39,10 +43,6 @@ pub const DAI: AssetId = 5;
 pub const TWAP_INTERVAL: Moment = 10;
 pub const MILLISECS_PER_BLOCK: u64 = 12000;
 
-parameter_types! {
-	pub const NativeAssetId: AssetId = 0;
-}
-
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -53,15 +53,13 @@ frame_support::construct_runtime!(
 		NodeBlock = Block,
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
-		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
-		Pablo : pallet_pablo::{Pallet, Call, Storage, Event<T>},
-		StakingRewards: pallet_staking_rewards::{Pallet, Storage, Call, Event<T>},
-		Tokens: orml_tokens::{Pallet, Call, Storage, Config<T>, Event<T>},
+		System: frame_system,
+		Balances: pallet_balances,
+		Tokens: orml_tokens,
 		AssetsRegistry: pallet_assets_registry,
-		AssetsTransactor: pallet_assets_transactor_router,
-		DexRouter: dex_router::{Pallet, Call, Storage, Event<T>},
-		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		Timestamp: pallet_timestamp,
+		Pablo: pallet_pablo,
+		DexRouter: dex_router,
 	}
 );
 
@@ -111,6 +109,10 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
+	type FreezeIdentifier = [u8; 8];
+	type HoldIdentifier = [u8; 8];
+	type MaxHolds = ConstU32<32>;
+	type MaxFreezes = ConstU32<32>;
 }
 
 parameter_type_with_key! {
@@ -154,6 +156,7 @@ parameter_types! {
 	pub MaxInitialWeight: Permill = Permill::from_percent(95);
 	pub MinFinalWeight: Permill = Permill::from_percent(5);
 	pub const TWAPInterval: Moment = MILLISECS_PER_BLOCK * TWAP_INTERVAL;
+	pub const MaxTWAPObservations: u32 = 16;
 }
 
 parameter_types! {
@@ -168,11 +171,6 @@ impl pallet_timestamp::Config for Test {
 }
 
 parameter_types! {
-	pub const StakingRewardsPalletId: PalletId = PalletId(*b"stk_rwrd");
-	pub const StakingRewardsLockId: LockIdentifier = *b"stk_lock";
-	pub const MaxStakingDurationPresets: u32 = 10;
-	pub const MaxRewardConfigsPerPool: u32 = 10;
-	pub const TreasuryAccountId: AccountId = 123_456_789_u128;
 	pub const PicassoNetworkId: u32 = 0;
 }
 
@@ -188,40 +186,8 @@ impl pallet_assets_registry::Config for Test {
 	type NetworkId = PicassoNetworkId;
 }
 
-impl pallet_assets_transactor_router::Config for Test {
-	type AssetId = AssetId;
-	type Balance = Balance;
-	type NativeAssetId = NativeAssetId;
-	type NativeTransactor = Balances;
-	type LocalTransactor = Tokens;
-	type ForeignTransactor = Tokens;
-	type WeightInfo = ();
-	type AdminOrigin = EnsureRoot<AccountId>;
-	type AssetLocation = ForeignAssetId;
-	type AssetsRegistry = AssetsRegistry;
-}
-
-impl pallet_staking_rewards::Config for Test {
-	type RuntimeEvent = RuntimeEvent;
-	type Balance = Balance;
-	type AssetId = AssetId;
-	type UnixTime = Timestamp;
-	type ReleaseRewardsPoolsBatchSize = frame_support::traits::ConstU8<13>;
-	type PalletId = StakingRewardsPalletId;
-	type MaxStakingDurationPresets = MaxStakingDurationPresets;
-	type MaxRewardConfigsPerPool = MaxRewardConfigsPerPool;
-	type RewardPoolCreationOrigin = EnsureRoot<Self::AccountId>;
-	type WeightInfo = ();
-	type RewardPoolUpdateOrigin = EnsureRoot<Self::AccountId>;
-	type FinancialNft = MockFnft;
-	type FinancialNftInstanceId = u64;
-	type LockId = StakingRewardsLockId;
-	type TreasuryAccount = TreasuryAccountId;
-	type ExistentialDeposits = ExistentialDeposits;
-	type AssetsTransactor = AssetsTransactor;
-}
-
-parameter_types! {
+ord_parameter_types! {
+	pub const RootAccount: AccountId = ALICE;
 	pub const LPTED: Balance = 0;
 }
 
@@ -230,14 +196,18 @@ impl pallet_pablo::Config for Test {
 	type AssetId = AssetId;
 	type Balance = Balance;
 	type Assets = Tokens;
-	type LPTokenFactory = AssetsTransactor;
+	type LPTokenFactory = AssetsRegistry;
 	type Convert = ConvertInto;
 	type PoolId = PoolId;
 	type PalletId = TestPalletID;
-	type PoolCreationOrigin = EnsureSigned<Self::AccountId>;
+	type PoolCreationOrigin = EitherOfDiverse<
+		EnsureSignedBy<RootAccount, AccountId>, // for tests
+		EnsureRoot<AccountId>,                  // for benchmarks
+	>;
 	type EnableTwapOrigin = EnsureRoot<AccountId>;
 	type Time = Timestamp;
 	type TWAPInterval = TWAPInterval;
+	type MaxTWAPObservations = MaxTWAPObservations;
 	type WeightInfo = ();
 	type LPTokenExistentialDeposit = LPTED;
 }
@@ -245,6 +215,9 @@ impl pallet_pablo::Config for Test {
 parameter_types! {
 	#[derive(TypeInfo, codec::MaxEncodedLen, codec::Encode)]
 	pub const MaxHopsCount: u32 = 4;
+	pub const MaxSplitParts: u32 = 10;
+	pub const MaxRoutingPools: u32 = 32;
+	pub const MaxRouteCandidates: u32 = 256;
 	// cspell:disable-next
 	pub TestDexRouterPalletID: PalletId = PalletId(*b"dex_rout");
 }
@@ -254,6 +227,9 @@ impl dex_router::Config for Test {
 	type AssetId = AssetId;
 	type Balance = Balance;
 	type MaxHopsInRoute = MaxHopsCount;
+	type MaxSplitParts = MaxSplitParts;
+	type MaxRoutingPools = MaxRoutingPools;
+	type MaxRouteCandidates = MaxRouteCandidates;
 	type PoolId = PoolId;
 	type Pablo = Pablo;
 	type PalletId = TestDexRouterPalletID;
@@ -263,5 +239,8 @@ impl dex_router::Config for Test {
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.expect("success")
+		.into()
 }
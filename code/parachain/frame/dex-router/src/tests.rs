@@ -516,19 +516,21 @@ fn single_pool_route_test() {
 			Some(dex_route.clone().try_into().unwrap())
 		));
 		assert_ok!(Tokens::mint_into(ETH, &CHARLIE, 3000_u128 * unit));
-		// buy 3000 USDC
+		// buy 300 USDC
 		let dy = <DexRouter as AmmTrait>::do_buy(
 			&CHARLIE,
 			currency_pair,
 			ETH,
-			AssetAmount::new(USDC, 3000_u128 * unit),
+			AssetAmount::new(USDC, 300_u128 * unit),
 			false,
 		)
 		.unwrap();
 
 		dbg!(dy);
 
-		assert_eq!(dy.value.amount, 976343977246420);
+		assert_eq!(dy.value.amount, 300_u128 * unit);
+		// 1000 ETH * ((3000 USDC / 2700 USDC)^(75% / 25%) - 1), rounded up
+		assert_eq!(Tokens::balance(ETH, &CHARLIE), 3000_u128 * unit - 371_742_112_482_854);
 
 		// let expected_value = 3000 * unit;
 		// let precision = 100;
@@ -552,7 +554,8 @@ fn single_pool_route_test() {
 
 		dbg!(dy);
 
-		assert_eq!(dy.value.amount, 491348479874);
+		// 2700 USDC * (1 - (1371.74 ETH / 1372.74 ETH)^(25% / 75%))
+		assert_ok!(acceptable_computation_error(dy.value.amount, 655_781_316_086, 1000, 1));
 
 		// let expected_value = 3000 * unit;
 		let _precision = 100;
@@ -593,3 +596,277 @@ fn single_pool_route_test() {
 		assert_eq!(8999999999999, bob_usdc_amount);
 	});
 }
+
+#[test]
+fn swap_without_pinned_route_uses_best_route() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		// USDT -> ETH is reachable through USDC or DAI, the DAI side having less liquidity
+		let usdc_eth_pool = create_usdc_eth_pool();
+		let usdt_usdc_pool = create_usdt_usdc_pool();
+		create_constant_product_amm_pool(
+			AssetAmountPair {
+				base: AssetAmount { asset_id: DAI, amount: 30 * unit },
+				quote: AssetAmount { asset_id: ETH, amount: 10 * unit },
+			},
+			Permill::zero(),
+		);
+		create_usdt_dai_pool();
+		assert_eq!(DexRouter::get_route(CurrencyPair::new(ETH, USDT)), None);
+
+		let in_asset = AssetAmount::new(USDT, 10 * unit);
+		let (route, quote) = DexRouter::best_swap_path(in_asset, ETH).unwrap();
+		assert_eq!(route, vec![usdt_usdc_pool, usdc_eth_pool]);
+
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, in_asset.amount));
+		assert_ok!(DexRouter::swap(
+			RuntimeOrigin::signed(CHARLIE),
+			in_asset,
+			AssetAmount::new(ETH, quote.value.amount),
+		));
+		assert_eq!(Tokens::balance(ETH, &CHARLIE), quote.value.amount);
+	});
+}
+
+#[test]
+fn swap_prefers_pinned_route() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		create_usdc_eth_pool();
+		create_usdt_usdc_pool();
+		let dex_route = vec![create_dai_eth_pool(), create_usdt_dai_pool()];
+		let currency_pair = CurrencyPair::new(USDT, ETH);
+		assert_ok!(DexRouter::update_route(
+			RuntimeOrigin::root(),
+			currency_pair,
+			Some(dex_route.clone().try_into().unwrap())
+		));
+
+		let quote = DexRouter::quote_swap(AssetAmount::new(ETH, unit), USDT).unwrap();
+		assert_eq!(quote.route(), dex_route);
+	});
+}
+
+#[test]
+fn quotes_detail_each_hop() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		let dex_route = vec![create_usdc_eth_pool(), create_usdt_usdc_pool()];
+		let currency_pair = CurrencyPair::new(USDT, ETH);
+		assert_ok!(DexRouter::update_route(
+			RuntimeOrigin::root(),
+			currency_pair,
+			Some(dex_route.clone().try_into().unwrap())
+		));
+		assert_eq!(DexRouter::all_routes(), vec![(currency_pair, dex_route.clone())]);
+
+		let quote = DexRouter::quote_swap(AssetAmount::new(ETH, unit), USDT).unwrap();
+		assert_eq!(quote.hops.len(), 2);
+		assert_eq!(quote.hops[0].in_asset, quote.in_asset);
+		assert_eq!(quote.hops[0].out_asset, quote.hops[1].in_asset);
+		assert_eq!(quote.hops[1].out_asset, quote.out_asset);
+		assert_eq!(quote.hops[0].out_asset.asset_id, USDC);
+
+		let out_asset = AssetAmount::new(ETH, unit);
+		let quote = DexRouter::quote_buy(USDT, out_asset).unwrap();
+		assert_eq!(quote.route(), vec![dex_route[1], dex_route[0]]);
+		assert_eq!(quote.in_asset.asset_id, USDT);
+		assert_eq!(quote.out_asset.asset_id, ETH);
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, quote.in_asset.amount));
+		assert_eq!(quote.out_asset, out_asset);
+		assert_ok!(DexRouter::buy(RuntimeOrigin::signed(CHARLIE), USDT, out_asset));
+		assert_eq!(Tokens::balance(ETH, &CHARLIE), out_asset.amount);
+		assert_eq!(Tokens::balance(USDT, &CHARLIE), 0);
+	});
+}
+
+#[test]
+fn swap_without_any_route_fails() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		create_usdt_usdc_pool();
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, 1_000));
+		assert_noop!(
+			DexRouter::swap(
+				RuntimeOrigin::signed(CHARLIE),
+				AssetAmount::new(USDT, 1_000),
+				AssetAmount::new(ETH, 0),
+			),
+			Error::<Test>::NoRouteFound,
+		);
+	});
+}
+
+#[test]
+fn swap_split_spreads_over_parallel_pools() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		// two USDT/USDC pools of the same depth
+		let first_pool = create_usdt_usdc_pool();
+		let second_pool = create_usdc_usdt_pool();
+		let in_asset = AssetAmount::new(USDT, 200 * unit);
+
+		// swapping the whole amount through one pool
+		let (_, single_route) = DexRouter::best_swap_path(in_asset, USDC).unwrap();
+
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, in_asset.amount));
+		let routes = Test::assert_extrinsic_event_with(
+			DexRouter::swap_split(
+				RuntimeOrigin::signed(CHARLIE),
+				in_asset,
+				AssetAmount::new(USDC, single_route.value.amount),
+				10,
+			),
+			|event| match event {
+				crate::Event::<Test>::SplitSwapped { routes, .. } => Some(routes),
+				_ => None,
+			},
+		);
+		assert!(Tokens::balance(USDC, &CHARLIE) > single_route.value.amount);
+		assert_eq!(Tokens::balance(USDT, &CHARLIE), 0);
+		assert_eq!(routes, vec![(vec![first_pool], 100 * unit), (vec![second_pool], 100 * unit)]);
+	});
+}
+
+#[test]
+fn swap_split_respects_min_receive_and_parts() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		create_usdt_usdc_pool();
+		create_usdc_usdt_pool();
+		let in_asset = AssetAmount::new(USDT, 200 * unit);
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, in_asset.amount));
+
+		assert_noop!(
+			DexRouter::swap_split(
+				RuntimeOrigin::signed(CHARLIE),
+				in_asset,
+				AssetAmount::new(USDC, 200 * unit),
+				10,
+			),
+			Error::<Test>::CanNotRespectMinAmountRequested,
+		);
+		assert_noop!(
+			DexRouter::swap_split(
+				RuntimeOrigin::signed(CHARLIE),
+				in_asset,
+				AssetAmount::new(USDC, 0),
+				0,
+			),
+			Error::<Test>::InvalidSplitParts,
+		);
+		assert_noop!(
+			DexRouter::swap_split(
+				RuntimeOrigin::signed(CHARLIE),
+				in_asset,
+				AssetAmount::new(USDC, 0),
+				MaxSplitParts::get() + 1,
+			),
+			Error::<Test>::InvalidSplitParts,
+		);
+	});
+}
+
+#[test]
+fn zap_in_and_out_through_routes() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		let usdc_eth_pool = create_usdc_eth_pool();
+		create_usdt_usdc_pool();
+		let lp_token = Pablo::lp_token(usdc_eth_pool).unwrap();
+
+		// USDT is not in the USDC/ETH pool and has to be swapped to USDC first
+		let in_asset = AssetAmount::new(USDT, 30 * unit);
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, in_asset.amount));
+		let minted_lp = Test::assert_extrinsic_event_with(
+			DexRouter::zap_in(RuntimeOrigin::signed(CHARLIE), usdc_eth_pool, in_asset, 0, false),
+			|event| match event {
+				crate::Event::<Test>::ZappedIn { minted_lp, .. } => Some(minted_lp),
+				_ => None,
+			},
+		);
+		assert_eq!(Tokens::balance(USDT, &CHARLIE), 0);
+		assert_eq!(Tokens::balance(lp_token, &CHARLIE), minted_lp);
+		// USDC is swapped to ETH in the pool ratio, so that at most dust is not deposited
+		let usdc_left = Tokens::balance(USDC, &CHARLIE);
+		let eth_left = Tokens::balance(ETH, &CHARLIE);
+		assert!(usdc_left <= unit / 1_000_000, "{usdc_left} USDC not deposited");
+		assert!(eth_left <= unit / 1_000_000, "{eth_left} ETH not deposited");
+
+		let out_asset = Test::assert_extrinsic_event_with(
+			DexRouter::zap_out(
+				RuntimeOrigin::signed(CHARLIE),
+				usdc_eth_pool,
+				minted_lp,
+				AssetAmount::new(USDT, 0),
+				false,
+			),
+			|event| match event {
+				crate::Event::<Test>::ZappedOut { out_asset, .. } => Some(out_asset),
+				_ => None,
+			},
+		);
+		assert_eq!(Tokens::balance(lp_token, &CHARLIE), 0);
+		assert_eq!(Tokens::balance(ETH, &CHARLIE), eth_left);
+		assert_eq!(Tokens::balance(USDC, &CHARLIE), usdc_left);
+		assert_eq!(Tokens::balance(USDT, &CHARLIE), out_asset.amount);
+		// only loses to the price impact of the swaps
+		assert_ok!(acceptable_computation_error(out_asset.amount, in_asset.amount, 100, 5));
+	});
+}
+
+#[test]
+fn zap_respects_slippage_limits() {
+	new_test_ext().execute_with(|| {
+		next_block::<DexRouter, Test>();
+
+		let unit = 1_000_000_000_000_u128;
+		let usdc_eth_pool = create_usdc_eth_pool();
+		create_usdt_usdc_pool();
+
+		let in_asset = AssetAmount::new(USDT, 30 * unit);
+		assert_ok!(Tokens::mint_into(USDT, &CHARLIE, in_asset.amount));
+		assert_noop!(
+			DexRouter::zap_in(
+				RuntimeOrigin::signed(CHARLIE),
+				usdc_eth_pool,
+				in_asset,
+				u128::MAX,
+				false
+			),
+			PabloError::<Test>::CannotRespectMinimumRequested,
+		);
+
+		assert_ok!(DexRouter::zap_in(
+			RuntimeOrigin::signed(CHARLIE),
+			usdc_eth_pool,
+			in_asset,
+			0,
+			false
+		));
+		let lp_amount = Tokens::balance(Pablo::lp_token(usdc_eth_pool).unwrap(), &CHARLIE);
+		assert_noop!(
+			DexRouter::zap_out(
+				RuntimeOrigin::signed(CHARLIE),
+				usdc_eth_pool,
+				lp_amount,
+				AssetAmount::new(USDT, in_asset.amount),
+				false,
+			),
+			Error::<Test>::CanNotRespectMinAmountRequested,
+		);
+	});
+}
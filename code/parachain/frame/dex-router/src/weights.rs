@@ -9,9 +9,13 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
   fn update_route() -> Weight;
   fn swap() -> Weight;
+  fn swap_split(p: u32) -> Weight;
   fn buy() -> Weight;
   fn add_liquidity() -> Weight;
   fn remove_liquidity() -> Weight;
+  fn zap_in() -> Weight;
+  fn zap_out() -> Weight;
+  fn route_search(p: u32, c: u32) -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -28,6 +32,10 @@ impl WeightInfo for () {
         Weight::from_ref_time(10_000)
     }
 
+    fn swap_split(p: u32) -> Weight {
+        Weight::from_ref_time(10_000).saturating_mul(p as u64)
+    }
+
     fn add_liquidity() -> Weight {
         Weight::from_ref_time(10_000)
     }
@@ -35,4 +43,18 @@ impl WeightInfo for () {
     fn remove_liquidity() -> Weight {
         Weight::from_ref_time(10_000)
     }
+
+    fn zap_in() -> Weight {
+        Weight::from_ref_time(10_000)
+    }
+
+    fn zap_out() -> Weight {
+        Weight::from_ref_time(10_000)
+    }
+
+    fn route_search(p: u32, c: u32) -> Weight {
+        Weight::from_ref_time(10_000)
+            .saturating_add(Weight::from_ref_time(1_000).saturating_mul(c as u64))
+            .saturating_add(RocksDbWeight::get().reads((p as u64).saturating_add(3 * c as u64)))
+    }
 }
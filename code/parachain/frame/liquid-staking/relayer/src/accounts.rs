@@ -0,0 +1,77 @@
+//! Relaychain accounts of the liquid staking pallet and the storage keys of their ledgers, derived
+//! the same way as `Pallet::derivative_sovereign_account_id` and `Pallet::get_staking_ledger_key`.
+
+use subxt::ext::{
+	codec::Encode,
+	sp_core::hashing::{blake2_128, blake2_256, twox_128},
+};
+
+pub type AccountId = [u8; 32];
+pub type DerivativeIndex = u16;
+pub type EraIndex = u32;
+
+/// Sovereign account of the parachain `para_id` on the relaychain.
+pub fn sovereign_account_id(para_id: u32) -> AccountId {
+	let mut account = AccountId::default();
+	let encoded = (b"para", para_id).encode();
+	account[..encoded.len()].copy_from_slice(&encoded);
+	account
+}
+
+/// Derivative account `index` of `who`, as created by `pallet_utility::as_derivative`.
+pub fn derivative_account_id(who: &AccountId, index: DerivativeIndex) -> AccountId {
+	blake2_256(&(b"modlpy/utilisuba", who, index).encode())
+}
+
+/// Key of the plain storage value `item` of `pallet`.
+pub fn storage_value_key(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// Key of `Staking::Ledger` for the controller `who`.
+pub fn staking_ledger_key(who: &AccountId) -> Vec<u8> {
+	[storage_value_key("Staking", "Ledger"), blake2_128(who).to_vec(), who.to_vec()].concat()
+}
+
+/// Key of `Staking::CurrentEra`.
+pub fn current_era_key() -> Vec<u8> {
+	storage_value_key("Staking", "CurrentEra")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+	use subxt::utils::AccountId32;
+
+	fn account(ss58: &str) -> AccountId {
+		AccountId32::from_str(ss58).expect("valid address").0
+	}
+
+	#[test]
+	fn test_derive_picasso_accounts() {
+		let sovereign = sovereign_account_id(2019);
+		assert_eq!(sovereign, account("13YMK2ecbyxtm4cmFs31PqzWmQ7gWVboJSmXbcA56DB94xB9"));
+		assert_eq!(
+			derivative_account_id(&sovereign, 0),
+			account("12x6QU4c9eRPxJMATFsRNFiZTMK5QgZkdZFFeu2QDKn4TR82")
+		);
+		assert_eq!(
+			derivative_account_id(&sovereign, 5),
+			account("14tDkT3U93Pc1wLrHEjfYuhPPnFpMwDr7o8phPCTwTRj5wfE")
+		);
+	}
+
+	#[test]
+	fn test_staking_ledger_key() {
+		let derivative = derivative_account_id(&sovereign_account_id(2019), 1);
+		assert_eq!(
+			hex::encode(staking_ledger_key(&derivative)),
+			"5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc491af1d8906a21795a98d84506b4216828886ca7474c66c027a9dc5d73901481568d551d01f13d0eb3bd36dd20ed2f13e"
+		);
+		assert_eq!(
+			hex::encode(current_era_key()),
+			"5f3e4907f716ac89b6347d15ececedca0b6a45321efae92aea15e0740ec7afe7"
+		);
+	}
+}
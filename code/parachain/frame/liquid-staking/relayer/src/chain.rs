@@ -0,0 +1,78 @@
+use crate::accounts::{DerivativeIndex, EraIndex};
+use async_trait::async_trait;
+use subxt::{ext::codec, utils::H256};
+
+#[derive(Debug)]
+pub enum RelayerError {
+	Connection(String),
+	Subxt(subxt::Error),
+	Decode(codec::Error),
+	/// The relaychain has no block at the relay parent of the latest block of the parachain.
+	MissingBlock(u32),
+	/// The relaychain block at the number of the relay parent has another storage root, the
+	/// relaychain node does not follow the fork the parachain builds on.
+	UnknownRelayParent(u32),
+	/// The relaychain has no head of the parachain at the block the proofs are built at.
+	MissingParaHead(u32),
+	/// The parachain does not expose the storage or constant the relayer relies on.
+	MissingParachainData(&'static str),
+	/// The parachain rejected the extrinsic.
+	Rejected(String),
+}
+
+impl From<subxt::Error> for RelayerError {
+	fn from(error: subxt::Error) -> Self {
+		RelayerError::Subxt(error)
+	}
+}
+
+impl From<codec::Error> for RelayerError {
+	fn from(error: codec::Error) -> Self {
+		RelayerError::Decode(error)
+	}
+}
+
+/// Relaychain storage the ledgers and the era are read and proven from.
+#[async_trait]
+pub trait RelayChain {
+	/// Block the proofs are built at: the relay parent of the latest block of the parachain, given
+	/// by its number and storage root. The parachain verifies the proofs of an extrinsic against
+	/// the storage root of the relay parent of the block before the one including it.
+	async fn proof_anchor(
+		&self,
+		para_id: u32,
+		relay_parent_number: u32,
+		relay_parent_storage_root: H256,
+	) -> Result<H256, RelayerError>;
+
+	async fn read(&self, at: H256, key: &[u8]) -> Result<Option<Vec<u8>>, RelayerError>;
+
+	/// Storage proof of `keys` at `at`.
+	async fn read_proof(&self, at: H256, keys: &[Vec<u8>]) -> Result<Vec<Vec<u8>>, RelayerError>;
+}
+
+/// Parachain running the liquid staking pallet.
+#[async_trait]
+pub trait Parachain {
+	async fn para_id(&self) -> Result<u32, RelayerError>;
+
+	/// Number and storage root of the relay parent of the latest block, as recorded by the liquid
+	/// staking pallet.
+	async fn relay_parent(&self) -> Result<(u32, H256), RelayerError>;
+
+	/// `DerivativeIndexList` constant of the liquid staking pallet.
+	async fn derivative_index_list(&self) -> Result<Vec<DerivativeIndex>, RelayerError>;
+
+	async fn current_era(&self) -> Result<EraIndex, RelayerError>;
+
+	/// Submit `set_staking_ledger` with the SCALE encoded `ledger`, as stored on the relaychain.
+	async fn set_staking_ledger(
+		&self,
+		derivative_index: DerivativeIndex,
+		ledger: Vec<u8>,
+		proof: Vec<Vec<u8>>,
+	) -> Result<(), RelayerError>;
+
+	async fn set_current_era(&self, era: EraIndex, proof: Vec<Vec<u8>>)
+		-> Result<(), RelayerError>;
+}
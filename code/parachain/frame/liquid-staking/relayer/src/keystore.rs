@@ -0,0 +1,69 @@
+use std::path::Path;
+use subxt::ext::sp_core::{sr25519, Pair};
+
+/// Key type of the files written by `subkey`/`key insert` for sr25519 keys, `sr25`.
+const SR25519_KEY_TYPE: &str = "73723235";
+
+#[derive(Debug)]
+pub enum KeystoreError {
+	CannotRead(std::io::Error),
+	CannotParse(serde_json::Error),
+	InvalidKey(String),
+	/// The file is named after another public key than the one it contains.
+	PublicKeyMismatch,
+}
+
+/// Load the sr25519 key of the file at `path`.
+///
+/// The file is either a file of a substrate keystore, a JSON string holding the secret URI named
+/// after the key type and the hex encoded public key, or a plain text file holding the secret URI.
+pub fn load(path: &Path, password: Option<&str>) -> Result<sr25519::Pair, KeystoreError> {
+	let content = std::fs::read_to_string(path).map_err(KeystoreError::CannotRead)?;
+	let content = content.trim();
+	let suri = if content.starts_with('"') {
+		serde_json::from_str::<String>(content).map_err(KeystoreError::CannotParse)?
+	} else {
+		content.to_string()
+	};
+	let pair = sr25519::Pair::from_string(&suri, password)
+		.map_err(|e| KeystoreError::InvalidKey(format!("{:?}", e)))?;
+
+	let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+	if let Some(public) = file_name.strip_prefix(SR25519_KEY_TYPE) {
+		if public != hex::encode(pair.public()) {
+			return Err(KeystoreError::PublicKeyMismatch)
+		}
+	}
+	Ok(pair)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{load, KeystoreError, SR25519_KEY_TYPE};
+	use std::path::PathBuf;
+
+	const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+	fn write(name: &str, content: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("lsd-relayer-keystore-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).expect("temporary directory");
+		let path = dir.join(name);
+		std::fs::write(&path, content).expect("temporary file");
+		path
+	}
+
+	#[test]
+	fn test_load_key() {
+		let plain = write("plain", "//Alice\n");
+		assert_eq!(hex::encode(load(&plain, None).expect("valid key").public()), ALICE);
+
+		let keystore = write(&format!("{SR25519_KEY_TYPE}{ALICE}"), "\"//Alice\"");
+		assert_eq!(hex::encode(load(&keystore, None).expect("valid key").public()), ALICE);
+
+		let mismatch = write(&format!("{SR25519_KEY_TYPE}{ALICE}"), "\"//Bob\"");
+		assert!(matches!(load(&mismatch, None), Err(KeystoreError::PublicKeyMismatch)));
+
+		let invalid = write("invalid", "\"not a key");
+		assert!(matches!(load(&invalid, None), Err(KeystoreError::CannotParse(_))));
+	}
+}
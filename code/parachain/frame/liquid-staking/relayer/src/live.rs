@@ -0,0 +1,182 @@
+use crate::{
+	accounts::{storage_value_key, DerivativeIndex, EraIndex},
+	chain::{Parachain, RelayChain, RelayerError},
+};
+use async_trait::async_trait;
+use grandpa_client_primitives::parachain_header_storage_key;
+use hyperspace_core::substrate::{
+	composable::parachain_subxt::api::{
+		self as parachain_api, runtime_types::pallet_liquid_staking::types::StakingLedger,
+	},
+	DefaultConfig as PolkadotConfig,
+};
+use jsonrpsee::ws_client::WsClientBuilder;
+use std::sync::Arc;
+use subxt::{
+	ext::{
+		codec::{self, Decode},
+		sp_core::sr25519,
+	},
+	tx::{PairSigner, TxPayload},
+	utils::{AccountId32, H256},
+	OnlineClient, SubstrateConfig,
+};
+
+const LIQUID_STAKING: &str = "PalletLiquidStaking";
+
+/// `PersistedValidationData` of the latest block, recorded by the liquid staking pallet.
+#[derive(Decode)]
+#[codec(crate = codec)]
+struct ValidationData {
+	_parent_head: Vec<u8>,
+	relay_parent_number: u32,
+	relay_parent_storage_root: H256,
+	_max_pov_size: u32,
+}
+
+/// Relaychain node reached over WebSocket.
+pub struct RelayNode {
+	client: OnlineClient<PolkadotConfig>,
+}
+
+impl RelayNode {
+	pub async fn connect(url: &str) -> Result<Self, RelayerError> {
+		let rpc = WsClientBuilder::default()
+			.build(url)
+			.await
+			.map_err(|e| RelayerError::Connection(e.to_string()))?;
+		let client = OnlineClient::from_rpc_client(Arc::new(rpc)).await?;
+		Ok(Self { client })
+	}
+}
+
+#[async_trait]
+impl RelayChain for RelayNode {
+	async fn proof_anchor(
+		&self,
+		para_id: u32,
+		relay_parent_number: u32,
+		relay_parent_storage_root: H256,
+	) -> Result<H256, RelayerError> {
+		let rpc = self.client.rpc();
+		let at = rpc
+			.block_hash(Some(relay_parent_number.into()))
+			.await?
+			.ok_or(RelayerError::MissingBlock(relay_parent_number))?;
+		let header = rpc
+			.header(Some(at))
+			.await?
+			.ok_or(RelayerError::MissingBlock(relay_parent_number))?;
+		// the block at this number on the fork followed by the node may not be the relay parent
+		if header.state_root != relay_parent_storage_root {
+			return Err(RelayerError::UnknownRelayParent(relay_parent_number))
+		}
+		let head_key = parachain_header_storage_key(para_id);
+		self.read(at, head_key.as_ref())
+			.await?
+			.ok_or(RelayerError::MissingParaHead(para_id))?;
+		Ok(at)
+	}
+
+	async fn read(&self, at: H256, key: &[u8]) -> Result<Option<Vec<u8>>, RelayerError> {
+		Ok(self.client.storage().at(at).fetch_raw(key).await?)
+	}
+
+	async fn read_proof(&self, at: H256, keys: &[Vec<u8>]) -> Result<Vec<Vec<u8>>, RelayerError> {
+		let proof = self.client.rpc().read_proof(keys.iter().map(AsRef::as_ref), Some(at)).await?;
+		Ok(proof.proof.into_iter().map(|node| node.0).collect())
+	}
+}
+
+/// Parachain node reached over WebSocket, signing the extrinsics with `signer`.
+pub struct ParachainNode {
+	client: OnlineClient<SubstrateConfig>,
+	signer: PairSigner<SubstrateConfig, sr25519::Pair>,
+}
+
+impl ParachainNode {
+	pub async fn connect(url: &str, key: sr25519::Pair) -> Result<Self, RelayerError> {
+		let client = OnlineClient::from_url(url).await?;
+		Ok(Self { client, signer: PairSigner::new(key) })
+	}
+
+	async fn read<T: Decode>(&self, pallet: &str, item: &str) -> Result<Option<T>, RelayerError> {
+		let key = storage_value_key(pallet, item);
+		let value = self.client.storage().at_latest().await?.fetch_raw(&key).await?;
+		Ok(value.map(|value| T::decode(&mut &value[..])).transpose()?)
+	}
+
+	/// Submit `call` and wait for it to be finalized, failing if it was not dispatched.
+	async fn submit<Call: TxPayload>(&self, call: &Call) -> Result<(), RelayerError> {
+		let events = self
+			.client
+			.tx()
+			.sign_and_submit_then_watch_default(call, &self.signer)
+			.await?
+			.wait_for_finalized_success()
+			.await
+			.map_err(|e| match e {
+				subxt::Error::Runtime(e) => RelayerError::Rejected(e.to_string()),
+				e => RelayerError::Subxt(e),
+			})?;
+		log::debug!("extrinsic {:?} finalized", events.extrinsic_hash());
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl Parachain for ParachainNode {
+	async fn para_id(&self) -> Result<u32, RelayerError> {
+		self.read("ParachainInfo", "ParachainId")
+			.await?
+			.ok_or(RelayerError::MissingParachainData("ParachainInfo::ParachainId"))
+	}
+
+	async fn relay_parent(&self) -> Result<(u32, H256), RelayerError> {
+		let validation_data: ValidationData = self
+			.read(LIQUID_STAKING, "ValidationData")
+			.await?
+			.ok_or(RelayerError::MissingParachainData("PalletLiquidStaking::ValidationData"))?;
+		Ok((validation_data.relay_parent_number, validation_data.relay_parent_storage_root))
+	}
+
+	async fn derivative_index_list(&self) -> Result<Vec<DerivativeIndex>, RelayerError> {
+		let metadata = self.client.metadata();
+		let constant = metadata
+			.pallet_by_name(LIQUID_STAKING)
+			.and_then(|pallet| pallet.constant_by_name("DerivativeIndexList"))
+			.ok_or(RelayerError::MissingParachainData("DerivativeIndexList"))?;
+		Ok(Vec::<DerivativeIndex>::decode(&mut constant.value())?)
+	}
+
+	async fn current_era(&self) -> Result<EraIndex, RelayerError> {
+		Ok(self.read(LIQUID_STAKING, "CurrentEra").await?.unwrap_or_default())
+	}
+
+	async fn set_staking_ledger(
+		&self,
+		derivative_index: DerivativeIndex,
+		ledger: Vec<u8>,
+		proof: Vec<Vec<u8>>,
+	) -> Result<(), RelayerError> {
+		// the ledger of the pallet is encoded like the one of the relaychain
+		let ledger = StakingLedger::<AccountId32, u128>::decode(&mut &ledger[..])?;
+		let call = parachain_api::tx()
+			.pallet_liquid_staking()
+			.set_staking_ledger(derivative_index, ledger, proof)
+			.unvalidated();
+		self.submit(&call).await
+	}
+
+	async fn set_current_era(
+		&self,
+		era: EraIndex,
+		proof: Vec<Vec<u8>>,
+	) -> Result<(), RelayerError> {
+		let call = parachain_api::tx()
+			.pallet_liquid_staking()
+			.set_current_era(era, proof)
+			.unvalidated();
+		self.submit(&call).await
+	}
+}
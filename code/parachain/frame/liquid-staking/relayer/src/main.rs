@@ -1,187 +1,96 @@
-use grandpa_client_primitives::parachain_header_storage_key;
-use grandpa_prover::GrandpaProver;
-use hyperspace_core::substrate::{
-	composable::{
-		parachain_subxt,
-		parachain_subxt::api::pallet_liquid_staking::calls::types::SetStakingLedger, relaychain,
-	},
-	ComposableConfig, DefaultConfig as PolkadotConfig,
+mod accounts;
+mod chain;
+mod keystore;
+mod live;
+mod mock;
+mod opts;
+mod relayer;
+
+use crate::{
+	accounts::{derivative_account_id, sovereign_account_id},
+	live::{ParachainNode, RelayNode},
+	mock::{ledger, MockParachain, MockRelay},
+	opts::Opts,
+	relayer::Relayer,
 };
-use jsonrpsee::{async_client::Client, tracing::log, ws_client::WsClientBuilder};
-use sp_core::storage::StorageKey;
-use std::{io::Bytes, str::FromStr, sync::Arc, time::Duration};
-use subxt::{
-	config::Header, dynamic::Value, ext::scale_value::Composite, rpc::types::StorageChangeSet,
-	tx::Payload, Config, OnlineClient, SubstrateConfig,
-};
-// use subxt_signer::sr25519::dev::{self};
-use crate::parachain_subxt::api::runtime_types::pallet_liquid_staking::types::{
-	StakingLedger, UnlockChunk,
-};
-use futures_util::stream::StreamExt;
-use sp_core::Pair;
-use sp_keyring::AccountKeyring;
-use subxt::{tx::PairSigner, utils::AccountId32};
+use clap::Parser;
+use std::{path::Path, time::Duration};
 
 #[tokio::main]
 async fn main() {
-	let sovereign_account_id = "13YMK2ecbyxtm4cmFs31PqzWmQ7gWVboJSmXbcA56DB94xB9";
-	let sovereign_account_id_index_0 = "12x6QU4c9eRPxJMATFsRNFiZTMK5QgZkdZFFeu2QDKn4TR82";
-	let sovereign_account_id_index_1 = "1461Z7Bm1bwQpz1PuYMQ8phj9bRpxNU7ZYsb7aXQRAUuNecG";
-	let sovereign_account_id_index_2 = "15ySsNFkAhswdn9hSKkzoK7LhmJrj8bgyUZQAiM7Df9JpBUH";
-	let sovereign_account_id_index_3 = "15s3DuzMeftBH7YdHykwPDUd2DBxdNbiyqgDfZDA3i5eRwUW";
-	let sovereign_account_id_index_4 = "12uNvUSK39SDbHbqWuMhFdw2hHySrkbenVrHdS678fkj9BBb";
-	let sovereign_account_id_index_5 = "14tDkT3U93Pc1wLrHEjfYuhPPnFpMwDr7o8phPCTwTRj5wfE";
-
-	let para_storage_key = parachain_header_storage_key(2019);
-
-	let p0 = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc456d08aea5b028f73699523ae21709a815640ec97748f5b5da9a2298e830e8971df7908861e1710b957fe06f0703bca7d";
-	let p1 = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc491af1d8906a21795a98d84506b4216828886ca7474c66c027a9dc5d73901481568d551d01f13d0eb3bd36dd20ed2f13e";
-	let p2 = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc4bc407118eef848de28bad224cc60c8fedbfdc1d87d5f65f94602d8e31d7426de7b7d0bb4a8e9223b72bb39231737377c";
-	let p3 = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc43802b39b0fa95ce7e37486987631cef3d71aafa64aa8d5d9164071a14106cdba71fe4b8fc08de39a7606c8d16f5b20f8";
-	let p4 = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc4b8ac2e6f27924e06455d8e5c75b6a775542eca539ca4e92c4b4e0496bef934595597645e21eb29b5ce94bc38b7e45181";
-	let p5 = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc4232647f67d6923193db6aedafe0d5ea3abc522231610ec74f6391785db08c68f3c07c1bb45e2193b413f66926dec5072";
+	env_logger::init();
 
-	let s0 = StorageKey(hex::decode(p0).expect("Failed to decode hex string p0"));
-	let s1 = StorageKey(hex::decode(p1).expect("Failed to decode hex string p1"));
-	let s2 = StorageKey(hex::decode(p2).expect("Failed to decode hex string p2"));
-	let s3 = StorageKey(hex::decode(p3).expect("Failed to decode hex string p3"));
-	let s4 = StorageKey(hex::decode(p4).expect("Failed to decode hex string p4"));
-	let s5 = StorageKey(hex::decode(p5).expect("Failed to decode hex string p5"));
+	let opts = Opts::parse();
+	let interval = Duration::from_secs(opts.interval);
+	let retry_delay = Duration::from_secs(opts.retry_delay);
 
-	let tuple = vec![
-		(sovereign_account_id_index_0, s0, 0),
-		(sovereign_account_id_index_1, s1, 1),
-		(sovereign_account_id_index_2, s2, 2),
-		(sovereign_account_id_index_3, s3, 3),
-		(sovereign_account_id_index_4, s4, 4),
-		(sovereign_account_id_index_5, s5, 5),
-	];
-	let relay = std::env::var("RELAY_HOST").unwrap_or_else(|_| "rpc.polkadot.io".to_string());
-	let para = std::env::var("PARA_HOST").unwrap_or_else(|_| "rpc.polkadot.io".to_string());
+	if opts.mock {
+		if !run_mock(opts.retries, opts.rounds.unwrap_or(2)).await {
+			std::process::exit(1);
+		}
+		return
+	}
 
-	let relay_ws_url = format!("wss://{relay}:443");
-	let relay_ws_url = format!("ws://127.0.0.1:8001");
-	let para_ws_url = format!("ws://127.0.0.1:8000");
+	// required by the options when not running against mocks
+	let keystore = opts.keystore.as_deref().unwrap_or_default();
+	let key = keystore::load(Path::new(keystore), opts.keystore_password.as_deref())
+		.map_err(|e| {
+			log::error!("could not load the key from {}: {:?}", keystore, e);
+			std::process::exit(1);
+		})
+		.unwrap();
 
-	let relay_ws_client = Arc::new(WsClientBuilder::default().build(relay_ws_url).await.unwrap());
-	let relay_client = OnlineClient::<PolkadotConfig>::from_rpc_client(relay_ws_client.clone())
+	let relay = RelayNode::connect(&opts.relay_node)
 		.await
+		.map_err(|e| {
+			log::error!("could not connect to the relaychain node: {:?}", e);
+			std::process::exit(1);
+		})
 		.unwrap();
-	let para_ws_client = Arc::new(WsClientBuilder::default().build(para_ws_url).await.unwrap());
-	let para_client = OnlineClient::<ComposableConfig>::from_rpc_client(para_ws_client.clone())
+	let para = ParachainNode::connect(&opts.para_node, key)
 		.await
+		.map_err(|e| {
+			log::error!("could not connect to the parachain node: {:?}", e);
+			std::process::exit(1);
+		})
 		.unwrap();
 
-	while true {
-		for i in &tuple {
-			let keys = vec![i.1.as_ref()];
-
-			let state_proof: Vec<Vec<u8>> = relay_client
-				.rpc()
-				.read_proof(keys.iter().map(AsRef::as_ref), None)
-				.await
-				.unwrap()
-				.proof
-				.into_iter()
-				.map(|p| p.0)
-				.collect();
-			assert!(state_proof.len() > 0);
-
-			let block_hash = relay_client.rpc().block_hash(None).await.unwrap().unwrap();
-			println!("block_hash: {:?}", block_hash);
-
-			use subxt::utils::AccountId32;
-			let account_id = AccountId32::from_str(i.0).unwrap();
-			let staking = relaychain::api::storage().staking().ledger(account_id);
-
-			let Some(ledger) = relay_client.storage().at(block_hash).fetch(&staking).await.unwrap()
-			else {
-				println!("ledger not found");
-				continue;
-			};
-
-			let sl = hyperspace_core::substrate::composable::relaychain::api::runtime_types::pallet_staking::StakingLedger::try_from(ledger).unwrap();
-			println!("sl: {:?}", sl);
-
-			let mut unlocking = vec![];
-			for chunk in sl.unlocking.0.iter() {
-				let e = UnlockChunk { value: chunk.value, era: chunk.era };
-				unlocking.push(e);
-			}
-
-			let mut claimed_rewards = vec![];
-
-			for claimed_reward in sl.claimed_rewards.0.iter() {
-				let e = claimed_reward.clone();
-				claimed_rewards.push(e);
-			}
-
-			let xxx = StakingLedger::<AccountId32, u128> {
-				stash: AccountId32::from_str(i.0).unwrap(),
-				total: sl.total,
-				active: sl.active,
-				unlocking,
-				claimed_rewards,
-			};
-			let tx_set_staking_ledger = parachain_subxt::api::tx()
-				.pallet_liquid_staking()
-				.set_staking_ledger(i.2, xxx, state_proof);
-			let tx_value =
-				parachain_subxt::api::tx().pallet_liquid_staking().initiate_exchange_rate();
-
-			let api = OnlineClient::<subxt::SubstrateConfig>::from_url("ws://127.0.0.1:8000")
-				.await
-				.unwrap();
-
-			use subxt::ext::sp_core::Pair;
-			//test wallet for lsd testing 5DPqUqEfnp3buHaqiVnPt8ryykJEQRgdqAjbscnrZG2qDADa
-			let key = sp_keyring::sr25519::sr25519::Pair::from_string(
-				&"private sentence hip meadow place say issue winner express edge royal aerobic",
-				None,
-			)
-			.expect("secret");
-			let signer: PairSigner<SubstrateConfig, sp_keyring::sr25519::sr25519::Pair> =
-				PairSigner::new(key.clone());
-
-			sign_and_submit_staking_ledger_update(&api, tx_set_staking_ledger, signer).await;
-			tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-		}
+	Relayer::new(relay, para, opts.retries, retry_delay)
+		.run(interval, opts.rounds)
+		.await;
+}
 
-		//sleep 5 hours before next submit of ledger proof to lsd pallet on composable
-		tokio::time::sleep(std::time::Duration::from_secs(60 * 50 * 5)).await;
+/// Relay the ledgers of a mock relaychain to a mock parachain rejecting its first extrinsic, and
+/// check that the parachain caught up.
+async fn run_mock(retries: u32, rounds: u32) -> bool {
+	const PARA_ID: u32 = 2019;
+	const DERIVATIVES: u16 = 6;
+
+	let relay = MockRelay::default();
+	relay.state().para_heads.insert(PARA_ID);
+	relay.set_current_era(1000);
+	let para = MockParachain::new(PARA_ID, (0..DERIVATIVES).collect());
+	para.state().reject_next = 1;
+	for index in 0..DERIVATIVES {
+		relay.bond(PARA_ID, index, 1_000_000_000_000 * u128::from(index + 1));
 	}
 
-	todo!();
-}
-
-async fn sign_and_submit_staking_ledger_update(
-	api: &OnlineClient<SubstrateConfig>,
-	p: Payload<SetStakingLedger>,
-	s: PairSigner<SubstrateConfig, sp_keyring::sr25519::sr25519::Pair>,
-) {
-	let mut i = 10;
-	while i > 0 {
-		let signed = api.tx().sign_and_submit_then_watch(&p, &s, <_>::default()).await;
-		println!("signed: {:?}", signed);
-		i -= 1;
-		match signed {
-			Ok(_) => {
-				i = 0;
-			},
-			Err(e) => {
-				println!("Error: {:?}", e);
-				tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-			},
-		}
+	Relayer::new(relay.clone(), para.clone(), retries, Duration::ZERO)
+		.run(Duration::ZERO, Some(rounds))
+		.await;
+
+	let sovereign = sovereign_account_id(PARA_ID);
+	let para = para.state();
+	let mut synced = para.current_era == 1000;
+	for index in 0..DERIVATIVES {
+		let stash = derivative_account_id(&sovereign, index);
+		let total = 1_000_000_000_000 * u128::from(index + 1);
+		synced &= para.staking_ledgers.get(&index) == Some(&ledger(stash, total, total));
+	}
+	if synced {
+		log::info!("mock parachain synced after {} extrinsics", para.extrinsics);
+	} else {
+		log::error!("mock parachain is not synced");
 	}
+	synced
 }
-
-// let event = relay_client.events();
-// let stream = relay_client.blocks().subscribe_finalized().await.unwrap()
-// .filter_map(|block| async {
-//     let block = block.ok().unwrap();
-//     let hash = block.hash();
-//     let events = event.at(hash).await.ok().unwrap();
-//     Some(events)
-// });
@@ -0,0 +1,193 @@
+//! In-memory relaychain and parachain, to run the relayer end to end without nodes.
+//!
+//! Proofs of the mock relaychain are the encoded `(storage_root, key, value)` triples, which the
+//! mock parachain checks against its relay parent and the key it expects, like the liquid staking
+//! pallet does with trie proofs.
+
+use crate::{
+	accounts::{
+		current_era_key, derivative_account_id, sovereign_account_id, staking_ledger_key,
+		AccountId, DerivativeIndex, EraIndex,
+	},
+	chain::{Parachain, RelayChain, RelayerError},
+};
+use async_trait::async_trait;
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	sync::{Arc, Mutex},
+};
+use subxt::{
+	ext::codec::{Compact, Encode},
+	utils::H256,
+};
+
+/// Encoded `Staking::Ledger` of `stash` with nothing unlocking.
+pub fn ledger(stash: AccountId, total: u128, active: u128) -> Vec<u8> {
+	(
+		stash,
+		Compact(total),
+		Compact(active),
+		Vec::<(Compact<u128>, Compact<u32>)>::new(),
+		Vec::<u32>::new(),
+	)
+		.encode()
+}
+
+/// Storage root of the mock relaychain block `number`.
+pub fn storage_root(number: u32) -> H256 {
+	H256::from_low_u64_be(number.into())
+}
+
+#[derive(Default)]
+pub struct MockRelayState {
+	/// Best block, the storage root of a block is its number unless it is `forked`.
+	pub block: u32,
+	pub forked: BTreeSet<u32>,
+	pub para_heads: BTreeSet<u32>,
+	pub storage: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+#[derive(Clone, Default)]
+pub struct MockRelay(pub Arc<Mutex<MockRelayState>>);
+
+impl MockRelay {
+	pub fn set_current_era(&self, era: EraIndex) {
+		self.state().storage.insert(current_era_key(), era.encode());
+	}
+
+	/// Bond `total` from the derivative `index` of the parachain `para_id`.
+	pub fn bond(&self, para_id: u32, index: DerivativeIndex, total: u128) {
+		let stash = derivative_account_id(&sovereign_account_id(para_id), index);
+		self.state()
+			.storage
+			.insert(staking_ledger_key(&stash), ledger(stash, total, total));
+	}
+
+	pub fn state(&self) -> std::sync::MutexGuard<'_, MockRelayState> {
+		self.0.lock().expect("mock relay state is not poisoned")
+	}
+}
+
+#[async_trait]
+impl RelayChain for MockRelay {
+	async fn proof_anchor(
+		&self,
+		para_id: u32,
+		relay_parent_number: u32,
+		relay_parent_storage_root: H256,
+	) -> Result<H256, RelayerError> {
+		let state = self.state();
+		if relay_parent_number > state.block {
+			return Err(RelayerError::MissingBlock(relay_parent_number))
+		}
+		if state.forked.contains(&relay_parent_number) ||
+			relay_parent_storage_root != storage_root(relay_parent_number)
+		{
+			return Err(RelayerError::UnknownRelayParent(relay_parent_number))
+		}
+		if !state.para_heads.contains(&para_id) {
+			return Err(RelayerError::MissingParaHead(para_id))
+		}
+		Ok(storage_root(relay_parent_number))
+	}
+
+	async fn read(&self, _at: H256, key: &[u8]) -> Result<Option<Vec<u8>>, RelayerError> {
+		Ok(self.state().storage.get(key).cloned())
+	}
+
+	async fn read_proof(&self, at: H256, keys: &[Vec<u8>]) -> Result<Vec<Vec<u8>>, RelayerError> {
+		let state = self.state();
+		Ok(keys.iter().map(|key| (at, key, state.storage.get(key)).encode()).collect())
+	}
+}
+
+#[derive(Default)]
+pub struct MockParachainState {
+	pub para_id: u32,
+	pub derivative_index_list: Vec<DerivativeIndex>,
+	/// Number of the relay parent of the latest block.
+	pub relay_parent: u32,
+	/// Relay parents the accepted proofs were checked against.
+	pub anchors: Vec<u32>,
+	pub current_era: EraIndex,
+	pub staking_ledgers: BTreeMap<DerivativeIndex, Vec<u8>>,
+	/// Number of the next extrinsics to reject, to exercise the retries.
+	pub reject_next: u32,
+	pub extrinsics: u32,
+}
+
+#[derive(Clone, Default)]
+pub struct MockParachain(pub Arc<Mutex<MockParachainState>>);
+
+impl MockParachain {
+	pub fn new(para_id: u32, derivative_index_list: Vec<DerivativeIndex>) -> Self {
+		Self(Arc::new(Mutex::new(MockParachainState {
+			para_id,
+			derivative_index_list,
+			..Default::default()
+		})))
+	}
+
+	pub fn state(&self) -> std::sync::MutexGuard<'_, MockParachainState> {
+		self.0.lock().expect("mock parachain state is not poisoned")
+	}
+
+	/// Count the extrinsic and check that `proof` proves `value` at `key` in the relay parent.
+	fn dispatch(&self, key: Vec<u8>, value: &[u8], proof: &[Vec<u8>]) -> Result<(), RelayerError> {
+		let mut state = self.state();
+		state.extrinsics += 1;
+		if state.reject_next > 0 {
+			state.reject_next -= 1;
+			return Err(RelayerError::Rejected("Unavailable".into()))
+		}
+		let relay_parent = state.relay_parent;
+		if !proof.contains(&(storage_root(relay_parent), key, Some(value)).encode()) {
+			return Err(RelayerError::Rejected("InvalidProof".into()))
+		}
+		state.anchors.push(relay_parent);
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl Parachain for MockParachain {
+	async fn para_id(&self) -> Result<u32, RelayerError> {
+		Ok(self.state().para_id)
+	}
+
+	async fn relay_parent(&self) -> Result<(u32, H256), RelayerError> {
+		let relay_parent = self.state().relay_parent;
+		Ok((relay_parent, storage_root(relay_parent)))
+	}
+
+	async fn derivative_index_list(&self) -> Result<Vec<DerivativeIndex>, RelayerError> {
+		Ok(self.state().derivative_index_list.clone())
+	}
+
+	async fn current_era(&self) -> Result<EraIndex, RelayerError> {
+		Ok(self.state().current_era)
+	}
+
+	async fn set_staking_ledger(
+		&self,
+		derivative_index: DerivativeIndex,
+		ledger: Vec<u8>,
+		proof: Vec<Vec<u8>>,
+	) -> Result<(), RelayerError> {
+		let para_id = self.state().para_id;
+		let stash = derivative_account_id(&sovereign_account_id(para_id), derivative_index);
+		self.dispatch(staking_ledger_key(&stash), &ledger, &proof)?;
+		self.state().staking_ledgers.insert(derivative_index, ledger);
+		Ok(())
+	}
+
+	async fn set_current_era(
+		&self,
+		era: EraIndex,
+		proof: Vec<Vec<u8>>,
+	) -> Result<(), RelayerError> {
+		self.dispatch(current_era_key(), &era.encode(), &proof)?;
+		self.state().current_era = era;
+		Ok(())
+	}
+}
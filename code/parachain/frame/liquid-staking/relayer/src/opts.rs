@@ -0,0 +1,43 @@
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[clap(version = "1.0", author = "Composable")]
+pub struct Opts {
+	/// WebSocket address of the relaychain node the ledgers are read and proven from.
+	#[clap(long, default_value = "ws://127.0.0.1:8001")]
+	pub relay_node: String,
+
+	/// WebSocket address of the parachain node running the liquid staking pallet.
+	#[clap(long, default_value = "ws://127.0.0.1:8000")]
+	pub para_node: String,
+
+	/// Keystore file of the sr25519 key signing the extrinsics, either a file of a substrate
+	/// keystore or a plain text file holding the secret URI.
+	#[clap(long, required_unless_present = "mock")]
+	pub keystore: Option<String>,
+
+	/// Password of the key in the keystore file.
+	#[clap(long, env = "LSD_RELAYER_KEYSTORE_PASSWORD", hide_env_values = true)]
+	pub keystore_password: Option<String>,
+
+	/// Seconds between two rounds of updates.
+	#[clap(long, default_value = "3600")]
+	pub interval: u64,
+
+	/// Attempts of an update, each with a new proof, before giving up until the next round.
+	#[clap(long, default_value = "10")]
+	pub retries: u32,
+
+	/// Seconds between two attempts of an update.
+	#[clap(long, default_value = "10")]
+	pub retry_delay: u64,
+
+	/// Stop after this number of rounds instead of running forever.
+	#[clap(long)]
+	pub rounds: Option<u32>,
+
+	/// Run against an in-memory relaychain and parachain instead of nodes, checking that every
+	/// ledger and the era were relayed.
+	#[clap(long)]
+	pub mock: bool,
+}
@@ -0,0 +1,236 @@
+use crate::{
+	accounts::{
+		current_era_key, derivative_account_id, sovereign_account_id, staking_ledger_key,
+		DerivativeIndex, EraIndex,
+	},
+	chain::{Parachain, RelayChain, RelayerError},
+};
+use std::{collections::HashMap, future::Future, time::Duration};
+use subxt::{ext::codec::Decode, utils::H256};
+
+/// Relays the staking ledgers of the derivative accounts and the era of the relaychain to the
+/// liquid staking pallet.
+pub struct Relayer<R, P> {
+	relay: R,
+	para: P,
+	/// Attempts of an update before giving up until the next round.
+	retries: u32,
+	retry_delay: Duration,
+	/// Ledgers submitted so far, not submitted again until they change on the relaychain.
+	submitted: HashMap<DerivativeIndex, Vec<u8>>,
+}
+
+/// Outcome of a round.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Round {
+	pub era: Option<EraIndex>,
+	pub ledgers: Vec<DerivativeIndex>,
+	pub failed: Vec<DerivativeIndex>,
+}
+
+impl<R: RelayChain, P: Parachain> Relayer<R, P> {
+	pub fn new(relay: R, para: P, retries: u32, retry_delay: Duration) -> Self {
+		Self { relay, para, retries: retries.max(1), retry_delay, submitted: HashMap::new() }
+	}
+
+	/// Relay the era if the relaychain moved to a new one, then the ledgers which changed.
+	pub async fn round(&mut self) -> Result<Round, RelayerError> {
+		let para_id = self.para.para_id().await?;
+		let sovereign = sovereign_account_id(para_id);
+		let mut round = Round { era: self.relay_era(para_id).await?, ..Default::default() };
+		for index in self.para.derivative_index_list().await? {
+			let key = staking_ledger_key(&derivative_account_id(&sovereign, index));
+			match self.relay_ledger(para_id, index, key).await {
+				Ok(true) => round.ledgers.push(index),
+				Ok(false) => {},
+				Err(e) => {
+					log::error!("could not relay the ledger of derivative {}: {:?}", index, e);
+					round.failed.push(index);
+				},
+			}
+		}
+		Ok(round)
+	}
+
+	/// Run a round every `interval`, stopping after `rounds` rounds if set.
+	pub async fn run(&mut self, interval: Duration, rounds: Option<u32>) {
+		let mut round = 0;
+		while rounds.map_or(true, |rounds| round < rounds) {
+			match self.round().await {
+				Ok(outcome) => log::info!("round {}: {:?}", round, outcome),
+				Err(e) => log::error!("round {} failed: {:?}", round, e),
+			}
+			round += 1;
+			if rounds.map_or(true, |rounds| round < rounds) {
+				tokio::time::sleep(interval).await;
+			}
+		}
+	}
+
+	/// Relaychain block the proofs submitted next are checked against.
+	async fn proof_anchor(&self, para_id: u32) -> Result<H256, RelayerError> {
+		let (number, storage_root) = self.para.relay_parent().await?;
+		self.relay.proof_anchor(para_id, number, storage_root).await
+	}
+
+	async fn relay_era(&self, para_id: u32) -> Result<Option<EraIndex>, RelayerError> {
+		let current_era = self.para.current_era().await?;
+		self.with_retries(|| async move {
+			let at = self.proof_anchor(para_id).await?;
+			let key = current_era_key();
+			let Some(era) = self.relay.read(at, &key).await? else { return Ok(None) };
+			let era = EraIndex::decode(&mut &era[..])?;
+			if era <= current_era {
+				return Ok(None)
+			}
+			let proof = self.relay.read_proof(at, &[key]).await?;
+			self.para.set_current_era(era, proof).await?;
+			Ok(Some(era))
+		})
+		.await
+	}
+
+	/// Relay the ledger stored at `key`, returning whether it was submitted.
+	async fn relay_ledger(
+		&mut self,
+		para_id: u32,
+		index: DerivativeIndex,
+		key: Vec<u8>,
+	) -> Result<bool, RelayerError> {
+		let (key, submitted) = (&key, self.submitted.get(&index));
+		let ledger = self
+			.with_retries(|| async move {
+				let at = self.proof_anchor(para_id).await?;
+				let Some(ledger) = self.relay.read(at, key).await? else { return Ok(None) };
+				if Some(&ledger) == submitted {
+					return Ok(None)
+				}
+				let proof = self.relay.read_proof(at, &[key.clone()]).await?;
+				self.para.set_staking_ledger(index, ledger.clone(), proof).await?;
+				Ok(Some(ledger))
+			})
+			.await?;
+		Ok(match ledger {
+			Some(ledger) => {
+				self.submitted.insert(index, ledger);
+				true
+			},
+			None => false,
+		})
+	}
+
+	/// Run `attempt` until it succeeds, at most `retries` times. Proofs are built again by each
+	/// attempt, as they are only valid while the parachain did not move to another relay parent.
+	async fn with_retries<T, F, Fut>(&self, attempt: F) -> Result<T, RelayerError>
+	where
+		F: Fn() -> Fut,
+		Fut: Future<Output = Result<T, RelayerError>>,
+	{
+		let mut tries = 1;
+		loop {
+			match attempt().await {
+				Ok(value) => return Ok(value),
+				Err(e) if tries < self.retries => {
+					log::warn!("attempt {} of {} failed: {:?}", tries, self.retries, e);
+					tries += 1;
+					tokio::time::sleep(self.retry_delay).await;
+				},
+				Err(e) => return Err(e),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Relayer, Round};
+	use crate::{
+		accounts::{derivative_account_id, sovereign_account_id},
+		chain::RelayerError,
+		mock::{ledger, MockParachain, MockRelay},
+	};
+	use std::time::Duration;
+
+	const PARA_ID: u32 = 2019;
+
+	fn setup(retries: u32) -> (MockRelay, MockParachain, Relayer<MockRelay, MockParachain>) {
+		let relay = MockRelay::default();
+		relay.state().para_heads.insert(PARA_ID);
+		let para = MockParachain::new(PARA_ID, vec![0, 1, 2]);
+		let relayer = Relayer::new(relay.clone(), para.clone(), retries, Duration::ZERO);
+		(relay, para, relayer)
+	}
+
+	#[tokio::test]
+	async fn test_relay_era_and_ledgers() {
+		let (relay, para, mut relayer) = setup(1);
+		relay.set_current_era(1000);
+		relay.bond(PARA_ID, 0, 100);
+		relay.bond(PARA_ID, 2, 200);
+
+		let round = relayer.round().await.expect("round succeeds");
+		assert_eq!(round, Round { era: Some(1000), ledgers: vec![0, 2], failed: vec![] });
+		let stash = derivative_account_id(&sovereign_account_id(PARA_ID), 2);
+		assert_eq!(para.state().current_era, 1000);
+		assert_eq!(para.state().staking_ledgers.get(&2), Some(&ledger(stash, 200, 200)));
+		assert_eq!(para.state().extrinsics, 3);
+
+		// nothing changed
+		assert_eq!(relayer.round().await.expect("round succeeds"), Round::default());
+		assert_eq!(para.state().extrinsics, 3);
+
+		relay.set_current_era(1001);
+		relay.bond(PARA_ID, 2, 250);
+		let round = relayer.round().await.expect("round succeeds");
+		assert_eq!(round, Round { era: Some(1001), ledgers: vec![2], failed: vec![] });
+		assert_eq!(para.state().staking_ledgers.get(&2), Some(&ledger(stash, 250, 250)));
+	}
+
+	#[tokio::test]
+	async fn test_retry_rejected_extrinsics() {
+		let (relay, para, mut relayer) = setup(3);
+		relay.bond(PARA_ID, 1, 100);
+		para.state().reject_next = 2;
+		let round = relayer.round().await.expect("round succeeds");
+		assert_eq!(round, Round { era: None, ledgers: vec![1], failed: vec![] });
+		assert_eq!(para.state().extrinsics, 3);
+
+		relay.bond(PARA_ID, 1, 200);
+		para.state().reject_next = 3;
+		let round = relayer.round().await.expect("round succeeds");
+		assert_eq!(round, Round { era: None, ledgers: vec![], failed: vec![1] });
+
+		// submitted again once accepted
+		let round = relayer.round().await.expect("round succeeds");
+		assert_eq!(round, Round { era: None, ledgers: vec![1], failed: vec![] });
+	}
+
+	#[tokio::test]
+	async fn test_missing_para_head() {
+		let (relay, _para, mut relayer) = setup(1);
+		relay.state().para_heads.clear();
+		relay.set_current_era(1);
+		assert!(matches!(relayer.round().await, Err(RelayerError::MissingParaHead(PARA_ID))));
+	}
+
+	#[tokio::test]
+	async fn test_anchor_proofs_at_relay_parent() {
+		let (relay, para, mut relayer) = setup(1);
+		relay.set_current_era(1);
+		relay.state().block = 10;
+		para.state().relay_parent = 8;
+		let round = relayer.round().await.expect("round succeeds");
+		assert_eq!(round.era, Some(1));
+		assert_eq!(para.state().anchors, vec![8]);
+
+		// the relay parent of the parachain is not on the relaychain the node follows
+		relay.set_current_era(2);
+		relay.state().forked.insert(9);
+		para.state().relay_parent = 9;
+		assert!(matches!(relayer.round().await, Err(RelayerError::UnknownRelayParent(9))));
+
+		// or not known yet
+		para.state().relay_parent = 11;
+		assert!(matches!(relayer.round().await, Err(RelayerError::MissingBlock(11))));
+	}
+}
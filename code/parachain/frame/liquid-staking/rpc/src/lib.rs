@@ -0,0 +1,236 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::{
+	defi::Rate,
+	liquid_staking::{
+		DerivativeLedger, EraIndex, LiquidStakingPosition, PendingXcmRequest, StakeSimulation,
+		UnstakeSimulation,
+	},
+};
+use core::{fmt::Display, str::FromStr};
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use liquid_staking_runtime_api::LiquidStakingRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, MaybeSerializeDeserialize};
+use sp_std::{sync::Arc, vec::Vec};
+
+#[rpc(client, server)]
+pub trait LiquidStakingApi<BlockHash, AccountId, Balance>
+where
+	Balance: FromStr + Display,
+{
+	#[method(name = "liquidStaking_exchangeRate")]
+	fn exchange_rate(&self, at: Option<BlockHash>) -> RpcResult<Rate>;
+
+	#[method(name = "liquidStaking_currentEra")]
+	fn current_era(&self, at: Option<BlockHash>) -> RpcResult<EraIndex>;
+
+	#[method(name = "liquidStaking_position")]
+	fn position(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<LiquidStakingPosition<SafeRpcWrapper<Balance>>>;
+
+	#[method(name = "liquidStaking_stakingLedgers")]
+	fn staking_ledgers(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<DerivativeLedger<SafeRpcWrapper<Balance>>>>;
+
+	#[method(name = "liquidStaking_simulateStake")]
+	fn simulate_stake(
+		&self,
+		amount: SafeRpcWrapper<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<StakeSimulation<SafeRpcWrapper<Balance>>>>;
+
+	#[method(name = "liquidStaking_simulateUnstake")]
+	fn simulate_unstake(
+		&self,
+		liquid_amount: SafeRpcWrapper<Balance>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<UnstakeSimulation<SafeRpcWrapper<Balance>>>>;
+
+	#[method(name = "liquidStaking_pendingXcmRequests")]
+	fn pending_xcm_requests(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PendingXcmRequest<SafeRpcWrapper<Balance>, u32>>>;
+}
+
+pub struct LiquidStaking<C, Block> {
+	client: Arc<C>,
+	_marker: sp_std::marker::PhantomData<Block>,
+}
+
+impl<C, M> LiquidStaking<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Balance>
+	LiquidStakingApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for LiquidStaking<C, (Block, AccountId, Balance)>
+where
+	Block: BlockT,
+	AccountId: Send + Sync + 'static + Codec + MaybeSerializeDeserialize,
+	Balance: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: LiquidStakingRuntimeApi<Block, AccountId, Balance>,
+{
+	fn exchange_rate(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Rate> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.exchange_rate(at);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn current_era(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<EraIndex> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.current_era(at);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn position(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<LiquidStakingPosition<SafeRpcWrapper<Balance>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.position(at, who);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn staking_ledgers(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<DerivativeLedger<SafeRpcWrapper<Balance>>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.staking_ledgers(at);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn simulate_stake(
+		&self,
+		amount: SafeRpcWrapper<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<StakeSimulation<SafeRpcWrapper<Balance>>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.simulate_stake(at, amount);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn simulate_unstake(
+		&self,
+		liquid_amount: SafeRpcWrapper<Balance>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<UnstakeSimulation<SafeRpcWrapper<Balance>>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.simulate_unstake(at, liquid_amount);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn pending_xcm_requests(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PendingXcmRequest<SafeRpcWrapper<Balance>, u32>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let api_version = api
+			.api_version::<dyn LiquidStakingRuntimeApi<Block, AccountId, Balance>>(at)
+			.map_err(|e| {
+				RpcError::Call(CallError::Custom(ErrorObject::owned(
+					9876,
+					"Something wrong",
+					Some(format!("{:?}", e)),
+				)))
+			})?;
+		if api_version.unwrap_or_default() < 2 {
+			return Err(RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some("pending xcm requests are not provided by the runtime at this block"),
+			))))
+		}
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.pending_xcm_requests(at);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+}
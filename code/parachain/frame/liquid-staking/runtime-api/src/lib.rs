@@ -0,0 +1,54 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::{
+	defi::Rate,
+	liquid_staking::{
+		DerivativeLedger, EraIndex, LiquidStakingPosition, PendingXcmRequest, StakeSimulation,
+		UnstakeSimulation,
+	},
+};
+use sp_std::vec::Vec;
+
+// Liquid staking Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	#[api_version(2)]
+	pub trait LiquidStakingRuntimeApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Staking currency per unit of liquid currency.
+		fn exchange_rate() -> Rate;
+
+		/// Era of the relaychain staking the pallet is at.
+		fn current_era() -> EraIndex;
+
+		/// Liquid currency, pending unlocks and fast unstake request of `who`.
+		fn position(who: AccountId) -> LiquidStakingPosition<SafeRpcWrapper<Balance>>;
+
+		/// Ledgers of the derivative accounts on the relaychain, by derivative index.
+		fn staking_ledgers() -> Vec<DerivativeLedger<SafeRpcWrapper<Balance>>>;
+
+		/// Liquid currency minted and fees charged when staking `amount`, `None` if staking it
+		/// would fail.
+		fn simulate_stake(
+			amount: SafeRpcWrapper<Balance>,
+		) -> Option<StakeSimulation<SafeRpcWrapper<Balance>>>;
+
+		/// Staking currency unlocked when unstaking `liquid_amount`, `None` if unstaking it would
+		/// fail.
+		fn simulate_unstake(
+			liquid_amount: SafeRpcWrapper<Balance>,
+		) -> Option<UnstakeSimulation<SafeRpcWrapper<Balance>>>;
+
+		/// XCM requests waiting for their response or to be resolved, by query id. Blocks are
+		/// parachain block numbers.
+		#[api_version(2)]
+		fn pending_xcm_requests() -> Vec<PendingXcmRequest<SafeRpcWrapper<Balance>, u32>>;
+	}
+}
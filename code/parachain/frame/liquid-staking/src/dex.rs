@@ -0,0 +1,50 @@
+use composable_traits::dex::{Amm, AssetAmount};
+use frame_support::Parameter;
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+
+/// Exchange on which `UnstakeProvider::Dex` sells the liquid currency for the staking currency.
+pub trait LiquidDex<AccountId, AssetId, Balance> {
+	/// Pool the liquid currency is sold in, set through `set_dex_pool`.
+	type PoolId: Parameter;
+
+	/// Sell `liquid` from `who` for at least `min_receive`, returning the amount received.
+	fn sell(
+		who: &AccountId,
+		pool_id: Self::PoolId,
+		liquid: AssetAmount<AssetId, Balance>,
+		min_receive: AssetAmount<AssetId, Balance>,
+	) -> Result<Balance, DispatchError>;
+}
+
+/// No exchange, unstaking through the dex always falls back to the matching pool.
+impl<AccountId, AssetId, Balance> LiquidDex<AccountId, AssetId, Balance> for () {
+	type PoolId = ();
+
+	fn sell(
+		_who: &AccountId,
+		_pool_id: Self::PoolId,
+		_liquid: AssetAmount<AssetId, Balance>,
+		_min_receive: AssetAmount<AssetId, Balance>,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("no dex"))
+	}
+}
+
+/// Sell through an [`Amm`], e.g. Pablo or the dex router.
+pub struct AmmDex<A>(PhantomData<A>);
+impl<A: Amm> LiquidDex<A::AccountId, A::AssetId, A::Balance> for AmmDex<A>
+where
+	A::PoolId: Parameter,
+{
+	type PoolId = A::PoolId;
+
+	fn sell(
+		who: &A::AccountId,
+		pool_id: Self::PoolId,
+		liquid: AssetAmount<A::AssetId, A::Balance>,
+		min_receive: AssetAmount<A::AssetId, A::Balance>,
+	) -> Result<A::Balance, DispatchError> {
+		A::do_swap(who, pool_id, liquid, min_receive, false).map(|result| result.value.amount)
+	}
+}
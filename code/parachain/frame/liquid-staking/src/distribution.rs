@@ -1,6 +1,6 @@
 use frame_support::traits::tokens::Balance as BalanceT;
-use sp_runtime::FixedPointOperand;
-use sp_std::vec::Vec;
+use sp_runtime::{FixedPointOperand, Perbill};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 use crate::types::DerivativeIndex;
 use sp_std::vec;
@@ -144,7 +144,26 @@ impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for Ma
 	) -> Vec<(DerivativeIndex, Balance)> {
 		// descending sequence
 		active_bonded_amounts.sort_by(|a, b| b.1.cmp(&a.1));
+		Self::get_unbond_distributions_in_order(active_bonded_amounts, input, min_nominator_bond)
+	}
+
+	fn get_rebond_distributions(
+		mut unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		// descending sequence
+		unbonding_amounts.sort_by(|a, b| b.1.cmp(&a.1));
+		Self::get_rebond_distributions_in_order(unbonding_amounts, input)
+	}
+}
 
+impl MaxMinDistribution {
+	/// Unbond from the derivatives in the given order.
+	fn get_unbond_distributions_in_order<Balance: BalanceT + FixedPointOperand>(
+		active_bonded_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
 		let mut distributions: Vec<(DerivativeIndex, Balance)> = vec![];
 		let mut remain = input;
 
@@ -163,13 +182,11 @@ impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for Ma
 		distributions
 	}
 
-	fn get_rebond_distributions(
-		mut unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
+	/// Rebond to the derivatives in the given order.
+	fn get_rebond_distributions_in_order<Balance: BalanceT + FixedPointOperand>(
+		unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
 		input: Balance,
 	) -> Vec<(DerivativeIndex, Balance)> {
-		// descending sequence
-		unbonding_amounts.sort_by(|a, b| b.1.cmp(&a.1));
-
 		let mut distributions: Vec<(DerivativeIndex, Balance)> = vec![];
 		let mut remain = input;
 
@@ -188,3 +205,270 @@ impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for Ma
 		distributions
 	}
 }
+
+/// Bond to the derivatives with the most remaining capacity first, filling each one up to the cap
+/// before moving on to the next. Unbond and rebond like `MaxMinDistribution`.
+pub struct LargestCapacityFirst;
+impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for LargestCapacityFirst {
+	fn get_bond_distributions(
+		bonded_amounts: Vec<(DerivativeIndex, Balance, Balance)>,
+		input: Balance,
+		cap: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		let mut capacities: Vec<(DerivativeIndex, Balance, Balance)> = bonded_amounts
+			.into_iter()
+			.map(|(index, active_bonded, total_bonded)| {
+				(index, active_bonded, cap.saturating_sub(total_bonded))
+			})
+			.collect();
+		// descending sequence
+		capacities.sort_by(|a, b| b.2.cmp(&a.2));
+
+		let mut distributions: Vec<(DerivativeIndex, Balance)> = vec![];
+		let mut remain = input;
+
+		for (index, active_bonded, capacity) in capacities.into_iter() {
+			if remain.is_zero() {
+				break
+			}
+			let amount = capacity.min(remain);
+			if amount.is_zero() {
+				break
+			}
+
+			if amount.saturating_add(active_bonded) < min_nominator_bond {
+				continue
+			}
+
+			distributions.push((index, amount));
+			remain = remain.saturating_sub(amount);
+		}
+
+		distributions
+	}
+
+	fn get_unbond_distributions(
+		active_bonded_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		MaxMinDistribution::get_unbond_distributions(
+			active_bonded_amounts,
+			input,
+			min_nominator_bond,
+		)
+	}
+
+	fn get_rebond_distributions(
+		unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		MaxMinDistribution::get_rebond_distributions(unbonding_amounts, input)
+	}
+}
+
+/// Send as few XCM calls as possible: the whole amount goes to the derivative which can take it
+/// with the least room left, otherwise it is split starting with the largest derivatives.
+pub struct MinimizeCalls;
+impl MinimizeCalls {
+	/// Index of the smallest `available` amount which covers `input`.
+	fn best_fit<Balance: BalanceT>(
+		available: impl Iterator<Item = (DerivativeIndex, Balance)>,
+		input: Balance,
+	) -> Option<DerivativeIndex> {
+		available
+			.filter(|(_, available)| *available >= input)
+			.min_by(|a, b| a.1.cmp(&b.1))
+			.map(|(index, _)| index)
+	}
+}
+
+impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for MinimizeCalls {
+	fn get_bond_distributions(
+		bonded_amounts: Vec<(DerivativeIndex, Balance, Balance)>,
+		input: Balance,
+		cap: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		if input.is_zero() {
+			return Default::default()
+		}
+		let available =
+			bonded_amounts.iter().filter_map(|&(index, active_bonded, total_bonded)| {
+				(input.saturating_add(active_bonded) >= min_nominator_bond)
+					.then(|| (index, cap.saturating_sub(total_bonded)))
+			});
+		match Self::best_fit(available, input) {
+			Some(index) => vec![(index, input)],
+			None => LargestCapacityFirst::get_bond_distributions(
+				bonded_amounts,
+				input,
+				cap,
+				min_nominator_bond,
+			),
+		}
+	}
+
+	fn get_unbond_distributions(
+		active_bonded_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		if input.is_zero() {
+			return Default::default()
+		}
+		let available = active_bonded_amounts
+			.iter()
+			.map(|&(index, bonded)| (index, bonded.saturating_sub(min_nominator_bond)));
+		match Self::best_fit(available, input) {
+			Some(index) => vec![(index, input)],
+			None => MaxMinDistribution::get_unbond_distributions(
+				active_bonded_amounts,
+				input,
+				min_nominator_bond,
+			),
+		}
+	}
+
+	fn get_rebond_distributions(
+		unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		if input.is_zero() {
+			return Default::default()
+		}
+		match Self::best_fit(unbonding_amounts.iter().copied(), input) {
+			Some(index) => vec![(index, input)],
+			None => MaxMinDistribution::get_rebond_distributions(unbonding_amounts, input),
+		}
+	}
+}
+
+/// Performance of the validators nominated by each derivative account.
+pub trait DerivativePerformance {
+	fn performance(index: DerivativeIndex) -> u32;
+}
+
+/// Bond to the derivatives in proportion to their performance, unbond from the worst performing
+/// ones and rebond to the best performing ones first.
+pub struct PerformanceWeighted<P>(PhantomData<P>);
+impl<P: DerivativePerformance> PerformanceWeighted<P> {
+	/// Split `input` in proportion to the weights, without exceeding the capacities. Amounts
+	/// left over by the rounding or the capacities are split again between the others.
+	fn fill<Balance: BalanceT + FixedPointOperand>(
+		derivatives: &[(DerivativeIndex, Balance, u64)],
+		input: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		let mut allocations: Vec<Balance> = vec![Balance::zero(); derivatives.len()];
+		let mut remain = input;
+
+		loop {
+			let open: Vec<usize> =
+				(0..derivatives.len()).filter(|&i| allocations[i] < derivatives[i].1).collect();
+			if remain.is_zero() || open.is_empty() {
+				break
+			}
+			// derivatives without performance only get a share when none has any
+			let total_weight: u64 = open.iter().map(|&i| derivatives[i].2).sum();
+			let weight = |i: usize| if total_weight == 0 { 1 } else { derivatives[i].2 };
+			let total_weight = if total_weight == 0 { open.len() as u64 } else { total_weight };
+
+			let mut distributed = Balance::zero();
+			for &i in open.iter() {
+				let share = Perbill::from_rational(weight(i), total_weight)
+					.mul_floor(remain)
+					.min(derivatives[i].1.saturating_sub(allocations[i]));
+				allocations[i] = allocations[i].saturating_add(share);
+				distributed = distributed.saturating_add(share);
+			}
+
+			if distributed.is_zero() {
+				// the rounding left less than a unit per derivative, which goes to the best ones
+				let mut open = open;
+				open.sort_by(|&a, &b| weight(b).cmp(&weight(a)));
+				for i in open.into_iter() {
+					let amount = remain.min(derivatives[i].1.saturating_sub(allocations[i]));
+					allocations[i] = allocations[i].saturating_add(amount);
+					remain = remain.saturating_sub(amount);
+				}
+				break
+			}
+			remain = remain.saturating_sub(distributed);
+		}
+
+		derivatives
+			.iter()
+			.zip(allocations.into_iter())
+			.filter(|(_, amount)| !amount.is_zero())
+			.map(|(&(index, _, _), amount)| (index, amount))
+			.collect()
+	}
+}
+
+impl<P: DerivativePerformance, Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance>
+	for PerformanceWeighted<P>
+{
+	fn get_bond_distributions(
+		bonded_amounts: Vec<(DerivativeIndex, Balance, Balance)>,
+		input: Balance,
+		cap: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		let mut derivatives: Vec<(DerivativeIndex, Balance, Balance, u64)> = bonded_amounts
+			.into_iter()
+			.map(|(index, active_bonded, total_bonded)| {
+				let capacity = cap.saturating_sub(total_bonded);
+				(index, active_bonded, capacity, P::performance(index).into())
+			})
+			.filter(|(_, _, capacity, _)| !capacity.is_zero())
+			.collect();
+
+		// leave out the derivatives whose share would not reach `min_nominator_bond`
+		loop {
+			let distributions = Self::fill(
+				&derivatives
+					.iter()
+					.map(|&(index, _, capacity, weight)| (index, capacity, weight))
+					.collect::<Vec<_>>(),
+				input,
+			);
+			let len = derivatives.len();
+			derivatives.retain(|&(index, active_bonded, _, _)| {
+				distributions.iter().all(|&(i, amount)| {
+					i != index || amount.saturating_add(active_bonded) >= min_nominator_bond
+				})
+			});
+			if derivatives.len() == len {
+				return distributions
+			}
+		}
+	}
+
+	fn get_unbond_distributions(
+		mut active_bonded_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+		min_nominator_bond: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		// ascending performance, the largest ledgers first
+		active_bonded_amounts.sort_by(|a, b| {
+			P::performance(a.0).cmp(&P::performance(b.0)).then_with(|| b.1.cmp(&a.1))
+		});
+		MaxMinDistribution::get_unbond_distributions_in_order(
+			active_bonded_amounts,
+			input,
+			min_nominator_bond,
+		)
+	}
+
+	fn get_rebond_distributions(
+		mut unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
+		input: Balance,
+	) -> Vec<(DerivativeIndex, Balance)> {
+		// descending performance, the largest unbondings first
+		unbonding_amounts.sort_by(|a, b| {
+			P::performance(b.0).cmp(&P::performance(a.0)).then_with(|| b.1.cmp(&a.1))
+		});
+		MaxMinDistribution::get_rebond_distributions_in_order(unbonding_amounts, input)
+	}
+}
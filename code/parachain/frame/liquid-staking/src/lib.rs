@@ -20,6 +20,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use composable_traits::oracle::ExchangeRateProvider;
 use frame_support::traits::{tokens::Balance as BalanceT, Get};
 use sp_runtime::{
 	traits::{One, Zero},
@@ -28,6 +29,15 @@ use sp_runtime::{
 
 pub use pallet::*;
 use pallet_xcm_helper::ump::RewardDestination;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use crate::{
+	distribution::{
+		AverageDistribution, DerivativePerformance, DistributionStrategy, LargestCapacityFirst,
+		MaxMinDistribution, MinimizeCalls, PerformanceWeighted,
+	},
+	types::DerivativeIndex,
+};
 
 // mod benchmarking;
 
@@ -36,8 +46,10 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod dex;
 pub mod distribution;
-// pub mod migrations;
+pub mod migrations;
+pub mod selection;
 pub mod types;
 pub mod weights;
 pub use weights::WeightInfo;
@@ -77,7 +89,18 @@ pub mod pallet {
 	use sp_trie::StorageProof;
 	use xcm::latest::prelude::*;
 
-	use crate::distribution::*;
+	use crate::{
+		dex::LiquidDex,
+		distribution::*,
+		selection::{select_targets, ValidatorSelection},
+	};
+	use composable_traits::{
+		dex::AssetAmount,
+		liquid_staking::{
+			DerivativeLedger, LiquidStakingPosition, PendingXcmRequest, StakeSimulation, Unlocking,
+			UnstakeSimulation,
+		},
+	};
 
 	use primitives::currency::CurrencyId;
 	pub type Balance = u128;
@@ -93,6 +116,11 @@ pub mod pallet {
 		<<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
 	pub type BalanceOf<T> =
 		<<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type DexPoolIdOf<T> = <<T as Config>::Dex as LiquidDex<
+		<T as frame_system::Config>::AccountId,
+		AssetIdOf<T>,
+		BalanceOf<T>,
+	>>::PoolId;
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
@@ -194,6 +222,20 @@ pub mod pallet {
 		/// Current strategy for distributing assets to multi-accounts
 		type DistributionStrategy: DistributionStrategy<BalanceOf<Self>>;
 
+		/// Strategy ranking the validator candidates nominated at each new era
+		type ValidatorSelection: ValidatorSelection<Self::AccountId>;
+
+		/// Exchange selling the liquid currency for `UnstakeProvider::Dex`
+		type Dex: LiquidDex<Self::AccountId, AssetIdOf<Self>, BalanceOf<Self>>;
+
+		/// Maximum number of validators nominated by each derivative account
+		#[pallet::constant]
+		type MaxNominations: Get<u32>;
+
+		/// Maximum number of validator candidates
+		#[pallet::constant]
+		type MaxValidatorCandidates: Get<u32>;
+
 		/// Number of blocknumbers that do_matching after each era updated.
 		/// Need to do_bond before relaychain store npos solution
 		#[pallet::constant]
@@ -283,6 +325,62 @@ pub mod pallet {
 		SetMembers {
 			members: Vec<T::AccountId>,
 		},
+		/// Validator added to the candidates
+		/// [validator]
+		ValidatorCandidateAdded(T::AccountId),
+		/// Validator removed from the candidates
+		/// [validator]
+		ValidatorCandidateRemoved(T::AccountId),
+		/// Validator preferences proven from the relaychain
+		/// [validator, prefs]
+		ValidatorPrefsUpdated(T::AccountId, ValidatorPrefs),
+		/// Reward points of the candidates proven from the relaychain
+		/// [era_index]
+		ValidatorEraPointsUpdated(EraIndex),
+		/// Distribution strategy used by `SelectableDistribution` was updated
+		DistributionStrategyUpdated(DistributionStrategyKind),
+		/// Pool used by `UnstakeProvider::Dex` was updated
+		DexPoolUpdated(Option<DexPoolIdOf<T>>),
+		/// Liquid currency was sold on the dex
+		/// [account_id, liquid_amount, received_staking_amount]
+		DexUnstaked(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Liquid currency could not be sold on the dex within the max discount, a fast unstake
+		/// was requested from the matching pool instead
+		/// [account_id, liquid_amount]
+		DexUnstakeFellBack(T::AccountId, BalanceOf<T>),
+		/// Policy accounting for the slashes of the derivative accounts was updated
+		SlashPolicyUpdated(SlashPolicy),
+		/// Derivative account was slashed on the relaychain, `covered_amount` was taken from the
+		/// reserves to be bonded again and the rest lowered the exchange rate
+		/// [derivative_index, slashed_amount, covered_amount]
+		Slashed(DerivativeIndex, BalanceOf<T>, BalanceOf<T>),
+		/// Timeout of a kind of xcm request was updated
+		XcmRequestTimeoutUpdated(XcmRequestKind, Option<XcmRequestTimeout<BlockNumberFor<T>>>),
+		/// Xcm request got no response within its timeout, it is stuck until its response or
+		/// `resolve_xcm_request`
+		/// [query_id]
+		XcmRequestTimedOut(QueryId),
+		/// Failed xcm request will be sent again
+		/// [query_id, retry_block_number]
+		XcmRequestRetryScheduled(QueryId, BlockNumberFor<T>),
+		/// Xcm request was sent again
+		/// [query_id, new_query_id]
+		XcmRequestRetried(QueryId, QueryId),
+		/// Xcm request was dropped, releasing what it locked in the matching pool
+		/// [query_id]
+		XcmRequestRolledBack(QueryId),
+		/// Failed or timed out xcm request waits to be resolved by `resolve_xcm_request`
+		/// [query_id]
+		XcmRequestStuck(QueryId),
+		/// Xcm request was resolved by `UpdateOrigin`
+		/// [query_id, resolution]
+		XcmRequestResolved(QueryId, XcmRequestResolution),
+		/// Sent xcm message to chill the derivative account
+		/// [derivative_index]
+		Chilling(DerivativeIndex),
+		/// Sent xcm message to set the reward destination of the derivative account
+		/// [derivative_index, payee]
+		SettingPayee(DerivativeIndex, RewardDestination<T::AccountId>),
 	}
 
 	#[pallet::error]
@@ -328,8 +426,24 @@ pub mod pallet {
 		NoUnlockings,
 		/// Invalid commission rate
 		InvalidCommissionRate,
+		/// Can not add more validator candidates, `MaxValidatorCandidates`.
+		TooManyValidatorCandidates,
+		/// Validator is already a candidate
+		ValidatorCandidateAlreadyExists,
+		/// Validator is not a candidate
+		ValidatorCandidateNotFound,
+		/// Reward points of a more recent era were already proven
+		StaleEraPoints,
+		/// No pool was set for `UnstakeProvider::Dex`
+		DexPoolNotSet,
+		/// Xcm request timeout cannot be zero
+		InvalidXcmRequestTimeout,
+		/// No xcm request with this query id
+		XcmRequestNotFound,
 
 		InvalidOrigin,
+		/// Reward points can only be proven once their era is over
+		EraPointsNotFinal,
 	}
 
 	/// The exchange rate between relaychain native asset and the voucher.
@@ -382,6 +496,38 @@ pub mod pallet {
 	#[pallet::getter(fn xcm_request)]
 	pub type XcmRequests<T> = StorageMap<_, Blake2_128Concat, QueryId, XcmRequest<T>, OptionQuery>;
 
+	/// Sending of the xcm requests, to retry or roll back the failing ones and hold the ones
+	/// timing out
+	#[pallet::storage]
+	#[pallet::getter(fn xcm_request_status)]
+	pub type XcmRequestStatuses<T: Config> =
+		StorageMap<_, Blake2_128Concat, QueryId, XcmRequestStatus<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Timeout of each kind of xcm request, requests of a kind without timeout are left to
+	/// `resolve_xcm_request` when failing
+	#[pallet::storage]
+	#[pallet::getter(fn xcm_request_timeout)]
+	pub type XcmRequestTimeouts<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		XcmRequestKind,
+		XcmRequestTimeout<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Flying xcm requests by the block at which they time out, and failed ones by the block at
+	/// which they are sent again. Entries of requests resolved in between are skipped.
+	#[pallet::storage]
+	pub type XcmRequestDeadlines<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		QueryId,
+		(),
+		OptionQuery,
+	>;
+
 	/// Users' fast unstake requests in liquid currency
 	#[pallet::storage]
 	#[pallet::getter(fn fast_unstake_requests)]
@@ -431,6 +577,41 @@ pub mod pallet {
 	#[allow(clippy::disallowed_types)]
 	pub type IsUpdated<T: Config> = StorageMap<_, Twox64Concat, DerivativeIndex, bool, ValueQuery>;
 
+	/// Validators which may be nominated, curated by `UpdateOrigin`
+	#[pallet::storage]
+	#[pallet::getter(fn validator_candidate)]
+	pub type ValidatorCandidates<T: Config> =
+		CountedStorageMap<_, Twox64Concat, T::AccountId, ValidatorCandidate, OptionQuery>;
+
+	/// Era of the reward points of the validator candidates
+	#[pallet::storage]
+	#[pallet::getter(fn reward_points_era)]
+	pub type RewardPointsEra<T: Config> = StorageValue<_, EraIndex, OptionQuery>;
+
+	/// Nominations confirmed by the relaychain
+	#[pallet::storage]
+	#[pallet::getter(fn nominations)]
+	pub type Nominations<T: Config> =
+		StorageMap<_, Twox64Concat, DerivativeIndex, Vec<T::AccountId>, OptionQuery>;
+
+	/// Distribution strategy used by `SelectableDistribution`
+	#[pallet::storage]
+	#[pallet::getter(fn distribution_strategy)]
+	#[allow(clippy::disallowed_types)]
+	pub type SelectedDistributionStrategy<T: Config> =
+		StorageValue<_, DistributionStrategyKind, ValueQuery>;
+
+	/// Pool in which `UnstakeProvider::Dex` sells the liquid currency
+	#[pallet::storage]
+	#[pallet::getter(fn dex_pool)]
+	pub type DexPool<T: Config> = StorageValue<_, DexPoolIdOf<T>, OptionQuery>;
+
+	/// How slashes of the derivative accounts are accounted for
+	#[pallet::storage]
+	#[pallet::getter(fn slash_policy)]
+	#[allow(clippy::disallowed_types)]
+	pub type SelectedSlashPolicy<T: Config> = StorageValue<_, SlashPolicy, ValueQuery>;
+
 	/// DefaultVersion is using for initialize the StorageVersion
 	#[pallet::type_value]
 	pub(super) fn DefaultVersion() -> Versions {
@@ -539,7 +720,11 @@ pub mod pallet {
 		///
 		/// - `amount`: the amount of derivative
 		#[pallet::call_index(1)]
-		#[pallet::weight(<T as Config>::WeightInfo::unstake())]
+		#[pallet::weight(if unstake_provider.is_dex() {
+			<T as Config>::WeightInfo::unstake_via_dex()
+		} else {
+			<T as Config>::WeightInfo::unstake()
+		})]
 		#[transactional]
 		pub fn unstake(
 			origin: OriginFor<T>,
@@ -551,20 +736,12 @@ pub mod pallet {
 			ensure!(liquid_amount >= T::MinUnstake::get(), Error::<T>::UnstakeTooSmall);
 
 			if unstake_provider.is_matching_pool() {
-				use frame_support::traits::tokens::{Fortitude, Preservation};
-				let keep_alive = false;
-				let keep_alive =
-					if keep_alive { Preservation::Preserve } else { Preservation::Expendable };
-				FastUnstakeRequests::<T>::try_mutate(&who, |b| -> DispatchResult {
-					let balance = T::Assets::reducible_balance(
-						Self::liquid_currency()?,
-						&who,
-						keep_alive,
-						Fortitude::Polite,
-					);
-					*b = b.saturating_add(liquid_amount).min(balance);
-					Ok(())
-				})?;
+				Self::do_request_fast_unstake(&who, liquid_amount)?;
+				return Ok(().into())
+			}
+
+			if let UnstakeProvider::Dex { max_discount } = unstake_provider {
+				Self::do_dex_unstake(&who, liquid_amount, max_discount)?;
 				return Ok(().into())
 			}
 
@@ -854,7 +1031,10 @@ pub mod pallet {
 
 		/// Force advance era
 		#[pallet::call_index(14)]
-		#[pallet::weight(<T as Config>::WeightInfo::force_advance_era())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::force_advance_era()
+				.saturating_add(Pallet::<T>::rotate_nominations_weight())
+		)]
 		#[transactional]
 		pub fn force_advance_era(
 			origin: OriginFor<T>,
@@ -905,7 +1085,10 @@ pub mod pallet {
 
 		/// Set current era by providing storage proof
 		#[pallet::call_index(17)]
-		#[pallet::weight(<T as Config>::WeightInfo::force_set_current_era())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::force_set_current_era()
+				.saturating_add(Pallet::<T>::rotate_nominations_weight())
+		)]
 		#[transactional]
 		pub fn set_current_era(
 			origin: OriginFor<T>,
@@ -938,9 +1121,13 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		/// Set staking_ledger by providing storage proof
+		/// Set staking_ledger by providing storage proof, accounting for the balance slashed since
+		/// the previous one according to the `SlashPolicy`
 		#[pallet::call_index(18)]
-		#[pallet::weight(<T as Config>::WeightInfo::force_set_staking_ledger())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::force_set_staking_ledger()
+				.saturating_add(<T as Config>::WeightInfo::slash())
+		)]
 		#[transactional]
 		pub fn set_staking_ledger(
 			origin: OriginFor<T>,
@@ -957,6 +1144,7 @@ pub mod pallet {
 				proof: proof.clone(),
 			});
 
+			let mut slashed = (Zero::zero(), Zero::zero());
 			Self::do_update_ledger(derivative_index, |ledger| {
 				ensure!(!Self::is_updated(derivative_index), Error::<T>::StakingLedgerLocked);
 				let requests = XcmRequests::<T>::iter().count();
@@ -978,6 +1166,14 @@ pub mod pallet {
 				let value = staking_ledger.encode();
 				ensure!(Self::verify_merkle_proof(key, value, proof), Error::<T>::InvalidProof);
 				let rewards = staking_ledger.total.saturating_sub(ledger.total);
+				// pending requests of this derivative may have changed its ledger on the relaychain
+				// already, the other requests leave it as it is
+				let ledger_changing = XcmRequests::<T>::iter_values().any(|request| {
+					request.derivative_index() == derivative_index && request.changes_ledger()
+				});
+				if !ledger_changing {
+					slashed = staking_ledger.slashed_since(ledger);
+				}
 
 				let inflate_liquid_amount = Self::get_inflate_liquid_amount(rewards)?;
 				if !inflate_liquid_amount.is_zero() {
@@ -1009,6 +1205,8 @@ pub mod pallet {
 				*ledger = staking_ledger;
 				Ok(())
 			})?;
+			let (slashed_active, slashed_unlocking) = slashed;
+			Self::do_slash(derivative_index, slashed_active, slashed_unlocking)?;
 
 			Ok(().into())
 		}
@@ -1184,11 +1382,231 @@ pub mod pallet {
 			Self::deposit_event(Event::<T>::ExchangeRateUpdated(rate));
 			Ok(())
 		}
+
+		/// Add a validator to the candidates nominated at each new era
+		#[pallet::call_index(28)]
+		#[pallet::weight(<T as Config>::WeightInfo::add_validator_candidate())]
+		#[transactional]
+		pub fn add_validator_candidate(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!ValidatorCandidates::<T>::contains_key(&validator),
+				Error::<T>::ValidatorCandidateAlreadyExists
+			);
+			ensure!(
+				ValidatorCandidates::<T>::count() < T::MaxValidatorCandidates::get(),
+				Error::<T>::TooManyValidatorCandidates
+			);
+			ValidatorCandidates::<T>::insert(&validator, ValidatorCandidate::default());
+			Self::deposit_event(Event::<T>::ValidatorCandidateAdded(validator));
+			Ok(())
+		}
+
+		/// Remove a validator from the candidates, current nominations are kept until the next
+		/// rotation
+		#[pallet::call_index(29)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_validator_candidate())]
+		#[transactional]
+		pub fn remove_validator_candidate(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				ValidatorCandidates::<T>::contains_key(&validator),
+				Error::<T>::ValidatorCandidateNotFound
+			);
+			ValidatorCandidates::<T>::remove(&validator);
+			Self::deposit_event(Event::<T>::ValidatorCandidateRemoved(validator));
+			Ok(())
+		}
+
+		/// Set the preferences of a validator candidate by providing storage proof, `None` when
+		/// the validator is not validating anymore
+		#[pallet::call_index(30)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_validator_prefs())]
+		#[transactional]
+		pub fn set_validator_prefs(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			prefs: Option<ValidatorPrefs>,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			let key = Self::get_validator_prefs_key(&validator);
+			ensure!(
+				Self::read_merkle_proof(key, proof) == Some(prefs.as_ref().map(Encode::encode)),
+				Error::<T>::InvalidProof
+			);
+
+			// A validator which stopped validating can't be nominated, record it as blocked.
+			let prefs = prefs.unwrap_or(ValidatorPrefs { blocked: true, ..Default::default() });
+			ValidatorCandidates::<T>::try_mutate(&validator, |candidate| -> DispatchResult {
+				let candidate = candidate.as_mut().ok_or(Error::<T>::ValidatorCandidateNotFound)?;
+				candidate.prefs = Some(prefs.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::ValidatorPrefsUpdated(validator, prefs));
+			Ok(())
+		}
+
+		/// Set the reward points of the validator candidates in `era` by providing storage proof.
+		/// The era must be over, so that its points are final and cannot be frozen half way by
+		/// anyone proving them early.
+		#[pallet::call_index(31)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_validator_era_points(
+			T::MaxValidatorCandidates::get()
+		))]
+		#[transactional]
+		pub fn set_validator_era_points(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			points: EraRewardPoints<T::AccountId>,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			ensure!(era < Self::current_era(), Error::<T>::EraPointsNotFinal);
+			ensure!(
+				Self::reward_points_era().map_or(true, |last_era| last_era < era),
+				Error::<T>::StaleEraPoints
+			);
+
+			let key = Self::get_era_reward_points_key(era);
+			let value = points.encode();
+			ensure!(Self::verify_merkle_proof(key, value, proof), Error::<T>::InvalidProof);
+
+			for validator in ValidatorCandidates::<T>::iter_keys().collect::<Vec<_>>() {
+				ValidatorCandidates::<T>::mutate(&validator, |candidate| {
+					if let Some(candidate) = candidate {
+						candidate.era_points =
+							points.individual.get(&validator).copied().unwrap_or_default();
+					}
+				});
+			}
+			RewardPointsEra::<T>::put(era);
+
+			Self::deposit_event(Event::<T>::ValidatorEraPointsUpdated(era));
+			Ok(())
+		}
+
+		/// Update the strategy used by `SelectableDistribution`
+		#[pallet::call_index(32)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_distribution_strategy())]
+		#[transactional]
+		pub fn set_distribution_strategy(
+			origin: OriginFor<T>,
+			strategy: DistributionStrategyKind,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			SelectedDistributionStrategy::<T>::put(strategy);
+			Self::deposit_event(Event::<T>::DistributionStrategyUpdated(strategy));
+			Ok(())
+		}
+
+		/// Update the pool in which `UnstakeProvider::Dex` sells the liquid currency, unstaking
+		/// through the dex falls back to the matching pool while it is not set
+		#[pallet::call_index(33)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_dex_pool())]
+		#[transactional]
+		pub fn set_dex_pool(
+			origin: OriginFor<T>,
+			pool_id: Option<DexPoolIdOf<T>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			DexPool::<T>::set(pool_id.clone());
+			Self::deposit_event(Event::<T>::DexPoolUpdated(pool_id));
+			Ok(())
+		}
+
+		/// Update how slashes of the derivative accounts, detected by `set_staking_ledger`, are
+		/// accounted for
+		#[pallet::call_index(34)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_slash_policy())]
+		#[transactional]
+		pub fn set_slash_policy(origin: OriginFor<T>, policy: SlashPolicy) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			SelectedSlashPolicy::<T>::put(policy);
+			Self::deposit_event(Event::<T>::SlashPolicyUpdated(policy));
+			Ok(())
+		}
+
+		/// Update the timeout of a kind of xcm request, applied to the requests sent afterwards.
+		/// Requests failing are sent again up to `max_retries` times, then rolled back. Requests
+		/// timing out may have been executed by the relaychain, so are left to
+		/// `resolve_xcm_request` like the failing ones without timeout
+		#[pallet::call_index(35)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_xcm_request_timeout())]
+		#[transactional]
+		pub fn set_xcm_request_timeout(
+			origin: OriginFor<T>,
+			kind: XcmRequestKind,
+			timeout: Option<XcmRequestTimeout<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(timeout) = timeout {
+				ensure!(!timeout.timeout.is_zero(), Error::<T>::InvalidXcmRequestTimeout);
+			}
+			XcmRequestTimeouts::<T>::set(kind, timeout);
+			Self::deposit_event(Event::<T>::XcmRequestTimeoutUpdated(kind, timeout));
+			Ok(())
+		}
+
+		/// Resolve a flying, failed or stuck xcm request, listed by `pending_xcm_requests`
+		#[pallet::call_index(36)]
+		#[pallet::weight(<T as Config>::WeightInfo::resolve_xcm_request())]
+		#[transactional]
+		pub fn resolve_xcm_request(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			resolution: XcmRequestResolution,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let request = Self::xcm_request(query_id).ok_or(Error::<T>::XcmRequestNotFound)?;
+			match resolution {
+				XcmRequestResolution::Retry => Self::do_retry_xcm_request(query_id, request)?,
+				XcmRequestResolution::RollBack =>
+					Self::do_roll_back_xcm_request(query_id, request)?,
+				XcmRequestResolution::Executed =>
+					Self::do_notification_received(query_id, request, None)?,
+			}
+			Self::deposit_event(Event::<T>::XcmRequestResolved(query_id, resolution));
+			Ok(())
+		}
+
+		/// Stop nominating on relaychain via xcm.transact, until the nominations are rotated
+		/// again at the next era
+		#[pallet::call_index(37)]
+		#[pallet::weight(<T as Config>::WeightInfo::chill())]
+		#[transactional]
+		pub fn chill(origin: OriginFor<T>, derivative_index: DerivativeIndex) -> DispatchResult {
+			Self::ensure_origin(origin)?;
+			Self::do_chill(derivative_index)?;
+			Ok(())
+		}
+
+		/// Set the reward destination on relaychain via xcm.transact
+		#[pallet::call_index(38)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_payee())]
+		#[transactional]
+		pub fn set_payee(
+			origin: OriginFor<T>,
+			derivative_index: DerivativeIndex,
+			payee: RewardDestination<T::AccountId>,
+		) -> DispatchResult {
+			Self::ensure_origin(origin)?;
+			Self::do_set_payee(derivative_index, payee)?;
+			Ok(())
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		fn on_initialize(_block_number: T::BlockNumber) -> frame_support::weights::Weight {
+		fn on_initialize(block_number: T::BlockNumber) -> frame_support::weights::Weight {
 			let mut weight = <T as Config>::WeightInfo::on_initialize();
 			// let relaychain_block_number =
 			// 	T::RelayChainValidationDataProvider::current_block_number();
@@ -1214,12 +1632,14 @@ pub mod pallet {
 					return Ok(())
 				}
 				weight += <T as Config>::WeightInfo::force_advance_era();
+				weight += Self::rotate_nominations_weight();
 				Self::do_advance_era(offset)
 			};
 			let _ = with_transaction(|| match do_on_initialize() {
 				Ok(()) => TransactionOutcome::Commit(Ok(())),
 				Err(err) => TransactionOutcome::Rollback(Err(err)),
 			});
+			weight.saturating_accrue(Self::do_check_xcm_requests(block_number));
 			weight
 		}
 
@@ -1285,6 +1705,95 @@ pub mod pallet {
 			pallet_utility::Pallet::<T>::derivative_account_id(para_account, index)
 		}
 
+		/// Liquid staking position of `who`.
+		pub fn position(who: &T::AccountId) -> LiquidStakingPosition<BalanceOf<T>> {
+			let liquid_balance = Self::liquid_currency()
+				.map(|liquid_currency| T::Assets::balance(liquid_currency, who))
+				.unwrap_or_default();
+			LiquidStakingPosition {
+				liquid_balance,
+				staked_value: Self::liquid_to_staking(liquid_balance).unwrap_or_default(),
+				unlockings: Self::unlockings(who)
+					.unwrap_or_default()
+					.into_iter()
+					.map(|chunk| Unlocking { value: chunk.value, era: chunk.era })
+					.collect(),
+				fast_unstake_request: Self::fast_unstake_requests(who),
+			}
+		}
+
+		/// Ledgers of the derivative accounts on the relaychain, by derivative index.
+		pub fn derivative_ledgers() -> Vec<DerivativeLedger<BalanceOf<T>>> {
+			let mut ledgers: Vec<_> = StakingLedgers::<T>::iter()
+				.map(|(derivative_index, ledger)| DerivativeLedger {
+					derivative_index,
+					total: ledger.total,
+					active: ledger.active,
+					unlocking: ledger
+						.unlocking
+						.into_iter()
+						.map(|chunk| Unlocking { value: chunk.value, era: chunk.era })
+						.collect(),
+				})
+				.collect();
+			ledgers.sort_by_key(|ledger| ledger.derivative_index);
+			ledgers
+		}
+
+		/// Xcm requests waiting for their response or to be resolved, by query id.
+		pub fn pending_xcm_requests() -> Vec<PendingXcmRequest<BalanceOf<T>, BlockNumberFor<T>>> {
+			let mut requests: Vec<_> = XcmRequests::<T>::iter()
+				.map(|(query_id, request)| {
+					let status = Self::xcm_request_status(query_id).unwrap_or_default();
+					PendingXcmRequest {
+						query_id,
+						kind: request.kind(),
+						derivative_index: request.derivative_index(),
+						amount: request.amount(),
+						attempts: status.attempts,
+						sent_at: status.sent_at,
+						state: status.state,
+					}
+				})
+				.collect();
+			requests.sort_by_key(|request| request.query_id);
+			requests
+		}
+
+		/// Liquid currency minted and fees charged when staking `amount`, as done by `stake`.
+		pub fn simulate_stake(
+			amount: BalanceOf<T>,
+		) -> Result<StakeSimulation<BalanceOf<T>>, DispatchError> {
+			ensure!(amount >= T::MinStake::get(), Error::<T>::StakeTooSmall);
+			let reserves = Self::reserve_factor().mul_floor(amount);
+			let xcm_fees = T::XcmFees::get();
+			let amount = amount
+				.checked_sub(xcm_fees)
+				.and_then(|amount| amount.checked_sub(reserves))
+				.ok_or(ArithmeticError::Underflow)?;
+			let liquid_amount =
+				Self::staking_to_liquid(amount).ok_or(Error::<T>::InvalidExchangeRate)?;
+			Self::ensure_market_cap(amount)?;
+			Ok(StakeSimulation { liquid_amount, reserves, xcm_fees })
+		}
+
+		/// Staking currency unlocked when unstaking `liquid_amount` through the relaychain, and
+		/// the fee charged when it is matched through the matching pool instead.
+		pub fn simulate_unstake(
+			liquid_amount: BalanceOf<T>,
+		) -> Result<UnstakeSimulation<BalanceOf<T>>, DispatchError> {
+			ensure!(liquid_amount >= T::MinUnstake::get(), Error::<T>::UnstakeTooSmall);
+			let staking_amount =
+				Self::liquid_to_staking(liquid_amount).ok_or(Error::<T>::InvalidExchangeRate)?;
+			Ok(UnstakeSimulation {
+				liquid_amount,
+				staking_amount,
+				unlock_era: Self::target_era(),
+				fast_unstake_fee: T::MatchingPoolFastUnstakeFee::get()
+					.saturating_mul_int(liquid_amount),
+			})
+		}
+
 		fn offset(relaychain_block_number: BlockNumberFor<T>) -> EraIndex {
 			relaychain_block_number
 				.checked_sub(&Self::era_start_block())
@@ -1381,9 +1890,10 @@ pub mod pallet {
 				Self::notify_placeholder(),
 			)?;
 
-			XcmRequests::<T>::insert(
+			Self::track_xcm_request(
 				query_id,
 				XcmRequest::Bond { index: derivative_index, amount },
+				1,
 			);
 
 			Self::deposit_event(Event::<T>::Bonding(
@@ -1430,9 +1940,10 @@ pub mod pallet {
 				Self::notify_placeholder(),
 			)?;
 
-			XcmRequests::<T>::insert(
+			Self::track_xcm_request(
 				query_id,
 				XcmRequest::BondExtra { index: derivative_index, amount },
+				1,
 			);
 
 			Self::deposit_event(Event::<T>::BondingExtra(derivative_index, amount));
@@ -1472,9 +1983,10 @@ pub mod pallet {
 
 			let query_id = T::XCM::do_unbond(amount, derivative_index, Self::notify_placeholder())?;
 
-			XcmRequests::<T>::insert(
+			Self::track_xcm_request(
 				query_id,
 				XcmRequest::Unbond { index: derivative_index, amount },
+				1,
 			);
 
 			Self::deposit_event(Event::<T>::Unbonding(derivative_index, amount));
@@ -1507,9 +2019,10 @@ pub mod pallet {
 
 			let query_id = T::XCM::do_rebond(amount, derivative_index, Self::notify_placeholder())?;
 
-			XcmRequests::<T>::insert(
+			Self::track_xcm_request(
 				query_id,
 				XcmRequest::Rebond { index: derivative_index, amount },
+				1,
 			);
 
 			Self::deposit_event(Event::<T>::Rebonding(derivative_index, amount));
@@ -1546,9 +2059,10 @@ pub mod pallet {
 				Self::notify_placeholder(),
 			)?;
 
-			XcmRequests::<T>::insert(
+			Self::track_xcm_request(
 				query_id,
 				XcmRequest::WithdrawUnbonded { index: derivative_index, num_slashing_spans },
+				1,
 			);
 
 			Self::deposit_event(Event::<T>::WithdrawingUnbonded(
@@ -1579,9 +2093,10 @@ pub mod pallet {
 			let query_id =
 				T::XCM::do_nominate(targets.clone(), derivative_index, Self::notify_placeholder())?;
 
-			XcmRequests::<T>::insert(
+			Self::track_xcm_request(
 				query_id,
 				XcmRequest::Nominate { index: derivative_index, targets: targets.clone() },
+				1,
 			);
 
 			Self::deposit_event(Event::<T>::Nominating(derivative_index, targets));
@@ -1589,6 +2104,61 @@ pub mod pallet {
 			Ok(())
 		}
 
+		#[require_transactional]
+		fn do_chill(derivative_index: DerivativeIndex) -> DispatchResult {
+			ensure!(
+				T::DerivativeIndexList::get().contains(&derivative_index),
+				Error::<T>::InvalidDerivativeIndex
+			);
+			ensure!(StakingLedgers::<T>::contains_key(derivative_index), Error::<T>::NotBonded);
+
+			log::trace!(
+				target: "liquidStaking::chill",
+				"index: {:?}",
+				&derivative_index,
+			);
+
+			let query_id = T::XCM::do_chill(derivative_index, Self::notify_placeholder())?;
+
+			Self::track_xcm_request(query_id, XcmRequest::Chill { index: derivative_index }, 1);
+
+			Self::deposit_event(Event::<T>::Chilling(derivative_index));
+
+			Ok(())
+		}
+
+		#[require_transactional]
+		fn do_set_payee(
+			derivative_index: DerivativeIndex,
+			payee: RewardDestination<T::AccountId>,
+		) -> DispatchResult {
+			ensure!(
+				T::DerivativeIndexList::get().contains(&derivative_index),
+				Error::<T>::InvalidDerivativeIndex
+			);
+			ensure!(StakingLedgers::<T>::contains_key(derivative_index), Error::<T>::NotBonded);
+
+			log::trace!(
+				target: "liquidStaking::set_payee",
+				"index: {:?}, payee: {:?}",
+				&derivative_index,
+				&payee,
+			);
+
+			let query_id =
+				T::XCM::do_set_payee(payee.clone(), derivative_index, Self::notify_placeholder())?;
+
+			Self::track_xcm_request(
+				query_id,
+				XcmRequest::SetPayee { index: derivative_index, payee: payee.clone() },
+				1,
+			);
+
+			Self::deposit_event(Event::<T>::SettingPayee(derivative_index, payee));
+
+			Ok(())
+		}
+
 		#[require_transactional]
 		fn do_multi_bond(
 			total_amount: BalanceOf<T>,
@@ -1688,7 +2258,7 @@ pub mod pallet {
 
 			let executed = res.is_none();
 			if !executed {
-				return Ok(())
+				return Self::do_xcm_request_failed(query_id, req)
 			}
 
 			match req {
@@ -1760,23 +2330,203 @@ pub mod pallet {
 						Ok(())
 					})?;
 				},
-				Nominate { targets: _, .. } => {},
+				Nominate { index: derivative_index, targets } => {
+					Nominations::<T>::insert(derivative_index, targets);
+				},
+				Chill { index: derivative_index } => {
+					Nominations::<T>::remove(derivative_index);
+				},
+				SetPayee { .. } => {},
+			}
+			Self::untrack_xcm_request(query_id);
+			Ok(())
+		}
+
+		pub(crate) fn track_xcm_request(query_id: QueryId, request: XcmRequest<T>, attempts: u32) {
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(timeout) = Self::xcm_request_timeout(request.kind()) {
+				XcmRequestDeadlines::<T>::insert(now.saturating_add(timeout.timeout), query_id, ());
 			}
+			XcmRequests::<T>::insert(query_id, request);
+			XcmRequestStatuses::<T>::insert(
+				query_id,
+				XcmRequestStatus { attempts, sent_at: now, state: XcmRequestState::Flying },
+			);
+		}
+
+		fn untrack_xcm_request(query_id: QueryId) {
 			XcmRequests::<T>::remove(query_id);
+			XcmRequestStatuses::<T>::remove(query_id);
+		}
+
+		/// Schedule a failed request to be sent again, or roll it back once out of retries.
+		/// Without timeout for its kind, the request is stuck until resolved by
+		/// `resolve_xcm_request`.
+		#[require_transactional]
+		fn do_xcm_request_failed(query_id: QueryId, request: XcmRequest<T>) -> DispatchResult {
+			let mut status = Self::xcm_request_status(query_id).unwrap_or_default();
+			match Self::xcm_request_timeout(request.kind()) {
+				Some(timeout) if status.attempts <= timeout.max_retries => {
+					let backoff = timeout.backoff.saturating_mul(
+						2u32.saturating_pow(status.attempts.saturating_sub(1)).into(),
+					);
+					let now = frame_system::Pallet::<T>::block_number();
+					let retry_at = now.saturating_add(backoff);
+					// the requests due now were checked already
+					XcmRequestDeadlines::<T>::insert(
+						retry_at.max(now.saturating_add(One::one())),
+						query_id,
+						(),
+					);
+					status.state = XcmRequestState::RetryAt(retry_at);
+					XcmRequestStatuses::<T>::insert(query_id, status);
+					Self::deposit_event(Event::<T>::XcmRequestRetryScheduled(query_id, retry_at));
+				},
+				Some(_) => return Self::do_roll_back_xcm_request(query_id, request),
+				None => {
+					status.state = XcmRequestState::Stuck;
+					XcmRequestStatuses::<T>::insert(query_id, status);
+					Self::deposit_event(Event::<T>::XcmRequestStuck(query_id));
+				},
+			}
+			Ok(())
+		}
+
+		/// Send a request again, as a new query. Bonds are sent again with
+		/// `RewardDestination::Staked`, as done by matching.
+		#[require_transactional]
+		fn do_retry_xcm_request(query_id: QueryId, request: XcmRequest<T>) -> DispatchResult {
+			use XcmRequest::*;
+
+			let attempts = Self::xcm_request_status(query_id).unwrap_or_default().attempts;
+			let notify = Self::notify_placeholder();
+			let new_query_id = match request.clone() {
+				Bond { index, amount } => T::XCM::do_bond(
+					amount,
+					RewardDestination::Staked,
+					Self::derivative_sovereign_account_id(index),
+					index,
+					notify,
+				)?,
+				BondExtra { index, amount } => T::XCM::do_bond_extra(
+					amount,
+					Self::derivative_sovereign_account_id(index),
+					index,
+					notify,
+				)?,
+				Unbond { index, amount } => T::XCM::do_unbond(amount, index, notify)?,
+				Rebond { index, amount } => T::XCM::do_rebond(amount, index, notify)?,
+				WithdrawUnbonded { index, num_slashing_spans } => T::XCM::do_withdraw_unbonded(
+					num_slashing_spans,
+					Self::sovereign_account_id(),
+					index,
+					notify,
+				)?,
+				Nominate { index, targets } => T::XCM::do_nominate(targets, index, notify)?,
+				Chill { index } => T::XCM::do_chill(index, notify)?,
+				SetPayee { index, payee } => T::XCM::do_set_payee(payee, index, notify)?,
+			};
+
+			Self::untrack_xcm_request(query_id);
+			Self::track_xcm_request(new_query_id, request, attempts.saturating_add(1));
+			Self::deposit_event(Event::<T>::XcmRequestRetried(query_id, new_query_id));
 			Ok(())
 		}
 
+		/// Drop a request, releasing what it locked in the matching pool to be matched again.
+		/// The staking ledgers are only updated once a request is executed, so are left as is.
+		#[require_transactional]
+		fn do_roll_back_xcm_request(query_id: QueryId, request: XcmRequest<T>) -> DispatchResult {
+			use XcmRequest::*;
+
+			match request {
+				Bond { amount, .. } | BondExtra { amount, .. } | Rebond { amount, .. } =>
+					MatchingPool::<T>::try_mutate(|p| p.remove_stake_amount_lock(amount))?,
+				Unbond { amount, .. } =>
+					MatchingPool::<T>::try_mutate(|p| p.remove_unstake_amount_lock(amount))?,
+				WithdrawUnbonded { .. } | Nominate { .. } | Chill { .. } | SetPayee { .. } => {},
+			}
+
+			Self::untrack_xcm_request(query_id);
+			Self::deposit_event(Event::<T>::XcmRequestRolledBack(query_id));
+			Ok(())
+		}
+
+		/// Hold the flying requests timing out at `now` as stuck, and send again the failed ones
+		/// due for a retry. A timed out request may have been executed by the relaychain, so it
+		/// waits for its response or `resolve_xcm_request`. A request whose retry fails is stuck.
+		fn do_check_xcm_requests(now: BlockNumberFor<T>) -> Weight {
+			let mut weight = Weight::zero();
+			let due = XcmRequestDeadlines::<T>::drain_prefix(now)
+				.map(|(query_id, ())| query_id)
+				.collect::<Vec<_>>();
+			for query_id in due {
+				weight.saturating_accrue(<T as Config>::WeightInfo::check_xcm_request());
+				let (Some(request), Some(status)) =
+					(Self::xcm_request(query_id), Self::xcm_request_status(query_id))
+				else {
+					continue
+				};
+
+				match status.state {
+					XcmRequestState::Flying => {
+						XcmRequestStatuses::<T>::insert(
+							query_id,
+							XcmRequestStatus { state: XcmRequestState::Stuck, ..status },
+						);
+						Self::deposit_event(Event::<T>::XcmRequestTimedOut(query_id));
+						Self::deposit_event(Event::<T>::XcmRequestStuck(query_id));
+					},
+					XcmRequestState::RetryAt(retry_at) if retry_at <= now => {
+						weight.saturating_accrue(<T as Config>::WeightInfo::retry_xcm_request());
+						let result = with_transaction(|| {
+							match Self::do_retry_xcm_request(query_id, request) {
+								Ok(()) => TransactionOutcome::Commit(Ok(())),
+								Err(err) => TransactionOutcome::Rollback(Err(err)),
+							}
+						});
+						if let Err(err) = result {
+							log::error!(
+								target: "liquidStaking::check_xcm_requests",
+								"query_id: {:?}, error: {:?}",
+								&query_id,
+								&err
+							);
+							XcmRequestStatuses::<T>::insert(
+								query_id,
+								XcmRequestStatus { state: XcmRequestState::Stuck, ..status },
+							);
+							Self::deposit_event(Event::<T>::XcmRequestStuck(query_id));
+						}
+					},
+					_ => {},
+				}
+			}
+			weight
+		}
+
 		#[require_transactional]
 		fn do_update_exchange_rate() -> DispatchResult {
+			let Some(new_exchange_rate) = Self::bonded_exchange_rate()? else { return Ok(()) };
+			// losses are only accounted for when a slash is detected, by `do_slash`
+			if new_exchange_rate > Self::exchange_rate() {
+				ExchangeRate::<T>::put(new_exchange_rate);
+				Self::deposit_event(Event::<T>::ExchangeRateUpdated(new_exchange_rate));
+			}
+			Ok(())
+		}
+
+		/// Exchange rate backed by the bonded and matched amounts, `None` without liquid currency.
+		fn bonded_exchange_rate() -> Result<Option<Rate>, DispatchError> {
 			let matching_ledger = Self::matching_pool();
 			let total_active_bonded = Self::get_total_active_bonded();
 			let issuance = T::Assets::total_issuance(Self::liquid_currency()?);
 			if issuance.is_zero() {
-				return Ok(())
+				return Ok(None)
 			}
 			// TODO: when one era has big amount of stakes, the exchange rate
 			// will not look great
-			let new_exchange_rate = Rate::checked_from_rational(
+			let exchange_rate = Rate::checked_from_rational(
 				total_active_bonded
 					.checked_add(matching_ledger.total_stake_amount.total)
 					.and_then(|r| r.checked_sub(matching_ledger.total_unstake_amount.total))
@@ -1784,12 +2534,50 @@ pub mod pallet {
 				issuance,
 			)
 			.ok_or(Error::<T>::InvalidExchangeRate)?;
-			// slashes should be handled properly offchain
-			// by doing `bond_extra` using OrmlXcm or PolkadotXcm
-			if new_exchange_rate > Self::exchange_rate() {
-				ExchangeRate::<T>::put(new_exchange_rate);
-				Self::deposit_event(Event::<T>::ExchangeRateUpdated(new_exchange_rate));
+			Ok(Some(exchange_rate))
+		}
+
+		/// Account for `slashed_active` and `slashed_unlocking` from the ledger of
+		/// `derivative_index`, covering them from the reserves first if the `SlashPolicy` says so.
+		/// The unlocking balance is owed in full to the unstakers: the reserves covering it are
+		/// kept to pay them, and the uncovered part is unbonded from the stakers. Reserves covering
+		/// the active balance go to the matching pool to be bonded again. What is not covered is
+		/// socialized by lowering the exchange rate.
+		#[require_transactional]
+		fn do_slash(
+			derivative_index: DerivativeIndex,
+			slashed_active: BalanceOf<T>,
+			slashed_unlocking: BalanceOf<T>,
+		) -> DispatchResult {
+			let slashed = slashed_active.saturating_add(slashed_unlocking);
+			if slashed.is_zero() {
+				return Ok(())
+			}
+			let covered = match Self::slash_policy() {
+				SlashPolicy::Socialize => Zero::zero(),
+				SlashPolicy::ReservesFirst => Self::total_reserves().min(slashed),
+			};
+			let covered_unlocking = covered.min(slashed_unlocking);
+			let covered_active = covered.saturating_sub(covered_unlocking);
+			if !covered.is_zero() {
+				TotalReserves::<T>::mutate(|reserves| *reserves = reserves.saturating_sub(covered));
 			}
+			if !covered_active.is_zero() {
+				MatchingPool::<T>::try_mutate(|p| p.add_stake_amount(covered_active))?;
+			}
+			let uncovered_unlocking = slashed_unlocking.saturating_sub(covered_unlocking);
+			if !uncovered_unlocking.is_zero() {
+				MatchingPool::<T>::try_mutate(|p| p.add_unstake_amount(uncovered_unlocking))?;
+			}
+			if covered < slashed {
+				if let Some(new_exchange_rate) = Self::bonded_exchange_rate()? {
+					if new_exchange_rate < Self::exchange_rate() {
+						ExchangeRate::<T>::put(new_exchange_rate);
+						Self::deposit_event(Event::<T>::ExchangeRateUpdated(new_exchange_rate));
+					}
+				}
+			}
+			Self::deposit_event(Event::<T>::Slashed(derivative_index, slashed, covered));
 			Ok(())
 		}
 
@@ -1860,11 +2648,70 @@ pub mod pallet {
 				log::error!(target: "liquidStaking::do_advance_era", "advance era error caught: {:?}", &e);
 			}
 
+			// ignore error, nominations are rotated again at the next era
+			if let Err(e) = with_transaction(|| match Self::do_rotate_nominations() {
+				Ok(()) => TransactionOutcome::Commit(Ok(())),
+				Err(err) => TransactionOutcome::Rollback(Err(err)),
+			}) {
+				log::error!(target: "liquidStaking::do_advance_era", "rotate nominations error caught: {:?}", &e);
+			}
+
 			IsMatched::<T>::put(false);
 			Self::deposit_event(Event::<T>::NewEra(Self::current_era()));
 			Ok(())
 		}
 
+		/// Nominate the validators picked by `ValidatorSelection` with every bonded derivative
+		/// whose nominations differ and are not already pending.
+		#[require_transactional]
+		fn do_rotate_nominations() -> DispatchResult {
+			let ranked = T::ValidatorSelection::rank(
+				ValidatorCandidates::<T>::iter().collect(),
+				Self::current_era(),
+			);
+			if ranked.is_empty() {
+				return Ok(())
+			}
+
+			let pending = XcmRequests::<T>::iter_values()
+				.filter_map(|request| match request {
+					XcmRequest::Nominate { index, .. } | XcmRequest::Chill { index } => Some(index),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			let max_targets = T::MaxNominations::get() as usize;
+			for (position, derivative_index) in
+				T::DerivativeIndexList::get().into_iter().enumerate()
+			{
+				if !StakingLedgers::<T>::contains_key(derivative_index) ||
+					pending.contains(&derivative_index)
+				{
+					continue
+				}
+				let targets = select_targets(&ranked, position, max_targets);
+				if Self::nominations(derivative_index).as_ref() == Some(&targets) {
+					continue
+				}
+
+				log::trace!(
+					target: "liquidStaking::do_rotate_nominations",
+					"index: {:?}, targets: {:?}",
+					&derivative_index,
+					&targets,
+				);
+				Self::do_nominate(derivative_index, targets)?;
+			}
+			Ok(())
+		}
+
+		pub(crate) fn rotate_nominations_weight() -> Weight {
+			<T as Config>::WeightInfo::rotate_nominations(T::MaxValidatorCandidates::get())
+				.saturating_add(
+					<T as Config>::WeightInfo::nominate()
+						.saturating_mul(T::DerivativeIndexList::get().len() as u64),
+				)
+		}
+
 		#[require_transactional]
 		fn do_claim_for(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
 			let module_id = Self::account_id();
@@ -1908,6 +2755,72 @@ pub mod pallet {
 			Ok(inflate_liquid_amount)
 		}
 
+		/// Request to unstake `liquid_amount` from the matching pool, up to the balance of `who`.
+		fn do_request_fast_unstake(
+			who: &T::AccountId,
+			liquid_amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let keep_alive = false;
+			let keep_alive =
+				if keep_alive { Preservation::Preserve } else { Preservation::Expendable };
+			FastUnstakeRequests::<T>::try_mutate(who, |b| -> DispatchResult {
+				let balance = T::Assets::reducible_balance(
+					Self::liquid_currency()?,
+					who,
+					keep_alive,
+					Fortitude::Polite,
+				);
+				*b = b.saturating_add(liquid_amount).min(balance);
+				Ok(())
+			})
+		}
+
+		/// Sell `liquid_amount` on the dex for at least its staking value minus `max_discount`,
+		/// falling back to a fast unstake request when it can not be sold.
+		#[require_transactional]
+		fn do_dex_unstake(
+			who: &T::AccountId,
+			liquid_amount: BalanceOf<T>,
+			max_discount: Ratio,
+		) -> DispatchResult {
+			let amount =
+				Self::liquid_to_staking(liquid_amount).ok_or(Error::<T>::InvalidExchangeRate)?;
+			let liquid = AssetAmount::new(Self::liquid_currency()?, liquid_amount);
+			let min_receive = AssetAmount::new(
+				Self::staking_currency()?,
+				amount.saturating_sub(max_discount.mul_ceil(amount)),
+			);
+
+			let sold = Self::dex_pool()
+				.ok_or_else(|| DispatchError::from(Error::<T>::DexPoolNotSet))
+				.and_then(|pool_id| {
+					with_transaction(|| match T::Dex::sell(who, pool_id, liquid, min_receive) {
+						Ok(received) => TransactionOutcome::Commit(Ok(received)),
+						Err(err) => TransactionOutcome::Rollback(Err(err)),
+					})
+				});
+
+			match sold {
+				Ok(received) => {
+					Self::deposit_event(Event::<T>::DexUnstaked(
+						who.clone(),
+						liquid_amount,
+						received,
+					));
+				},
+				Err(err) => {
+					log::trace!(
+						target: "liquidStaking::do_dex_unstake",
+						"falling back to the matching pool: {:?}",
+						&err,
+					);
+					Self::do_request_fast_unstake(who, liquid_amount)?;
+					Self::deposit_event(Event::<T>::DexUnstakeFellBack(who.clone(), liquid_amount));
+				},
+			}
+			Ok(())
+		}
+
 		#[require_transactional]
 		fn do_fast_match_unstake(unstaker: &T::AccountId) -> DispatchResult {
 			FastUnstakeRequests::<T>::try_mutate_exists(unstaker, |b| -> DispatchResult {
@@ -2031,9 +2944,18 @@ pub mod pallet {
 			value: Vec<u8>,
 			proof: Vec<Vec<u8>>,
 		) -> bool {
+			Self::read_merkle_proof(key, proof) == Some(Some(value))
+		}
+
+		/// Value of `key` in the relaychain storage, `None` if the proof is invalid and
+		/// `Some(None)` if it proves that there is no such value.
+		pub(crate) fn read_merkle_proof(
+			key: Vec<u8>,
+			proof: Vec<Vec<u8>>,
+		) -> Option<Option<Vec<u8>>> {
 			let validation_data = Self::validation_data();
 			if validation_data.is_none() {
-				return false
+				return None
 			}
 			let PersistedValidationData { relay_parent_number, relay_parent_storage_root, .. } =
 				validation_data.expect("Could not be none, qed;");
@@ -2044,16 +2966,14 @@ pub mod pallet {
 			);
 			let relay_proof = StorageProof::new(proof);
 			let db = relay_proof.into_memory_db();
-			if let Ok(Some(result)) = sp_trie::read_trie_value::<sp_trie::LayoutV1<BlakeTwo256>, _>(
+			sp_trie::read_trie_value::<sp_trie::LayoutV1<BlakeTwo256>, _>(
 				&db,
 				&relay_parent_storage_root,
 				&key,
 				None,
 				None,
-			) {
-				return result == value
-			}
-			false
+			)
+			.ok()
 		}
 
 		pub(crate) fn get_staking_ledger_key(derivative_index: DerivativeIndex) -> Vec<u8> {
@@ -2072,10 +2992,33 @@ pub mod pallet {
 		pub(crate) fn get_current_era_key() -> Vec<u8> {
 			storage_prefix("Staking".as_bytes(), "CurrentEra".as_bytes()).to_vec()
 		}
+
+		pub(crate) fn get_validator_prefs_key(validator: &T::AccountId) -> Vec<u8> {
+			let storage_prefix = storage_prefix("Staking".as_bytes(), "Validators".as_bytes());
+			let key_hashed = validator.using_encoded(Twox64Concat::hash);
+			let mut final_key = Vec::with_capacity(storage_prefix.len() + key_hashed.len());
+
+			final_key.extend_from_slice(&storage_prefix);
+			final_key.extend_from_slice(&key_hashed);
+
+			final_key
+		}
+
+		pub(crate) fn get_era_reward_points_key(era: EraIndex) -> Vec<u8> {
+			let storage_prefix =
+				storage_prefix("Staking".as_bytes(), "ErasRewardPoints".as_bytes());
+			let key_hashed = era.using_encoded(Twox64Concat::hash);
+			let mut final_key = Vec::with_capacity(storage_prefix.len() + key_hashed.len());
+
+			final_key.extend_from_slice(&storage_prefix);
+			final_key.extend_from_slice(&key_hashed);
+
+			final_key
+		}
 	}
 }
 
-use crate::types::DecimalProvider;
+use crate::types::{DecimalProvider, DistributionStrategyKind, Rate};
 impl<T: Config> LiquidStakingCurrenciesProvider<AssetIdOf<T>> for Pallet<T> {
 	fn get_staking_currency() -> Option<AssetIdOf<T>> {
 		let asset_id = T::StakingCurrency::get();
@@ -2115,3 +3058,126 @@ impl<T: Config, Balance: BalanceT + FixedPointOperand> LiquidStakingConvert<Bala
 		Self::exchange_rate().checked_mul_int(liquid_amount)
 	}
 }
+
+impl<T: Config> ExchangeRateProvider<AssetIdOf<T>> for Pallet<T> {
+	fn exchange_rate(derivative: AssetIdOf<T>) -> Option<(AssetIdOf<T>, Rate)> {
+		let rate = ExchangeRate::<T>::get();
+		(derivative == T::LiquidCurrency::get() && !rate.is_zero())
+			.then(|| (T::StakingCurrency::get(), rate))
+	}
+}
+
+impl<T: Config> DerivativePerformance for Pallet<T> {
+	/// Average reward points of the validators nominated by the derivative.
+	fn performance(index: DerivativeIndex) -> u32 {
+		let targets = Self::nominations(index).unwrap_or_default();
+		if targets.is_empty() {
+			return 0
+		}
+		let points = targets
+			.iter()
+			.filter_map(Self::validator_candidate)
+			.fold(0u64, |acc, candidate| acc.saturating_add(candidate.era_points.into()));
+		(points / targets.len() as u64).try_into().unwrap_or(u32::MAX)
+	}
+}
+
+/// Distribution strategy selected by `UpdateOrigin` through `set_distribution_strategy`.
+pub struct SelectableDistribution<T>(PhantomData<T>);
+impl<T: Config> DistributionStrategy<BalanceOf<T>> for SelectableDistribution<T> {
+	fn get_bond_distributions(
+		bonded_amounts: Vec<(DerivativeIndex, BalanceOf<T>, BalanceOf<T>)>,
+		input: BalanceOf<T>,
+		cap: BalanceOf<T>,
+		min_nominator_bond: BalanceOf<T>,
+	) -> Vec<(DerivativeIndex, BalanceOf<T>)> {
+		match Pallet::<T>::distribution_strategy() {
+			DistributionStrategyKind::Average => AverageDistribution::get_bond_distributions(
+				bonded_amounts,
+				input,
+				cap,
+				min_nominator_bond,
+			),
+			DistributionStrategyKind::MaxMin => MaxMinDistribution::get_bond_distributions(
+				bonded_amounts,
+				input,
+				cap,
+				min_nominator_bond,
+			),
+			DistributionStrategyKind::LargestCapacityFirst =>
+				LargestCapacityFirst::get_bond_distributions(
+					bonded_amounts,
+					input,
+					cap,
+					min_nominator_bond,
+				),
+			DistributionStrategyKind::PerformanceWeighted =>
+				PerformanceWeighted::<Pallet<T>>::get_bond_distributions(
+					bonded_amounts,
+					input,
+					cap,
+					min_nominator_bond,
+				),
+			DistributionStrategyKind::MinimizeCalls => MinimizeCalls::get_bond_distributions(
+				bonded_amounts,
+				input,
+				cap,
+				min_nominator_bond,
+			),
+		}
+	}
+
+	fn get_unbond_distributions(
+		active_bonded_amounts: Vec<(DerivativeIndex, BalanceOf<T>)>,
+		input: BalanceOf<T>,
+		min_nominator_bond: BalanceOf<T>,
+	) -> Vec<(DerivativeIndex, BalanceOf<T>)> {
+		match Pallet::<T>::distribution_strategy() {
+			DistributionStrategyKind::Average => AverageDistribution::get_unbond_distributions(
+				active_bonded_amounts,
+				input,
+				min_nominator_bond,
+			),
+			DistributionStrategyKind::MaxMin => MaxMinDistribution::get_unbond_distributions(
+				active_bonded_amounts,
+				input,
+				min_nominator_bond,
+			),
+			DistributionStrategyKind::LargestCapacityFirst =>
+				LargestCapacityFirst::get_unbond_distributions(
+					active_bonded_amounts,
+					input,
+					min_nominator_bond,
+				),
+			DistributionStrategyKind::PerformanceWeighted =>
+				PerformanceWeighted::<Pallet<T>>::get_unbond_distributions(
+					active_bonded_amounts,
+					input,
+					min_nominator_bond,
+				),
+			DistributionStrategyKind::MinimizeCalls => MinimizeCalls::get_unbond_distributions(
+				active_bonded_amounts,
+				input,
+				min_nominator_bond,
+			),
+		}
+	}
+
+	fn get_rebond_distributions(
+		unbonding_amounts: Vec<(DerivativeIndex, BalanceOf<T>)>,
+		input: BalanceOf<T>,
+	) -> Vec<(DerivativeIndex, BalanceOf<T>)> {
+		match Pallet::<T>::distribution_strategy() {
+			DistributionStrategyKind::Average =>
+				AverageDistribution::get_rebond_distributions(unbonding_amounts, input),
+			DistributionStrategyKind::MaxMin =>
+				MaxMinDistribution::get_rebond_distributions(unbonding_amounts, input),
+			DistributionStrategyKind::LargestCapacityFirst =>
+				LargestCapacityFirst::get_rebond_distributions(unbonding_amounts, input),
+			DistributionStrategyKind::PerformanceWeighted =>
+				PerformanceWeighted::<Pallet<T>>::get_rebond_distributions(unbonding_amounts, input),
+			DistributionStrategyKind::MinimizeCalls =>
+				MinimizeCalls::get_rebond_distributions(unbonding_amounts, input),
+		}
+	}
+}
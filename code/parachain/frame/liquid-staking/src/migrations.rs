@@ -0,0 +1,29 @@
+use crate::{Config, Pallet, StorageVersion, Versions, XcmRequestStatuses, XcmRequests};
+use frame_support::{
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::vec::Vec;
+
+/// Tracks the sending of the xcm requests already waiting for their response, as sent at the
+/// upgrade. They time out from then on if their kind has a timeout.
+pub struct LiquidStakingV2ToV3<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for LiquidStakingV2ToV3<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if StorageVersion::<T>::get() == Versions::V2 {
+			let requests = XcmRequests::<T>::iter()
+				.filter(|(query_id, _request)| !XcmRequestStatuses::<T>::contains_key(query_id))
+				.collect::<Vec<_>>();
+			let total = requests.len() as u64;
+			for (query_id, request) in requests {
+				Pallet::<T>::track_xcm_request(query_id, request, 1);
+			}
+			StorageVersion::<T>::put(Versions::V3);
+			// the timeout of each request is read, its status and deadline written
+			T::DbWeight::get().reads_writes(3 * total + 1, 3 * total + 1)
+		} else {
+			T::DbWeight::get().reads(1)
+		}
+	}
+}
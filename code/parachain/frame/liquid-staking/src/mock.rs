@@ -1,4 +1,5 @@
 use crate::{
+	dex::LiquidDex,
 	types::{EraIndex, PersistedValidationData, Rate, Ratio, UnstakeProvider},
 	Balance, DecimalProvider,
 };
@@ -29,7 +30,7 @@ use polkadot_parachain::primitives::{IsSystem, Sibling};
 pub type Price = FixedU128;
 use crate::types::ValidationDataProvider;
 use composable_support::collections::vec::bounded::BiBoundedVec;
-use composable_traits::{assets::AssetInfo, rational};
+use composable_traits::{assets::AssetInfo, dex::AssetAmount, rational};
 use sp_runtime::FixedU128;
 
 // use pallet_loans::{InterestRateModel, JumpModel, Market, MarketState};
@@ -63,7 +64,9 @@ use xcm_executor::{traits::ConvertOrigin, Config, XcmExecutor};
 use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
 
 pub type AccountId = AccountId32;
-use crate::{distribution::AverageDistribution, types::StakingLedger, BalanceOf};
+use crate::{
+	distribution::AverageDistribution, selection::LowestCommission, types::StakingLedger, BalanceOf,
+};
 pub use kusama_runtime;
 use primitives::currency::CurrencyId;
 
@@ -556,7 +559,10 @@ parameter_types! {
 	pub static DerivativeIndexList: Vec<u16> = vec![0];
 	pub static RelayChainValidationDataProvider: BlockNumber = 0;
 	pub const ElectionSolutionStoredOffset: BlockNumber = 10;
+	pub const MaxNominations: u32 = 2;
+	pub const MaxValidatorCandidates: u32 = 4;
 	pub const DefaultProtocolFeeReceiver: AccountId32 = AccountId32::new([100u8; 32]);
+	pub static DexPrice: Rate = Rate::one();
 }
 
 impl crate::Config for Test {
@@ -584,12 +590,45 @@ impl crate::Config for Test {
 	type Members = BobOrigin;
 	type NumSlashingSpans = NumSlashingSpans;
 	type DistributionStrategy = AverageDistribution;
+	type ValidatorSelection = LowestCommission;
+	type Dex = MockDex;
+	type MaxNominations = MaxNominations;
+	type MaxValidatorCandidates = MaxValidatorCandidates;
 	type ElectionSolutionStoredOffset = ElectionSolutionStoredOffset;
 	type ProtocolFeeReceiver = DefaultProtocolFeeReceiver;
 	type Decimal = Decimal;
 	type NativeCurrency = NativeCurrencyId;
 }
 
+/// Sells the liquid currency at `DexPrice` staking currency per unit.
+pub struct MockDex;
+impl LiquidDex<AccountId, CurrencyId, Balance> for MockDex {
+	type PoolId = u32;
+
+	fn sell(
+		who: &AccountId,
+		_pool_id: u32,
+		liquid: AssetAmount<CurrencyId, Balance>,
+		min_receive: AssetAmount<CurrencyId, Balance>,
+	) -> Result<Balance, DispatchError> {
+		use frame_support::traits::{
+			fungibles::Mutate,
+			tokens::{Fortitude, Precision},
+		};
+		let received = DexPrice::get().saturating_mul_int(liquid.amount);
+		ensure!(received >= min_receive.amount, DispatchError::Other("price too low"));
+		<Assets as Mutate<AccountId>>::burn_from(
+			liquid.asset_id,
+			who,
+			liquid.amount,
+			Precision::Exact,
+			Fortitude::Polite,
+		)?;
+		<Assets as Mutate<AccountId>>::mint_into(min_receive.asset_id, who, received)?;
+		Ok(received)
+	}
+}
+
 pub struct Decimal;
 #[allow(non_upper_case_globals)]
 impl DecimalProvider<CurrencyId> for Decimal {
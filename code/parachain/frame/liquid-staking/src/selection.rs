@@ -0,0 +1,95 @@
+use sp_std::{cmp::Reverse, vec::Vec};
+
+use crate::types::{EraIndex, ValidatorCandidate};
+
+/// Strategy picking the validators nominated by the derivative accounts each era.
+pub trait ValidatorSelection<AccountId> {
+	/// Rank the `candidates`, most preferred first, leaving out the ones which should not be
+	/// nominated. Nominations are left untouched when the ranking is empty.
+	fn rank(candidates: Vec<(AccountId, ValidatorCandidate)>, era: EraIndex) -> Vec<AccountId>;
+}
+
+/// No automatic nominations, targets are only set through `nominate`.
+impl<AccountId> ValidatorSelection<AccountId> for () {
+	fn rank(_candidates: Vec<(AccountId, ValidatorCandidate)>, _era: EraIndex) -> Vec<AccountId> {
+		Vec::new()
+	}
+}
+
+/// Candidates whose preferences were proven and which accept nominations.
+fn eligible<AccountId>(
+	candidates: Vec<(AccountId, ValidatorCandidate)>,
+) -> Vec<(AccountId, ValidatorCandidate)> {
+	candidates
+		.into_iter()
+		.filter(|(_, candidate)| candidate.prefs.as_ref().map_or(false, |prefs| !prefs.blocked))
+		.collect()
+}
+
+/// Prefer the validators taking the lowest commission, then the ones earning the most points.
+pub struct LowestCommission;
+impl<AccountId: Ord> ValidatorSelection<AccountId> for LowestCommission {
+	fn rank(candidates: Vec<(AccountId, ValidatorCandidate)>, _era: EraIndex) -> Vec<AccountId> {
+		let mut candidates = eligible(candidates);
+		candidates.sort_by(|(x, x_candidate), (y, y_candidate)| {
+			(x_candidate.commission(), Reverse(x_candidate.era_points), x).cmp(&(
+				y_candidate.commission(),
+				Reverse(y_candidate.era_points),
+				y,
+			))
+		});
+		candidates.into_iter().map(|(account, _)| account).collect()
+	}
+}
+
+/// Prefer the validators earning the most points, then the ones taking the lowest commission.
+pub struct HighestEraPoints;
+impl<AccountId: Ord> ValidatorSelection<AccountId> for HighestEraPoints {
+	fn rank(candidates: Vec<(AccountId, ValidatorCandidate)>, _era: EraIndex) -> Vec<AccountId> {
+		let mut candidates = eligible(candidates);
+		candidates.sort_by(|(x, x_candidate), (y, y_candidate)| {
+			(Reverse(x_candidate.era_points), x_candidate.commission(), x).cmp(&(
+				Reverse(y_candidate.era_points),
+				y_candidate.commission(),
+				y,
+			))
+		});
+		candidates.into_iter().map(|(account, _)| account).collect()
+	}
+}
+
+/// Shift the eligible validators by one each era, so that every candidate gets nominated in turn.
+pub struct RoundRobin;
+impl<AccountId: Ord> ValidatorSelection<AccountId> for RoundRobin {
+	fn rank(candidates: Vec<(AccountId, ValidatorCandidate)>, era: EraIndex) -> Vec<AccountId> {
+		let mut accounts =
+			eligible(candidates).into_iter().map(|(account, _)| account).collect::<Vec<_>>();
+		accounts.sort();
+		if !accounts.is_empty() {
+			let shift = (era as usize) % accounts.len();
+			accounts.rotate_left(shift);
+		}
+		accounts
+	}
+}
+
+/// Targets of the derivative at `position` in the derivative index list. Consecutive derivatives
+/// nominate consecutive windows of `max_targets` validators out of the `ranked` ones, so that the
+/// stake is spread when there are enough candidates.
+pub fn select_targets<AccountId: Clone>(
+	ranked: &[AccountId],
+	position: usize,
+	max_targets: usize,
+) -> Vec<AccountId> {
+	if ranked.is_empty() {
+		return Vec::new()
+	}
+	let start = position.saturating_mul(max_targets) % ranked.len();
+	ranked
+		.iter()
+		.cycle()
+		.skip(start)
+		.take(max_targets.min(ranked.len()))
+		.cloned()
+		.collect()
+}
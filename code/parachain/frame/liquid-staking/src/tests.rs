@@ -3,19 +3,31 @@ use frame_support::{
 	dispatch::DispatchResult,
 	error::BadOrigin,
 	storage::with_transaction,
-	traits::{fungibles::Inspect, Hooks},
+	traits::{fungibles::Inspect, Hooks, OnRuntimeUpgrade},
 };
 use sp_runtime::{
 	traits::{BlakeTwo256, One, Saturating, Zero},
 	ArithmeticError::Underflow,
 	MultiAddress::Id,
-	TransactionOutcome,
+	Perbill, TransactionOutcome,
 };
 use sp_trie::StorageProof;
 use xcm_simulator::TestExt;
 
-use crate::{mock::*, types::*, *};
-use parity_scale_codec::Encode;
+use crate::{mock::*, selection::*, types::*, *};
+use composable_traits::liquid_staking::{
+	DerivativeLedger, LiquidStakingPosition, PendingXcmRequest, StakeSimulation, Unlocking,
+	UnstakeSimulation,
+};
+use pallet_xcm_helper::ump::{
+	BalancesCall, BalancesTransferAllCall, BalancesTransferKeepAliveCall, KusamaCall,
+	NominationPoolsCall, NominationPoolsJoinCall, NominationPoolsUnbondCall,
+	NominationPoolsWithdrawUnbondedCall, ProxyAddProxyCall, ProxyCall, ProxyRemoveProxyCall,
+	ProxyType, StakingCall, StakingPayoutStakersCall, StakingSetPayeeCall, UtilityAsDerivativeCall,
+	UtilityCall, XcmCall, XcmWeightFeeMisc,
+};
+use parity_scale_codec::{DecodeAll, Encode};
+use proptest::prelude::*;
 
 #[test]
 fn stake_should_work() {
@@ -576,6 +588,138 @@ fn test_transact_nominate_work() {
 	});
 }
 
+#[test]
+fn test_transact_chill_work() {
+	TestNet::reset();
+	let derivative_index = 0u16;
+	ParaA::execute_with(|| {
+		assert_ok!(XcmHelper::update_xcm_weight_fee(
+			RuntimeOrigin::root(),
+			XcmCall::Chill,
+			XcmWeightFeeMisc::default(),
+		));
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(4000f64),));
+		assert_ok!(LiquidStaking::bond(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			ksm(10f64),
+			RewardDestination::Staked
+		));
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			0,
+			Response::ExecutionResult(None),
+		));
+		assert_ok!(LiquidStaking::nominate(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			vec![ALICE, BOB],
+		));
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			1,
+			Response::ExecutionResult(None),
+		));
+		assert_eq!(LiquidStaking::nominations(derivative_index), Some(vec![ALICE, BOB]));
+
+		assert_ok!(LiquidStaking::chill(RuntimeOrigin::signed(ALICE), derivative_index));
+		assert_eq!(
+			LiquidStaking::xcm_request(2),
+			Some(XcmRequest::Chill { index: derivative_index })
+		);
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			2,
+			Response::ExecutionResult(None),
+		));
+		assert_eq!(LiquidStaking::nominations(derivative_index), None);
+		assert_eq!(LiquidStaking::xcm_request(2), None);
+	});
+
+	Relay::execute_with(|| {
+		assert!(RelayStaking::nominators(LiquidStaking::derivative_sovereign_account_id(
+			derivative_index,
+		))
+		.is_none());
+	});
+}
+
+#[test]
+fn chill_needs_configured_xcm_weight_fee() {
+	new_test_ext().execute_with(|| {
+		let derivative_index = 0u16;
+		StakingLedgers::<Test>::insert(
+			derivative_index,
+			<StakingLedger<AccountId, BalanceOf<Test>>>::new(
+				LiquidStaking::derivative_sovereign_account_id(derivative_index),
+				ksm(10f64),
+			),
+		);
+		assert_noop!(
+			LiquidStaking::chill(RuntimeOrigin::signed(ALICE), derivative_index),
+			pallet_xcm_helper::Error::<Test>::XcmCallNotConfigured
+		);
+	})
+}
+
+#[test]
+fn test_transact_set_payee_work() {
+	TestNet::reset();
+	let derivative_index = 0u16;
+	ParaA::execute_with(|| {
+		assert_ok!(XcmHelper::update_xcm_weight_fee(
+			RuntimeOrigin::root(),
+			XcmCall::SetPayee,
+			XcmWeightFeeMisc::default(),
+		));
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(4000f64),));
+		assert_ok!(LiquidStaking::bond(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			ksm(10f64),
+			RewardDestination::Staked
+		));
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			0,
+			Response::ExecutionResult(None),
+		));
+
+		assert_ok!(LiquidStaking::set_payee(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			RewardDestination::Stash,
+		));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::SettingPayee(
+			derivative_index,
+			RewardDestination::Stash,
+		)));
+		// a failed request is stuck until resolved, as no timeout is set for its kind
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			1,
+			Response::ExecutionResult(Some((0, XcmError::Unimplemented))),
+		));
+		assert_eq!(
+			LiquidStaking::xcm_request_status(1).map(|status| status.state),
+			Some(XcmRequestState::Stuck)
+		);
+		assert_ok!(LiquidStaking::resolve_xcm_request(
+			RuntimeOrigin::root(),
+			1,
+			XcmRequestResolution::Executed,
+		));
+		assert_eq!(LiquidStaking::xcm_request(1), None);
+	});
+
+	Relay::execute_with(|| {
+		assert_eq!(
+			RelayStaking::payee(LiquidStaking::derivative_sovereign_account_id(derivative_index)),
+			pallet_staking::RewardDestination::Stash
+		);
+	});
+}
+
 #[test]
 fn test_transfer_bond() {
 	TestNet::reset();
@@ -949,165 +1093,833 @@ fn cancel_unstake_works() {
 }
 
 #[test]
-fn test_charge_commission_work() {
+fn set_dex_pool_should_work() {
 	new_test_ext().execute_with(|| {
-		let derivative_index = 0u16;
-		let bond_amount = ksm(200f64);
-		let staking_ledger = <StakingLedger<AccountId, BalanceOf<Test>>>::new(
-			LiquidStaking::derivative_sovereign_account_id(derivative_index),
-			bond_amount,
-		);
-		StakingLedgers::<Test>::insert(derivative_index, staking_ledger.clone());
-		assert_ok!(LiquidStaking::update_commission_rate(
-			RuntimeOrigin::root(),
-			Rate::from_rational(1, 100)
-		));
-		LiquidStaking::on_finalize(1);
+		assert_noop!(LiquidStaking::set_dex_pool(RuntimeOrigin::signed(ALICE), Some(1)), BadOrigin);
+		assert_ok!(LiquidStaking::set_dex_pool(RuntimeOrigin::root(), Some(1)));
+		assert_eq!(LiquidStaking::dex_pool(), Some(1));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::DexPoolUpdated(
+			Some(1),
+		)));
+		assert_ok!(LiquidStaking::set_dex_pool(RuntimeOrigin::signed(BOB), None));
+		assert_eq!(LiquidStaking::dex_pool(), None);
+	})
+}
 
-		// liquid_amount_to_fee=TotalLiquidCurrency *
-		// (commission_rate*total_rewards/(TotalStakeCurrency+(1-commission_rate)*total_rewards))
-		let commission_rate = CommissionRate::<Test>::get();
-		let total_rewards = MOCK_LEDGER_AMOUNT - bond_amount;
-		let commission_staking_amount = commission_rate.saturating_mul_int(total_rewards);
-		let issurance = <Test as Config>::Assets::total_issuance(SKSM);
-		let matching_ledger = LiquidStaking::matching_pool();
-		let total_active_bonded: u128 = StakingLedgers::<Test>::iter_values()
-			.fold(Zero::zero(), |acc, ledger| acc.saturating_add(ledger.active));
-		let total_bonded = total_active_bonded + matching_ledger.total_stake_amount.total -
-			matching_ledger.total_unstake_amount.total;
-		let inflate_rate = Rate::checked_from_rational(
-			commission_staking_amount,
-			total_bonded + total_rewards - commission_staking_amount,
-		)
-		.unwrap();
+#[test]
+fn unstake_through_dex_works() {
+	new_test_ext().execute_with(|| {
+		let dex = UnstakeProvider::Dex { max_discount: Ratio::from_percent(10) };
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(10f64)));
+		assert_ok!(LiquidStaking::set_dex_pool(RuntimeOrigin::root(), Some(1)));
+		DexPrice::set(Rate::from_rational(95, 100));
+
+		let liquid_balance = <Test as Config>::Assets::balance(SKSM, &ALICE);
+		let staking_balance = <Test as Config>::Assets::balance(KSM, &ALICE);
+		assert_ok!(LiquidStaking::unstake(RuntimeOrigin::signed(ALICE), ksm(2f64), dex));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::DexUnstaked(
+			ALICE,
+			ksm(2f64),
+			ksm(1.9f64),
+		)));
+		assert_eq!(<Test as Config>::Assets::balance(SKSM, &ALICE), liquid_balance - ksm(2f64));
+		assert_eq!(<Test as Config>::Assets::balance(KSM, &ALICE), staking_balance + ksm(1.9f64));
+		// sold right away, nothing left to match or to unbond
+		assert_eq!(LiquidStaking::fast_unstake_requests(&ALICE), 0);
+		assert_eq!(Unlockings::<Test>::get(&ALICE), None);
+	})
+}
 
-		let inflate_liquid_amount = inflate_rate.saturating_mul_int(issurance);
+#[test]
+fn unstake_through_dex_falls_back_to_matching_pool() {
+	new_test_ext().execute_with(|| {
+		let dex = UnstakeProvider::Dex { max_discount: Ratio::from_percent(10) };
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(10f64)));
+		let liquid_balance = <Test as Config>::Assets::balance(SKSM, &ALICE);
 
-		assert_ok!(LiquidStaking::set_staking_ledger(
-			RuntimeOrigin::signed(ALICE),
-			derivative_index,
-			get_mock_staking_ledger(derivative_index),
-			get_mock_proof_bytes()
+		// no pool
+		assert_ok!(LiquidStaking::unstake(RuntimeOrigin::signed(ALICE), ksm(2f64), dex));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::DexUnstakeFellBack(ALICE, ksm(2f64)),
+		));
+		assert_eq!(LiquidStaking::fast_unstake_requests(&ALICE), ksm(2f64));
+
+		// price below the max discount
+		assert_ok!(LiquidStaking::set_dex_pool(RuntimeOrigin::root(), Some(1)));
+		DexPrice::set(Rate::from_rational(85, 100));
+		assert_ok!(LiquidStaking::unstake(RuntimeOrigin::signed(ALICE), ksm(3f64), dex));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::DexUnstakeFellBack(ALICE, ksm(3f64)),
 		));
+		assert_eq!(LiquidStaking::fast_unstake_requests(&ALICE), ksm(5f64));
+		assert_eq!(<Test as Config>::Assets::balance(SKSM, &ALICE), liquid_balance);
+	})
+}
 
+#[test]
+fn simulate_stake_matches_stake() {
+	new_test_ext().execute_with(|| {
+		let simulation = LiquidStaking::simulate_stake(ksm(10f64)).unwrap();
 		assert_eq!(
-			LiquidStaking::staking_ledger(derivative_index).unwrap().total,
-			MOCK_LEDGER_AMOUNT
+			simulation,
+			StakeSimulation { liquid_amount: ksm(9.95f64), reserves: ksm(0.05f64), xcm_fees: 0 }
 		);
 
+		let liquid_balance = <Test as Config>::Assets::balance(SKSM, &ALICE);
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(10f64)));
 		assert_eq!(
-			<Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get()),
-			inflate_liquid_amount
-		)
+			<Test as Config>::Assets::balance(SKSM, &ALICE),
+			liquid_balance + simulation.liquid_amount
+		);
+		assert_eq!(LiquidStaking::total_reserves(), simulation.reserves);
 	})
 }
 
 #[test]
-fn test_complete_fast_match_unstake_work() {
+fn simulate_unstake_matches_unstake() {
 	new_test_ext().execute_with(|| {
-		let reserve_factor = LiquidStaking::reserve_factor();
-		let xcm_fees = XcmFees::get();
-		let bond_amount = ksm(10f64);
-		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(BOB), bond_amount));
-		let total_stake_amount = bond_amount - xcm_fees - reserve_factor.mul_floor(bond_amount);
-
-		let fast_unstake_amount = ksm(3f64);
-		assert_ok!(LiquidStaking::unstake(
-			RuntimeOrigin::signed(BOB),
-			fast_unstake_amount,
-			UnstakeProvider::MatchingPool
-		));
-		assert_ok!(LiquidStaking::fast_match_unstake(RuntimeOrigin::signed(BOB), [BOB].to_vec(),));
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(10f64)));
+		ExchangeRate::<Test>::set(Rate::saturating_from_rational(2, 1));
 
+		let simulation = LiquidStaking::simulate_unstake(ksm(3f64)).unwrap();
 		assert_eq!(
-			<Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get()),
-			MatchingPoolFastUnstakeFee::get().saturating_mul_int(fast_unstake_amount)
+			simulation,
+			UnstakeSimulation {
+				liquid_amount: ksm(3f64),
+				staking_amount: ksm(6f64),
+				unlock_era: 4,
+				fast_unstake_fee: ksm(0.003f64),
+			}
 		);
 
+		assert_ok!(LiquidStaking::unstake(
+			RuntimeOrigin::signed(ALICE),
+			ksm(3f64),
+			Default::default()
+		));
 		assert_eq!(
-			<Test as Config>::Assets::balance(SKSM, &BOB),
-			total_stake_amount - fast_unstake_amount
-		);
-		let pool_stake_amount = total_stake_amount -
-			Rate::one()
-				.saturating_sub(MatchingPoolFastUnstakeFee::get())
-				.saturating_mul_int(fast_unstake_amount);
-		assert_eq!(
-			LiquidStaking::matching_pool(),
-			MatchingLedger {
-				total_stake_amount: ReservableAmount { total: pool_stake_amount, reserved: 0 },
-				total_unstake_amount: Default::default(),
-			}
+			Unlockings::<Test>::get(ALICE).unwrap(),
+			vec![UnlockChunk { value: simulation.staking_amount, era: simulation.unlock_era }]
 		);
 	})
 }
 
 #[test]
-fn test_partial_fast_match_unstake_work() {
+fn position_and_derivative_ledgers_should_work() {
 	new_test_ext().execute_with(|| {
-		let reserve_factor = LiquidStaking::reserve_factor();
-		let xcm_fees = XcmFees::get();
-		let bond_amount = ksm(5f64);
-		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), bond_amount));
-		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(BOB), bond_amount));
-
-		let alice_stake_amount = bond_amount - xcm_fees - reserve_factor.mul_floor(bond_amount);
-		let bob_stake_amount = alice_stake_amount;
-
-		// default exchange_rate is 1
-		let alice_fast_unstake_amount = ksm(10f64);
-		let bob_fast_unstake_amount = ksm(1f64);
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(10f64)));
 		assert_ok!(LiquidStaking::unstake(
 			RuntimeOrigin::signed(ALICE),
-			alice_fast_unstake_amount,
-			UnstakeProvider::MatchingPool
+			ksm(6f64),
+			Default::default()
 		));
 		assert_ok!(LiquidStaking::unstake(
-			RuntimeOrigin::signed(BOB),
-			bob_fast_unstake_amount,
+			RuntimeOrigin::signed(ALICE),
+			ksm(1f64),
 			UnstakeProvider::MatchingPool
 		));
-		assert_ok!(LiquidStaking::fast_match_unstake(
-			RuntimeOrigin::signed(BOB),
-			[BOB, ALICE].to_vec(),
-		));
-
-		assert_eq!(
-			<Test as Config>::Assets::balance(SKSM, &BOB),
-			bob_stake_amount - bob_fast_unstake_amount
-		);
 
-		let bob_matched_amount = Rate::one()
-			.saturating_sub(MatchingPoolFastUnstakeFee::get())
-			.saturating_mul_int(bob_fast_unstake_amount);
-
-		let available_amount = (alice_stake_amount + bob_stake_amount - bob_matched_amount)
-			.min(alice_fast_unstake_amount);
-		let alice_matched_amount = Rate::one()
-			.saturating_sub(MatchingPoolFastUnstakeFee::get())
-			.saturating_mul_int(available_amount);
-
-		// mint in mock
-		let alice_initial_amount = ksm(100f64);
+		let liquid_balance = <Test as Config>::Assets::balance(SKSM, &ALICE);
 		assert_eq!(
-			<Test as Config>::Assets::balance(SKSM, &ALICE),
-			alice_initial_amount + alice_stake_amount - available_amount
+			LiquidStaking::position(&ALICE),
+			LiquidStakingPosition {
+				liquid_balance,
+				staked_value: liquid_balance,
+				unlockings: vec![Unlocking { value: ksm(6f64), era: 4 }],
+				fast_unstake_request: ksm(1f64),
+			}
 		);
+		assert_eq!(LiquidStaking::position(&BOB).unlockings, vec![]);
 
+		assert_eq!(LiquidStaking::derivative_ledgers(), vec![]);
+		for derivative_index in [1u16, 0u16] {
+			StakingLedgers::<Test>::insert(
+				derivative_index,
+				<StakingLedger<AccountId, BalanceOf<Test>>>::new(
+					LiquidStaking::derivative_sovereign_account_id(derivative_index),
+					ksm(derivative_index as f64 + 1f64),
+				),
+			);
+		}
 		assert_eq!(
-			LiquidStaking::matching_pool(),
-			MatchingLedger {
-				total_stake_amount: ReservableAmount {
-					total: alice_stake_amount + bob_stake_amount -
-						bob_matched_amount - alice_matched_amount,
-					reserved: 0
+			LiquidStaking::derivative_ledgers(),
+			vec![
+				DerivativeLedger {
+					derivative_index: 0,
+					total: ksm(1f64),
+					active: ksm(1f64),
+					unlocking: vec![],
 				},
-				total_unstake_amount: Default::default(),
-			}
+				DerivativeLedger {
+					derivative_index: 1,
+					total: ksm(2f64),
+					active: ksm(2f64),
+					unlocking: vec![],
+				},
+			]
 		);
-		assert_eq!(
-			LiquidStaking::fast_unstake_requests(&ALICE),
-			alice_fast_unstake_amount - available_amount
+	})
+}
+
+#[test]
+fn set_slash_policy_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LiquidStaking::slash_policy(), SlashPolicy::ReservesFirst);
+		assert_noop!(
+			LiquidStaking::set_slash_policy(RuntimeOrigin::signed(ALICE), SlashPolicy::Socialize),
+			BadOrigin
+		);
+		assert_ok!(LiquidStaking::set_slash_policy(
+			RuntimeOrigin::signed(BOB),
+			SlashPolicy::Socialize
+		));
+		assert_eq!(LiquidStaking::slash_policy(), SlashPolicy::Socialize);
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::SlashPolicyUpdated(SlashPolicy::Socialize),
+		));
+	})
+}
+
+#[test]
+fn staking_ledger_slashed_since() {
+	let previous = <StakingLedger<AccountId, BalanceOf<Test>>>::new(ALICE, ksm(100f64));
+	let mut ledger = previous.clone();
+	ledger.unbond(ksm(30f64), 10);
+	assert_eq!(ledger.slashed_since(&previous), (0, 0));
+
+	// the relaychain slashes the active balance first, then the unlocking chunks
+	let unbonded = ledger.clone();
+	ledger.active = 0;
+	ledger.unlocking[0].value = ksm(25f64);
+	assert_eq!(ledger.slashed_since(&unbonded), (ksm(70f64), ksm(5f64)));
+	assert_eq!(unbonded.slashed_since(&ledger), (0, 0));
+}
+
+/// Store a ledger of `slashed` more than the mock one at derivative 0 and set the exchange rate
+/// backed by it, before proving the mock ledger.
+fn slash_mock_ledger(slashed: BalanceOf<Test>) -> BalanceOf<Test> {
+	let derivative_index = 0u16;
+	StakingLedgers::<Test>::insert(
+		derivative_index,
+		<StakingLedger<AccountId, BalanceOf<Test>>>::new(
+			LiquidStaking::derivative_sovereign_account_id(derivative_index),
+			MOCK_LEDGER_AMOUNT + slashed,
+		),
+	);
+	let issuance = <Test as Config>::Assets::total_issuance(SKSM);
+	ExchangeRate::<Test>::put(
+		Rate::checked_from_rational(MOCK_LEDGER_AMOUNT + slashed, issuance).unwrap(),
+	);
+	assert_ok!(LiquidStaking::set_staking_ledger(
+		RuntimeOrigin::signed(ALICE),
+		derivative_index,
+		get_mock_staking_ledger(derivative_index),
+		get_mock_proof_bytes()
+	));
+	issuance
+}
+
+#[test]
+fn slash_should_be_socialized() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidStaking::set_slash_policy(RuntimeOrigin::root(), SlashPolicy::Socialize));
+		TotalReserves::<Test>::put(ksm(20f64));
+
+		let issuance = slash_mock_ledger(ksm(10f64));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Slashed(
+			0,
+			ksm(10f64),
+			0,
+		)));
+		assert_eq!(
+			LiquidStaking::exchange_rate(),
+			Rate::checked_from_rational(MOCK_LEDGER_AMOUNT, issuance).unwrap()
+		);
+		assert_eq!(LiquidStaking::total_reserves(), ksm(20f64));
+	})
+}
+
+#[test]
+fn slash_should_be_covered_by_reserves_first() {
+	new_test_ext().execute_with(|| {
+		TotalReserves::<Test>::put(ksm(4f64));
+
+		let issuance = slash_mock_ledger(ksm(10f64));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Slashed(
+			0,
+			ksm(10f64),
+			ksm(4f64),
+		)));
+		assert_eq!(LiquidStaking::total_reserves(), 0);
+		assert_eq!(LiquidStaking::matching_pool().total_stake_amount.total, ksm(4f64));
+		// the covered part is bonded again, the rest is socialized
+		assert_eq!(
+			LiquidStaking::exchange_rate(),
+			Rate::checked_from_rational(MOCK_LEDGER_AMOUNT + ksm(4f64), issuance).unwrap()
+		);
+	})
+}
+
+#[test]
+fn slash_of_unlocking_should_be_unbonded_from_stakers() {
+	new_test_ext().execute_with(|| {
+		TotalReserves::<Test>::put(ksm(4f64));
+		let derivative_index = 0u16;
+		let mut ledger = <StakingLedger<AccountId, BalanceOf<Test>>>::new(
+			LiquidStaking::derivative_sovereign_account_id(derivative_index),
+			MOCK_LEDGER_AMOUNT,
+		);
+		ledger.total += ksm(10f64);
+		ledger.unlocking.push(UnlockChunk { value: ksm(10f64), era: 10 });
+		StakingLedgers::<Test>::insert(derivative_index, ledger);
+		let issuance = <Test as Config>::Assets::total_issuance(SKSM);
+		ExchangeRate::<Test>::put(
+			Rate::checked_from_rational(MOCK_LEDGER_AMOUNT, issuance).unwrap(),
+		);
+
+		// the whole unlocking chunk is gone
+		assert_ok!(LiquidStaking::set_staking_ledger(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			get_mock_staking_ledger(derivative_index),
+			get_mock_proof_bytes()
+		));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Slashed(
+			0,
+			ksm(10f64),
+			ksm(4f64),
+		)));
+		// the reserves pay the unstakers, the rest is unbonded from the stakers
+		assert_eq!(LiquidStaking::total_reserves(), 0);
+		assert_eq!(LiquidStaking::matching_pool().total_stake_amount.total, 0);
+		assert_eq!(LiquidStaking::matching_pool().total_unstake_amount.total, ksm(6f64));
+		assert_eq!(
+			LiquidStaking::exchange_rate(),
+			Rate::checked_from_rational(MOCK_LEDGER_AMOUNT - ksm(6f64), issuance).unwrap()
+		);
+	})
+}
+
+#[test]
+fn slash_fully_covered_keeps_exchange_rate() {
+	new_test_ext().execute_with(|| {
+		TotalReserves::<Test>::put(ksm(20f64));
+		let exchange_rate = Rate::checked_from_rational(
+			MOCK_LEDGER_AMOUNT + ksm(10f64),
+			<Test as Config>::Assets::total_issuance(SKSM),
+		)
+		.unwrap();
+
+		slash_mock_ledger(ksm(10f64));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Slashed(
+			0,
+			ksm(10f64),
+			ksm(10f64),
+		)));
+		assert_eq!(LiquidStaking::total_reserves(), ksm(10f64));
+		assert_eq!(LiquidStaking::exchange_rate(), exchange_rate);
+	})
+}
+
+#[test]
+fn slash_should_be_detected_despite_requests_of_other_derivatives() {
+	new_test_ext().execute_with(|| {
+		TotalReserves::<Test>::put(ksm(20f64));
+		// nominating does not change the ledger, bonding another derivative does not either
+		XcmRequests::<Test>::insert(0, XcmRequest::Nominate { index: 0, targets: vec![ALICE] });
+		XcmRequests::<Test>::insert(1, XcmRequest::Bond { index: 1, amount: ksm(5f64) });
+
+		slash_mock_ledger(ksm(10f64));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Slashed(
+			0,
+			ksm(10f64),
+			ksm(10f64),
+		)));
+	})
+}
+
+#[test]
+fn slash_should_not_be_detected_with_pending_ledger_change() {
+	new_test_ext().execute_with(|| {
+		TotalReserves::<Test>::put(ksm(20f64));
+		// the relaychain may have unbonded already, the ledger is not comparable
+		XcmRequests::<Test>::insert(0, XcmRequest::Unbond { index: 0, amount: ksm(10f64) });
+
+		slash_mock_ledger(ksm(10f64));
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			mock::RuntimeEvent::LiquidStaking(crate::Event::Slashed(..))
+		)));
+		assert_eq!(LiquidStaking::total_reserves(), ksm(20f64));
+	})
+}
+
+#[test]
+fn set_xcm_request_timeout_should_work() {
+	new_test_ext().execute_with(|| {
+		let timeout = XcmRequestTimeout { timeout: 5, max_retries: 1, backoff: 2 };
+		assert_noop!(
+			LiquidStaking::set_xcm_request_timeout(
+				RuntimeOrigin::signed(ALICE),
+				XcmRequestKind::Bond,
+				Some(timeout)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			LiquidStaking::set_xcm_request_timeout(
+				RuntimeOrigin::signed(BOB),
+				XcmRequestKind::Bond,
+				Some(XcmRequestTimeout { timeout: 0, ..timeout })
+			),
+			Error::<Test>::InvalidXcmRequestTimeout
+		);
+		assert_ok!(LiquidStaking::set_xcm_request_timeout(
+			RuntimeOrigin::signed(BOB),
+			XcmRequestKind::Bond,
+			Some(timeout)
+		));
+		assert_eq!(LiquidStaking::xcm_request_timeout(XcmRequestKind::Bond), Some(timeout));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::XcmRequestTimeoutUpdated(XcmRequestKind::Bond, Some(timeout)),
+		));
+
+		assert_ok!(LiquidStaking::set_xcm_request_timeout(
+			RuntimeOrigin::signed(BOB),
+			XcmRequestKind::Bond,
+			None
+		));
+		assert_eq!(LiquidStaking::xcm_request_timeout(XcmRequestKind::Bond), None);
+	})
+}
+
+fn bond_pending_request(amount: BalanceOf<Test>) -> QueryId {
+	assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(20f64)));
+	assert_ok!(LiquidStaking::bond(
+		RuntimeOrigin::signed(ALICE),
+		0,
+		amount,
+		RewardDestination::Staked
+	));
+	// keep matching from sending more requests in `on_initialize`
+	IsMatched::<Test>::put(true);
+	0
+}
+
+fn check_xcm_requests_at(block_number: u64) {
+	System::set_block_number(block_number);
+	LiquidStaking::on_initialize(block_number);
+}
+
+#[test]
+fn failed_xcm_request_without_timeout_is_stuck() {
+	new_test_ext().execute_with(|| {
+		let amount = ksm(10f64);
+		let query_id = bond_pending_request(amount);
+		let sent_at = System::block_number();
+		let matching_pool = LiquidStaking::matching_pool();
+		assert_eq!(matching_pool.total_stake_amount.reserved, amount);
+
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			query_id,
+			Response::ExecutionResult(Some((1, XcmError::Unimplemented))),
+		));
+		System::assert_has_event(mock::RuntimeEvent::LiquidStaking(crate::Event::XcmRequestStuck(
+			query_id,
+		)));
+		assert_eq!(
+			LiquidStaking::pending_xcm_requests(),
+			vec![PendingXcmRequest {
+				query_id,
+				kind: XcmRequestKind::Bond,
+				derivative_index: 0,
+				amount,
+				attempts: 1,
+				sent_at,
+				state: XcmRequestState::Stuck,
+			}]
+		);
+
+		// never retried without timeout
+		check_xcm_requests_at(sent_at + 100);
+		assert_eq!(
+			LiquidStaking::xcm_request(query_id),
+			Some(XcmRequest::Bond { index: 0, amount })
+		);
+
+		assert_noop!(
+			LiquidStaking::resolve_xcm_request(
+				RuntimeOrigin::signed(ALICE),
+				query_id,
+				XcmRequestResolution::RollBack
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			LiquidStaking::resolve_xcm_request(
+				RuntimeOrigin::signed(BOB),
+				query_id + 1,
+				XcmRequestResolution::RollBack
+			),
+			Error::<Test>::XcmRequestNotFound
+		);
+		assert_ok!(LiquidStaking::resolve_xcm_request(
+			RuntimeOrigin::signed(BOB),
+			query_id,
+			XcmRequestResolution::RollBack
+		));
+		System::assert_last_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::XcmRequestResolved(query_id, XcmRequestResolution::RollBack),
+		));
+		assert_eq!(LiquidStaking::pending_xcm_requests(), vec![]);
+		assert_eq!(LiquidStaking::xcm_request_status(query_id), None);
+		assert_eq!(LiquidStaking::staking_ledger(0), None);
+		assert_eq!(
+			LiquidStaking::matching_pool().total_stake_amount,
+			ReservableAmount { total: matching_pool.total_stake_amount.total, reserved: 0 }
+		);
+	})
+}
+
+#[test]
+fn stuck_xcm_request_can_be_resolved_as_executed() {
+	new_test_ext().execute_with(|| {
+		let amount = ksm(10f64);
+		let query_id = bond_pending_request(amount);
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			query_id,
+			Response::ExecutionResult(Some((1, XcmError::Unimplemented))),
+		));
+
+		assert_ok!(LiquidStaking::resolve_xcm_request(
+			RuntimeOrigin::signed(BOB),
+			query_id,
+			XcmRequestResolution::Executed
+		));
+		assert_eq!(LiquidStaking::pending_xcm_requests(), vec![]);
+		assert_eq!(
+			LiquidStaking::staking_ledger(0),
+			Some(<StakingLedger<AccountId, BalanceOf<Test>>>::new(
+				LiquidStaking::derivative_sovereign_account_id(0),
+				amount,
+			))
+		);
+	})
+}
+
+#[test]
+fn timed_out_xcm_request_is_stuck() {
+	new_test_ext().execute_with(|| {
+		let amount = ksm(10f64);
+		assert_ok!(LiquidStaking::set_xcm_request_timeout(
+			RuntimeOrigin::signed(BOB),
+			XcmRequestKind::Bond,
+			Some(XcmRequestTimeout { timeout: 5, max_retries: 1, backoff: 2 })
+		));
+		let query_id = bond_pending_request(amount);
+		let sent_at = System::block_number();
+
+		check_xcm_requests_at(sent_at + 4);
+		assert_eq!(
+			LiquidStaking::xcm_request_status(query_id).map(|status| status.state),
+			Some(XcmRequestState::Flying)
+		);
+
+		// the relaychain may have bonded it, so it is neither sent again nor rolled back
+		check_xcm_requests_at(sent_at + 5);
+		System::assert_has_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::XcmRequestTimedOut(query_id),
+		));
+		System::assert_has_event(mock::RuntimeEvent::LiquidStaking(crate::Event::XcmRequestStuck(
+			query_id,
+		)));
+		assert_eq!(
+			LiquidStaking::xcm_request_status(query_id),
+			Some(XcmRequestStatus { attempts: 1, sent_at, state: XcmRequestState::Stuck })
+		);
+		assert_eq!(XcmRequestDeadlines::<Test>::iter().count(), 0);
+		assert_eq!(LiquidStaking::matching_pool().total_stake_amount.reserved, amount);
+
+		check_xcm_requests_at(sent_at + 100);
+		assert_eq!(
+			LiquidStaking::xcm_request(query_id),
+			Some(XcmRequest::Bond { index: 0, amount })
+		);
+
+		// a late response is still accounted for
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			query_id,
+			Response::ExecutionResult(None),
+		));
+		assert_eq!(LiquidStaking::pending_xcm_requests(), vec![]);
+		assert_eq!(
+			LiquidStaking::staking_ledger(0),
+			Some(<StakingLedger<AccountId, BalanceOf<Test>>>::new(
+				LiquidStaking::derivative_sovereign_account_id(0),
+				amount,
+			))
+		);
+	})
+}
+
+#[test]
+fn failed_xcm_request_is_retried_then_rolled_back() {
+	new_test_ext().execute_with(|| {
+		let amount = ksm(10f64);
+		assert_ok!(LiquidStaking::set_xcm_request_timeout(
+			RuntimeOrigin::signed(BOB),
+			XcmRequestKind::Bond,
+			Some(XcmRequestTimeout { timeout: 5, max_retries: 1, backoff: 2 })
+		));
+		let query_id = bond_pending_request(amount);
+		let sent_at = System::block_number();
+
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			query_id,
+			Response::ExecutionResult(Some((1, XcmError::Unimplemented))),
+		));
+		System::assert_has_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::XcmRequestRetryScheduled(query_id, sent_at + 2),
+		));
+		assert_eq!(
+			LiquidStaking::xcm_request_status(query_id).map(|status| status.state),
+			Some(XcmRequestState::RetryAt(sent_at + 2))
+		);
+
+		check_xcm_requests_at(sent_at + 1);
+		assert!(LiquidStaking::xcm_request(query_id).is_some());
+
+		check_xcm_requests_at(sent_at + 2);
+		let retry_query_id = query_id + 1;
+		System::assert_has_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::XcmRequestRetried(query_id, retry_query_id),
+		));
+		assert_eq!(LiquidStaking::xcm_request(query_id), None);
+		assert_eq!(LiquidStaking::xcm_request_status(query_id), None);
+		assert_eq!(
+			LiquidStaking::xcm_request(retry_query_id),
+			Some(XcmRequest::Bond { index: 0, amount })
+		);
+		assert_eq!(
+			LiquidStaking::xcm_request_status(retry_query_id),
+			Some(XcmRequestStatus {
+				attempts: 2,
+				sent_at: sent_at + 2,
+				state: XcmRequestState::Flying
+			})
+		);
+		// the original timeout is skipped, the retry times out on its own
+		check_xcm_requests_at(sent_at + 5);
+		assert_eq!(
+			LiquidStaking::xcm_request_status(retry_query_id).map(|status| status.state),
+			Some(XcmRequestState::Flying)
+		);
+		// the locked stake is kept for the retry
+		assert_eq!(LiquidStaking::matching_pool().total_stake_amount.reserved, amount);
+
+		// out of retries
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			retry_query_id,
+			Response::ExecutionResult(Some((1, XcmError::Unimplemented))),
+		));
+		System::assert_has_event(mock::RuntimeEvent::LiquidStaking(
+			crate::Event::XcmRequestRolledBack(retry_query_id),
+		));
+		assert_eq!(LiquidStaking::pending_xcm_requests(), vec![]);
+		assert_eq!(LiquidStaking::matching_pool().total_stake_amount.reserved, 0);
+	})
+}
+
+#[test]
+fn migration_tracks_pending_xcm_requests() {
+	new_test_ext().execute_with(|| {
+		let amount = ksm(10f64);
+		let query_id = bond_pending_request(amount);
+		XcmRequestStatuses::<Test>::remove(query_id);
+		StorageVersion::<Test>::put(Versions::V2);
+
+		migrations::LiquidStakingV2ToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::<Test>::get(), Versions::V3);
+		assert_eq!(
+			LiquidStaking::xcm_request_status(query_id),
+			Some(XcmRequestStatus {
+				attempts: 1,
+				sent_at: System::block_number(),
+				state: XcmRequestState::Flying
+			})
+		);
+		assert_eq!(
+			LiquidStaking::xcm_request(query_id),
+			Some(XcmRequest::Bond { index: 0, amount })
+		);
+	})
+}
+
+#[test]
+fn test_charge_commission_work() {
+	new_test_ext().execute_with(|| {
+		let derivative_index = 0u16;
+		let bond_amount = ksm(200f64);
+		let staking_ledger = <StakingLedger<AccountId, BalanceOf<Test>>>::new(
+			LiquidStaking::derivative_sovereign_account_id(derivative_index),
+			bond_amount,
+		);
+		StakingLedgers::<Test>::insert(derivative_index, staking_ledger.clone());
+		assert_ok!(LiquidStaking::update_commission_rate(
+			RuntimeOrigin::root(),
+			Rate::from_rational(1, 100)
+		));
+		LiquidStaking::on_finalize(1);
+
+		// liquid_amount_to_fee=TotalLiquidCurrency *
+		// (commission_rate*total_rewards/(TotalStakeCurrency+(1-commission_rate)*total_rewards))
+		let commission_rate = CommissionRate::<Test>::get();
+		let total_rewards = MOCK_LEDGER_AMOUNT - bond_amount;
+		let commission_staking_amount = commission_rate.saturating_mul_int(total_rewards);
+		let issurance = <Test as Config>::Assets::total_issuance(SKSM);
+		let matching_ledger = LiquidStaking::matching_pool();
+		let total_active_bonded: u128 = StakingLedgers::<Test>::iter_values()
+			.fold(Zero::zero(), |acc, ledger| acc.saturating_add(ledger.active));
+		let total_bonded = total_active_bonded + matching_ledger.total_stake_amount.total -
+			matching_ledger.total_unstake_amount.total;
+		let inflate_rate = Rate::checked_from_rational(
+			commission_staking_amount,
+			total_bonded + total_rewards - commission_staking_amount,
+		)
+		.unwrap();
+
+		let inflate_liquid_amount = inflate_rate.saturating_mul_int(issurance);
+
+		assert_ok!(LiquidStaking::set_staking_ledger(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			get_mock_staking_ledger(derivative_index),
+			get_mock_proof_bytes()
+		));
+
+		assert_eq!(
+			LiquidStaking::staking_ledger(derivative_index).unwrap().total,
+			MOCK_LEDGER_AMOUNT
+		);
+
+		assert_eq!(
+			<Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get()),
+			inflate_liquid_amount
+		)
+	})
+}
+
+#[test]
+fn test_complete_fast_match_unstake_work() {
+	new_test_ext().execute_with(|| {
+		let reserve_factor = LiquidStaking::reserve_factor();
+		let xcm_fees = XcmFees::get();
+		let bond_amount = ksm(10f64);
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(BOB), bond_amount));
+		let total_stake_amount = bond_amount - xcm_fees - reserve_factor.mul_floor(bond_amount);
+
+		let fast_unstake_amount = ksm(3f64);
+		assert_ok!(LiquidStaking::unstake(
+			RuntimeOrigin::signed(BOB),
+			fast_unstake_amount,
+			UnstakeProvider::MatchingPool
+		));
+		assert_ok!(LiquidStaking::fast_match_unstake(RuntimeOrigin::signed(BOB), [BOB].to_vec(),));
+
+		assert_eq!(
+			<Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get()),
+			MatchingPoolFastUnstakeFee::get().saturating_mul_int(fast_unstake_amount)
+		);
+
+		assert_eq!(
+			<Test as Config>::Assets::balance(SKSM, &BOB),
+			total_stake_amount - fast_unstake_amount
+		);
+		let pool_stake_amount = total_stake_amount -
+			Rate::one()
+				.saturating_sub(MatchingPoolFastUnstakeFee::get())
+				.saturating_mul_int(fast_unstake_amount);
+		assert_eq!(
+			LiquidStaking::matching_pool(),
+			MatchingLedger {
+				total_stake_amount: ReservableAmount { total: pool_stake_amount, reserved: 0 },
+				total_unstake_amount: Default::default(),
+			}
+		);
+	})
+}
+
+#[test]
+fn test_partial_fast_match_unstake_work() {
+	new_test_ext().execute_with(|| {
+		let reserve_factor = LiquidStaking::reserve_factor();
+		let xcm_fees = XcmFees::get();
+		let bond_amount = ksm(5f64);
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), bond_amount));
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(BOB), bond_amount));
+
+		let alice_stake_amount = bond_amount - xcm_fees - reserve_factor.mul_floor(bond_amount);
+		let bob_stake_amount = alice_stake_amount;
+
+		// default exchange_rate is 1
+		let alice_fast_unstake_amount = ksm(10f64);
+		let bob_fast_unstake_amount = ksm(1f64);
+		assert_ok!(LiquidStaking::unstake(
+			RuntimeOrigin::signed(ALICE),
+			alice_fast_unstake_amount,
+			UnstakeProvider::MatchingPool
+		));
+		assert_ok!(LiquidStaking::unstake(
+			RuntimeOrigin::signed(BOB),
+			bob_fast_unstake_amount,
+			UnstakeProvider::MatchingPool
+		));
+		assert_ok!(LiquidStaking::fast_match_unstake(
+			RuntimeOrigin::signed(BOB),
+			[BOB, ALICE].to_vec(),
+		));
+
+		assert_eq!(
+			<Test as Config>::Assets::balance(SKSM, &BOB),
+			bob_stake_amount - bob_fast_unstake_amount
+		);
+
+		let bob_matched_amount = Rate::one()
+			.saturating_sub(MatchingPoolFastUnstakeFee::get())
+			.saturating_mul_int(bob_fast_unstake_amount);
+
+		let available_amount = (alice_stake_amount + bob_stake_amount - bob_matched_amount)
+			.min(alice_fast_unstake_amount);
+		let alice_matched_amount = Rate::one()
+			.saturating_sub(MatchingPoolFastUnstakeFee::get())
+			.saturating_mul_int(available_amount);
+
+		// mint in mock
+		let alice_initial_amount = ksm(100f64);
+		assert_eq!(
+			<Test as Config>::Assets::balance(SKSM, &ALICE),
+			alice_initial_amount + alice_stake_amount - available_amount
+		);
+
+		assert_eq!(
+			LiquidStaking::matching_pool(),
+			MatchingLedger {
+				total_stake_amount: ReservableAmount {
+					total: alice_stake_amount + bob_stake_amount -
+						bob_matched_amount - alice_matched_amount,
+					reserved: 0
+				},
+				total_unstake_amount: Default::default(),
+			}
+		);
+		assert_eq!(
+			LiquidStaking::fast_unstake_requests(&ALICE),
+			alice_fast_unstake_amount - available_amount
 		);
 
 		assert_ok!(with_transaction(|| -> TransactionOutcome<DispatchResult> {
@@ -1116,3 +1928,822 @@ fn test_partial_fast_match_unstake_work() {
 		}));
 	})
 }
+
+const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+
+fn candidate(commission: u32, blocked: bool, era_points: u32) -> ValidatorCandidate {
+	ValidatorCandidate {
+		prefs: Some(ValidatorPrefs { commission: Perbill::from_percent(commission), blocked }),
+		era_points,
+	}
+}
+
+/// Validate with ALICE (5% commission), BOB (1% commission, blocked) and CHARLIE (3% commission)
+/// on the relaychain.
+fn relay_validate() {
+	Relay::execute_with(|| {
+		for (validator, commission, blocked) in
+			[(ALICE, 5, false), (BOB, 1, true), (CHARLIE, 3, false)]
+		{
+			pallet_staking::Validators::<KusamaRuntime>::insert(
+				validator,
+				pallet_staking::ValidatorPrefs {
+					commission: Perbill::from_percent(commission),
+					blocked,
+				},
+			);
+		}
+	});
+}
+
+/// Root and proof of the values of `keys` in the relaychain storage.
+fn relay_storage_proof(keys: &[Vec<u8>]) -> (sp_core::H256, Vec<Vec<u8>>) {
+	let entries = Relay::execute_with(|| {
+		keys.iter()
+			.filter_map(|key| {
+				frame_support::storage::unhashed::get_raw(key).map(|value| (key.clone(), value))
+			})
+			.collect::<Vec<_>>()
+	});
+	let mut ext = sp_io::TestExternalities::new_empty();
+	entries.into_iter().for_each(|(key, value)| ext.insert(key, value));
+	ext.commit_all().unwrap();
+	let root = *ext.backend.root();
+	let (_, proof) = ext.execute_and_prove(|| {
+		keys.iter().for_each(|key| {
+			sp_io::storage::get(key);
+		})
+	});
+	(root, proof.into_iter_nodes().collect())
+}
+
+fn set_relay_storage_root(root: sp_core::H256) {
+	ValidationData::<Test>::put(PersistedValidationData {
+		relay_parent_number: 100,
+		relay_parent_storage_root: root,
+		..Default::default()
+	});
+}
+
+#[test]
+fn validator_selection_strategies_work() {
+	let candidates = || {
+		vec![
+			(1u32, candidate(10, false, 20)),
+			(2, candidate(5, false, 10)),
+			(3, candidate(5, false, 30)),
+			(4, candidate(1, true, 40)),
+			(5, ValidatorCandidate { prefs: None, era_points: 50 }),
+			(6, candidate(20, false, 60)),
+		]
+	};
+
+	// validators with unknown preferences or blocking nominations are never ranked
+	assert_eq!(<() as ValidatorSelection<u32>>::rank(candidates(), 0), Vec::<u32>::new());
+	assert_eq!(LowestCommission::rank(candidates(), 0), vec![3, 2, 1, 6]);
+	assert_eq!(HighestEraPoints::rank(candidates(), 0), vec![6, 3, 1, 2]);
+	assert_eq!(RoundRobin::rank(candidates(), 0), vec![1, 2, 3, 6]);
+	assert_eq!(RoundRobin::rank(candidates(), 1), vec![2, 3, 6, 1]);
+	assert_eq!(RoundRobin::rank(candidates(), 6), vec![3, 6, 1, 2]);
+	assert_eq!(RoundRobin::rank(vec![], 6), Vec::<u32>::new());
+
+	let ranked = vec![1, 2, 3, 4, 5];
+	assert_eq!(select_targets(&ranked, 0, 2), vec![1, 2]);
+	assert_eq!(select_targets(&ranked, 1, 2), vec![3, 4]);
+	assert_eq!(select_targets(&ranked, 2, 2), vec![5, 1]);
+	assert_eq!(select_targets(&ranked, 3, 8), vec![5, 1, 2, 3, 4]);
+	assert_eq!(select_targets(&ranked, usize::MAX, usize::MAX), vec![1, 2, 3, 4, 5]);
+	assert_eq!(select_targets::<u32>(&[], 1, 2), Vec::<u32>::new());
+}
+
+#[test]
+fn update_validator_candidates_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LiquidStaking::add_validator_candidate(RuntimeOrigin::signed(ALICE), ALICE),
+			BadOrigin
+		);
+		assert_ok!(LiquidStaking::add_validator_candidate(RuntimeOrigin::signed(BOB), ALICE));
+		assert_eq!(LiquidStaking::validator_candidate(ALICE), Some(Default::default()));
+		assert_noop!(
+			LiquidStaking::add_validator_candidate(RuntimeOrigin::root(), ALICE),
+			Error::<Test>::ValidatorCandidateAlreadyExists
+		);
+
+		for i in 1..MaxValidatorCandidates::get() {
+			assert_ok!(LiquidStaking::add_validator_candidate(
+				RuntimeOrigin::root(),
+				AccountId::new([10 + i as u8; 32])
+			));
+		}
+		assert_noop!(
+			LiquidStaking::add_validator_candidate(RuntimeOrigin::root(), BOB),
+			Error::<Test>::TooManyValidatorCandidates
+		);
+
+		assert_ok!(LiquidStaking::remove_validator_candidate(RuntimeOrigin::root(), ALICE));
+		assert_eq!(LiquidStaking::validator_candidate(ALICE), None);
+		assert_noop!(
+			LiquidStaking::remove_validator_candidate(RuntimeOrigin::root(), ALICE),
+			Error::<Test>::ValidatorCandidateNotFound
+		);
+		assert_ok!(LiquidStaking::add_validator_candidate(RuntimeOrigin::root(), BOB));
+	})
+}
+
+#[test]
+fn test_set_validator_prefs_work() {
+	TestNet::reset();
+	relay_validate();
+	Relay::execute_with(|| {
+		assert_eq!(
+			LiquidStaking::get_validator_prefs_key(&ALICE),
+			pallet_staking::Validators::<KusamaRuntime>::hashed_key_for(ALICE)
+		);
+	});
+	let dave = AccountId::new([4u8; 32]);
+	let (root, proof) = relay_storage_proof(&[
+		LiquidStaking::get_validator_prefs_key(&ALICE),
+		LiquidStaking::get_validator_prefs_key(&BOB),
+		LiquidStaking::get_validator_prefs_key(&dave),
+	]);
+
+	ParaA::execute_with(|| {
+		set_relay_storage_root(root);
+		let alice_prefs = ValidatorPrefs { commission: Perbill::from_percent(5), blocked: false };
+		assert_noop!(
+			LiquidStaking::set_validator_prefs(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				Some(alice_prefs.clone()),
+				proof.clone()
+			),
+			Error::<Test>::ValidatorCandidateNotFound
+		);
+		for validator in [ALICE, BOB, dave.clone()] {
+			assert_ok!(LiquidStaking::add_validator_candidate(RuntimeOrigin::root(), validator));
+		}
+
+		assert_noop!(
+			LiquidStaking::set_validator_prefs(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				Some(ValidatorPrefs { commission: Perbill::from_percent(1), blocked: false }),
+				proof.clone()
+			),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			LiquidStaking::set_validator_prefs(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				None,
+				proof.clone()
+			),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			LiquidStaking::set_validator_prefs(
+				RuntimeOrigin::signed(BOB),
+				CHARLIE,
+				Some(alice_prefs.clone()),
+				proof.clone()
+			),
+			Error::<Test>::InvalidProof
+		);
+
+		assert_ok!(LiquidStaking::set_validator_prefs(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			Some(alice_prefs),
+			proof.clone()
+		));
+		assert_eq!(LiquidStaking::validator_candidate(ALICE), Some(candidate(5, false, 0)));
+		assert_ok!(LiquidStaking::set_validator_prefs(
+			RuntimeOrigin::signed(BOB),
+			BOB,
+			Some(ValidatorPrefs { commission: Perbill::from_percent(1), blocked: true }),
+			proof.clone()
+		));
+		assert_eq!(LiquidStaking::validator_candidate(BOB), Some(candidate(1, true, 0)));
+		// not validating on the relaychain
+		assert_ok!(LiquidStaking::set_validator_prefs(
+			RuntimeOrigin::signed(BOB),
+			dave.clone(),
+			None,
+			proof
+		));
+		assert_eq!(LiquidStaking::validator_candidate(dave), Some(candidate(0, true, 0)));
+	});
+}
+
+#[test]
+fn test_set_validator_era_points_work() {
+	TestNet::reset();
+	let era = 5;
+	Relay::execute_with(|| {
+		pallet_staking::ErasRewardPoints::<KusamaRuntime>::insert(
+			era,
+			pallet_staking::EraRewardPoints {
+				total: 60,
+				individual: [(ALICE, 40), (CHARLIE, 20)].into_iter().collect(),
+			},
+		);
+		assert_eq!(
+			LiquidStaking::get_era_reward_points_key(era),
+			pallet_staking::ErasRewardPoints::<KusamaRuntime>::hashed_key_for(era)
+		);
+	});
+	let (root, proof) = relay_storage_proof(&[LiquidStaking::get_era_reward_points_key(era)]);
+
+	ParaA::execute_with(|| {
+		set_relay_storage_root(root);
+		for validator in [ALICE, BOB] {
+			assert_ok!(LiquidStaking::add_validator_candidate(RuntimeOrigin::root(), validator));
+		}
+		let points = EraRewardPoints {
+			total: 60,
+			individual: [(ALICE, 40), (CHARLIE, 20)].into_iter().collect(),
+		};
+
+		// the points of the active era are still accumulating
+		assert_ok!(LiquidStaking::force_set_current_era(RuntimeOrigin::root(), era));
+		assert_noop!(
+			LiquidStaking::set_validator_era_points(
+				RuntimeOrigin::signed(ALICE),
+				era,
+				points.clone(),
+				proof.clone()
+			),
+			Error::<Test>::EraPointsNotFinal
+		);
+
+		assert_ok!(LiquidStaking::force_set_current_era(RuntimeOrigin::root(), era + 1));
+		assert_noop!(
+			LiquidStaking::set_validator_era_points(
+				RuntimeOrigin::signed(ALICE),
+				era,
+				EraRewardPoints { total: 70, ..points.clone() },
+				proof.clone()
+			),
+			Error::<Test>::InvalidProof
+		);
+		assert_ok!(LiquidStaking::set_validator_era_points(
+			RuntimeOrigin::signed(ALICE),
+			era,
+			points.clone(),
+			proof.clone()
+		));
+		assert_eq!(LiquidStaking::reward_points_era(), Some(era));
+		assert_eq!(
+			LiquidStaking::validator_candidate(ALICE),
+			Some(ValidatorCandidate { prefs: None, era_points: 40 })
+		);
+		assert_eq!(LiquidStaking::validator_candidate(BOB), Some(Default::default()));
+		assert_eq!(LiquidStaking::validator_candidate(CHARLIE), None);
+
+		assert_noop!(
+			LiquidStaking::set_validator_era_points(
+				RuntimeOrigin::signed(ALICE),
+				era,
+				points,
+				proof
+			),
+			Error::<Test>::StaleEraPoints
+		);
+	});
+}
+
+#[test]
+fn test_rotate_nominations_work() {
+	TestNet::reset();
+	let derivative_index = 0u16;
+	relay_validate();
+	let (root, proof) = relay_storage_proof(&[
+		LiquidStaking::get_validator_prefs_key(&ALICE),
+		LiquidStaking::get_validator_prefs_key(&BOB),
+		LiquidStaking::get_validator_prefs_key(&CHARLIE),
+	]);
+
+	ParaA::execute_with(|| {
+		assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(ALICE), ksm(4000f64),));
+		assert_ok!(LiquidStaking::bond(
+			RuntimeOrigin::signed(ALICE),
+			derivative_index,
+			ksm(10f64),
+			RewardDestination::Staked
+		));
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			0,
+			Response::ExecutionResult(None),
+		));
+
+		// no candidates, nominations are left untouched
+		assert_ok!(LiquidStaking::force_advance_era(RuntimeOrigin::root(), 1));
+		assert_eq!(XcmRequests::<Test>::iter().count(), 0);
+
+		set_relay_storage_root(root);
+		for (validator, commission, blocked) in
+			[(ALICE, 5, false), (BOB, 1, true), (CHARLIE, 3, false)]
+		{
+			assert_ok!(LiquidStaking::add_validator_candidate(
+				RuntimeOrigin::root(),
+				validator.clone()
+			));
+			assert_ok!(LiquidStaking::set_validator_prefs(
+				RuntimeOrigin::signed(ALICE),
+				validator,
+				Some(ValidatorPrefs { commission: Perbill::from_percent(commission), blocked }),
+				proof.clone()
+			));
+		}
+
+		assert_ok!(LiquidStaking::force_advance_era(RuntimeOrigin::root(), 1));
+		ParaSystem::assert_has_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Nominating(
+			derivative_index,
+			vec![CHARLIE, ALICE],
+		)));
+		assert_eq!(LiquidStaking::nominations(derivative_index), None);
+
+		// still pending
+		assert_ok!(LiquidStaking::force_advance_era(RuntimeOrigin::root(), 1));
+		assert_eq!(XcmRequests::<Test>::iter().count(), 1);
+	});
+
+	Relay::execute_with(|| {
+		let nominators = RelayStaking::nominators(LiquidStaking::derivative_sovereign_account_id(
+			derivative_index,
+		))
+		.unwrap();
+		assert_eq!(nominators.targets, vec![CHARLIE, ALICE]);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(LiquidStaking::notification_received(
+			pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+			1,
+			Response::ExecutionResult(None),
+		));
+		assert_eq!(LiquidStaking::nominations(derivative_index), Some(vec![CHARLIE, ALICE]));
+
+		// same targets, nothing to nominate
+		assert_ok!(LiquidStaking::force_advance_era(RuntimeOrigin::root(), 1));
+		assert_eq!(XcmRequests::<Test>::iter().count(), 0);
+
+		assert_ok!(LiquidStaking::remove_validator_candidate(RuntimeOrigin::root(), CHARLIE));
+		assert_ok!(LiquidStaking::force_advance_era(RuntimeOrigin::root(), 1));
+		ParaSystem::assert_has_event(mock::RuntimeEvent::LiquidStaking(crate::Event::Nominating(
+			derivative_index,
+			vec![ALICE],
+		)));
+	});
+
+	Relay::execute_with(|| {
+		let nominators = RelayStaking::nominators(LiquidStaking::derivative_sovereign_account_id(
+			derivative_index,
+		))
+		.unwrap();
+		assert_eq!(nominators.targets, vec![ALICE]);
+	});
+}
+
+#[test]
+fn set_distribution_strategy_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LiquidStaking::distribution_strategy(), DistributionStrategyKind::MaxMin);
+		assert_noop!(
+			LiquidStaking::set_distribution_strategy(
+				RuntimeOrigin::signed(ALICE),
+				DistributionStrategyKind::MinimizeCalls
+			),
+			BadOrigin
+		);
+		assert_ok!(LiquidStaking::set_distribution_strategy(
+			RuntimeOrigin::root(),
+			DistributionStrategyKind::MinimizeCalls
+		));
+		assert_eq!(LiquidStaking::distribution_strategy(), DistributionStrategyKind::MinimizeCalls);
+
+		let bonded_amounts = vec![(0, 40, 40), (1, 10, 10), (2, 25, 25)];
+		assert_eq!(
+			SelectableDistribution::<Test>::get_bond_distributions(
+				bonded_amounts.clone(),
+				30,
+				80,
+				10
+			),
+			vec![(0, 30)]
+		);
+		assert_ok!(LiquidStaking::set_distribution_strategy(
+			RuntimeOrigin::signed(BOB),
+			DistributionStrategyKind::LargestCapacityFirst
+		));
+		assert_eq!(
+			SelectableDistribution::<Test>::get_bond_distributions(bonded_amounts, 30, 80, 10),
+			vec![(1, 30)]
+		);
+	})
+}
+
+#[test]
+fn derivative_performance_should_work() {
+	new_test_ext().execute_with(|| {
+		ValidatorCandidates::<Test>::insert(ALICE, candidate(5, false, 10));
+		ValidatorCandidates::<Test>::insert(CHARLIE, candidate(3, false, 30));
+		Nominations::<Test>::insert(0, vec![ALICE, CHARLIE]);
+		// validators which are no longer candidates earn no points
+		Nominations::<Test>::insert(1, vec![ALICE, BOB]);
+
+		assert_eq!(LiquidStaking::performance(0), 20);
+		assert_eq!(LiquidStaking::performance(1), 5);
+		assert_eq!(LiquidStaking::performance(2), 0);
+	})
+}
+
+#[test]
+fn capacity_aware_distributions_work() {
+	// capacities are 30, 70 and 80
+	let bonded_amounts = vec![(0, 40, 50), (1, 10, 10), (2, 0, 0)];
+	assert_eq!(
+		LargestCapacityFirst::get_bond_distributions(bonded_amounts.clone(), 100, 80, 75),
+		vec![(2, 80)]
+	);
+	// derivative 2 would not reach the minimum with 70
+	assert_eq!(
+		LargestCapacityFirst::get_bond_distributions(bonded_amounts.clone(), 70, 80, 75),
+		vec![(1, 70)]
+	);
+	assert_eq!(
+		MinimizeCalls::get_bond_distributions(bonded_amounts.clone(), 60, 80, 5),
+		vec![(1, 60)]
+	);
+	assert_eq!(
+		MinimizeCalls::get_bond_distributions(bonded_amounts, 100, 80, 5),
+		vec![(2, 80), (1, 20)]
+	);
+	assert_eq!(
+		MinimizeCalls::get_unbond_distributions(vec![(0, 40), (1, 20), (2, 30)], 10, 5),
+		vec![(1, 10)]
+	);
+	assert_eq!(
+		MinimizeCalls::get_rebond_distributions(vec![(0, 40), (1, 20), (2, 30)], 50),
+		vec![(0, 40), (2, 10)]
+	);
+}
+
+/// Performance of derivative `index` is `index`.
+struct IndexPerformance;
+impl DerivativePerformance for IndexPerformance {
+	fn performance(index: DerivativeIndex) -> u32 {
+		index.into()
+	}
+}
+
+#[test]
+fn performance_weighted_distributions_work() {
+	type Strategy = PerformanceWeighted<IndexPerformance>;
+	let bonded_amounts = vec![(0, 10, 10), (1, 10, 10), (3, 10, 10)];
+	// derivative 0 has no performance, 1 and 3 split the amount
+	assert_eq!(
+		Strategy::get_bond_distributions(bonded_amounts.clone(), 100, 100, 5),
+		vec![(1, 25), (3, 75)]
+	);
+	// the excess over the capacity of derivative 3 goes to derivative 1
+	assert_eq!(
+		Strategy::get_bond_distributions(bonded_amounts.clone(), 160, 100, 5),
+		vec![(1, 70), (3, 90)]
+	);
+	// derivatives 0 and 1 would not reach the minimum and are left out
+	assert_eq!(Strategy::get_bond_distributions(bonded_amounts, 100, 100, 50), vec![(3, 90)]);
+	assert_eq!(
+		Strategy::get_unbond_distributions(vec![(3, 40), (1, 20), (0, 30)], 35, 5),
+		vec![(0, 25), (1, 10)]
+	);
+	assert_eq!(
+		Strategy::get_rebond_distributions(vec![(1, 20), (0, 30), (3, 10)], 25),
+		vec![(3, 10), (1, 15)]
+	);
+}
+
+const MAX_DERIVATIVES: usize = 8;
+const MAX_AMOUNT: u128 = 1_000_000;
+
+prop_compose! {
+	fn bonded_amounts()
+		(amounts in prop::collection::vec((0..MAX_AMOUNT, 0..MAX_AMOUNT), 0..MAX_DERIVATIVES))
+		-> Vec<(DerivativeIndex, u128, u128)> {
+			amounts
+				.into_iter()
+				.enumerate()
+				.map(|(index, (x, y))| (index as DerivativeIndex, x.min(y), x.max(y)))
+				.collect()
+		}
+}
+
+prop_compose! {
+	fn amounts()
+		(amounts in prop::collection::vec(0..MAX_AMOUNT, 0..MAX_DERIVATIVES))
+		-> Vec<(DerivativeIndex, u128)> {
+			amounts
+				.into_iter()
+				.enumerate()
+				.map(|(index, amount)| (index as DerivativeIndex, amount))
+				.collect()
+		}
+}
+
+fn check_distributions(
+	distributions: &[(DerivativeIndex, u128)],
+	input: u128,
+) -> Result<u128, TestCaseError> {
+	let mut indexes = distributions.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+	indexes.sort();
+	indexes.dedup();
+	prop_assert_eq!(indexes.len(), distributions.len(), "derivative used twice");
+	prop_assert!(distributions.iter().all(|(_, amount)| !amount.is_zero()));
+	let total = distributions.iter().map(|(_, amount)| amount).sum::<u128>();
+	prop_assert!(total <= input);
+	Ok(total)
+}
+
+fn check_bond<S: DistributionStrategy<u128>>(
+	bonded_amounts: &[(DerivativeIndex, u128, u128)],
+	input: u128,
+	cap: u128,
+	min_nominator_bond: u128,
+) -> Result<Vec<(DerivativeIndex, u128)>, TestCaseError> {
+	let distributions =
+		S::get_bond_distributions(bonded_amounts.to_vec(), input, cap, min_nominator_bond);
+	check_distributions(&distributions, input)?;
+	for (index, amount) in distributions.iter() {
+		let (_, active_bonded, total_bonded) =
+			bonded_amounts.iter().find(|(i, _, _)| i == index).copied().unwrap();
+		prop_assert!(amount + total_bonded <= cap);
+		prop_assert!(amount + active_bonded >= min_nominator_bond);
+	}
+	Ok(distributions)
+}
+
+fn check_unbond<S: DistributionStrategy<u128>>(
+	active_bonded_amounts: &[(DerivativeIndex, u128)],
+	input: u128,
+	min_nominator_bond: u128,
+) -> Result<Vec<(DerivativeIndex, u128)>, TestCaseError> {
+	let distributions =
+		S::get_unbond_distributions(active_bonded_amounts.to_vec(), input, min_nominator_bond);
+	check_distributions(&distributions, input)?;
+	for (index, amount) in distributions.iter() {
+		let (_, active_bonded) =
+			active_bonded_amounts.iter().find(|(i, _)| i == index).copied().unwrap();
+		prop_assert!(*amount <= active_bonded);
+		prop_assert!(active_bonded - amount >= min_nominator_bond);
+	}
+	Ok(distributions)
+}
+
+fn check_rebond<S: DistributionStrategy<u128>>(
+	unbonding_amounts: &[(DerivativeIndex, u128)],
+	input: u128,
+) -> Result<Vec<(DerivativeIndex, u128)>, TestCaseError> {
+	let distributions = S::get_rebond_distributions(unbonding_amounts.to_vec(), input);
+	check_distributions(&distributions, input)?;
+	for (index, amount) in distributions.iter() {
+		let (_, unbonding) = unbonding_amounts.iter().find(|(i, _)| i == index).copied().unwrap();
+		prop_assert!(*amount <= unbonding);
+	}
+	Ok(distributions)
+}
+
+fn total(distributions: &[(DerivativeIndex, u128)]) -> u128 {
+	distributions.iter().map(|(_, amount)| amount).sum()
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(1000))]
+
+	#[test]
+	fn bond_distributions_respect_cap_and_min_nominator_bond(
+		bonded_amounts in bonded_amounts(),
+		input in 0..MAX_AMOUNT * 2,
+		cap in 0..MAX_AMOUNT * 2,
+		min_nominator_bond in 0..MAX_AMOUNT,
+	) {
+		// `AverageDistribution` checks the min nominator bond against the even share, before it
+		// is clamped to the cap, so it only keeps to the cap.
+		let average = AverageDistribution::get_bond_distributions(
+			bonded_amounts.clone(),
+			input,
+			cap,
+			min_nominator_bond,
+		);
+		check_distributions(&average, input)?;
+		for (index, amount) in average.iter() {
+			let (_, _, total_bonded) =
+				bonded_amounts.iter().find(|(i, _, _)| i == index).copied().unwrap();
+			prop_assert!(amount + total_bonded <= cap);
+		}
+		check_bond::<MaxMinDistribution>(&bonded_amounts, input, cap, min_nominator_bond)?;
+		let largest =
+			check_bond::<LargestCapacityFirst>(&bonded_amounts, input, cap, min_nominator_bond)?;
+		let weighted = check_bond::<PerformanceWeighted<IndexPerformance>>(
+			&bonded_amounts,
+			input,
+			cap,
+			min_nominator_bond,
+		)?;
+		let minimal = check_bond::<MinimizeCalls>(&bonded_amounts, input, cap, min_nominator_bond)?;
+		prop_assert!(minimal.len() <= largest.len());
+
+		// every derivative can take any amount, so the whole input is bonded up to the capacity
+		if bonded_amounts.iter().all(|(_, active_bonded, _)| *active_bonded >= min_nominator_bond) {
+			let capacity = bonded_amounts
+				.iter()
+				.map(|(_, _, total_bonded)| cap.saturating_sub(*total_bonded))
+				.sum::<u128>();
+			let expected = input.min(capacity);
+			prop_assert_eq!(total(&largest), expected);
+			prop_assert_eq!(total(&weighted), expected);
+			prop_assert_eq!(total(&minimal), expected);
+		}
+	}
+
+	#[test]
+	fn unbond_distributions_respect_min_nominator_bond(
+		active_bonded_amounts in amounts(),
+		input in 0..MAX_AMOUNT * 2,
+		min_nominator_bond in 1..MAX_AMOUNT,
+	) {
+		check_unbond::<AverageDistribution>(&active_bonded_amounts, input, min_nominator_bond)?;
+		let max_min =
+			check_unbond::<MaxMinDistribution>(&active_bonded_amounts, input, min_nominator_bond)?;
+		let largest = check_unbond::<LargestCapacityFirst>(
+			&active_bonded_amounts,
+			input,
+			min_nominator_bond,
+		)?;
+		let weighted = check_unbond::<PerformanceWeighted<IndexPerformance>>(
+			&active_bonded_amounts,
+			input,
+			min_nominator_bond,
+		)?;
+		let minimal =
+			check_unbond::<MinimizeCalls>(&active_bonded_amounts, input, min_nominator_bond)?;
+		prop_assert!(minimal.len() <= max_min.len());
+
+		let available = active_bonded_amounts
+			.iter()
+			.map(|(_, active_bonded)| active_bonded.saturating_sub(min_nominator_bond))
+			.sum::<u128>();
+		let expected = input.min(available);
+		prop_assert_eq!(total(&max_min), expected);
+		prop_assert_eq!(total(&largest), expected);
+		prop_assert_eq!(total(&weighted), expected);
+		prop_assert_eq!(total(&minimal), expected);
+	}
+
+	#[test]
+	fn rebond_distributions_respect_unbonding(
+		unbonding_amounts in amounts(),
+		input in 0..MAX_AMOUNT * 2,
+	) {
+		check_rebond::<AverageDistribution>(&unbonding_amounts, input)?;
+		let max_min = check_rebond::<MaxMinDistribution>(&unbonding_amounts, input)?;
+		let largest = check_rebond::<LargestCapacityFirst>(&unbonding_amounts, input)?;
+		let weighted =
+			check_rebond::<PerformanceWeighted<IndexPerformance>>(&unbonding_amounts, input)?;
+		let minimal = check_rebond::<MinimizeCalls>(&unbonding_amounts, input)?;
+		prop_assert!(minimal.len() <= max_min.len());
+
+		let expected = input.min(unbonding_amounts.iter().map(|(_, unbonding)| unbonding).sum());
+		prop_assert_eq!(total(&max_min), expected);
+		prop_assert_eq!(total(&largest), expected);
+		prop_assert_eq!(total(&weighted), expected);
+		prop_assert_eq!(total(&minimal), expected);
+	}
+}
+
+/// Assert that `call` encodes to the `expected` kusama call, which the kusama runtime decodes.
+fn assert_kusama_call(call: KusamaCall<Test>, expected: Vec<u8>) {
+	let encoded = call.encode();
+	assert_eq!(encoded, expected);
+	assert_ok!(kusama_runtime::RuntimeCall::decode_all(&mut &encoded[..]));
+}
+
+/// Kusama `utility.as_derivative(1, call)`, as sent for the derivative account 1.
+fn as_derivative(call: KusamaCall<Test>) -> KusamaCall<Test> {
+	KusamaCall::Utility(Box::new(UtilityCall::AsDerivative(UtilityAsDerivativeCall {
+		index: 1,
+		call,
+	})))
+}
+
+const AS_DERIVATIVE: [u8; 4] = [24, 1, 1, 0];
+
+#[test]
+fn xcm_helper_encodes_chill() {
+	assert_kusama_call(
+		as_derivative(KusamaCall::Staking(StakingCall::Chill)),
+		[&AS_DERIVATIVE[..], &[6, 6]].concat(),
+	);
+}
+
+#[test]
+fn xcm_helper_encodes_payout_stakers() {
+	assert_kusama_call(
+		KusamaCall::Staking(StakingCall::PayoutStakers(StakingPayoutStakersCall {
+			validator_stash: CHARLIE,
+			era: 3,
+		})),
+		[&[6, 18][..], &[3; 32], &[3, 0, 0, 0]].concat(),
+	);
+}
+
+#[test]
+fn xcm_helper_encodes_set_payee() {
+	assert_kusama_call(
+		as_derivative(KusamaCall::Staking(StakingCall::SetPayee(StakingSetPayeeCall {
+			payee: RewardDestination::Account(CHARLIE),
+		}))),
+		[&AS_DERIVATIVE[..], &[6, 7, 3], &[3; 32]].concat(),
+	);
+}
+
+#[test]
+fn xcm_helper_encodes_join_pool() {
+	// the stash is funded first, then joins from the derivative account
+	assert_kusama_call(
+		KusamaCall::Balances(BalancesCall::TransferKeepAlive(BalancesTransferKeepAliveCall {
+			dest: Id(CHARLIE),
+			value: 10,
+		})),
+		[&[4, 3, 0][..], &[3; 32], &[40]].concat(),
+	);
+	assert_kusama_call(
+		as_derivative(KusamaCall::NominationPools(NominationPoolsCall::Join(
+			NominationPoolsJoinCall { amount: 10, pool_id: 2 },
+		))),
+		[&AS_DERIVATIVE[..], &[41, 0, 40, 2, 0, 0, 0]].concat(),
+	);
+}
+
+#[test]
+fn xcm_helper_encodes_unbond_from_pool() {
+	assert_kusama_call(
+		as_derivative(KusamaCall::NominationPools(NominationPoolsCall::Unbond(
+			NominationPoolsUnbondCall { member_account: Id(CHARLIE), unbonding_points: 10 },
+		))),
+		[&AS_DERIVATIVE[..], &[41, 3, 0], &[3; 32], &[40]].concat(),
+	);
+}
+
+#[test]
+fn xcm_helper_encodes_withdraw_from_pool() {
+	// the withdrawn funds are sent back to the parachain account
+	assert_kusama_call(
+		as_derivative(KusamaCall::NominationPools(NominationPoolsCall::WithdrawUnbonded(
+			NominationPoolsWithdrawUnbondedCall {
+				member_account: Id(CHARLIE),
+				num_slashing_spans: 0,
+			},
+		))),
+		[&AS_DERIVATIVE[..], &[41, 5, 0], &[3; 32], &[0, 0, 0, 0]].concat(),
+	);
+	assert_kusama_call(
+		as_derivative(KusamaCall::Balances(BalancesCall::TransferAll(BalancesTransferAllCall {
+			dest: Id(AccountId::new([4u8; 32])),
+			keep_alive: true,
+		}))),
+		[&AS_DERIVATIVE[..], &[4, 4, 0], &[4; 32], &[1]].concat(),
+	);
+}
+
+#[test]
+fn xcm_helper_encodes_add_proxy() {
+	let add_proxy = || {
+		KusamaCall::Proxy(Box::new(ProxyCall::AddProxy(ProxyAddProxyCall {
+			delegate: Id(CHARLIE),
+			proxy_type: ProxyType::Staking,
+			delay: 0,
+		})))
+	};
+	let expected = [&[22, 1, 0][..], &[3; 32], &[3, 0, 0, 0, 0]].concat();
+	// from the sovereign account
+	assert_kusama_call(add_proxy(), expected.clone());
+	assert_kusama_call(as_derivative(add_proxy()), [&AS_DERIVATIVE[..], &expected].concat());
+}
+
+#[test]
+fn xcm_helper_encodes_remove_proxy() {
+	let remove_proxy = || {
+		KusamaCall::Proxy(Box::new(ProxyCall::RemoveProxy(ProxyRemoveProxyCall {
+			delegate: Id(CHARLIE),
+			proxy_type: ProxyType::Staking,
+			delay: 0,
+		})))
+	};
+	let expected = [&[22, 2, 0][..], &[3; 32], &[3, 0, 0, 0, 0]].concat();
+	// from the sovereign account
+	assert_kusama_call(remove_proxy(), expected.clone());
+	assert_kusama_call(as_derivative(remove_proxy()), [&AS_DERIVATIVE[..], &expected].concat());
+}
@@ -1,5 +1,5 @@
 use parity_scale_codec::{Decode, Encode, HasCompact};
-use sp_runtime::{FixedU128, Permill, RuntimeDebug};
+use sp_runtime::{FixedU128, Perbill, Permill, RuntimeDebug};
 // pub use cumulus_primitives_core::{PersistedValidationData};
 
 pub type Ratio = Permill;
@@ -12,12 +12,15 @@ use frame_support::{
 	dispatch::DispatchResult,
 	traits::{tokens::Balance as BalanceT, DefensiveSaturating},
 };
+use pallet_xcm_helper::ump::RewardDestination;
 use scale_info::TypeInfo;
 use sp_runtime::{traits::Zero, ArithmeticError, DispatchError, FixedPointOperand};
-use sp_std::{cmp::Ordering, result::Result, vec, vec::Vec};
+use sp_std::{cmp::Ordering, collections::btree_map::BTreeMap, result::Result, vec, vec::Vec};
 // use polkadot_primitives::PersistedValidationData;
 pub use polkadot_primitives::PersistedValidationData;
 
+pub use composable_traits::liquid_staking::{XcmRequestKind, XcmRequestState};
+
 pub trait ValidationDataProvider {
 	fn validation_data() -> Option<PersistedValidationData>;
 }
@@ -149,7 +152,7 @@ impl<Balance: BalanceT + FixedPointOperand> MatchingLedger<Balance> {
 		Ok(())
 	}
 
-	fn remove_stake_amount_lock(&mut self, amount: Balance) -> DispatchResult {
+	pub fn remove_stake_amount_lock(&mut self, amount: Balance) -> DispatchResult {
 		self.total_stake_amount.reserved = self
 			.total_stake_amount
 			.reserved
@@ -171,7 +174,7 @@ impl<Balance: BalanceT + FixedPointOperand> MatchingLedger<Balance> {
 		Ok(())
 	}
 
-	fn remove_unstake_amount_lock(&mut self, amount: Balance) -> DispatchResult {
+	pub fn remove_unstake_amount_lock(&mut self, amount: Balance) -> DispatchResult {
 		self.total_unstake_amount.reserved = self
 			.total_unstake_amount
 			.reserved
@@ -210,6 +213,103 @@ pub enum XcmRequest<T: Config> {
 	Rebond { index: DerivativeIndex, amount: BalanceOf<T> },
 	WithdrawUnbonded { index: DerivativeIndex, num_slashing_spans: u32 },
 	Nominate { index: DerivativeIndex, targets: Vec<T::AccountId> },
+	Chill { index: DerivativeIndex },
+	SetPayee { index: DerivativeIndex, payee: RewardDestination<T::AccountId> },
+}
+
+impl<T: Config> XcmRequest<T> {
+	pub fn kind(&self) -> XcmRequestKind {
+		match self {
+			XcmRequest::Bond { .. } => XcmRequestKind::Bond,
+			XcmRequest::BondExtra { .. } => XcmRequestKind::BondExtra,
+			XcmRequest::Unbond { .. } => XcmRequestKind::Unbond,
+			XcmRequest::Rebond { .. } => XcmRequestKind::Rebond,
+			XcmRequest::WithdrawUnbonded { .. } => XcmRequestKind::WithdrawUnbonded,
+			XcmRequest::Nominate { .. } => XcmRequestKind::Nominate,
+			XcmRequest::Chill { .. } => XcmRequestKind::Chill,
+			XcmRequest::SetPayee { .. } => XcmRequestKind::SetPayee,
+		}
+	}
+
+	pub fn derivative_index(&self) -> DerivativeIndex {
+		match self {
+			XcmRequest::Bond { index, .. } |
+			XcmRequest::BondExtra { index, .. } |
+			XcmRequest::Unbond { index, .. } |
+			XcmRequest::Rebond { index, .. } |
+			XcmRequest::WithdrawUnbonded { index, .. } |
+			XcmRequest::Nominate { index, .. } |
+			XcmRequest::Chill { index } |
+			XcmRequest::SetPayee { index, .. } => *index,
+		}
+	}
+
+	/// Staking currency bonded, unbonded or rebonded, zero for the other requests.
+	pub fn amount(&self) -> BalanceOf<T> {
+		match self {
+			XcmRequest::Bond { amount, .. } |
+			XcmRequest::BondExtra { amount, .. } |
+			XcmRequest::Unbond { amount, .. } |
+			XcmRequest::Rebond { amount, .. } => *amount,
+			XcmRequest::WithdrawUnbonded { .. } |
+			XcmRequest::Nominate { .. } |
+			XcmRequest::Chill { .. } |
+			XcmRequest::SetPayee { .. } => Zero::zero(),
+		}
+	}
+
+	/// Whether the request bonds, unbonds, rebonds or withdraws, changing the staking ledger of
+	/// its derivative on the relaychain.
+	pub fn changes_ledger(&self) -> bool {
+		match self {
+			XcmRequest::Bond { .. } |
+			XcmRequest::BondExtra { .. } |
+			XcmRequest::Unbond { .. } |
+			XcmRequest::Rebond { .. } |
+			XcmRequest::WithdrawUnbonded { .. } => true,
+			XcmRequest::Nominate { .. } |
+			XcmRequest::Chill { .. } |
+			XcmRequest::SetPayee { .. } => false,
+		}
+	}
+}
+
+/// Timeout of a kind of xcm request, see `set_xcm_request_timeout`.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct XcmRequestTimeout<BlockNumber> {
+	/// Blocks without response after which a request is stuck.
+	pub timeout: BlockNumber,
+	/// Times a request which failed is sent again before being rolled back.
+	pub max_retries: u32,
+	/// Blocks waited before sending a request again, doubled at each retry.
+	pub backoff: BlockNumber,
+}
+
+/// Sending of a flying or failed xcm request.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct XcmRequestStatus<BlockNumber> {
+	/// Times the request was sent.
+	pub attempts: u32,
+	/// Block at which the request was last sent.
+	pub sent_at: BlockNumber,
+	pub state: XcmRequestState<BlockNumber>,
+}
+
+impl<BlockNumber: Zero> Default for XcmRequestStatus<BlockNumber> {
+	fn default() -> Self {
+		Self { attempts: 1, sent_at: Zero::zero(), state: XcmRequestState::Flying }
+	}
+}
+
+/// How `resolve_xcm_request` resolves a failed or stuck xcm request.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum XcmRequestResolution {
+	/// Send it again.
+	Retry,
+	/// Drop it, releasing what it locked in the matching pool.
+	RollBack,
+	/// Account for it as executed by the relaychain.
+	Executed,
 }
 
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
@@ -313,14 +413,39 @@ impl<AccountId, Balance: BalanceT + FixedPointOperand> StakingLedger<AccountId,
 		// 2. No minimum balance check
 		self.active -= value;
 	}
+
+	/// Balance slashed on the relaychain since `previous`, from `active` and from `unlocking`.
+	/// Only meaningful when nothing was bonded, unbonded, rebonded or withdrawn in between.
+	pub fn slashed_since(&self, previous: &Self) -> (Balance, Balance) {
+		let unlocking = |ledger: &Self| {
+			ledger
+				.unlocking
+				.iter()
+				.fold(Zero::zero(), |acc: Balance, chunk| acc.saturating_add(chunk.value))
+		};
+		let (unlocking, previous_unlocking) = (unlocking(self), unlocking(previous));
+		let slashed = previous
+			.active
+			.saturating_add(previous_unlocking)
+			.saturating_sub(self.active.saturating_add(unlocking));
+		let from_unlocking = previous_unlocking.saturating_sub(unlocking).min(slashed);
+		(slashed.saturating_sub(from_unlocking), from_unlocking)
+	}
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
 pub enum UnstakeProvider {
 	#[default]
-	RelayChain = 0,
-	Loans = 1,
-	MatchingPool = 2,
+	#[codec(index = 0)]
+	RelayChain,
+	#[codec(index = 1)]
+	Loans,
+	#[codec(index = 2)]
+	MatchingPool,
+	/// Sell on the dex for at least the staking value minus `max_discount`, otherwise request a
+	/// fast unstake from the matching pool.
+	#[codec(index = 3)]
+	Dex { max_discount: Ratio },
 }
 
 impl UnstakeProvider {
@@ -335,4 +460,63 @@ impl UnstakeProvider {
 	pub fn is_matching_pool(&self) -> bool {
 		self == &UnstakeProvider::MatchingPool
 	}
+
+	pub fn is_dex(&self) -> bool {
+		matches!(self, UnstakeProvider::Dex { .. })
+	}
+}
+
+/// Preferences of a validator, as stored in `Staking::Validators` on the relaychain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ValidatorPrefs {
+	/// Reward that the validator takes up-front, the rest is split between the nominators.
+	#[codec(compact)]
+	pub commission: Perbill,
+	/// Whether the validator accepts new nominations.
+	pub blocked: bool,
+}
+
+/// Reward points of an era, as stored in `Staking::ErasRewardPoints` on the relaychain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EraRewardPoints<AccountId: Ord> {
+	/// Total number of points.
+	pub total: u32,
+	/// The reward points earned by each validator.
+	pub individual: BTreeMap<AccountId, u32>,
+}
+
+/// A validator which may be nominated by the derivative accounts.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ValidatorCandidate {
+	/// Preferences proven from the relaychain, `None` until then.
+	pub prefs: Option<ValidatorPrefs>,
+	/// Reward points earned in `RewardPointsEra`.
+	pub era_points: u32,
+}
+
+impl ValidatorCandidate {
+	pub fn commission(&self) -> Perbill {
+		self.prefs.as_ref().map(|prefs| prefs.commission).unwrap_or_default()
+	}
+}
+
+/// Distribution strategies which `UpdateOrigin` can switch between, see `SelectableDistribution`.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
+pub enum DistributionStrategyKind {
+	Average,
+	#[default]
+	MaxMin,
+	LargestCapacityFirst,
+	PerformanceWeighted,
+	MinimizeCalls,
+}
+
+/// How a slash of a derivative account on the relaychain is accounted for, see `set_slash_policy`.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
+pub enum SlashPolicy {
+	/// The holders of the liquid currency bear the loss, through a lower exchange rate.
+	Socialize,
+	/// The reserves cover the loss, which is bonded again, the holders bearing what exceeds them.
+	#[default]
+	ReservesFirst,
 }
@@ -68,6 +68,22 @@ pub trait WeightInfo {
 	fn update_commission_rate() -> Weight;
 	fn fast_match_unstake(n: u32, ) -> Weight;
 	fn update_incentive() -> Weight;
+	fn add_validator_candidate() -> Weight;
+	fn remove_validator_candidate() -> Weight;
+	fn set_validator_prefs() -> Weight;
+	fn set_validator_era_points(n: u32, ) -> Weight;
+	fn rotate_nominations(n: u32, ) -> Weight;
+	fn set_distribution_strategy() -> Weight;
+	fn unstake_via_dex() -> Weight;
+	fn set_dex_pool() -> Weight;
+	fn set_slash_policy() -> Weight;
+	fn slash() -> Weight;
+	fn set_xcm_request_timeout() -> Weight;
+	fn resolve_xcm_request() -> Weight;
+	fn check_xcm_request() -> Weight;
+	fn retry_xcm_request() -> Weight;
+	fn chill() -> Weight;
+	fn set_payee() -> Weight;
 }
 
 /// Weights for pallet_liquid_staking using the Substrate node and recommended hardware.
@@ -388,315 +404,201 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2u64))
 			.saturating_add(T::DbWeight::get().writes(2u64))
 	}
+	// NOT GENERATED: conservative estimates for the calls below, which have not been benchmarked
+	// yet. To be replaced by the output of the benchmark CLI once `mod benchmarking` is enabled.
+	fn add_validator_candidate() -> Weight {
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(2u64))
+			.saturating_add(T::DbWeight::get().writes(2u64))
+	}
+	fn remove_validator_candidate() -> Weight {
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(2u64))
+			.saturating_add(T::DbWeight::get().writes(2u64))
+	}
+	fn set_validator_prefs() -> Weight {
+		Weight::from_ref_time(150_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(2u64))
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	fn set_validator_era_points(n: u32, ) -> Weight {
+		Weight::from_ref_time(160_000_000u64)
+			.saturating_add(Weight::from_ref_time(15_000_000u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2u64))
+			.saturating_add(T::DbWeight::get().reads((1u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1u64))
+			.saturating_add(T::DbWeight::get().writes((1u64).saturating_mul(n as u64)))
+	}
+	fn rotate_nominations(n: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000u64)
+			.saturating_add(Weight::from_ref_time(10_000_000u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(4u64))
+			.saturating_add(T::DbWeight::get().reads((1u64).saturating_mul(n as u64)))
+	}
+	fn set_distribution_strategy() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	fn unstake_via_dex() -> Weight {
+		Weight::from_ref_time(330_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(13u64))
+			.saturating_add(T::DbWeight::get().writes(6u64))
+	}
+	fn set_dex_pool() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	fn set_slash_policy() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	fn slash() -> Weight {
+		Weight::from_ref_time(80_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(11u64))
+			.saturating_add(T::DbWeight::get().writes(3u64))
+	}
+	fn set_xcm_request_timeout() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	fn resolve_xcm_request() -> Weight {
+		Weight::from_ref_time(150_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(7u64))
+			.saturating_add(T::DbWeight::get().writes(6u64))
+	}
+	fn check_xcm_request() -> Weight {
+		Weight::from_ref_time(15_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(2u64))
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	fn retry_xcm_request() -> Weight {
+		Weight::from_ref_time(135_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(6u64))
+			.saturating_add(T::DbWeight::get().writes(6u64))
+	}
+	fn chill() -> Weight {
+		Weight::from_ref_time(275_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(12u64))
+			.saturating_add(T::DbWeight::get().writes(9u64))
+	}
+	fn set_payee() -> Weight {
+		Weight::from_ref_time(275_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(12u64))
+			.saturating_add(T::DbWeight::get().writes(9u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking ReserveFactor (r:1 w:0)
-	// Storage: Assets Metadata (r:2 w:0)
-	// Storage: Assets Asset (r:2 w:2)
-	// Storage: Assets Account (r:4 w:4)
-	// Storage: System Account (r:2 w:2)
-	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: LiquidStaking StakingLedgerCap (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: LiquidStaking TotalReserves (r:1 w:1)
 	fn stake() -> Weight {
-		Weight::from_ref_time(288_998_000u64)
+		Weight::from_ref_time(435_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(17u64))
 			.saturating_add(RocksDbWeight::get().writes(11u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
-	// Storage: LiquidStaking Unlockings (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
 	fn unstake() -> Weight {
-		Weight::from_ref_time(124_428_000u64)
+		Weight::from_ref_time(190_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(8u64))
 			.saturating_add(RocksDbWeight::get().writes(5u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: LiquidStaking StakingLedgerCap (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:1)
-	// Storage: PolkadotXcm Queries (r:0 w:1)
 	fn bond() -> Weight {
-		Weight::from_ref_time(204_060_000u64)
+		Weight::from_ref_time(310_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(14u64))
 			.saturating_add(RocksDbWeight::get().writes(9u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:1)
-	// Storage: PolkadotXcm Queries (r:0 w:1)
 	fn nominate() -> Weight {
-		Weight::from_ref_time(187_662_000u64)
+		Weight::from_ref_time(285_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(12u64))
 			.saturating_add(RocksDbWeight::get().writes(8u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: LiquidStaking StakingLedgerCap (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:1)
-	// Storage: PolkadotXcm Queries (r:0 w:1)
 	fn bond_extra() -> Weight {
-		Weight::from_ref_time(209_641_000u64)
+		Weight::from_ref_time(315_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(14u64))
 			.saturating_add(RocksDbWeight::get().writes(9u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:1)
-	// Storage: LiquidStaking IsUpdated (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:1 w:0)
 	fn force_set_staking_ledger() -> Weight {
-		Weight::from_ref_time(75_995_000u64)
+		Weight::from_ref_time(115_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(4u64))
 			.saturating_add(RocksDbWeight::get().writes(3u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:1)
-	// Storage: PolkadotXcm Queries (r:0 w:1)
 	fn unbond() -> Weight {
-		Weight::from_ref_time(196_990_000u64)
+		Weight::from_ref_time(300_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(13u64))
 			.saturating_add(RocksDbWeight::get().writes(9u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:1)
-	// Storage: PolkadotXcm Queries (r:0 w:1)
 	fn rebond() -> Weight {
-		Weight::from_ref_time(195_931_000u64)
+		Weight::from_ref_time(295_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(13u64))
 			.saturating_add(RocksDbWeight::get().writes(9u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:1 w:0)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:1)
-	// Storage: PolkadotXcm Queries (r:0 w:1)
 	fn withdraw_unbonded() -> Weight {
-		Weight::from_ref_time(199_966_000u64)
+		Weight::from_ref_time(300_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(13u64))
 			.saturating_add(RocksDbWeight::get().writes(8u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking ReserveFactor (r:1 w:1)
 	fn update_reserve_factor() -> Weight {
-		Weight::from_ref_time(40_612_000u64)
+		Weight::from_ref_time(65_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(2u64))
 			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgerCap (r:1 w:1)
 	fn update_staking_ledger_cap() -> Weight {
-		Weight::from_ref_time(39_767_000u64)
+		Weight::from_ref_time(60_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(2u64))
 			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking XcmRequests (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:1 w:1)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
 	fn notification_received() -> Weight {
-		Weight::from_ref_time(137_351_000u64)
+		Weight::from_ref_time(210_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(8u64))
 			.saturating_add(RocksDbWeight::get().writes(6u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:1 w:0)
-	// Storage: LiquidStaking Unlockings (r:1 w:1)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:2 w:2)
-	// Storage: LiquidStaking TotalReserves (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:0)
 	fn claim_for() -> Weight {
-		Weight::from_ref_time(157_173_000u64)
+		Weight::from_ref_time(240_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(9u64))
 			.saturating_add(RocksDbWeight::get().writes(5u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking EraStartBlock (r:0 w:1)
 	fn force_set_era_start_block() -> Weight {
-		Weight::from_ref_time(12_342_000u64)
+		Weight::from_ref_time(20_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(1u64))
 			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:0 w:1)
-	// Storage: LiquidStaking IsMatched (r:0 w:1)
 	fn force_set_current_era() -> Weight {
-		Weight::from_ref_time(14_154_000u64)
+		Weight::from_ref_time(25_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(1u64))
 			.saturating_add(RocksDbWeight::get().writes(3u64))
 	}
-	// Storage: ParachainSystem ValidationData (r:1 w:0)
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking IsMatched (r:1 w:0)
-	// Storage: LiquidStaking EraStartBlock (r:1 w:0)
 	fn on_initialize() -> Weight {
-		Weight::from_ref_time(21_084_000u64)
+		Weight::from_ref_time(35_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(4u64))
 			.saturating_add(RocksDbWeight::get().writes(1u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:3 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgerCap (r:1 w:0)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: XcmHelper XcmWeightFee (r:2 w:0)
-	// Storage: Assets Asset (r:2 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:1 w:1)
-	// Storage: ParachainSystem ValidationData (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
-	// Storage: LiquidStaking EraStartBlock (r:0 w:1)
-	// Storage: LiquidStaking IsMatched (r:0 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:2)
-	// Storage: PolkadotXcm Queries (r:0 w:2)
 	fn force_advance_era() -> Weight {
-		Weight::from_ref_time(465_583_000u64)
+		Weight::from_ref_time(700_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(22u64))
 			.saturating_add(RocksDbWeight::get().writes(15u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgers (r:3 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: LiquidStaking StakingLedgerCap (r:1 w:0)
-	// Storage: ParachainInfo ParachainId (r:1 w:0)
-	// Storage: XcmHelper XcmWeightFee (r:2 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
-	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
-	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
-	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
-	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
-	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:1 w:0)
-	// Storage: LiquidStaking IsMatched (r:0 w:1)
-	// Storage: LiquidStaking XcmRequests (r:0 w:2)
-	// Storage: PolkadotXcm Queries (r:0 w:2)
 	fn force_matching() -> Weight {
-		Weight::from_ref_time(391_066_000u64)
+		Weight::from_ref_time(590_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(18u64))
 			.saturating_add(RocksDbWeight::get().writes(12u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking TotalReserves (r:1 w:1)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:2 w:2)
 	fn reduce_reserves() -> Weight {
-		Weight::from_ref_time(125_618_000u64)
+		Weight::from_ref_time(190_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(6u64))
 			.saturating_add(RocksDbWeight::get().writes(5u64))
 	}
-	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
-	// Storage: LiquidStaking Unlockings (r:1 w:1)
-	// Storage: LiquidStaking CurrentEra (r:1 w:0)
-	// Storage: LiquidStaking MatchingPool (r:1 w:1)
-	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
 	fn cancel_unstake() -> Weight {
-		Weight::from_ref_time(117_945_000u64)
+		Weight::from_ref_time(180_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(8u64))
 			.saturating_add(RocksDbWeight::get().writes(5u64))
 	}
 
 	fn update_commission_rate() -> Weight {
-		Weight::from_ref_time(40_612_000u64)
+		Weight::from_ref_time(65_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(2u64))
 			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
 	fn fast_match_unstake(n: u32, ) -> Weight {
-		Weight::from_ref_time(21_480_000u64)
-			.saturating_add(Weight::from_ref_time(82_727_000u64).saturating_mul(n as u64))
+		Weight::from_ref_time(35_000_000u64)
+			.saturating_add(Weight::from_ref_time(125_000_000u64).saturating_mul(n as u64))
 			.saturating_add(RocksDbWeight::get().reads(7u64))
 			.saturating_add(RocksDbWeight::get().reads((4u64).saturating_mul(n as u64)))
 			.saturating_add(RocksDbWeight::get().writes(4u64))
@@ -708,4 +610,86 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2u64))
 			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
+	// NOT GENERATED: conservative estimates for the calls below, which have not been benchmarked
+	// yet. To be replaced by the output of the benchmark CLI once `mod benchmarking` is enabled.
+	fn add_validator_candidate() -> Weight {
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	fn remove_validator_candidate() -> Weight {
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	fn set_validator_prefs() -> Weight {
+		Weight::from_ref_time(150_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	fn set_validator_era_points(n: u32, ) -> Weight {
+		Weight::from_ref_time(160_000_000u64)
+			.saturating_add(Weight::from_ref_time(15_000_000u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().reads((1u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+			.saturating_add(RocksDbWeight::get().writes((1u64).saturating_mul(n as u64)))
+	}
+	fn rotate_nominations(n: u32, ) -> Weight {
+		Weight::from_ref_time(30_000_000u64)
+			.saturating_add(Weight::from_ref_time(10_000_000u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(4u64))
+			.saturating_add(RocksDbWeight::get().reads((1u64).saturating_mul(n as u64)))
+	}
+	fn set_distribution_strategy() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	fn unstake_via_dex() -> Weight {
+		Weight::from_ref_time(330_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(13u64))
+			.saturating_add(RocksDbWeight::get().writes(6u64))
+	}
+	fn set_dex_pool() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	fn set_slash_policy() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	fn slash() -> Weight {
+		Weight::from_ref_time(80_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(11u64))
+			.saturating_add(RocksDbWeight::get().writes(3u64))
+	}
+	fn set_xcm_request_timeout() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	fn resolve_xcm_request() -> Weight {
+		Weight::from_ref_time(150_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(7u64))
+			.saturating_add(RocksDbWeight::get().writes(6u64))
+	}
+	fn check_xcm_request() -> Weight {
+		Weight::from_ref_time(15_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	fn retry_xcm_request() -> Weight {
+		Weight::from_ref_time(135_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(6u64))
+			.saturating_add(RocksDbWeight::get().writes(6u64))
+	}
+	fn chill() -> Weight {
+		Weight::from_ref_time(275_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(12u64))
+			.saturating_add(RocksDbWeight::get().writes(9u64))
+	}
+	fn set_payee() -> Weight {
+		Weight::from_ref_time(275_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(12u64))
+			.saturating_add(RocksDbWeight::get().writes(9u64))
+	}
 }
@@ -0,0 +1,204 @@
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::oracle::{OracleStatus, Price};
+use core::{fmt::Display, str::FromStr};
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use oracle_runtime_api::OracleRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	traits::{Block as BlockT, MaybeSerializeDeserialize},
+	Percent,
+};
+use sp_std::{sync::Arc, vec::Vec};
+
+#[rpc(client, server)]
+pub trait OracleApi<BlockHash, AssetId, PriceValue, BlockNumber, AccountId, Balance>
+where
+	AssetId: FromStr + Display,
+	PriceValue: FromStr + Display,
+	Balance: FromStr + Display,
+{
+	#[method(name = "oracle_getPrice")]
+	fn get_price(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Price<SafeRpcWrapper<PriceValue>, BlockNumber>>>;
+
+	#[method(name = "oracle_getTwap")]
+	fn get_twap(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		weights: Vec<Percent>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SafeRpcWrapper<PriceValue>>>;
+
+	#[method(name = "oracle_priceHistory")]
+	fn price_history(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		count: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Price<SafeRpcWrapper<PriceValue>, BlockNumber>>>;
+
+	#[method(name = "oracle_pendingRequests")]
+	fn pending_requests(&self, at: Option<BlockHash>) -> RpcResult<Vec<SafeRpcWrapper<AssetId>>>;
+
+	#[method(name = "oracle_oracleStatus")]
+	fn oracle_status(
+		&self,
+		signer: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<OracleStatus<AccountId, SafeRpcWrapper<Balance>, BlockNumber>>>;
+}
+
+pub struct Oracle<C, Block> {
+	client: Arc<C>,
+	_marker: sp_std::marker::PhantomData<Block>,
+}
+
+impl<C, M> Oracle<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AssetId, PriceValue, BlockNumber, AccountId, Balance>
+	OracleApiServer<<Block as BlockT>::Hash, AssetId, PriceValue, BlockNumber, AccountId, Balance>
+	for Oracle<C, (Block, AssetId, PriceValue, BlockNumber, AccountId, Balance)>
+where
+	Block: BlockT,
+	AssetId: Send + Sync + 'static + Codec + FromStr + Display,
+	PriceValue: Send + Sync + 'static + Codec + FromStr + Display,
+	BlockNumber: Send + Sync + 'static + Codec + MaybeSerializeDeserialize,
+	AccountId: Send + Sync + 'static + Codec + MaybeSerializeDeserialize,
+	Balance: Send + Sync + 'static + Codec + FromStr + Display,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: OracleRuntimeApi<Block, AssetId, PriceValue, BlockNumber, AccountId, Balance>,
+{
+	fn get_price(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Price<SafeRpcWrapper<PriceValue>, BlockNumber>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.get_price(at, asset_id);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_twap(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		weights: Vec<Percent>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SafeRpcWrapper<PriceValue>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.get_twap(at, asset_id, weights);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn price_history(
+		&self,
+		asset_id: SafeRpcWrapper<AssetId>,
+		count: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Price<SafeRpcWrapper<PriceValue>, BlockNumber>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.price_history(at, asset_id, count);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn pending_requests(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<SafeRpcWrapper<AssetId>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.pending_requests(at);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn oracle_status(
+		&self,
+		signer: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<OracleStatus<AccountId, SafeRpcWrapper<Balance>, BlockNumber>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let api_version = api
+			.api_version::<dyn OracleRuntimeApi<Block, AssetId, PriceValue, BlockNumber, AccountId, Balance>>(
+				at,
+			)
+			.map_err(|e| {
+				RpcError::Call(CallError::Custom(ErrorObject::owned(
+					9876,
+					"Something wrong",
+					Some(format!("{:?}", e)),
+				)))
+			})?;
+
+		// calling ../../runtime-api
+		let runtime_api_result = if api_version.unwrap_or_default() < 2 {
+			#[allow(deprecated)]
+			api.oracle_status_before_version_2(at, signer)
+				.map(|status| status.map(Into::into))
+		} else {
+			api.oracle_status(at, signer)
+		};
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+}
@@ -0,0 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use composable_support::rpc_helpers::SafeRpcWrapper;
+use composable_traits::oracle::{OracleStatus, OracleStatusV1, Price};
+use sp_runtime::Percent;
+use sp_std::vec::Vec;
+
+// Oracle Runtime API declaration. Implemented for each runtime at
+// `runtime/<runtime-name>/src/lib.rs`.
+sp_api::decl_runtime_apis! {
+	#[api_version(2)]
+	pub trait OracleRuntimeApi<AssetId, PriceValue, BlockNumber, AccountId, Balance>
+	where
+		AssetId: Codec,
+		PriceValue: Codec,
+		BlockNumber: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Last aggregated price of `asset_id`, `None` if there is none yet or the asset is
+		/// frozen.
+		fn get_price(
+			asset_id: SafeRpcWrapper<AssetId>,
+		) -> Option<Price<SafeRpcWrapper<PriceValue>, BlockNumber>>;
+
+		/// Average of the latest prices of `asset_id` weighted by `weights`, most recent first.
+		/// `None` if the weights do not sum to 100% or go deeper than the price history.
+		fn get_twap(
+			asset_id: SafeRpcWrapper<AssetId>,
+			weights: Vec<Percent>,
+		) -> Option<SafeRpcWrapper<PriceValue>>;
+
+		/// Up to `count` of the most recent prices of `asset_id`, oldest first.
+		fn price_history(
+			asset_id: SafeRpcWrapper<AssetId>,
+			count: u32,
+		) -> Vec<Price<SafeRpcWrapper<PriceValue>, BlockNumber>>;
+
+		/// Assets whose price is due for an update.
+		fn pending_requests() -> Vec<SafeRpcWrapper<AssetId>>;
+
+		/// Stake and rewards of the oracle signing with `signer`, `None` if it is unknown.
+		#[changed_in(2)]
+		fn oracle_status(
+			signer: AccountId,
+		) -> Option<OracleStatusV1<AccountId, SafeRpcWrapper<Balance>, BlockNumber>>;
+
+		/// Stake, rewards and reputation of the oracle signing with `signer`, `None` if it is
+		/// unknown.
+		fn oracle_status(
+			signer: AccountId,
+		) -> Option<OracleStatus<AccountId, SafeRpcWrapper<Balance>, BlockNumber>>;
+	}
+}
@@ -20,6 +20,8 @@ pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 const SEED: u32 = 0;
+/// Length of the chain of derived prices walked when setting a derived price.
+const DERIVATION_DEPTH: u32 = 16;
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
@@ -146,6 +148,9 @@ benchmarks! {
 			reward_weight: T::Currency::minimum_balance(),
 			slash: T::Currency::minimum_balance(),
 			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
 		});
 		frame_system::Pallet::<T>::set_block_number(6u32.into());
 		PrePrices::<T>::mutate(asset_id, |current_prices| -> DispatchResult {
@@ -164,6 +169,183 @@ benchmarks! {
 		assert_last_event::<T>(Event::PriceSubmitted(caller, asset_id, price.into()).into())
 	}
 
+	set_price_exponent {
+		let asset_id: T::AssetId = 1.into();
+		AssetsInfo::<T>::insert(asset_id, AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: T::MaxAnswerBound::get(),
+			block_interval: T::StalePrice::get(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
+		});
+	}: _(RawOrigin::Root, asset_id, 12)
+	verify {
+		assert_last_event::<T>(Event::PriceExponentChanged(asset_id, 12).into())
+	}
+
+	set_circuit_breaker {
+		let asset_id: T::AssetId = 1.into();
+		AssetsInfo::<T>::insert(asset_id, AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: T::MaxAnswerBound::get(),
+			block_interval: T::StalePrice::get(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
+		});
+		let max_staleness = Some(T::StalePrice::get());
+		let max_deviation = Some(Percent::from_percent(10));
+	}: _(RawOrigin::Root, asset_id, max_staleness, max_deviation)
+	verify {
+		assert_last_event::<T>(Event::CircuitBreakerChanged(asset_id, max_staleness, max_deviation).into())
+	}
+
+	unfreeze_asset {
+		let asset_id: T::AssetId = 1.into();
+		FrozenAssets::<T>::insert(asset_id, FreezeReason::Deviation);
+	}: _(RawOrigin::Root, asset_id)
+	verify {
+		assert!(UnfrozenAssets::<T>::contains_key(asset_id));
+		assert_last_event::<T>(Event::AssetUnfrozen(asset_id).into())
+	}
+
+	set_reveal_period {
+		let asset_id: T::AssetId = 1.into();
+		AssetsInfo::<T>::insert(asset_id, AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: T::MaxAnswerBound::get(),
+			block_interval: T::StalePrice::get(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
+		});
+		let reveal_period = Some(T::StalePrice::get());
+	}: _(RawOrigin::Root, asset_id, reveal_period)
+	verify {
+		assert_last_event::<T>(Event::RevealPeriodChanged(asset_id, reveal_period).into())
+	}
+
+	set_reputation_weighting {
+		let asset_id: T::AssetId = 1.into();
+		AssetsInfo::<T>::insert(asset_id, AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: T::MaxAnswerBound::get(),
+			block_interval: T::StalePrice::get(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
+		});
+	}: _(RawOrigin::Root, asset_id, true)
+	verify {
+		assert_last_event::<T>(Event::ReputationWeightingChanged(asset_id, true).into())
+	}
+
+	set_derived_price {
+		let asset_id: T::AssetId = 1.into();
+		let derivation = PriceDerivation::Ratio { numerator: 2.into(), denominator: 3.into() };
+		// chain of derived prices walked by the cycle check, 3 -> 5 -> 7 -> ...
+		for i in 0..DERIVATION_DEPTH {
+			DerivedPrices::<T>::insert(
+				T::AssetId::from(3 + 2 * i as u128),
+				PriceDerivation::Ratio {
+					numerator: (4 + 2 * i as u128).into(),
+					denominator: (5 + 2 * i as u128).into(),
+				},
+			);
+		}
+	}: _(RawOrigin::Root, asset_id, Some(derivation))
+	verify {
+		assert_last_event::<T>(Event::DerivedPriceChanged(asset_id, Some(derivation)).into())
+	}
+
+	commit_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id: T::AssetId = 1.into();
+		OracleStake::<T>::insert(&caller, T::MinStake::get());
+		AssetsInfo::<T>::insert(asset_id, AssetInfo {
+			threshold: Percent::from_percent(80),
+			min_answers: 1,
+			max_answers: T::MaxAnswerBound::get(),
+			block_interval: T::StalePrice::get(),
+			reward_weight: T::Currency::minimum_balance(),
+			slash: T::Currency::minimum_balance(),
+			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
+		});
+		RevealPeriods::<T>::insert(asset_id, T::StalePrice::get());
+		// the caller takes the last slot of the round, after every other oracle
+		let block = frame_system::Pallet::<T>::block_number();
+		let commitments = (1..T::MaxAnswerBound::get())
+			.map(|i| {
+				let who: T::AccountId = account("candidate", i, SEED);
+				PriceCommitment {
+					commitment: Oracle::<T>::price_commitment(&who, 100_000_u128.into(), &[1; 32]),
+					block,
+					who,
+					revealed: false,
+				}
+			})
+			.collect::<Vec<_>>();
+		PriceCommitments::<T>::insert(asset_id, BoundedVec::try_from(commitments).unwrap());
+		let commitment = Oracle::<T>::price_commitment(&caller, 100_000_u128.into(), &[1; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, commitment)
+	verify {
+		assert_last_event::<T>(Event::PriceCommitted(caller, asset_id).into())
+	}
+
+	reveal_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id: T::AssetId = 1.into();
+		let price: T::PriceValue = 100_000_u128.into();
+		let reveal_period: T::BlockNumber = 1u32.into();
+		RevealPeriods::<T>::insert(asset_id, reveal_period);
+		let block = frame_system::Pallet::<T>::block_number();
+		// the caller revealing last, after every other oracle of the round
+		let mut commitments = Vec::new();
+		let mut pre_prices = Vec::new();
+		for i in 1..T::MaxAnswerBound::get() {
+			let who: T::AccountId = account("candidate", i, SEED);
+			commitments.push(PriceCommitment {
+				commitment: Oracle::<T>::price_commitment(&who, price, &[1; 32]),
+				block,
+				who: who.clone(),
+				revealed: true,
+			});
+			pre_prices.push(PrePrice { price, block, who });
+		}
+		commitments.push(PriceCommitment {
+			commitment: Oracle::<T>::price_commitment(&caller, price, &[1; 32]),
+			block,
+			who: caller.clone(),
+			revealed: false,
+		});
+		PriceCommitments::<T>::insert(asset_id, BoundedVec::try_from(commitments).unwrap());
+		PrePrices::<T>::insert(asset_id, BoundedVec::try_from(pre_prices).unwrap());
+		frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + reveal_period);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, price, [1; 32])
+	verify {
+		assert_last_event::<T>(Event::PriceSubmitted(caller, asset_id, price).into())
+	}
+
 	update_pre_prices {
 		let p in 1 .. T::MaxAnswerBound::get();
 		let who: T::AccountId = whitelisted_caller();
@@ -177,6 +359,9 @@ benchmarks! {
 			reward_weight: T::Currency::minimum_balance(),
 			slash: T::Currency::minimum_balance(),
 			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
 		};
 		let pre_prices = (0..p).map(|i| {
 			PrePrice {
@@ -204,6 +389,9 @@ benchmarks! {
 			reward_weight: T::Currency::minimum_balance(),
 			slash: T::Currency::minimum_balance(),
 			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
 		};
 		let pre_prices = (0..p).map(|_| {
 			PrePrice {
@@ -15,6 +15,8 @@
 #![allow(clippy::too_many_arguments)]
 pub use pallet::*;
 
+pub mod migrations;
+pub mod sources;
 mod validation;
 
 #[cfg(test)]
@@ -29,8 +31,11 @@ pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use crate::validation::{ValidBlockInterval, ValidMaxAnswer, ValidMinAnswers, ValidThreshold};
 	pub use crate::weights::WeightInfo;
+	use crate::{
+		sources::{self, SourceOutcome},
+		validation::{ValidBlockInterval, ValidMaxAnswer, ValidMinAnswers, ValidThreshold},
+	};
 	use codec::{Codec, FullCodec};
 	use composable_support::{
 		abstractions::{
@@ -45,7 +50,8 @@ pub mod pallet {
 	};
 	use composable_traits::{
 		currency::{BalanceLike, LocalAssets},
-		oracle::{Oracle, Price, RewardTracker},
+		dex::Amm,
+		oracle::{ExchangeRateProvider, Oracle, OracleStatus, Price, RewardTracker},
 		time::MS_PER_YEAR_NAIVE,
 	};
 	use frame_support::{
@@ -69,13 +75,14 @@ pub mod pallet {
 	use scale_info::TypeInfo;
 	use sp_core::crypto::KeyTypeId;
 	use sp_runtime::{
-		offchain::{http, Duration},
+		offchain::{http, storage::StorageValueRef, Duration},
 		traits::{
 			AccountIdConversion, AtLeast32Bit, AtLeast32BitUnsigned, CheckedAdd, CheckedDiv,
-			CheckedMul, CheckedSub, IdentifyAccount, Saturating, UniqueSaturatedInto as _, Zero,
+			CheckedMul, CheckedSub, Hash as _, IdentifyAccount, Saturating,
+			UniqueSaturatedInto as _, Zero,
 		},
 		AccountId32, ArithmeticError, FixedPointNumber, FixedU128, KeyTypeId as CryptoKeyTypeId,
-		PerThing, Percent, RuntimeAppPublic, RuntimeDebug,
+		PerThing, Percent, Permill, RuntimeAppPublic, RuntimeDebug,
 	};
 	use sp_std::{
 		borrow::ToOwned, collections::btree_set::BTreeSet, fmt::Debug, str, vec, vec::Vec,
@@ -124,6 +131,7 @@ pub mod pallet {
 		type AssetId: FullCodec
 			+ Eq
 			+ PartialEq
+			+ Ord
 			+ Copy
 			+ MaybeSerializeDeserialize
 			+ MaxEncodedLen
@@ -180,6 +188,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MsPerBlock: Get<u64>;
 
+		/// Weight of the deviation of the last answer of an oracle in the moving average of the
+		/// deviations of its answers.
+		#[pallet::constant]
+		type ReputationSmoothing: Get<Permill>;
+
 		/// The weight information of this pallet.
 		type WeightInfo: WeightInfo;
 		type LocalAssets: LocalAssets<Self::AssetId>;
@@ -192,6 +205,15 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// Type of the ids of the pools of `Amm`.
+		type PoolId: Parameter + MaxEncodedLen + Copy;
+
+		/// Pools whose reserves price their LP tokens.
+		type Amm: Amm<AssetId = Self::AssetId, Balance = Self::PriceValue, PoolId = Self::PoolId>;
+
+		/// Exchange rates of derivative assets, e.g. liquid staking vouchers.
+		type ExchangeRates: ExchangeRateProvider<Self::AssetId>;
 	}
 
 	#[derive(Encode, Decode, MaxEncodedLen, Default, Debug, PartialEq, Eq, TypeInfo, Clone)]
@@ -222,11 +244,84 @@ pub mod pallet {
 		pub reward_weight: Balance,
 		pub slash: Balance,
 		pub emit_price_changes: bool,
+		/// Decimals of the fixed-point prices submitted by the offchain worker, fetched prices are
+		/// multiplied by `10^price_exponent`.
+		pub price_exponent: u8,
+		/// Blocks after which a price not updated freezes the asset.
+		pub max_staleness: Option<BlockNumber>,
+		/// Largest change of the price in a single update before the asset is frozen.
+		pub max_deviation: Option<Percent>,
+	}
+
+	/// Hash of a price submitted ahead of revealing it.
+	#[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
+	pub struct PriceCommitment<Hash, BlockNumber, AccountId> {
+		/// `hash(who, price, salt)` of the price to reveal.
+		pub commitment: Hash,
+		/// The block the commitment was submitted at.
+		pub block: BlockNumber,
+		/// The account that submitted the commitment.
+		pub who: AccountId,
+		/// Whether the price was revealed.
+		pub revealed: bool,
+	}
+
+	/// Long-term accuracy record of an oracle for an asset, keyed by its signing key and the asset.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, Clone, Copy, Default, Debug, PartialEq, Eq, TypeInfo,
+	)]
+	pub struct Reputation {
+		/// Exponential moving average of the deviation of the answers from the aggregated prices.
+		pub deviation: Permill,
+		/// Aggregation rounds answered.
+		pub answered: u32,
+		/// Value of `AggregationRounds` of the asset before the first answer.
+		pub first_round: u32,
+	}
+
+	/// How the price of an asset not reported by the oracles is derived from other prices.
+	#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+	pub enum PriceDerivation<AssetId, PoolId> {
+		/// Price of `numerator` in units of `denominator`, normalized to 12 decimals.
+		Ratio { numerator: AssetId, denominator: AssetId },
+		/// Price of the underlying asset times the exchange rate of the derivative asset.
+		ExchangeRate,
+		/// Value of the reserves redeemable for a unit of the LP token of `pool_id`.
+		LpToken { pool_id: PoolId },
 	}
 
+	/// Phase of a commit-reveal round of an asset.
+	#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+	pub enum CommitRevealPhase {
+		/// Commitments are accepted, the round starts with the first one.
+		Commit,
+		/// Commitments can be revealed.
+		Reveal,
+		/// The round is over, waiting for unrevealed commitments to be slashed.
+		Closed,
+	}
+
+	/// Why a circuit breaker froze the price of an asset.
+	#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+	pub enum FreezeReason {
+		/// The price was not updated for more than `max_staleness` blocks.
+		Stale,
+		/// An update changed the price by more than `max_deviation`.
+		Deviation,
+	}
+
+	/// Unit of the prices, normalized to 12 decimals.
+	const PRICE_UNIT: u128 = 1_000_000_000_000;
+
+	/// Largest price exponent, `10^38` being the largest power of ten fitting in a `u128`.
+	pub const MAX_PRICE_EXPONENT: u8 = 38;
+
 	type BalanceOf<T> = <T as Config>::Balance;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -273,6 +368,13 @@ pub mod pallet {
 	pub type AccumulatedRewardsPerAsset<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AssetId, BalanceOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_rewards)]
+	#[allow(clippy::disallowed_types)] // no reward paid is zero, which is valid in this context.
+	/// Mapping of signing key to total rewards paid for its answers
+	pub type OracleRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn answer_in_transit)]
 	/// Mapping of slash amounts currently in transit
@@ -329,6 +431,76 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn reveal_period)]
+	/// Length of the commit and reveal phases of assets whose prices are committed before being
+	/// revealed
+	pub type RevealPeriods<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn price_commitments)]
+	#[allow(clippy::disallowed_types)] // no commitment for an asset is an empty list, which is valid in this context.
+	/// Price commitments not revealed yet
+	pub type PriceCommitments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		BoundedVec<PriceCommitment<T::Hash, T::BlockNumber, T::AccountId>, T::MaxPrePrices>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn aggregation_rounds)]
+	#[allow(clippy::disallowed_types)] // no round aggregated yet is zero, which is valid in this context.
+	/// Number of prices aggregated per asset
+	pub type AggregationRounds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reputation)]
+	/// Mapping of signing key and asset to the accuracy record of the oracle for the asset
+	pub type Reputations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AssetId,
+		Reputation,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reputation_weighted)]
+	#[allow(clippy::disallowed_types)] // unweighted median by default is valid in this context.
+	/// Assets whose median price weights answers by the reputation of the oracles
+	pub type ReputationWeightedAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn derived_price)]
+	/// Mapping of assets priced from other prices to their derivation
+	pub type DerivedPrices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		PriceDerivation<T::AssetId, T::PoolId>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_assets)]
+	/// Assets whose prices are not served until unfrozen by governance
+	pub type FrozenAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, FreezeReason, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn unfrozen_at)]
+	/// Block at which governance unfroze an asset, until its next price is aggregated. That price
+	/// is accepted without the deviation check and the staleness is counted from this block.
+	pub type UnfrozenAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, T::BlockNumber, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -357,6 +529,22 @@ pub mod pallet {
 		PriceChanged(T::AssetId, T::PriceValue),
 		/// Signer removed
 		SignerRemoved(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Price exponent of asset changed \[asset_id, price_exponent\]
+		PriceExponentChanged(T::AssetId, u8),
+		/// Circuit breaker of asset changed \[asset_id, max_staleness, max_deviation\]
+		CircuitBreakerChanged(T::AssetId, Option<T::BlockNumber>, Option<Percent>),
+		/// Asset price frozen by its circuit breaker \[asset_id, reason\]
+		AssetFrozen(T::AssetId, FreezeReason),
+		/// Asset price unfrozen \[asset_id\]
+		AssetUnfrozen(T::AssetId),
+		/// Reveal period of asset changed \[asset_id, reveal_period\]
+		RevealPeriodChanged(T::AssetId, Option<T::BlockNumber>),
+		/// Price commitment submitted by oracle. \[oracle_address, asset_id\]
+		PriceCommitted(T::AccountId, T::AssetId),
+		/// Weighting of the median price of asset by reputation changed \[asset_id, enabled\]
+		ReputationWeightingChanged(T::AssetId, bool),
+		/// Derivation of the price of asset changed \[asset_id, derivation\]
+		DerivedPriceChanged(T::AssetId, Option<PriceDerivation<T::AssetId, T::PoolId>>),
 	}
 
 	#[pallet::error]
@@ -422,6 +610,34 @@ pub mod pallet {
 		NoRewardTrackerSet,
 		/// Annual rewarding cost too high
 		AnnualRewardLessThanAlreadyRewarded,
+		/// Price exponent is greater than `MAX_PRICE_EXPONENT`
+		InvalidPriceExponent,
+		/// Asset price is frozen by its circuit breaker
+		AssetFrozen,
+		/// Asset price is not frozen
+		AssetNotFrozen,
+		/// Reveal period is zero or longer than the stale price period
+		InvalidRevealPeriod,
+		/// Asset prices must be committed and revealed
+		CommitRevealRequired,
+		/// Asset prices are submitted without commitment
+		CommitRevealDisabled,
+		/// A commit-reveal round is in progress
+		CommitRevealInProgress,
+		/// Commitments are not accepted in the current phase
+		NotCommitPhase,
+		/// Commitments can not be revealed in the current phase
+		NotRevealPhase,
+		/// No commitment to reveal
+		NoCommitment,
+		/// Revealed price does not match the commitment
+		InvalidReveal,
+		/// Derived price depends on itself
+		DerivedPriceCycle,
+		/// Derived price can not be resolved from its definition
+		InvalidDerivedPrice,
+		/// Asset is reported by the oracles or has a derived price
+		DerivedPriceOfReportedAsset,
 	}
 
 	#[pallet::hooks]
@@ -450,8 +666,7 @@ pub mod pallet {
 			asset_id: Self::AssetId,
 			amount: Self::Balance,
 		) -> Result<Price<Self::Balance, Self::Timestamp>, DispatchError> {
-			let Price { price, block } =
-				Prices::<T>::try_get(asset_id).map_err(|_| Error::<T>::PriceNotFound)?;
+			let Price { price, block } = Self::unit_price(asset_id, &mut Vec::new())?;
 			// dbg!(&price);
 			let price = Self::quote(asset_id, price, amount)?;
 			// dbg!(&price);
@@ -463,6 +678,7 @@ pub mod pallet {
 			asset_id: Self::AssetId,
 			amount: Self::Balance,
 		) -> Result<Self::Balance, DispatchError> {
+			ensure!(!FrozenAssets::<T>::contains_key(asset_id), Error::<T>::AssetFrozen);
 			let prices_length = Self::price_history(asset_id).len();
 			let twap_window: usize = <Self as Oracle>::TwapWindow::get().into();
 			if twap_window > prices_length + 1 {
@@ -544,12 +760,17 @@ pub mod pallet {
 			T::AddOracle::ensure_origin(origin)?;
 
 			ensure!(*max_answers >= *min_answers, Error::<T>::MaxAnswersLessThanMinAnswers);
+			ensure!(
+				!DerivedPrices::<T>::contains_key(asset_id),
+				Error::<T>::DerivedPriceOfReportedAsset
+			);
 
 			ensure!(
 				AssetsCount::<T>::get() < T::MaxAssetsCount::get(),
 				Error::<T>::ExceedAssetsCount
 			);
 
+			let current_asset_info = Self::asset_info(asset_id);
 			let asset_info = AssetInfo {
 				threshold: *threshold,
 				min_answers: *min_answers,
@@ -558,10 +779,19 @@ pub mod pallet {
 				reward_weight,
 				slash,
 				emit_price_changes,
+				price_exponent: current_asset_info
+					.as_ref()
+					.map_or(0, |asset_info| asset_info.price_exponent),
+				max_staleness: current_asset_info
+					.as_ref()
+					.and_then(|asset_info| asset_info.max_staleness),
+				max_deviation: current_asset_info
+					.as_ref()
+					.and_then(|asset_info| asset_info.max_deviation),
 			};
 			// track reward total weight for all assets
 			let mut reward_tracker = RewardTrackerStore::<T>::get().unwrap_or_default();
-			if let Some(current_asset_info) = Self::asset_info(asset_id) {
+			if let Some(current_asset_info) = current_asset_info {
 				reward_tracker.total_reward_weight = reward_tracker.total_reward_weight +
 					reward_weight - current_asset_info
 					.reward_weight;
@@ -729,16 +959,8 @@ pub mod pallet {
 			asset_id: T::AssetId,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let author_stake = OracleStake::<T>::get(&who).unwrap_or_else(Zero::zero);
-			ensure!(
-				author_stake >=
-					T::MinStake::get().saturating_add(
-						Self::answer_in_transit(&who).unwrap_or_else(Zero::zero)
-					),
-				Error::<T>::NotEnoughStake
-			);
-			let asset_info = Self::asset_info(asset_id).ok_or(Error::<T>::InvalidAssetId)?;
-			ensure!(author_stake >= asset_info.slash, Error::<T>::NotEnoughStake);
+			let asset_info = Self::ensure_can_answer(&who, asset_id)?;
+			ensure!(!RevealPeriods::<T>::contains_key(asset_id), Error::<T>::CommitRevealRequired);
 
 			PrePrices::<T>::try_mutate(asset_id, |current_prices| -> Result<(), DispatchError> {
 				// current_prices.len() can be casted to u32 safely because current_prices.len() is
@@ -791,6 +1013,264 @@ pub mod pallet {
 			Self::deposit_event(Event::SignerRemoved(who, signer, stake));
 			Ok(().into())
 		}
+
+		/// Permissioned call to set the decimals of the prices fetched for an asset
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `price_exponent`: fetched prices are multiplied by `10^price_exponent`
+		///
+		/// Emits `PriceExponentChanged` event when successful.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::set_price_exponent())]
+		pub fn set_price_exponent(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			price_exponent: u8,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			ensure!(price_exponent <= MAX_PRICE_EXPONENT, Error::<T>::InvalidPriceExponent);
+			AssetsInfo::<T>::try_mutate(asset_id, |asset_info| -> DispatchResult {
+				let asset_info = asset_info.as_mut().ok_or(Error::<T>::InvalidAssetId)?;
+				asset_info.price_exponent = price_exponent;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::PriceExponentChanged(asset_id, price_exponent));
+			Ok(().into())
+		}
+
+		/// Permissioned call to set the circuit breaker of an asset
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `max_staleness`: blocks without price update before the asset is frozen
+		/// - `max_deviation`: largest price change in one update before the asset is frozen
+		///
+		/// `None` disables the corresponding guard.
+		///
+		/// Emits `CircuitBreakerChanged` event when successful.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_circuit_breaker())]
+		pub fn set_circuit_breaker(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			max_staleness: Option<T::BlockNumber>,
+			max_deviation: Option<Percent>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			AssetsInfo::<T>::try_mutate(asset_id, |asset_info| -> DispatchResult {
+				let asset_info = asset_info.as_mut().ok_or(Error::<T>::InvalidAssetId)?;
+				asset_info.max_staleness = max_staleness;
+				asset_info.max_deviation = max_deviation;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::CircuitBreakerChanged(
+				asset_id,
+				max_staleness,
+				max_deviation,
+			));
+			Ok(().into())
+		}
+
+		/// Permissioned call to serve again the price of an asset frozen by its circuit breaker.
+		/// The next aggregated price is accepted as the new reference price, however far it is
+		/// from the last one.
+		///
+		/// - `asset_id`: Id for the asset
+		///
+		/// Emits `AssetUnfrozen` event when successful.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::unfreeze_asset())]
+		pub fn unfreeze_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			FrozenAssets::<T>::take(asset_id).ok_or(Error::<T>::AssetNotFrozen)?;
+			UnfrozenAssets::<T>::insert(asset_id, frame_system::Pallet::<T>::block_number());
+			Self::deposit_event(Event::AssetUnfrozen(asset_id));
+			Ok(().into())
+		}
+
+		/// Permissioned call to make the prices of an asset committed before being revealed
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `reveal_period`: blocks of the commit phase and of the reveal phase, `None` to submit
+		///   prices without commitment
+		///
+		/// Emits `RevealPeriodChanged` event when successful.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_reveal_period())]
+		pub fn set_reveal_period(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			reveal_period: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			ensure!(AssetsInfo::<T>::contains_key(asset_id), Error::<T>::InvalidAssetId);
+			// revealed prices must not go stale before the end of the round
+			ensure!(
+				reveal_period.map_or(true, |reveal_period| !reveal_period.is_zero() &&
+					reveal_period <= T::StalePrice::get()),
+				Error::<T>::InvalidRevealPeriod
+			);
+			ensure!(
+				PriceCommitments::<T>::decode_len(asset_id).unwrap_or_default() == 0,
+				Error::<T>::CommitRevealInProgress
+			);
+			RevealPeriods::<T>::set(asset_id, reveal_period);
+			Self::deposit_event(Event::RevealPeriodChanged(asset_id, reveal_period));
+			Ok(().into())
+		}
+
+		/// Call to commit to a price, to be revealed with `reveal_price` once the commit phase
+		/// is over. Gas is returned if extrinsic is successful.
+		///
+		/// This is an operational transaction.
+		///
+		/// - `asset_id`: id for the asset
+		/// - `commitment`: hash of the signer, price and salt, see `price_commitment`
+		///
+		/// Emits `PriceCommitted` event when successful.
+		#[pallet::call_index(12)]
+		#[pallet::weight((T::WeightInfo::commit_price(), DispatchClass::Operational))]
+		pub fn commit_price(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			commitment: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let reveal_period =
+				Self::reveal_period(asset_id).ok_or(Error::<T>::CommitRevealDisabled)?;
+			let asset_info = Self::ensure_can_answer(&who, asset_id)?;
+			let block = frame_system::Pallet::<T>::block_number();
+
+			PriceCommitments::<T>::try_mutate(asset_id, |commitments| -> DispatchResult {
+				ensure!(
+					Self::commit_reveal_phase(commitments, reveal_period, block) ==
+						CommitRevealPhase::Commit,
+					Error::<T>::NotCommitPhase
+				);
+				ensure!((commitments.len() as u32) < asset_info.max_answers, Error::<T>::MaxPrices);
+				ensure!(
+					commitments.iter().all(|candidate| candidate.who != who),
+					Error::<T>::AlreadySubmitted
+				);
+				commitments
+					.try_push(PriceCommitment {
+						commitment,
+						block,
+						who: who.clone(),
+						revealed: false,
+					})
+					.map_err(|_| Error::<T>::MaxPrePrices)?;
+				Ok(())
+			})?;
+
+			AnswerInTransit::<T>::mutate(&who, |transit| {
+				*transit =
+					Some(transit.unwrap_or_else(Zero::zero).saturating_add(asset_info.slash));
+			});
+
+			Self::deposit_event(Event::PriceCommitted(who, asset_id));
+			Ok(Pays::No.into())
+		}
+
+		/// Call to reveal a price committed with `commit_price`, once the commit phase is over.
+		/// Gas is returned if extrinsic is successful.
+		///
+		/// This is an operational transaction.
+		///
+		/// - `asset_id`: id for the asset
+		/// - `price`: committed price, normalized to 12 decimals
+		/// - `salt`: committed salt
+		///
+		/// Emits `PriceSubmitted` event when successful.
+		#[pallet::call_index(13)]
+		#[pallet::weight((T::WeightInfo::reveal_price(), DispatchClass::Operational))]
+		pub fn reveal_price(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			price: T::PriceValue,
+			salt: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let reveal_period =
+				Self::reveal_period(asset_id).ok_or(Error::<T>::CommitRevealDisabled)?;
+			let block = frame_system::Pallet::<T>::block_number();
+
+			PriceCommitments::<T>::try_mutate(asset_id, |commitments| -> DispatchResult {
+				ensure!(
+					Self::commit_reveal_phase(commitments, reveal_period, block) ==
+						CommitRevealPhase::Reveal,
+					Error::<T>::NotRevealPhase
+				);
+				let commitment = commitments
+					.iter_mut()
+					.find(|candidate| candidate.who == who)
+					.ok_or(Error::<T>::NoCommitment)?;
+				ensure!(!commitment.revealed, Error::<T>::AlreadySubmitted);
+				ensure!(
+					commitment.commitment == Self::price_commitment(&who, price, &salt),
+					Error::<T>::InvalidReveal
+				);
+				commitment.revealed = true;
+				PrePrices::<T>::try_mutate(asset_id, |current_prices| {
+					current_prices
+						.try_push(PrePrice { price, block, who: who.clone() })
+						.map_err(|_| Error::<T>::MaxPrePrices)
+				})?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::PriceSubmitted(who, asset_id, price));
+			Ok(Pays::No.into())
+		}
+
+		/// Permissioned call to weight the answers aggregated in the median price of an asset
+		/// by the reputation of the oracles which submitted them
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `enabled`: whether the median is weighted
+		///
+		/// Emits `ReputationWeightingChanged` event when successful.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_reputation_weighting())]
+		pub fn set_reputation_weighting(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			ensure!(AssetsInfo::<T>::contains_key(asset_id), Error::<T>::InvalidAssetId);
+			ReputationWeightedAssets::<T>::set(asset_id, enabled);
+			Self::deposit_event(Event::ReputationWeightingChanged(asset_id, enabled));
+			Ok(().into())
+		}
+
+		/// Permissioned call to price an asset not reported by the oracles from other prices
+		///
+		/// - `asset_id`: Id for the asset
+		/// - `derivation`: how the price is derived, `None` to remove it
+		///
+		/// Emits `DerivedPriceChanged` event when successful.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::set_derived_price())]
+		pub fn set_derived_price(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			derivation: Option<PriceDerivation<T::AssetId, T::PoolId>>,
+		) -> DispatchResultWithPostInfo {
+			T::AddOracle::ensure_origin(origin)?;
+			ensure!(
+				!AssetsInfo::<T>::contains_key(asset_id),
+				Error::<T>::DerivedPriceOfReportedAsset
+			);
+			if let Some(derivation) = derivation {
+				Self::ensure_acyclic_derivation(asset_id, derivation)?;
+			}
+			DerivedPrices::<T>::set(asset_id, derivation);
+			Self::deposit_event(Event::DerivedPriceChanged(asset_id, derivation));
+			Ok(().into())
+		}
 	}
 
 	/// Payload used by this example crate to hold price
@@ -825,6 +1305,30 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Slashes `slash_amount` of the stake of `who` to the treasury.
+		fn slash(who: &T::AccountId, asset_id: T::AssetId, slash_amount: BalanceOf<T>) {
+			let new_amount_staked = Self::oracle_stake(who.clone())
+				.unwrap_or_else(|| 0_u32.into())
+				.saturating_sub(slash_amount);
+			OracleStake::<T>::insert(who, new_amount_staked);
+			let result = T::Currency::repatriate_reserved(
+				who,
+				&T::TreasuryAccount::get(),
+				slash_amount,
+				BalanceStatus::Free,
+			);
+			match result {
+				Ok(remaining_val) =>
+					if remaining_val > BalanceOf::<T>::zero() {
+						log::warn!("Only slashed {:?}", slash_amount - remaining_val);
+					},
+				Err(e) => {
+					log::warn!("Failed to slash {:?} due to {:?}", who, e);
+				},
+			}
+			Self::deposit_event(Event::UserSlashed(who.clone(), asset_id, slash_amount));
+		}
+
 		pub fn handle_payout(
 			pre_prices: &[PrePrice<T::PriceValue, T::BlockNumber, T::AccountId>],
 			price: T::PriceValue,
@@ -832,7 +1336,12 @@ pub mod pallet {
 			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
 		) -> DispatchResult {
 			let mut rewarded_oracles = BTreeSet::new();
+			let round = AggregationRounds::<T>::mutate(asset_id, |rounds| {
+				*rounds = rounds.saturating_add(1);
+				*rounds
+			});
 			for answer in pre_prices {
+				Self::update_reputation(&answer.who, asset_id, answer.price, price, round);
 				// TODO vim: duplicated code could be refactored to do these accuracy calculations
 				// once
 				let accuracy: Percent = if answer.price < price {
@@ -843,31 +1352,7 @@ pub mod pallet {
 				};
 				let min_accuracy = asset_info.threshold;
 				if accuracy < min_accuracy {
-					let slash_amount = asset_info.slash;
-					let new_amount_staked = Self::oracle_stake(answer.who.clone())
-						.unwrap_or_else(|| 0_u32.into())
-						.saturating_sub(slash_amount);
-					OracleStake::<T>::insert(&answer.who, new_amount_staked);
-					let result = T::Currency::repatriate_reserved(
-						&answer.who,
-						&T::TreasuryAccount::get(),
-						slash_amount,
-						BalanceStatus::Free,
-					);
-					match result {
-						Ok(remaining_val) =>
-							if remaining_val > BalanceOf::<T>::zero() {
-								log::warn!("Only slashed {:?}", slash_amount - remaining_val);
-							},
-						Err(e) => {
-							log::warn!("Failed to slash {:?} due to {:?}", answer.who, e);
-						},
-					}
-					Self::deposit_event(Event::UserSlashed(
-						answer.who.clone(),
-						asset_id,
-						slash_amount,
-					));
+					Self::slash(&answer.who, asset_id, asset_info.slash);
 				} else {
 					let controller = SignerToController::<T>::get(&answer.who)
 						.unwrap_or_else(|| answer.who.clone());
@@ -917,6 +1402,65 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Records the deviation of the answer of `who` from the aggregated `price` of `round` of
+		/// `asset_id` in its reputation for the asset.
+		fn update_reputation(
+			who: &T::AccountId,
+			asset_id: T::AssetId,
+			answer: T::PriceValue,
+			price: T::PriceValue,
+			round: u32,
+		) {
+			let (answer, price): (u128, u128) = (answer.into(), price.into());
+			let deviation = if price.is_zero() {
+				if answer.is_zero() {
+					Permill::zero()
+				} else {
+					Permill::one()
+				}
+			} else {
+				Permill::from_rational(answer.abs_diff(price), price)
+			};
+			Reputations::<T>::mutate(who, asset_id, |reputation| {
+				let reputation = reputation.get_or_insert(Reputation {
+					deviation,
+					answered: 0,
+					first_round: round.saturating_sub(1),
+				});
+				let smoothing = T::ReputationSmoothing::get();
+				reputation.deviation = (smoothing * deviation).saturating_add(
+					Permill::one().saturating_sub(smoothing) * reputation.deviation,
+				);
+				reputation.answered = reputation.answered.saturating_add(1);
+			});
+		}
+
+		/// Reputation of the oracle signing with `who` for `asset_id`: the agreement of its answers
+		/// with the aggregated prices, scaled by the share of the rounds of the asset aggregated
+		/// since its first answer it answered. Zero for oracles which never answered for the asset.
+		pub fn reputation_score(who: &T::AccountId, asset_id: T::AssetId) -> Permill {
+			Self::reputation(who, asset_id)
+				.map_or_else(Permill::zero, |reputation| Self::score(asset_id, reputation))
+		}
+
+		/// Average reputation of the oracle signing with `who` over the assets it answered for.
+		pub fn average_reputation_score(who: &T::AccountId) -> Permill {
+			let (sum, count) = Reputations::<T>::iter_prefix(who).fold(
+				(0_u64, 0_u64),
+				|(sum, count), (asset_id, reputation)| {
+					let score = Self::score(asset_id, reputation).deconstruct();
+					(sum.saturating_add(score.into()), count.saturating_add(1))
+				},
+			);
+			Permill::from_rational(sum, count.saturating_mul(Permill::ACCURACY.into()).max(1))
+		}
+
+		fn score(asset_id: T::AssetId, reputation: Reputation) -> Permill {
+			let rounds = Self::aggregation_rounds(asset_id).saturating_sub(reputation.first_round);
+			let participation = Permill::from_rational(reputation.answered, rounds.max(1));
+			Permill::one().saturating_sub(reputation.deviation) * participation
+		}
+
 		fn get_reward_tracker_if_enabled(
 		) -> Option<RewardTracker<<T as Config>::Balance, <T as Config>::Moment>> {
 			RewardTrackerStore::<T>::get().and_then(|r| {
@@ -935,6 +1479,9 @@ pub mod pallet {
 			reward_amount: BalanceOf<T>,
 		) -> DispatchResult {
 			T::Currency::transfer(&Self::account_id(), &controller, reward_amount, KeepAlive)?;
+			OracleRewards::<T>::mutate(&who, |rewards| {
+				*rewards = rewards.saturating_add(reward_amount)
+			});
 			Self::deposit_event(Event::OracleRewarded(who, asset_id, reward_amount));
 			Ok(())
 		}
@@ -987,7 +1534,13 @@ pub mod pallet {
 					};
 				};
 				total_weight += one_read;
-				if Self::is_requested(&asset_id) {
+				// prices are aggregated once all commitments had the time to be revealed
+				let round_in_progress =
+					Self::reveal_period(asset_id).map_or(false, |reveal_period| {
+						total_weight += one_read;
+						Self::close_commit_reveal_round(asset_id, &asset_info, reveal_period, block)
+					});
+				if !round_in_progress && Self::is_requested(&asset_id) {
 					if let Ok((removed_pre_prices_len, pre_prices)) =
 						Self::update_pre_prices(asset_id, &asset_info, block)
 					{
@@ -1002,6 +1555,20 @@ pub mod pallet {
 						total_weight += T::WeightInfo::update_price(pre_prices_len as u32);
 					};
 				}
+				if let Some(max_staleness) = asset_info.max_staleness {
+					total_weight += one_read.saturating_mul(2);
+					if let Ok(last_update) = Prices::<T>::try_get(asset_id) {
+						let last_update = UnfrozenAssets::<T>::get(asset_id)
+							.map_or(last_update.block, |unfrozen_at| {
+								last_update.block.max(unfrozen_at)
+							});
+						if last_update.saturating_add(max_staleness) < block &&
+							Self::freeze(asset_id, FreezeReason::Stale)
+						{
+							total_weight += one_write;
+						}
+					}
+				}
 			}
 			total_weight
 		}
@@ -1054,12 +1621,31 @@ pub mod pallet {
 			// because pre_prices.len() limited by u32
 			// (type of AssetsInfo::<T>::get(asset_id).max_answers).
 			if pre_prices.len() as u32 >= asset_info.min_answers {
-				if let Some(price) = Self::calculate_price(&pre_prices, &asset_info) {
+				if let Some(price) = Self::calculate_price(asset_id, &pre_prices, &asset_info) {
 					let last_price = match pre_prices.last() {
 						Some(pre_price) => pre_price.price,
 						_ => Zero::zero(),
 					};
 
+					// the first price after an unfreeze becomes the new reference price
+					let unfrozen = UnfrozenAssets::<T>::take(asset_id).is_some();
+					if let Some(max_deviation) = asset_info.max_deviation.filter(|_| !unfrozen) {
+						let previous_price = Self::prices(asset_id).price;
+						let deviation = price.max(previous_price) - price.min(previous_price);
+						if !previous_price.is_zero() &&
+							deviation > max_deviation.mul_floor(previous_price)
+						{
+							// the round is dropped, the price stays the last accepted one until
+							// the asset is unfrozen and a new round is aggregated
+							Self::freeze(asset_id, FreezeReason::Deviation);
+							PrePrices::<T>::remove(asset_id);
+							for answer in &pre_prices {
+								Self::remove_price_in_transit(&answer.who, &asset_info);
+							}
+							return Ok(())
+						}
+					}
+
 					Prices::<T>::insert(asset_id, Price { price, block });
 					PriceHistory::<T>::try_mutate(asset_id, |prices| -> DispatchResult {
 						if prices.len() as u32 >= T::MaxHistory::get() {
@@ -1135,11 +1721,33 @@ pub mod pallet {
 			}
 		}
 
+		/// Median of the prices weighted by the reputation of the oracles which submitted them.
+		/// Every answer weighs at least as much as one of an oracle without reputation, so that
+		/// new oracles are not ignored.
+		pub fn get_reputation_weighted_median_price(
+			asset_id: T::AssetId,
+			prices: &[PrePrice<T::PriceValue, T::BlockNumber, T::AccountId>],
+		) -> Option<T::PriceValue> {
+			let answers = prices
+				.iter()
+				.map(|answer| {
+					let weight = Self::reputation_score(&answer.who, asset_id).deconstruct().max(1);
+					(answer.price.into(), weight)
+				})
+				.collect::<Vec<(u128, u32)>>();
+			sources::weighted_median(&answers).map(Into::into)
+		}
+
 		pub fn calculate_price(
+			asset_id: T::AssetId,
 			prices: &[PrePrice<T::PriceValue, T::BlockNumber, T::AccountId>],
 			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
 		) -> Option<T::PriceValue> {
-			let median_price = Self::get_median_price(prices)?;
+			let median_price = if Self::reputation_weighted(asset_id) {
+				Self::get_reputation_weighted_median_price(asset_id, prices)
+			} else {
+				Self::get_median_price(prices)
+			}?;
 			let mut sum_of_price = T::PriceValue::zero();
 			let mut number_of_prices = 0_u32;
 			for answer in prices {
@@ -1170,6 +1778,84 @@ pub mod pallet {
 			}
 		}
 
+		/// Checks that `who` has enough stake to answer for `asset_id`.
+		fn ensure_can_answer(
+			who: &T::AccountId,
+			asset_id: T::AssetId,
+		) -> Result<AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>, DispatchError> {
+			let author_stake = OracleStake::<T>::get(who).unwrap_or_else(Zero::zero);
+			ensure!(
+				author_stake >=
+					T::MinStake::get()
+						.saturating_add(Self::answer_in_transit(who).unwrap_or_else(Zero::zero)),
+				Error::<T>::NotEnoughStake
+			);
+			let asset_info = Self::asset_info(asset_id).ok_or(Error::<T>::InvalidAssetId)?;
+			ensure!(author_stake >= asset_info.slash, Error::<T>::NotEnoughStake);
+			Ok(asset_info)
+		}
+
+		/// Commitment of `who` to `price`, to be revealed with `salt`.
+		pub fn price_commitment(
+			who: &T::AccountId,
+			price: T::PriceValue,
+			salt: &[u8; 32],
+		) -> T::Hash {
+			T::Hashing::hash_of(&(who, price, salt))
+		}
+
+		/// Phase at `block` of the round started by the first of `commitments`.
+		pub fn commit_reveal_phase(
+			commitments: &[PriceCommitment<T::Hash, T::BlockNumber, T::AccountId>],
+			reveal_period: T::BlockNumber,
+			block: T::BlockNumber,
+		) -> CommitRevealPhase {
+			match commitments.first() {
+				Some(first)
+					if block >=
+						first
+							.block
+							.saturating_add(reveal_period.saturating_add(reveal_period)) =>
+					CommitRevealPhase::Closed,
+				Some(first) if block >= first.block.saturating_add(reveal_period) =>
+					CommitRevealPhase::Reveal,
+				_ => CommitRevealPhase::Commit,
+			}
+		}
+
+		/// Slashes the unrevealed commitments of a round once it is over, returns whether the
+		/// round is still in progress.
+		pub fn close_commit_reveal_round(
+			asset_id: T::AssetId,
+			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
+			reveal_period: T::BlockNumber,
+			block: T::BlockNumber,
+		) -> bool {
+			let commitments = PriceCommitments::<T>::get(asset_id);
+			match Self::commit_reveal_phase(&commitments, reveal_period, block) {
+				CommitRevealPhase::Closed => {
+					for commitment in commitments.iter().filter(|commitment| !commitment.revealed) {
+						Self::slash(&commitment.who, asset_id, asset_info.slash);
+						Self::remove_price_in_transit(&commitment.who, asset_info);
+					}
+					PriceCommitments::<T>::remove(asset_id);
+					false
+				},
+				_ => !commitments.is_empty(),
+			}
+		}
+
+		/// Freezes the price of `asset_id` if not already frozen, returns whether it was frozen.
+		pub fn freeze(asset_id: T::AssetId, reason: FreezeReason) -> bool {
+			if FrozenAssets::<T>::contains_key(asset_id) {
+				return false
+			}
+			FrozenAssets::<T>::insert(asset_id, reason);
+			UnfrozenAssets::<T>::remove(asset_id);
+			Self::deposit_event(Event::AssetFrozen(asset_id, reason));
+			true
+		}
+
 		pub fn is_requested(price_id: &T::AssetId) -> bool {
 			let last_update = Self::prices(price_id);
 			let current_block = frame_system::Pallet::<T>::block_number();
@@ -1181,6 +1867,178 @@ pub mod pallet {
 			}
 		}
 
+		/// Assets whose price is due for an update.
+		pub fn pending_requests() -> Vec<T::AssetId> {
+			AssetsInfo::<T>::iter_keys().filter(Self::is_requested).collect()
+		}
+
+		/// Last aggregated or derived price of `asset_id`, `None` if there is none yet or the asset
+		/// is frozen.
+		pub fn latest_price(asset_id: T::AssetId) -> Option<Price<T::PriceValue, T::BlockNumber>> {
+			Self::unit_price(asset_id, &mut Vec::new()).ok()
+		}
+
+		/// Price of a unit of `asset_id`, normalized to 12 decimals, resolving derived prices.
+		/// The block of a derived price is the oldest block of the prices it is derived from.
+		///
+		/// `resolving` holds the derived assets being resolved, to fail on cycles instead of
+		/// recursing forever.
+		fn unit_price(
+			asset_id: T::AssetId,
+			resolving: &mut Vec<T::AssetId>,
+		) -> Result<Price<T::PriceValue, T::BlockNumber>, DispatchError> {
+			ensure!(!FrozenAssets::<T>::contains_key(asset_id), Error::<T>::AssetFrozen);
+			let derivation = match DerivedPrices::<T>::get(asset_id) {
+				Some(derivation) => derivation,
+				None =>
+					return Prices::<T>::try_get(asset_id)
+						.map_err(|_| Error::<T>::PriceNotFound.into()),
+			};
+			ensure!(!resolving.contains(&asset_id), Error::<T>::DerivedPriceCycle);
+			resolving.push(asset_id);
+			let price = match derivation {
+				PriceDerivation::Ratio { numerator, denominator } => {
+					let numerator = Self::unit_price(numerator, resolving)?;
+					let denominator = Self::unit_price(denominator, resolving)?;
+					let price = safe_multiply_by_rational(
+						numerator.price.into(),
+						PRICE_UNIT,
+						denominator.price.into(),
+					)?;
+					Price { price: price.into(), block: numerator.block.min(denominator.block) }
+				},
+				PriceDerivation::ExchangeRate => {
+					let (underlying, rate) = T::ExchangeRates::exchange_rate(asset_id)
+						.ok_or(Error::<T>::InvalidDerivedPrice)?;
+					let Price { price, block } = Self::unit_price(underlying, resolving)?;
+					let price: u128 = price.into();
+					let price = rate.checked_mul_int(price).ok_or(ArithmeticError::Overflow)?;
+					Price { price: price.into(), block }
+				},
+				PriceDerivation::LpToken { pool_id } => {
+					let unit = T::LocalAssets::unit(asset_id)?;
+					let reserves = T::Amm::redeemable_assets_for_lp_tokens(pool_id, unit)?;
+					let mut value = T::PriceValue::zero();
+					let mut oldest = None;
+					for (reserve_asset, amount) in reserves {
+						let Price { price, block } = Self::unit_price(reserve_asset, resolving)?;
+						let reserve_value = Self::quote(reserve_asset, price, amount)?;
+						value =
+							value.checked_add(&reserve_value).ok_or(ArithmeticError::Overflow)?;
+						oldest =
+							Some(oldest.map_or(block, |oldest: T::BlockNumber| oldest.min(block)));
+					}
+					Price { price: value, block: oldest.ok_or(Error::<T>::InvalidDerivedPrice)? }
+				},
+			};
+			resolving.pop();
+			Ok(price)
+		}
+
+		/// Assets the price of `asset_id` is derived from with `derivation`.
+		fn derivation_dependencies(
+			asset_id: T::AssetId,
+			derivation: PriceDerivation<T::AssetId, T::PoolId>,
+		) -> Result<Vec<T::AssetId>, DispatchError> {
+			match derivation {
+				PriceDerivation::Ratio { numerator, denominator } =>
+					Ok(vec![numerator, denominator]),
+				PriceDerivation::ExchangeRate => {
+					let (underlying, _) = T::ExchangeRates::exchange_rate(asset_id)
+						.ok_or(Error::<T>::InvalidDerivedPrice)?;
+					Ok(vec![underlying])
+				},
+				PriceDerivation::LpToken { pool_id } => {
+					ensure!(
+						T::Amm::lp_token(pool_id) == Ok(asset_id),
+						Error::<T>::InvalidDerivedPrice
+					);
+					Ok(T::Amm::assets(pool_id)?.into_keys().collect())
+				},
+			}
+		}
+
+		/// Checks that deriving the price of `asset_id` with `derivation` does not make it depend
+		/// on itself, through the other derived prices.
+		fn ensure_acyclic_derivation(
+			asset_id: T::AssetId,
+			derivation: PriceDerivation<T::AssetId, T::PoolId>,
+		) -> DispatchResult {
+			let mut visited = BTreeSet::new();
+			let mut pending = Self::derivation_dependencies(asset_id, derivation)?;
+			while let Some(dependency) = pending.pop() {
+				ensure!(dependency != asset_id, Error::<T>::DerivedPriceCycle);
+				if !visited.insert(dependency) {
+					continue
+				}
+				if let Some(derivation) = DerivedPrices::<T>::get(dependency) {
+					pending.extend(Self::derivation_dependencies(dependency, derivation)?);
+				}
+			}
+			Ok(())
+		}
+
+		/// Up to `count` of the most recent prices of `asset_id` from the history, oldest first.
+		/// Empty while the asset is frozen.
+		pub fn latest_history(
+			asset_id: T::AssetId,
+			count: u32,
+		) -> Vec<Price<T::PriceValue, T::BlockNumber>> {
+			if FrozenAssets::<T>::contains_key(asset_id) {
+				return Vec::new()
+			}
+			let history = Self::price_history(asset_id);
+			let skip = history.len().saturating_sub(count as usize);
+			history.into_iter().skip(skip).collect()
+		}
+
+		/// Stake and rewards of the oracle signing with `signer`, `None` if it is unknown.
+		pub fn oracle_status(
+			signer: &T::AccountId,
+		) -> Option<OracleStatus<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+			let controller = SignerToController::<T>::get(signer);
+			let stake = OracleStake::<T>::get(signer);
+			let withdrawal = DeclaredWithdraws::<T>::get(signer);
+			if controller.is_none() && stake.is_none() && withdrawal.is_none() {
+				return None
+			}
+			Some(OracleStatus {
+				controller,
+				stake: stake.unwrap_or_else(Zero::zero),
+				answer_in_transit: AnswerInTransit::<T>::get(signer).unwrap_or_else(Zero::zero),
+				withdrawal: withdrawal.map(|withdraw| (withdraw.stake, withdraw.unlock_block)),
+				rewards: OracleRewards::<T>::get(signer),
+				reputation: Self::average_reputation_score(signer),
+			})
+		}
+
+		/// Average of the latest prices of `asset_id` weighted by `weights`, the first weight
+		/// applying to the current price and each following one to the previous price in the
+		/// history.
+		pub fn get_weighted_twap(
+			asset_id: T::AssetId,
+			weights: Vec<Percent>,
+		) -> Result<T::PriceValue, DispatchError> {
+			let weights_sum: u32 =
+				weights.iter().map(|weight| u32::from(weight.deconstruct())).sum();
+			ensure!(weights_sum == 100, Error::<T>::MustSumTo100);
+			ensure!(!FrozenAssets::<T>::contains_key(asset_id), Error::<T>::AssetFrozen);
+			// the history ends with the current price
+			let historical_prices = Self::price_history(asset_id);
+			ensure!(!historical_prices.is_empty(), Error::<T>::PriceNotFound);
+			ensure!(weights.len() <= historical_prices.len(), Error::<T>::DepthTooLarge);
+			let weighted_sum =
+				historical_prices
+					.iter()
+					.rev()
+					.zip(weights)
+					.fold(0_u128, |sum, (price, weight)| {
+						let price: u128 = price.price.into();
+						sum.saturating_add(price.saturating_mul(weight.deconstruct().into()))
+					});
+			Ok((weighted_sum / 100).into())
+		}
+
 		pub fn remove_price_in_transit(
 			who: &T::AccountId,
 			asset_info: &AssetInfo<Percent, T::BlockNumber, BalanceOf<T>>,
@@ -1194,6 +2052,7 @@ pub mod pallet {
 			asset_id: T::AssetId,
 			twap_window: usize,
 		) -> Result<T::PriceValue, DispatchError> {
+			ensure!(!FrozenAssets::<T>::contains_key(asset_id), Error::<T>::AssetFrozen);
 			let historical_prices = Self::price_history(asset_id);
 
 			ensure!(twap_window <= historical_prices.len() + 1, Error::<T>::DepthTooLarge);
@@ -1305,6 +2164,15 @@ pub mod pallet {
 
 					if let Some(account) = accounts.find(|x| x.id == address) {
 						log::info!("found account");
+						if let Some(reveal_period) = Self::reveal_period(price_id) {
+							Self::commit_or_reveal_price(
+								price_id,
+								&address,
+								account.public,
+								reveal_period,
+							)?;
+							continue
+						}
 						// Make an external HTTP request to fetch the current price.
 						// Note this call will block until response is received.
 						let price =
@@ -1323,7 +2191,7 @@ pub mod pallet {
 						for (acc, res) in &results {
 							match res {
 								Ok(()) => {
-									log::info!("[{:?}] Submitted price of {} cents", acc.id, price)
+									log::info!("[{:?}] Submitted price of {}", acc.id, price)
 								},
 								Err(e) => log::error!(
 									"[{:?}] Failed to submit transaction: {:?}",
@@ -1339,61 +2207,199 @@ pub mod pallet {
 			Ok(())
 		}
 
-		pub fn fetch_price(price_id: &T::AssetId) -> Result<u64, http::Error> {
+		/// Commits to a freshly fetched price during the commit phase of `price_id`, and reveals it
+		/// during the reveal phase.
+		fn commit_or_reveal_price(
+			price_id: &T::AssetId,
+			address: &T::AccountId,
+			public: T::Public,
+			reveal_period: T::BlockNumber,
+		) -> Result<(), &'static str> {
+			let commitments = PriceCommitments::<T>::get(price_id);
+			let block = frame_system::Pallet::<T>::block_number();
+			let committed = commitments.iter().find(|commitment| commitment.who == *address);
+			// the committed price and salt are kept locally until revealed
+			let key = (b"ocw-commitment", price_id, address).encode();
+			let storage = StorageValueRef::persistent(&key);
+			let call =
+				match (Self::commit_reveal_phase(&commitments, reveal_period, block), committed) {
+					(CommitRevealPhase::Commit, None) => {
+						let price =
+							Self::fetch_price(price_id).map_err(|_| "Failed to fetch price")?;
+						let salt = sp_io::offchain::random_seed();
+						storage.set(&(price, salt));
+						Call::commit_price {
+							asset_id: *price_id,
+							commitment: Self::price_commitment(address, price.into(), &salt),
+						}
+					},
+					(CommitRevealPhase::Reveal, Some(commitment)) if !commitment.revealed => {
+						let (price, salt) = storage
+							.get::<(u128, [u8; 32])>()
+							.ok()
+							.flatten()
+							.ok_or("Committed price not found")?;
+						Call::reveal_price { asset_id: *price_id, price: price.into(), salt }
+					},
+					_ => return Ok(()),
+				};
+			let results = Signer::<T, T::AuthorityId>::all_accounts()
+				.with_filter(vec![public])
+				.send_signed_transaction(|_account| call.clone());
+			for (acc, res) in &results {
+				match res {
+					Ok(()) => log::info!("[{:?}] Submitted {:?}", acc.id, call),
+					Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
+				}
+			}
+			Ok(())
+		}
+
+		/// Queries every configured price source and returns the median of the answers which
+		/// agree within the `threshold` of the asset, ignoring answers older than the max age.
+		pub fn fetch_price(price_id: &T::AssetId) -> Result<u128, http::Error> {
 			// We want to keep the offchain worker execution time reasonable, so we set a hard-coded
-			// deadline to 2s to complete the external call.
-			// You can also wait indefinitely for the response, however you may still get a timeout
-			// coming from the host machine.
+			// deadline to 2s to complete the external calls.
+			// You can also wait indefinitely for the responses, however you may still get a
+			// timeout coming from the host machine.
 			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(2_000));
 
-			// Check if the node has another endpoint to call if not fall back to localhost:3001
-			// Then build the endpoint
-			let kind = sp_core::offchain::StorageKind::PERSISTENT;
-			let from_local = sp_io::offchain::local_storage_get(kind, b"ocw-url")
-				.unwrap_or_else(|| b"http://localhost:3001/price/".to_vec());
-			let base = str::from_utf8(&from_local).unwrap_or("http://localhost:3001/price/");
 			let string_id =
 				serde_json::to_string(&(*price_id).into()).map_err(|_| http::Error::IoError)?;
-			let url = base.to_owned() + &string_id;
+			let asset_info = Self::asset_info(price_id);
+			let price_exponent = asset_info.as_ref().map_or(0, |info| info.price_exponent);
+
+			// Send all the requests before waiting for any, so that they share the deadline.
+			let mut queried = Vec::new();
+			let mut pending = Vec::new();
+			for source in sources::configured_sources() {
+				let url = source.url.clone() + &string_id;
+				let request = http::Request::get(&url);
+				log::info!("request incoming {:#?}", request);
+				match request.deadline(deadline).send() {
+					Ok(request) => {
+						queried.push(source);
+						pending.push(request);
+					},
+					Err(_) => {
+						log::warn!("Failed to send request to {}", url);
+						sources::record_outcome(&source.url, SourceOutcome::Failure);
+					},
+				}
+			}
 
-			// Initiate an external HTTP GET request.
-			let request = http::Request::get(&url);
+			let now = sp_io::offchain::timestamp().unix_millis() / 1_000;
+			let max_age = sources::configured_max_age();
+			let mut answered = Vec::new();
+			let mut answers = Vec::new();
+			for (source, response) in
+				queried.into_iter().zip(http::PendingRequest::try_wait_all(pending, deadline))
+			{
+				match Self::read_price_response(response, &string_id, price_exponent) {
+					Some((price, timestamp)) if sources::is_fresh(timestamp, now, max_age) => {
+						answers.push((price, source.weight));
+						answered.push(source);
+					},
+					Some(_) => {
+						log::warn!("Discarded price older than {}s from {}", max_age, source.url);
+						sources::record_outcome(&source.url, SourceOutcome::Failure);
+					},
+					None => sources::record_outcome(&source.url, SourceOutcome::Failure),
+				}
+			}
 
-			log::info!("request incoming {:#?}", request);
+			let (price, kept) = sources::aggregate(&answers, asset_info.map(|info| info.threshold))
+				.ok_or_else(|| {
+					log::warn!("No agreeing price among {} answers", answers.len());
+					http::Error::Unknown
+				})?;
+			for (source, kept) in answered.iter().zip(kept) {
+				if kept {
+					sources::record_outcome(&source.url, SourceOutcome::Answer);
+				} else {
+					log::warn!("Discarded outlier price from {}", source.url);
+					sources::record_outcome(&source.url, SourceOutcome::Outlier);
+				}
+			}
 
-			// set the deadline for sending of the request
-			let pending = request.deadline(deadline).send().map_err(|_| http::Error::IoError)?;
+			log::info!("Got price: {}", price);
 
-			let response =
-				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+			Ok(price)
+		}
+
+		fn read_price_response(
+			response: Result<http::HttpResult, http::PendingRequest>,
+			string_id: &str,
+			price_exponent: u8,
+		) -> Option<(u128, Option<u64>)> {
+			let response = match response {
+				Ok(Ok(response)) => response,
+				Ok(Err(error)) => {
+					log::warn!("Price request failed: {:?}", error);
+					return None
+				},
+				Err(_) => {
+					log::warn!("Price request deadline reached");
+					return None
+				},
+			};
 			// Let's check the status code before we proceed to reading the response.
 			if response.code != 200 {
 				log::warn!("Unexpected status code: {}", response.code);
-				return Err(http::Error::Unknown)
+				return None
 			}
 
 			let body = response.body().collect::<Vec<u8>>();
 
 			// Create a str slice from the body.
-			let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-				log::warn!("No UTF8 body");
-				http::Error::Unknown
-			})?;
-
-			let price = match Self::parse_price(body_str, &string_id) {
-				Some(price) => Ok(price),
-				None => {
-					log::warn!("Unable to extract price from the response: {:?}", body_str);
-					Err(http::Error::Unknown)
-				},
-			}?;
+			let body_str =
+				sp_std::str::from_utf8(&body).map_err(|_| log::warn!("No UTF8 body")).ok()?;
 
-			log::warn!("Got price: {} cents", price);
+			let price = Self::parse_price(body_str, string_id, price_exponent);
+			if price.is_none() {
+				log::warn!("Unable to extract price from the response: {:?}", body_str);
+			}
+			Some((price?, Self::parse_timestamp(body_str)))
+		}
 
-			Ok(price)
+		/// Reads the UNIX timestamp, in seconds, of a price from the `timestamp` of a JSON
+		/// object, or else from the oldest `timestamp` of the objects listed under its `sources`.
+		/// `None` when the answer is not timestamped.
+		pub fn parse_timestamp(price_str: &str) -> Option<u64> {
+			fn field<'a>(obj: &'a [(Vec<char>, JsonValue)], name: &str) -> Option<&'a JsonValue> {
+				obj.iter().find(|(k, _)| k.iter().copied().eq(name.chars())).map(|(_, v)| v)
+			}
+			fn timestamp(value: &JsonValue) -> Option<u64> {
+				match value {
+					JsonValue::Number(number) if !number.negative =>
+						u64::try_from(number.integer).ok(),
+					_ => None,
+				}
+			}
+			let obj = match lite_json::parse_json(price_str).ok()? {
+				JsonValue::Object(obj) => obj,
+				_ => return None,
+			};
+			if let Some(value) = field(&obj, "timestamp") {
+				return timestamp(value)
+			}
+			match field(&obj, "sources")? {
+				JsonValue::Array(sources) => sources
+					.iter()
+					.filter_map(|source| match source {
+						JsonValue::Object(source) => timestamp(field(source, "timestamp")?),
+						_ => None,
+					})
+					.min(),
+				_ => None,
+			}
 		}
 
-		pub fn parse_price(price_str: &str, asset_id: &str) -> Option<u64> {
+		/// Reads the price of `asset_id` from a `{"<asset_id>": <number>}` JSON object, as a
+		/// fixed-point value with `price_exponent` decimals. Extra decimals are rounded half up,
+		/// except at the default `price_exponent` of 0 where they are truncated as they always
+		/// were. Negative and overflowing prices are rejected.
+		pub fn parse_price(price_str: &str, asset_id: &str, price_exponent: u8) -> Option<u128> {
 			let val = lite_json::parse_json(price_str);
 			let price = match val.ok()? {
 				JsonValue::Object(obj) => {
@@ -1406,7 +2412,33 @@ pub mod pallet {
 				},
 				_ => return None,
 			};
-			Some(price.integer as u64)
+			// the sign is carried by `negative` only, `integer` of -0.5 is 0
+			if price.negative {
+				return None
+			}
+			let integer = u128::try_from(i128::from(price.integer)).ok()?;
+			let digits = integer
+				.checked_mul(10_u128.checked_pow(price.fraction_length)?)?
+				.checked_add(u128::from(price.fraction))?;
+			// `digits` is the price with `fraction_length` decimals, shift it to `price_exponent`
+			let shift = i64::from(price_exponent) + i64::from(price.exponent) -
+				i64::from(price.fraction_length);
+			if shift >= 0 {
+				digits.checked_mul(10_u128.checked_pow(u32::try_from(shift).ok()?)?)
+			} else {
+				match u32::try_from(-shift).ok().and_then(|shift| 10_u128.checked_pow(shift)) {
+					Some(divisor) => {
+						let (quotient, remainder) = (digits / divisor, digits % divisor);
+						Some(if price_exponent != 0 && remainder.saturating_mul(2) >= divisor {
+							quotient + 1
+						} else {
+							quotient
+						})
+					},
+					// `digits` is lower than `10^39 / 2`, so rounds to 0
+					None => Some(0),
+				}
+			}
 		}
 
 		/// The AccountId of this pallet.
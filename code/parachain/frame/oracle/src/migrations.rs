@@ -0,0 +1,145 @@
+use crate::{AssetInfo, AssetsInfo, Config, Pallet, Reputation};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::GetStorageVersion,
+	traits::{Get, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::Percent;
+
+/// `AssetInfo` before `price_exponent` was added.
+#[derive(Encode, Decode)]
+struct AssetInfoV0<Percent, BlockNumber, Balance> {
+	threshold: Percent,
+	min_answers: u32,
+	max_answers: u32,
+	block_interval: BlockNumber,
+	reward_weight: Balance,
+	slash: Balance,
+	emit_price_changes: bool,
+}
+
+/// `AssetInfo` before the circuit breaker parameters were added.
+#[derive(Encode, Decode)]
+pub struct AssetInfoV1<Percent, BlockNumber, Balance> {
+	threshold: Percent,
+	min_answers: u32,
+	max_answers: u32,
+	block_interval: BlockNumber,
+	reward_weight: Balance,
+	slash: Balance,
+	emit_price_changes: bool,
+	price_exponent: u8,
+}
+
+mod v2 {
+	use super::*;
+	use frame_support::{storage_alias, Blake2_128Concat};
+
+	#[storage_alias]
+	pub type AggregationRounds<T: Config> = StorageValue<Pallet<T>, u32>;
+
+	#[storage_alias]
+	pub type Reputations<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::AccountId, Reputation>;
+}
+
+mod v1 {
+	use super::*;
+	use frame_support::{storage_alias, Blake2_128Concat};
+
+	#[storage_alias]
+	pub type AssetsInfo<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as Config>::AssetId,
+		AssetInfoV1<Percent, <T as frame_system::Config>::BlockNumber, <T as Config>::Balance>,
+	>;
+}
+
+/// Adds `price_exponent` to the info of existing assets, defaulting to 0 which keeps fetching
+/// integer prices.
+pub struct OracleV0ToV1<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for OracleV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let current = Pallet::<T>::on_chain_storage_version();
+		let new = StorageVersion::new(1);
+		if current < new {
+			let mut total = 0_u64;
+			v1::AssetsInfo::<T>::translate::<AssetInfoV0<Percent, T::BlockNumber, T::Balance>, _>(
+				|_asset_id, asset_info| {
+					total += 1;
+					Some(AssetInfoV1 {
+						threshold: asset_info.threshold,
+						min_answers: asset_info.min_answers,
+						max_answers: asset_info.max_answers,
+						block_interval: asset_info.block_interval,
+						reward_weight: asset_info.reward_weight,
+						slash: asset_info.slash,
+						emit_price_changes: asset_info.emit_price_changes,
+						price_exponent: 0,
+					})
+				},
+			);
+			new.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(total + 1, total + 1)
+		} else {
+			Weight::zero()
+		}
+	}
+}
+
+/// Adds the circuit breaker parameters to the info of existing assets, disabled by default.
+pub struct OracleV1ToV2<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for OracleV1ToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let current = Pallet::<T>::on_chain_storage_version();
+		let new = StorageVersion::new(2);
+		if current == StorageVersion::new(1) {
+			let mut total = 0_u64;
+			AssetsInfo::<T>::translate::<AssetInfoV1<Percent, T::BlockNumber, T::Balance>, _>(
+				|_asset_id, asset_info| {
+					total += 1;
+					Some(AssetInfo {
+						threshold: asset_info.threshold,
+						min_answers: asset_info.min_answers,
+						max_answers: asset_info.max_answers,
+						block_interval: asset_info.block_interval,
+						reward_weight: asset_info.reward_weight,
+						slash: asset_info.slash,
+						emit_price_changes: asset_info.emit_price_changes,
+						price_exponent: asset_info.price_exponent,
+						max_staleness: None,
+						max_deviation: None,
+					})
+				},
+			);
+			new.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(total + 1, total + 1)
+		} else {
+			Weight::zero()
+		}
+	}
+}
+
+/// Drops the rounds counted across all assets and the reputations computed from them, which
+/// cannot be attributed to single assets. Rounds and reputations are counted per asset from
+/// then on.
+pub struct OracleV2ToV3<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for OracleV2ToV3<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let current = Pallet::<T>::on_chain_storage_version();
+		let new = StorageVersion::new(3);
+		if current == StorageVersion::new(2) {
+			v2::AggregationRounds::<T>::kill();
+			let removed = v2::Reputations::<T>::clear(u32::MAX, None).unique;
+			new.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(u64::from(removed) + 1, u64::from(removed) + 2)
+		} else {
+			Weight::zero()
+		}
+	}
+}
@@ -1,6 +1,11 @@
 use crate as pallet_oracle;
 use crate::*;
 
+use composable_traits::{
+	defi::Ratio,
+	dex::{Amm, AssetAmount, SwapResult},
+	oracle::ExchangeRateProvider,
+};
 use frame_support::{
 	ord_parameter_types,
 	pallet_prelude::ConstU32,
@@ -14,7 +19,9 @@ use sp_core::{sr25519, sr25519::Signature, ConstU128, H256};
 use sp_runtime::{
 	testing::{Header, TestXt},
 	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
+	DispatchError, FixedPointNumber, Permill,
 };
+use std::collections::BTreeMap;
 use system::EnsureRoot;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -157,12 +164,144 @@ where
 
 pub type AssetId = u128;
 pub type PriceValue = u128;
+pub type PoolId = u128;
+
+/// Liquid staking voucher of asset `0`, worth 1.5 of it.
+pub const LIQUID_ASSET: AssetId = 10;
+/// Pool of assets `0` and `1`, a unit of its LP token redeems for 2 units of `0` and 3 of `1`.
+pub const POOL: PoolId = 1;
+pub const POOL_LP_TOKEN: AssetId = 100;
+/// Pool of `POOL_LP_TOKEN` and asset `0`, a unit of its LP token redeems for a unit of each.
+pub const LP_POOL: PoolId = 2;
+pub const LP_POOL_LP_TOKEN: AssetId = 101;
+
+pub struct MockExchangeRates;
+
+impl ExchangeRateProvider<AssetId> for MockExchangeRates {
+	fn exchange_rate(derivative: AssetId) -> Option<(AssetId, Ratio)> {
+		(derivative == LIQUID_ASSET).then(|| (0, Ratio::saturating_from_rational(3, 2)))
+	}
+}
+
+pub struct MockAmm;
+
+impl MockAmm {
+	fn reserves_per_lp_token(pool_id: PoolId) -> Result<Vec<(AssetId, Balance)>, DispatchError> {
+		match pool_id {
+			POOL => Ok(vec![(0, 2), (1, 3)]),
+			LP_POOL => Ok(vec![(POOL_LP_TOKEN, 1), (0, 1)]),
+			_ => Err(DispatchError::Other("pool not found")),
+		}
+	}
+}
+
+impl Amm for MockAmm {
+	type AssetId = AssetId;
+	type Balance = Balance;
+	type AccountId = AccountId;
+	type PoolId = PoolId;
+
+	fn pool_exists(pool_id: PoolId) -> bool {
+		Self::reserves_per_lp_token(pool_id).is_ok()
+	}
+
+	fn assets(pool_id: PoolId) -> Result<BTreeMap<AssetId, Permill>, DispatchError> {
+		Ok(Self::reserves_per_lp_token(pool_id)?
+			.into_iter()
+			.map(|(asset_id, _)| (asset_id, Permill::from_percent(50)))
+			.collect())
+	}
+
+	fn lp_token(pool_id: PoolId) -> Result<AssetId, DispatchError> {
+		match pool_id {
+			POOL => Ok(POOL_LP_TOKEN),
+			LP_POOL => Ok(LP_POOL_LP_TOKEN),
+			_ => Err(DispatchError::Other("pool not found")),
+		}
+	}
+
+	fn redeemable_assets_for_lp_tokens(
+		pool_id: PoolId,
+		lp_amount: Balance,
+	) -> Result<BTreeMap<AssetId, Balance>, DispatchError> {
+		Ok(Self::reserves_per_lp_token(pool_id)?
+			.into_iter()
+			.map(|(asset_id, amount)| (asset_id, amount * lp_amount))
+			.collect())
+	}
+
+	fn simulate_add_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_amounts: BTreeMap<AssetId, Balance>,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn simulate_remove_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_lp_amount: Balance,
+		_min_amounts: BTreeMap<AssetId, Balance>,
+	) -> Result<BTreeMap<AssetId, Balance>, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn spot_price(
+		_pool_id: PoolId,
+		_base_asset: AssetAmount<AssetId, Balance>,
+		_quote_asset_id: AssetId,
+		_calculate_with_fees: bool,
+	) -> Result<SwapResult<AssetId, Balance>, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn add_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_assets: BTreeMap<AssetId, Balance>,
+		_min_mint_amount: Balance,
+		_keep_alive: bool,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn remove_liquidity(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_lp_amount: Balance,
+		_min_receive: BTreeMap<AssetId, Balance>,
+	) -> Result<BTreeMap<AssetId, Balance>, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn do_buy(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_in_asset_id: AssetId,
+		_out_asset: AssetAmount<AssetId, Balance>,
+		_keep_alive: bool,
+	) -> Result<SwapResult<AssetId, Balance>, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn do_swap(
+		_who: &AccountId,
+		_pool_id: PoolId,
+		_in_asset: AssetAmount<AssetId, Balance>,
+		_min_receive: AssetAmount<AssetId, Balance>,
+		_keep_alive: bool,
+	) -> Result<SwapResult<AssetId, Balance>, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+}
 
 parameter_types! {
 	pub const TreasuryAccountId : AccountId= sr25519::Public([10u8; 32]);
 	// cspell:disable-next
 	pub const OraclePalletId: PalletId = PalletId(*b"plt_orac");
 	pub const MsPerBlock: u64 = MILLISECS_PER_BLOCK;
+	pub const ReputationSmoothing: Permill = Permill::from_percent(50);
 }
 
 impl pallet_oracle::Config for Test {
@@ -196,7 +335,11 @@ impl pallet_oracle::Config for Test {
 	type RewardOrigin = EnsureRoot<AccountId>;
 	type PalletId = OraclePalletId;
 	type MsPerBlock = MsPerBlock;
+	type ReputationSmoothing = ReputationSmoothing;
 	type Balance = Balance;
+	type PoolId = PoolId;
+	type Amm = MockAmm;
+	type ExchangeRates = MockExchangeRates;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -0,0 +1,175 @@
+//! Price sources queried by the offchain worker and aggregation of their answers.
+use codec::{Decode, Encode};
+use composable_traits::prelude::String;
+use sp_core::offchain::StorageKind;
+use sp_runtime::{
+	offchain::storage::{StorageRetrievalError, StorageValueRef},
+	Percent,
+};
+use sp_std::{borrow::ToOwned, str, vec, vec::Vec};
+
+/// Offchain local storage key of the list of price endpoints, separated by commas or new lines.
+/// Each endpoint may be followed by a whitespace separated weight (1 by default), e.g.
+/// `http://localhost:3001/price/ 2,https://feed.example/price/`.
+pub const SOURCES_KEY: &[u8] = b"ocw-urls";
+/// Offchain local storage key of the single endpoint used when no list is configured.
+pub const SOURCE_KEY: &[u8] = b"ocw-url";
+pub const DEFAULT_SOURCE: &str = "http://localhost:3001/price/";
+/// Prefix of the offchain local storage keys of the [`SourceMetrics`] of each endpoint.
+pub const METRICS_KEY_PREFIX: &[u8] = b"ocw-source-metrics/";
+/// Offchain local storage key of the max age, in seconds, of the timestamped answers.
+pub const MAX_AGE_KEY: &[u8] = b"ocw-max-age";
+pub const DEFAULT_MAX_AGE: u64 = 60;
+
+/// An endpoint answering `{"<asset_id>": <price>}` under `<url><asset_id>`, optionally along with
+/// the UNIX timestamp, in seconds, of the price under `timestamp` or of each of the prices it was
+/// merged from under `sources`, as the price-feed does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriceSource {
+	pub url: String,
+	/// Weight of the answers of this source in the median.
+	pub weight: u32,
+}
+
+/// Outcomes of the requests made to a source, kept in offchain local storage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub struct SourceMetrics {
+	/// Answers used to compute the submitted price.
+	pub answers: u32,
+	/// Requests which failed, timed out or were answered with something else than a price, or
+	/// with a price older than the max age.
+	pub failures: u32,
+	/// Answers discarded for deviating too much from the other sources.
+	pub outliers: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceOutcome {
+	Answer,
+	Failure,
+	Outlier,
+}
+
+/// Sources configured under [`SOURCES_KEY`], falling back to [`SOURCE_KEY`] and then to
+/// [`DEFAULT_SOURCE`].
+pub fn configured_sources() -> Vec<PriceSource> {
+	let kind = StorageKind::PERSISTENT;
+	if let Some(list) = sp_io::offchain::local_storage_get(kind, SOURCES_KEY) {
+		let sources = parse_sources(&list);
+		if !sources.is_empty() {
+			return sources
+		}
+		log::warn!("No valid price source in `ocw-urls`, falling back to `ocw-url`");
+	}
+	let from_local = sp_io::offchain::local_storage_get(kind, SOURCE_KEY).unwrap_or_default();
+	let url = str::from_utf8(&from_local)
+		.ok()
+		.filter(|url| !url.is_empty())
+		.unwrap_or(DEFAULT_SOURCE);
+	vec![PriceSource { url: url.to_owned(), weight: 1 }]
+}
+
+/// Max age configured under [`MAX_AGE_KEY`] as a decimal number of seconds, [`DEFAULT_MAX_AGE`]
+/// when missing or malformed.
+pub fn configured_max_age() -> u64 {
+	sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, MAX_AGE_KEY)
+		.and_then(|max_age| str::from_utf8(&max_age).ok()?.trim().parse().ok())
+		.unwrap_or(DEFAULT_MAX_AGE)
+}
+
+/// Whether an answer timestamped at `timestamp` is at most `max_age` seconds old at `now`.
+/// Answers without a timestamp cannot be told apart and are always fresh.
+pub fn is_fresh(timestamp: Option<u64>, now: u64, max_age: u64) -> bool {
+	timestamp.map_or(true, |timestamp| now.saturating_sub(timestamp) <= max_age)
+}
+
+/// Parses a list of sources in the [`SOURCES_KEY`] format, skipping malformed entries.
+pub fn parse_sources(list: &[u8]) -> Vec<PriceSource> {
+	let list = match str::from_utf8(list) {
+		Ok(list) => list,
+		Err(_) => return Vec::new(),
+	};
+	list.split(|c| c == ',' || c == '\n')
+		.filter_map(|entry| {
+			let mut parts = entry.split_whitespace();
+			let url = parts.next()?;
+			let weight = match parts.next() {
+				Some(weight) => weight.parse().ok().filter(|weight| *weight > 0)?,
+				None => 1,
+			};
+			if parts.next().is_some() {
+				return None
+			}
+			Some(PriceSource { url: url.to_owned(), weight })
+		})
+		.collect()
+}
+
+fn metrics_key(url: &str) -> Vec<u8> {
+	[METRICS_KEY_PREFIX, url.as_bytes()].concat()
+}
+
+pub fn source_metrics(url: &str) -> SourceMetrics {
+	StorageValueRef::persistent(&metrics_key(url))
+		.get()
+		.ok()
+		.flatten()
+		.unwrap_or_default()
+}
+
+pub fn record_outcome(url: &str, outcome: SourceOutcome) {
+	let key = metrics_key(url);
+	let result = StorageValueRef::persistent(&key).mutate(
+		|metrics: Result<Option<SourceMetrics>, StorageRetrievalError>| -> Result<_, ()> {
+			let mut metrics = metrics.ok().flatten().unwrap_or_default();
+			let counter = match outcome {
+				SourceOutcome::Answer => &mut metrics.answers,
+				SourceOutcome::Failure => &mut metrics.failures,
+				SourceOutcome::Outlier => &mut metrics.outliers,
+			};
+			*counter = counter.saturating_add(1);
+			Ok(metrics)
+		},
+	);
+	if result.is_err() {
+		log::warn!("Failed to record the metrics of price source {}", url);
+	}
+}
+
+/// Median of `(price, weight)` answers, the average of the two middle prices when the weights
+/// split evenly between them.
+pub fn weighted_median(answers: &[(u128, u32)]) -> Option<u128> {
+	let mut answers = answers.iter().filter(|(_, weight)| *weight > 0).collect::<Vec<_>>();
+	answers.sort_unstable_by_key(|(price, _)| *price);
+	let total: u64 = answers.iter().map(|(_, weight)| u64::from(*weight)).sum();
+	let mut cumulated = 0_u64;
+	let mut answers = answers.into_iter().peekable();
+	while let Some((price, weight)) = answers.next() {
+		cumulated += u64::from(*weight);
+		if cumulated * 2 == total {
+			let (next, _) = answers.peek()?;
+			return Some(price.saturating_add(*next) / 2)
+		}
+		if cumulated * 2 > total {
+			return Some(*price)
+		}
+	}
+	None
+}
+
+/// Weighted median of the answers deviating from the weighted median of all answers by at most
+/// `threshold`, along with which answers were kept. Without a threshold all answers are kept.
+pub fn aggregate(answers: &[(u128, u32)], threshold: Option<Percent>) -> Option<(u128, Vec<bool>)> {
+	let median = weighted_median(answers)?;
+	let max_deviation = threshold.map_or(u128::MAX, |threshold| threshold.mul_floor(median));
+	let kept = answers
+		.iter()
+		.map(|(price, _)| price.abs_diff(median) <= max_deviation)
+		.collect::<Vec<_>>();
+	let agreeing = answers
+		.iter()
+		.zip(&kept)
+		.filter_map(|(answer, kept)| kept.then_some(*answer))
+		.collect::<Vec<_>>();
+	Some((weighted_median(&agreeing)?, kept))
+}
@@ -18,7 +18,7 @@ use sp_io::TestExternalities;
 use sp_keystore::KeystoreExt;
 use sp_runtime::{
 	traits::{BadOrigin, Zero},
-	FixedPointNumber, FixedU128, Percent, RuntimeAppPublic, TokenError,
+	FixedPointNumber, FixedU128, Percent, Permill, RuntimeAppPublic, TokenError,
 };
 use std::sync::Arc;
 
@@ -61,6 +61,9 @@ prop_compose! {
 				reward_weight: reward,
 				slash,
 				emit_price_changes: false,
+				price_exponent: 0,
+				max_staleness: None,
+				max_deviation: None,
 			}
 		}
 }
@@ -87,6 +90,1043 @@ prop_compose! {
 		}
 }
 
+mod set_price_exponent {
+	use super::*;
+
+	#[test]
+	fn should_set_price_exponent_of_existing_asset() {
+		new_test_ext().execute_with(|| {
+			// events are not deposited at genesis
+			System::set_block_number(1);
+			let root_account = get_root_account();
+			assert_ok!(Oracle::add_asset_and_info(
+				RuntimeOrigin::signed(root_account),
+				0,
+				Validated::new(Percent::from_percent(80)).unwrap(),
+				Validated::new(3).unwrap(),
+				Validated::new(5).unwrap(),
+				Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+				5,
+				5,
+				false,
+			));
+			assert_eq!(Oracle::asset_info(0).unwrap().price_exponent, 0);
+
+			assert_ok!(Oracle::set_price_exponent(RuntimeOrigin::signed(root_account), 0, 12));
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::PriceExponentChanged(
+				0, 12,
+			)));
+			assert_eq!(Oracle::asset_info(0).unwrap().price_exponent, 12);
+
+			// updating the asset info keeps the exponent
+			assert_ok!(Oracle::add_asset_and_info(
+				RuntimeOrigin::signed(root_account),
+				0,
+				Validated::new(Percent::from_percent(90)).unwrap(),
+				Validated::new(3).unwrap(),
+				Validated::new(5).unwrap(),
+				Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+				5,
+				5,
+				false,
+			));
+			assert_eq!(Oracle::asset_info(0).unwrap().price_exponent, 12);
+		});
+	}
+
+	#[test]
+	fn should_not_set_invalid_price_exponent() {
+		new_test_ext().execute_with(|| {
+			let root_account = get_root_account();
+			assert_noop!(
+				Oracle::set_price_exponent(RuntimeOrigin::signed(get_account_1()), 0, 12),
+				BadOrigin
+			);
+			assert_noop!(
+				Oracle::set_price_exponent(RuntimeOrigin::signed(root_account), 0, 12),
+				Error::<Test>::InvalidAssetId
+			);
+			assert_ok!(Oracle::add_asset_and_info(
+				RuntimeOrigin::signed(root_account),
+				0,
+				Validated::new(Percent::from_percent(80)).unwrap(),
+				Validated::new(3).unwrap(),
+				Validated::new(5).unwrap(),
+				Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+				5,
+				5,
+				false,
+			));
+			assert_noop!(
+				Oracle::set_price_exponent(
+					RuntimeOrigin::signed(root_account),
+					0,
+					MAX_PRICE_EXPONENT + 1
+				),
+				Error::<Test>::InvalidPriceExponent
+			);
+		});
+	}
+}
+
+mod circuit_breaker {
+	use super::*;
+	use crate::FreezeReason;
+	use composable_traits::oracle::Oracle as _;
+
+	fn add_asset() {
+		assert_ok!(Oracle::add_asset_and_info(
+			RuntimeOrigin::signed(get_root_account()),
+			0,
+			Validated::new(Percent::from_percent(80)).unwrap(),
+			Validated::new(3).unwrap(),
+			Validated::new(5).unwrap(),
+			Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+			5,
+			5,
+			false,
+		));
+	}
+
+	#[test]
+	fn should_set_circuit_breaker() {
+		new_test_ext().execute_with(|| {
+			// events are not deposited at genesis
+			System::set_block_number(1);
+			let root_account = get_root_account();
+			assert_noop!(
+				Oracle::set_circuit_breaker(RuntimeOrigin::signed(get_account_1()), 0, None, None),
+				BadOrigin
+			);
+			assert_noop!(
+				Oracle::set_circuit_breaker(RuntimeOrigin::signed(root_account), 0, None, None),
+				Error::<Test>::InvalidAssetId
+			);
+			add_asset();
+
+			let max_deviation = Some(Percent::from_percent(10));
+			assert_ok!(Oracle::set_circuit_breaker(
+				RuntimeOrigin::signed(root_account),
+				0,
+				Some(10),
+				max_deviation
+			));
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::CircuitBreakerChanged(
+				0,
+				Some(10),
+				max_deviation,
+			)));
+
+			// updating the asset info keeps the circuit breaker
+			add_asset();
+			let asset_info = Oracle::asset_info(0).unwrap();
+			assert_eq!(asset_info.max_staleness, Some(10));
+			assert_eq!(asset_info.max_deviation, max_deviation);
+		});
+	}
+
+	#[test]
+	fn should_freeze_on_price_deviation() {
+		new_test_ext().execute_with(|| {
+			let root_account = get_root_account();
+			add_asset();
+			assert_ok!(Oracle::set_circuit_breaker(
+				RuntimeOrigin::signed(root_account),
+				0,
+				None,
+				Some(Percent::from_percent(10))
+			));
+
+			// 101 -> 110 is within the deviation
+			do_price_update(0, 6);
+			for price in [109, 110, 111] {
+				add_price_storage(price, 0, get_account_1(), 12);
+			}
+			System::set_block_number(12);
+			Oracle::on_initialize(12);
+			assert_eq!(Oracle::frozen_assets(0), None);
+			assert_eq!(Oracle::get_price(0, 1).unwrap().price, 110);
+
+			// 110 -> 201 is not
+			let answer_in_transit = Oracle::answer_in_transit(get_account_1());
+			for price in [200, 201, 202] {
+				add_price_storage(price, 0, get_account_1(), 18);
+			}
+			System::set_block_number(18);
+			Oracle::on_initialize(18);
+			System::assert_has_event(RuntimeEvent::Oracle(crate::Event::AssetFrozen(
+				0,
+				FreezeReason::Deviation,
+			)));
+			assert_eq!(Oracle::frozen_assets(0), Some(FreezeReason::Deviation));
+			// the price breaching the circuit breaker is dropped
+			assert_eq!(Oracle::prices(0).price, 110);
+			assert_eq!(Oracle::price_history(0).last().map(|price| price.price), Some(110));
+			assert!(Oracle::pre_prices(0).is_empty());
+			assert_eq!(Oracle::answer_in_transit(get_account_1()), answer_in_transit);
+			assert_noop!(Oracle::get_price(0, 1), Error::<Test>::AssetFrozen);
+			assert_noop!(Oracle::get_twap_for_amount(0, 1), Error::<Test>::AssetFrozen);
+			assert_noop!(Oracle::get_price_inverse(0, 1), Error::<Test>::AssetFrozen);
+			assert_noop!(Oracle::get_twap(0, 1), Error::<Test>::AssetFrozen);
+			assert_eq!(Oracle::latest_price(0), None);
+			assert!(Oracle::latest_history(0, 10).is_empty());
+
+			assert_noop!(
+				Oracle::unfreeze_asset(RuntimeOrigin::signed(get_account_1()), 0),
+				BadOrigin
+			);
+			assert_ok!(Oracle::unfreeze_asset(RuntimeOrigin::signed(root_account), 0));
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::AssetUnfrozen(0)));
+			assert_eq!(Oracle::unfrozen_at(0), Some(18));
+			assert_eq!(Oracle::get_price(0, 1).unwrap().price, 110);
+			assert_noop!(
+				Oracle::unfreeze_asset(RuntimeOrigin::signed(root_account), 0),
+				Error::<Test>::AssetNotFrozen
+			);
+
+			// the next price is accepted however far it is from 110, and becomes the reference
+			for price in [200, 201, 202] {
+				add_price_storage(price, 0, get_account_1(), 24);
+			}
+			System::set_block_number(24);
+			Oracle::on_initialize(24);
+			assert_eq!(Oracle::frozen_assets(0), None);
+			assert_eq!(Oracle::unfrozen_at(0), None);
+			assert_eq!(Oracle::get_price(0, 1).unwrap().price, 201);
+
+			// 201 -> 215 is within the deviation again, 201 -> 110 is not
+			for price in [214, 215, 216] {
+				add_price_storage(price, 0, get_account_1(), 30);
+			}
+			System::set_block_number(30);
+			Oracle::on_initialize(30);
+			assert_eq!(Oracle::get_price(0, 1).unwrap().price, 215);
+			for price in [109, 110, 111] {
+				add_price_storage(price, 0, get_account_1(), 36);
+			}
+			System::set_block_number(36);
+			Oracle::on_initialize(36);
+			assert_eq!(Oracle::frozen_assets(0), Some(FreezeReason::Deviation));
+			assert_eq!(Oracle::prices(0).price, 215);
+		});
+	}
+
+	#[test]
+	fn should_freeze_stale_price() {
+		new_test_ext().execute_with(|| {
+			add_asset();
+			assert_ok!(Oracle::set_circuit_breaker(
+				RuntimeOrigin::signed(get_root_account()),
+				0,
+				Some(10),
+				None
+			));
+
+			// no price to be stale yet
+			System::set_block_number(5);
+			Oracle::on_initialize(5);
+			assert_eq!(Oracle::frozen_assets(0), None);
+
+			do_price_update(0, 6);
+			System::set_block_number(16);
+			Oracle::on_initialize(16);
+			assert_eq!(Oracle::frozen_assets(0), None);
+			assert_ok!(Oracle::get_price(0, 1));
+
+			System::set_block_number(17);
+			Oracle::on_initialize(17);
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::AssetFrozen(
+				0,
+				FreezeReason::Stale,
+			)));
+			assert_noop!(Oracle::get_price(0, 1), Error::<Test>::AssetFrozen);
+
+			// the staleness is counted from the unfreeze until a new price is aggregated
+			System::set_block_number(20);
+			assert_ok!(Oracle::unfreeze_asset(RuntimeOrigin::signed(get_root_account()), 0));
+			System::set_block_number(30);
+			Oracle::on_initialize(30);
+			assert_eq!(Oracle::frozen_assets(0), None);
+			assert_ok!(Oracle::get_price(0, 1));
+
+			System::set_block_number(31);
+			Oracle::on_initialize(31);
+			assert_eq!(Oracle::frozen_assets(0), Some(FreezeReason::Stale));
+			assert_eq!(Oracle::unfrozen_at(0), None);
+		});
+	}
+}
+
+mod commit_reveal {
+	use super::*;
+	use crate::PriceCommitment;
+
+	fn add_asset() {
+		assert_ok!(Oracle::add_asset_and_info(
+			RuntimeOrigin::signed(get_root_account()),
+			0,
+			Validated::new(Percent::from_percent(80)).unwrap(),
+			Validated::new(2).unwrap(),
+			Validated::new(3).unwrap(),
+			Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+			5,
+			5,
+			false,
+		));
+	}
+
+	#[test]
+	fn should_set_reveal_period() {
+		new_test_ext().execute_with(|| {
+			// events are not deposited at genesis
+			System::set_block_number(1);
+			let root_account = get_root_account();
+			assert_noop!(
+				Oracle::set_reveal_period(RuntimeOrigin::signed(get_account_1()), 0, Some(2)),
+				BadOrigin
+			);
+			assert_noop!(
+				Oracle::set_reveal_period(RuntimeOrigin::signed(root_account), 0, Some(2)),
+				Error::<Test>::InvalidAssetId
+			);
+			add_asset();
+			// revealed prices would go stale after `StalePrice` blocks
+			for reveal_period in [0, 3] {
+				assert_noop!(
+					Oracle::set_reveal_period(
+						RuntimeOrigin::signed(root_account),
+						0,
+						Some(reveal_period)
+					),
+					Error::<Test>::InvalidRevealPeriod
+				);
+			}
+
+			assert_ok!(Oracle::set_reveal_period(RuntimeOrigin::signed(root_account), 0, Some(2)));
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::RevealPeriodChanged(
+				0,
+				Some(2),
+			)));
+			assert_eq!(Oracle::reveal_period(0), Some(2));
+
+			assert_ok!(Oracle::set_signer(
+				RuntimeOrigin::signed(root_account),
+				root_account,
+				get_account_1()
+			));
+			assert_ok!(Oracle::add_stake(RuntimeOrigin::signed(root_account), 50));
+			assert_noop!(
+				Oracle::submit_price(RuntimeOrigin::signed(get_account_1()), 100_u128, 0_u128),
+				Error::<Test>::CommitRevealRequired
+			);
+			assert_ok!(Oracle::commit_price(
+				RuntimeOrigin::signed(get_account_1()),
+				0,
+				H256::repeat_byte(1)
+			));
+			assert_noop!(
+				Oracle::set_reveal_period(RuntimeOrigin::signed(root_account), 0, None),
+				Error::<Test>::CommitRevealInProgress
+			);
+		});
+	}
+
+	#[test]
+	fn should_not_commit_without_reveal_period() {
+		new_test_ext().execute_with(|| {
+			let root_account = get_root_account();
+			add_asset();
+			assert_ok!(Oracle::set_signer(
+				RuntimeOrigin::signed(root_account),
+				root_account,
+				get_account_1()
+			));
+			assert_noop!(
+				Oracle::commit_price(RuntimeOrigin::signed(get_account_1()), 0, H256::zero()),
+				Error::<Test>::CommitRevealDisabled
+			);
+			assert_noop!(
+				Oracle::reveal_price(RuntimeOrigin::signed(get_account_1()), 0, 100, [0; 32]),
+				Error::<Test>::CommitRevealDisabled
+			);
+		});
+	}
+
+	#[test]
+	fn should_aggregate_revealed_prices_and_slash_non_revealers() {
+		new_test_ext().execute_with(|| {
+			let root_account = get_root_account();
+			let account_1 = get_account_1();
+			let account_4 = get_account_4();
+			let account_5 = get_account_5();
+			add_asset();
+			assert_ok!(Oracle::set_reveal_period(RuntimeOrigin::signed(root_account), 0, Some(2)));
+			assert_ok!(Oracle::set_signer(
+				RuntimeOrigin::signed(root_account),
+				root_account,
+				account_1
+			));
+			assert_ok!(Oracle::set_signer(
+				RuntimeOrigin::signed(root_account),
+				account_1,
+				root_account
+			));
+			assert_ok!(Oracle::set_signer(
+				RuntimeOrigin::signed(root_account),
+				account_5,
+				account_4
+			));
+			assert_ok!(Oracle::add_stake(RuntimeOrigin::signed(account_1), 50));
+			assert_ok!(Oracle::add_stake(RuntimeOrigin::signed(root_account), 50));
+			assert_ok!(Oracle::add_stake(RuntimeOrigin::signed(account_5), 50));
+
+			// commit phase, from the first commitment
+			System::set_block_number(6);
+			let salt = [7; 32];
+			for who in [account_1, root_account, account_4] {
+				assert_ok!(Oracle::commit_price(
+					RuntimeOrigin::signed(who),
+					0,
+					Oracle::price_commitment(&who, 100, &salt)
+				));
+				System::assert_last_event(RuntimeEvent::Oracle(crate::Event::PriceCommitted(
+					who, 0,
+				)));
+				assert_eq!(Oracle::answer_in_transit(who), Some(5));
+			}
+			assert_noop!(
+				Oracle::commit_price(RuntimeOrigin::signed(account_1), 0, H256::zero()),
+				Error::<Test>::AlreadySubmitted
+			);
+			assert_noop!(
+				Oracle::reveal_price(RuntimeOrigin::signed(account_1), 0, 100, salt),
+				Error::<Test>::NotRevealPhase
+			);
+
+			// reveal phase
+			System::set_block_number(8);
+			assert_noop!(
+				Oracle::commit_price(RuntimeOrigin::signed(account_5), 0, H256::zero()),
+				Error::<Test>::NotCommitPhase
+			);
+			assert_noop!(
+				Oracle::reveal_price(RuntimeOrigin::signed(account_1), 0, 101, salt),
+				Error::<Test>::InvalidReveal
+			);
+			assert_noop!(
+				Oracle::reveal_price(RuntimeOrigin::signed(account_5), 0, 100, salt),
+				Error::<Test>::NoCommitment
+			);
+			assert_ok!(Oracle::reveal_price(RuntimeOrigin::signed(account_1), 0, 100, salt));
+			assert_ok!(Oracle::reveal_price(RuntimeOrigin::signed(root_account), 0, 100, salt));
+			assert_noop!(
+				Oracle::reveal_price(RuntimeOrigin::signed(account_1), 0, 100, salt),
+				Error::<Test>::AlreadySubmitted
+			);
+			assert_eq!(
+				Oracle::pre_prices(0),
+				vec![
+					PrePrice { price: 100, block: 8, who: account_1 },
+					PrePrice { price: 100, block: 8, who: root_account },
+				]
+			);
+
+			// prices are not aggregated before the end of the round
+			Oracle::on_initialize(8);
+			assert_eq!(Oracle::prices(0), Price::default());
+
+			System::set_block_number(10);
+			Oracle::on_initialize(10);
+			System::assert_has_event(RuntimeEvent::Oracle(crate::Event::UserSlashed(
+				account_4, 0, 5,
+			)));
+			assert_eq!(Oracle::oracle_stake(account_4), Some(46));
+			assert_eq!(Oracle::oracle_stake(account_1), Some(51));
+			assert_eq!(Oracle::answer_in_transit(account_4), Some(0));
+			assert_eq!(Oracle::price_commitments(0), vec![]);
+			assert_eq!(Oracle::prices(0), Price { price: 100, block: 10 });
+		});
+	}
+
+	#[test]
+	fn should_commit_and_reveal_from_offchain_worker() {
+		let (mut t, oracle_account_id, pool_state) =
+			offchain_worker_env(|state| price_oracle_response(state, "0"));
+
+		t.execute_with(|| {
+			add_asset();
+			assert_ok!(Oracle::set_reveal_period(
+				RuntimeOrigin::signed(get_root_account()),
+				0,
+				Some(2)
+			));
+			System::set_block_number(1);
+
+			Oracle::fetch_price_and_send_signed(&0, Oracle::asset_info(0).unwrap()).unwrap();
+			let tx = pool_state.write().transactions.pop().unwrap();
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			let commitment = match tx.call {
+				RuntimeCall::Oracle(crate::Call::commit_price { asset_id: 0, commitment }) =>
+					commitment,
+				call => panic!("unexpected call {call:?}"),
+			};
+			PriceCommitments::<Test>::insert(
+				0,
+				BoundedVec::try_from(vec![PriceCommitment {
+					commitment,
+					block: 1,
+					who: oracle_account_id,
+					revealed: false,
+				}])
+				.unwrap(),
+			);
+
+			// nothing to do until the reveal phase
+			Oracle::fetch_price_and_send_signed(&0, Oracle::asset_info(0).unwrap()).unwrap();
+			assert!(pool_state.read().transactions.is_empty());
+
+			System::set_block_number(3);
+			Oracle::fetch_price_and_send_signed(&0, Oracle::asset_info(0).unwrap()).unwrap();
+			let tx = pool_state.write().transactions.pop().unwrap();
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			match tx.call {
+				RuntimeCall::Oracle(crate::Call::reveal_price { asset_id: 0, price, salt }) => {
+					assert_eq!(price, 15523);
+					assert_eq!(
+						Oracle::price_commitment(&oracle_account_id, price, &salt),
+						commitment
+					);
+				},
+				call => panic!("unexpected call {call:?}"),
+			}
+		});
+	}
+}
+
+mod queries {
+	use super::*;
+	use crate::FreezeReason;
+	use composable_traits::oracle::OracleStatus;
+
+	fn add_asset(asset_id: AssetId) {
+		assert_ok!(Oracle::add_asset_and_info(
+			RuntimeOrigin::signed(get_root_account()),
+			asset_id,
+			Validated::new(Percent::from_percent(80)).unwrap(),
+			Validated::new(3).unwrap(),
+			Validated::new(5).unwrap(),
+			Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+			5,
+			5,
+			false,
+		));
+	}
+
+	fn set_prices(asset_id: AssetId) {
+		let history = [(100, 21), (100, 22), (120, 23), (101, 24)]
+			.map(|(price, block)| Price { price, block })
+			.to_vec();
+		Prices::<Test>::insert(asset_id, Price { price: 101, block: 24 });
+		set_historic_prices(asset_id, history);
+	}
+
+	#[test]
+	fn should_list_pending_requests() {
+		new_test_ext().execute_with(|| {
+			assert!(Oracle::pending_requests().is_empty());
+			add_asset(0);
+			add_asset(1);
+			System::set_block_number(6);
+			let mut pending = Oracle::pending_requests();
+			pending.sort();
+			assert_eq!(pending, vec![0, 1]);
+
+			Prices::<Test>::insert(0, Price { price: 100, block: 6 });
+			assert_eq!(Oracle::pending_requests(), vec![1]);
+		});
+	}
+
+	#[test]
+	fn should_read_latest_prices() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(Oracle::latest_price(0), None);
+			assert!(Oracle::latest_history(0, 2).is_empty());
+			add_asset(0);
+			set_prices(0);
+
+			assert_eq!(Oracle::latest_price(0), Some(Price { price: 101, block: 24 }));
+			assert_eq!(
+				Oracle::latest_history(0, 2),
+				vec![Price { price: 120, block: 23 }, Price { price: 101, block: 24 }]
+			);
+			assert_eq!(Oracle::latest_history(0, 10).len(), 4);
+			assert!(Oracle::latest_history(0, 0).is_empty());
+
+			Oracle::freeze(0, FreezeReason::Stale);
+			assert_eq!(Oracle::latest_price(0), None);
+		});
+	}
+
+	#[test]
+	fn should_weight_latest_prices() {
+		new_test_ext().execute_with(|| {
+			let weights = |weights: &[u8]| {
+				weights.iter().copied().map(Percent::from_percent).collect::<Vec<_>>()
+			};
+			assert_noop!(
+				Oracle::get_weighted_twap(0, weights(&[50, 50])),
+				Error::<Test>::PriceNotFound
+			);
+			add_asset(0);
+			set_prices(0);
+
+			// (101 * 50 + 120 * 30 + 100 * 20) / 100
+			assert_eq!(Oracle::get_weighted_twap(0, weights(&[50, 30, 20])), Ok(106));
+			assert_eq!(Oracle::get_weighted_twap(0, weights(&[100])), Ok(101));
+			assert_noop!(
+				Oracle::get_weighted_twap(0, weights(&[50, 40])),
+				Error::<Test>::MustSumTo100
+			);
+			assert_noop!(
+				Oracle::get_weighted_twap(0, weights(&[20, 20, 20, 20, 20])),
+				Error::<Test>::DepthTooLarge
+			);
+
+			Oracle::freeze(0, FreezeReason::Deviation);
+			assert_noop!(Oracle::get_weighted_twap(0, weights(&[100])), Error::<Test>::AssetFrozen);
+		});
+	}
+
+	#[test]
+	fn should_report_oracle_status() {
+		new_test_ext().execute_with(|| {
+			let controller = get_account_1();
+			let signer = get_account_3();
+			System::set_block_number(1);
+			assert_eq!(Oracle::oracle_status(&signer), None);
+			assert_ok!(Oracle::set_signer(RuntimeOrigin::signed(controller), signer));
+			assert_eq!(
+				Oracle::oracle_status(&signer),
+				Some(OracleStatus {
+					controller: Some(controller),
+					stake: 1,
+					answer_in_transit: 0,
+					withdrawal: None,
+					rewards: 0,
+					reputation: Permill::zero(),
+				})
+			);
+
+			let reward_tracker = RewardTracker { start: 1, ..Default::default() };
+			RewardTrackerStore::<Test>::set(Some(reward_tracker));
+			Balances::make_free_balance_be(&Oracle::account_id(), 100);
+			AccumulatedRewardsPerAsset::<Test>::insert(0, 10);
+			add_asset(0);
+			add_price_storage(100, 0, signer, 0);
+			let answer = PrePrice { price: 100, block: 0, who: signer };
+			assert_ok!(Oracle::handle_payout(&[answer], 100, 0, &Oracle::asset_info(0).unwrap()));
+
+			assert_ok!(Oracle::remove_stake(RuntimeOrigin::signed(controller)));
+			assert_eq!(
+				Oracle::oracle_status(&signer),
+				Some(OracleStatus {
+					controller: Some(controller),
+					stake: 0,
+					answer_in_transit: 0,
+					withdrawal: Some((1, 1 + StakeLock::get())),
+					rewards: 10,
+					reputation: Permill::one(),
+				})
+			);
+		});
+	}
+}
+
+mod reputation {
+	use super::*;
+	use crate::Reputation;
+
+	fn asset_info(threshold: u8) -> AssetInfo<Percent, BlockNumber, Balance> {
+		AssetInfo {
+			threshold: Percent::from_percent(threshold),
+			min_answers: 1,
+			max_answers: 5,
+			block_interval: 5,
+			reward_weight: 0,
+			slash: 0,
+			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
+		}
+	}
+
+	fn answer(price: PriceValue, who: AccountId) -> PrePrice<PriceValue, BlockNumber, AccountId> {
+		PrePrice { price, block: 0, who }
+	}
+
+	#[test]
+	fn should_track_deviation_and_participation() {
+		new_test_ext().execute_with(|| {
+			let accurate = get_account_1();
+			let flaky = get_account_3();
+			let asset_info = asset_info(0);
+			assert_eq!(Oracle::reputation_score(&accurate, 0), Permill::zero());
+
+			assert_ok!(Oracle::handle_payout(
+				&[answer(100, accurate), answer(110, flaky)],
+				100,
+				0,
+				&asset_info
+			));
+			assert_eq!(
+				Oracle::reputation(flaky, 0),
+				Some(Reputation {
+					deviation: Permill::from_percent(10),
+					answered: 1,
+					first_round: 0
+				})
+			);
+			assert_eq!(Oracle::reputation_score(&accurate, 0), Permill::one());
+			assert_eq!(Oracle::reputation_score(&flaky, 0), Permill::from_percent(90));
+
+			assert_ok!(Oracle::handle_payout(&[answer(100, accurate)], 100, 0, &asset_info));
+			assert_eq!(Oracle::aggregation_rounds(0), 2);
+			assert_eq!(Oracle::reputation_score(&accurate, 0), Permill::one());
+			// 90% accurate in half of the rounds
+			assert_eq!(Oracle::reputation_score(&flaky, 0), Permill::from_percent(45));
+
+			assert_ok!(Oracle::handle_payout(
+				&[answer(100, accurate), answer(100, flaky)],
+				100,
+				0,
+				&asset_info
+			));
+			// smoothed by half with the exact answer
+			assert_eq!(
+				Oracle::reputation(flaky, 0),
+				Some(Reputation {
+					deviation: Permill::from_percent(5),
+					answered: 2,
+					first_round: 0
+				})
+			);
+
+			let newcomer = get_account_4();
+			assert_ok!(Oracle::handle_payout(&[answer(100, newcomer)], 100, 0, &asset_info));
+			assert_eq!(
+				Oracle::reputation(newcomer, 0),
+				Some(Reputation { deviation: Permill::zero(), answered: 1, first_round: 3 })
+			);
+			assert_eq!(Oracle::reputation_score(&newcomer, 0), Permill::one());
+		});
+	}
+
+	#[test]
+	fn should_count_participation_per_asset() {
+		new_test_ext().execute_with(|| {
+			let generalist = get_account_1();
+			let specialist = get_account_3();
+			let asset_info = asset_info(0);
+
+			assert_ok!(Oracle::handle_payout(
+				&[answer(100, generalist), answer(100, specialist)],
+				100,
+				0,
+				&asset_info
+			));
+			// rounds of other assets do not dilute the participation of the specialist
+			for _ in 0..3 {
+				assert_ok!(Oracle::handle_payout(&[answer(100, generalist)], 100, 1, &asset_info));
+			}
+			assert_eq!(Oracle::aggregation_rounds(0), 1);
+			assert_eq!(Oracle::aggregation_rounds(1), 3);
+			assert_eq!(Oracle::reputation_score(&specialist, 0), Permill::one());
+			assert_eq!(Oracle::reputation_score(&specialist, 1), Permill::zero());
+			assert_eq!(Oracle::average_reputation_score(&specialist), Permill::one());
+
+			assert_ok!(Oracle::handle_payout(&[answer(110, generalist)], 100, 0, &asset_info));
+			// 90% accurate for the first asset, exact for the second one
+			assert_eq!(Oracle::reputation_score(&generalist, 0), Permill::from_percent(95));
+			assert_eq!(Oracle::reputation_score(&generalist, 1), Permill::one());
+			assert_eq!(
+				Oracle::average_reputation_score(&generalist),
+				Permill::from_rational(195_u32, 200_u32)
+			);
+			// the specialist missed a round of its asset
+			assert_eq!(Oracle::reputation_score(&specialist, 0), Permill::from_percent(50));
+		});
+	}
+
+	#[test]
+	fn should_weight_median_by_reputation() {
+		new_test_ext().execute_with(|| {
+			let trusted = get_account_1();
+			AggregationRounds::<Test>::insert(0, 10);
+			Reputations::<Test>::insert(
+				trusted,
+				0,
+				Reputation { deviation: Permill::zero(), answered: 10, first_round: 0 },
+			);
+			let answers =
+				[answer(100, trusted), answer(200, get_account_3()), answer(300, get_account_4())];
+
+			assert_eq!(Oracle::get_median_price(&answers), Some(200));
+			assert_eq!(Oracle::get_reputation_weighted_median_price(0, &answers), Some(100));
+
+			// oracles without reputation still count
+			Reputations::<Test>::remove(trusted, 0);
+			assert_eq!(Oracle::get_reputation_weighted_median_price(0, &answers), Some(200));
+		});
+	}
+
+	#[test]
+	fn should_set_reputation_weighting() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let root_account = get_root_account();
+			assert_noop!(
+				Oracle::set_reputation_weighting(RuntimeOrigin::signed(get_account_1()), 0, true),
+				BadOrigin
+			);
+			assert_noop!(
+				Oracle::set_reputation_weighting(RuntimeOrigin::signed(root_account), 0, true),
+				Error::<Test>::InvalidAssetId
+			);
+			assert_ok!(Oracle::add_asset_and_info(
+				RuntimeOrigin::signed(root_account),
+				0,
+				Validated::new(Percent::from_percent(80)).unwrap(),
+				Validated::new(3).unwrap(),
+				Validated::new(5).unwrap(),
+				Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+				5,
+				5,
+				false,
+			));
+
+			let trusted = get_account_1();
+			AggregationRounds::<Test>::insert(0, 10);
+			Reputations::<Test>::insert(
+				trusted,
+				0,
+				Reputation { deviation: Permill::zero(), answered: 10, first_round: 0 },
+			);
+			let answers =
+				[answer(100, trusted), answer(200, get_account_3()), answer(300, get_account_4())];
+			let asset_info = asset_info(80);
+			assert_eq!(Oracle::calculate_price(0, &answers, &asset_info), Some(200));
+
+			assert_ok!(Oracle::set_reputation_weighting(
+				RuntimeOrigin::signed(root_account),
+				0,
+				true
+			));
+			System::assert_last_event(RuntimeEvent::Oracle(
+				crate::Event::ReputationWeightingChanged(0, true),
+			));
+			assert!(Oracle::reputation_weighted(0));
+			assert_eq!(Oracle::calculate_price(0, &answers, &asset_info), Some(100));
+		});
+	}
+}
+
+mod derived_prices {
+	use super::*;
+	use crate::{FreezeReason, PriceDerivation};
+	use frame_support::dispatch::DispatchResultWithPostInfo;
+
+	fn set_derived_price(
+		asset_id: AssetId,
+		derivation: PriceDerivation<AssetId, PoolId>,
+	) -> DispatchResultWithPostInfo {
+		Oracle::set_derived_price(
+			RuntimeOrigin::signed(get_root_account()),
+			asset_id,
+			Some(derivation),
+		)
+	}
+
+	fn add_asset(asset_id: AssetId) -> DispatchResultWithPostInfo {
+		Oracle::add_asset_and_info(
+			RuntimeOrigin::signed(get_root_account()),
+			asset_id,
+			Validated::new(Percent::from_percent(80)).unwrap(),
+			Validated::new(3).unwrap(),
+			Validated::new(5).unwrap(),
+			Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+			5,
+			5,
+			false,
+		)
+	}
+
+	#[test]
+	fn should_resolve_derived_prices() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Prices::<Test>::insert(0, Price { price: 2 * UNIT, block: 1 });
+			Prices::<Test>::insert(1, Price { price: 5 * UNIT, block: 3 });
+
+			assert_ok!(set_derived_price(
+				20,
+				PriceDerivation::Ratio { numerator: 1, denominator: 0 }
+			));
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::DerivedPriceChanged(
+				20,
+				Some(PriceDerivation::Ratio { numerator: 1, denominator: 0 }),
+			)));
+			assert_eq!(
+				<Oracle as oracle::Oracle>::get_price(20, UNIT),
+				Ok(Price { price: 5 * UNIT / 2, block: 1 })
+			);
+
+			assert_ok!(set_derived_price(LIQUID_ASSET, PriceDerivation::ExchangeRate));
+			assert_eq!(
+				<Oracle as oracle::Oracle>::get_price(LIQUID_ASSET, UNIT),
+				Ok(Price { price: 3 * UNIT, block: 1 })
+			);
+
+			// 2 units of asset `0` and 3 of asset `1`
+			assert_ok!(set_derived_price(
+				POOL_LP_TOKEN,
+				PriceDerivation::LpToken { pool_id: POOL }
+			));
+			assert_eq!(
+				<Oracle as oracle::Oracle>::get_price(POOL_LP_TOKEN, 2 * UNIT),
+				Ok(Price { price: 38 * UNIT, block: 1 })
+			);
+
+			// a unit of `POOL_LP_TOKEN` and a unit of asset `0`
+			assert_ok!(set_derived_price(
+				LP_POOL_LP_TOKEN,
+				PriceDerivation::LpToken { pool_id: LP_POOL }
+			));
+			assert_eq!(
+				Oracle::latest_price(LP_POOL_LP_TOKEN),
+				Some(Price { price: 21 * UNIT, block: 1 })
+			);
+
+			assert_ok!(Oracle::set_derived_price(
+				RuntimeOrigin::signed(get_root_account()),
+				20,
+				None
+			));
+			System::assert_last_event(RuntimeEvent::Oracle(crate::Event::DerivedPriceChanged(
+				20, None,
+			)));
+			assert_noop!(
+				<Oracle as oracle::Oracle>::get_price(20, UNIT),
+				Error::<Test>::PriceNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn should_not_resolve_frozen_components() {
+		new_test_ext().execute_with(|| {
+			Prices::<Test>::insert(0, Price { price: 2 * UNIT, block: 1 });
+			assert_ok!(set_derived_price(LIQUID_ASSET, PriceDerivation::ExchangeRate));
+			FrozenAssets::<Test>::insert(0, FreezeReason::Stale);
+			assert_noop!(
+				<Oracle as oracle::Oracle>::get_price(LIQUID_ASSET, UNIT),
+				Error::<Test>::AssetFrozen
+			);
+			assert_eq!(Oracle::latest_price(LIQUID_ASSET), None);
+		});
+	}
+
+	#[test]
+	fn should_reject_invalid_derivations() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Oracle::set_derived_price(
+					RuntimeOrigin::signed(get_account_1()),
+					LIQUID_ASSET,
+					Some(PriceDerivation::ExchangeRate)
+				),
+				BadOrigin
+			);
+			// no exchange rate
+			assert_noop!(
+				set_derived_price(20, PriceDerivation::ExchangeRate),
+				Error::<Test>::InvalidDerivedPrice
+			);
+			// not the LP token of the pool
+			assert_noop!(
+				set_derived_price(20, PriceDerivation::LpToken { pool_id: POOL }),
+				Error::<Test>::InvalidDerivedPrice
+			);
+			assert_noop!(
+				set_derived_price(POOL_LP_TOKEN, PriceDerivation::LpToken { pool_id: 3 }),
+				Error::<Test>::InvalidDerivedPrice
+			);
+
+			assert_ok!(add_asset(0));
+			assert_noop!(
+				set_derived_price(0, PriceDerivation::Ratio { numerator: 1, denominator: 2 }),
+				Error::<Test>::DerivedPriceOfReportedAsset
+			);
+			assert_ok!(set_derived_price(
+				20,
+				PriceDerivation::Ratio { numerator: 1, denominator: 0 }
+			));
+			assert_noop!(add_asset(20), Error::<Test>::DerivedPriceOfReportedAsset);
+		});
+	}
+
+	#[test]
+	fn should_reject_cycles() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				set_derived_price(20, PriceDerivation::Ratio { numerator: 20, denominator: 0 }),
+				Error::<Test>::DerivedPriceCycle
+			);
+			assert_ok!(set_derived_price(
+				20,
+				PriceDerivation::Ratio { numerator: 21, denominator: 0 }
+			));
+			assert_ok!(set_derived_price(
+				21,
+				PriceDerivation::Ratio { numerator: 1, denominator: 22 }
+			));
+			assert_noop!(
+				set_derived_price(22, PriceDerivation::Ratio { numerator: 0, denominator: 20 }),
+				Error::<Test>::DerivedPriceCycle
+			);
+
+			// through the reserves of a pool
+			assert_ok!(set_derived_price(
+				POOL_LP_TOKEN,
+				PriceDerivation::Ratio { numerator: LP_POOL_LP_TOKEN, denominator: 0 }
+			));
+			assert_noop!(
+				set_derived_price(LP_POOL_LP_TOKEN, PriceDerivation::LpToken { pool_id: LP_POOL }),
+				Error::<Test>::DerivedPriceCycle
+			);
+		});
+	}
+
+	#[test]
+	fn should_not_resolve_cycles() {
+		new_test_ext().execute_with(|| {
+			Prices::<Test>::insert(0, Price { price: 2 * UNIT, block: 1 });
+			DerivedPrices::<Test>::insert(
+				20,
+				PriceDerivation::Ratio { numerator: 21, denominator: 0 },
+			);
+			DerivedPrices::<Test>::insert(
+				21,
+				PriceDerivation::Ratio { numerator: 20, denominator: 0 },
+			);
+			assert_noop!(
+				<Oracle as oracle::Oracle>::get_price(20, UNIT),
+				Error::<Test>::DerivedPriceCycle
+			);
+		});
+	}
+}
+
 mod add_asset_and_info {
 	use super::*;
 
@@ -1381,6 +2421,9 @@ fn test_payout_slash() {
 			reward_weight: 0,
 			slash: 0,
 			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
 		};
 		// doesn't panic when percent not set
 		// need to set AccumulatedRewardsPerAsset because previously rewards were not accumlated but
@@ -2186,6 +3229,9 @@ fn prune_old_pre_prices_edgecase() {
 			reward_weight: 5,
 			slash: 5,
 			emit_price_changes: false,
+			price_exponent: 0,
+			max_staleness: None,
+			max_deviation: None,
 		};
 		Oracle::prune_old_pre_prices(&asset_info, vec![], 0);
 	});
@@ -2340,6 +3386,9 @@ fn should_check_oracles_max_answer() {
 		reward_weight: 0,
 		slash: 0,
 		emit_price_changes: false,
+		price_exponent: 0,
+		max_staleness: None,
+		max_deviation: None,
 	};
 	t.execute_with(|| {
 		Oracle::fetch_price_and_send_signed(&0, asset_info).unwrap();
@@ -2356,7 +3405,281 @@ fn parse_price_works() {
 	];
 
 	for (json, expected) in test_data {
-		assert_eq!(expected, Oracle::parse_price(json, "1"));
+		assert_eq!(expected, Oracle::parse_price(json, "1", 0));
+	}
+}
+
+#[test]
+fn parse_price_scales_to_exponent() {
+	let test_data = vec![
+		("{\"1\":6536.92}", 2, Some(653692)),
+		("{\"1\":6536.92}", 12, Some(6_536_920_000_000_000)),
+		("{\"1\":0.000001234}", 12, Some(1_234_000)),
+		("{\"1\":1.5e-3}", 6, Some(1_500)),
+		("{\"1\":15e2}", 0, Some(1_500)),
+		("{\"1\":0}", 12, Some(0)),
+	];
+
+	for (json, exponent, expected) in test_data {
+		assert_eq!(expected, Oracle::parse_price(json, "1", exponent), "{json}");
+	}
+}
+
+#[test]
+fn parse_price_rounds_half_up() {
+	let test_data = vec![
+		("{\"1\":0.0000004}", 6, Some(0)),
+		("{\"1\":0.0000005}", 6, Some(1)),
+		("{\"1\":0.149}", 1, Some(1)),
+		("{\"1\":0.15}", 1, Some(2)),
+		("{\"1\":0.25}", 1, Some(3)),
+		("{\"1\":1e-40}", 1, Some(0)),
+	];
+
+	for (json, exponent, expected) in test_data {
+		assert_eq!(expected, Oracle::parse_price(json, "1", exponent), "{json}");
+	}
+}
+
+#[test]
+fn parse_price_truncates_at_default_exponent() {
+	let test_data = vec![
+		("{\"1\":1.49}", Some(1)),
+		("{\"1\":1.5}", Some(1)),
+		("{\"1\":2.99}", Some(2)),
+		("{\"1\":1e-40}", Some(0)),
+	];
+
+	for (json, expected) in test_data {
+		assert_eq!(expected, Oracle::parse_price(json, "1", 0), "{json}");
+	}
+}
+
+#[test]
+fn parse_price_rejects_overflows_and_negatives() {
+	let test_data = vec![
+		("{\"1\":1e39}", 0),
+		("{\"1\":3402823669.20938463463}", 30),
+		("{\"1\":4}", 38),
+		("{\"1\":-3}", 0),
+		("{\"1\":-0.5}", 0),
+		("{\"1\":-0.5}", 6),
+	];
+
+	for (json, exponent) in test_data {
+		assert_eq!(None, Oracle::parse_price(json, "1", exponent), "{json}");
+	}
+	assert_eq!(Some(10_u128.pow(38)), Oracle::parse_price("{\"1\":1}", "1", 38));
+}
+
+#[test]
+fn should_scale_fetched_price_to_asset_exponent() {
+	let (mut t, _, _) = offchain_worker_env(|state| {
+		state.expect_request(testing::PendingRequest {
+			method: "GET".into(),
+			uri: "http://localhost:3001/price/0".into(),
+			response: Some(br#"{"0": 0.0123}"#.to_vec()),
+			sent: true,
+			..Default::default()
+		});
+	});
+
+	t.execute_with(|| {
+		assert_ok!(Oracle::add_asset_and_info(
+			RuntimeOrigin::signed(get_root_account()),
+			0,
+			Validated::new(Percent::from_percent(80)).unwrap(),
+			Validated::new(3).unwrap(),
+			Validated::new(5).unwrap(),
+			Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+			5,
+			5,
+			false,
+		));
+		assert_ok!(Oracle::set_price_exponent(RuntimeOrigin::signed(get_root_account()), 0, 12));
+
+		assert_eq!(Oracle::fetch_price(&0).unwrap(), 12_300_000_000);
+	});
+}
+
+mod price_sources {
+	use super::*;
+	use crate::sources::{aggregate, parse_sources, source_metrics, weighted_median, PriceSource};
+	use sp_core::offchain::{StorageKind, Timestamp as OffchainTimestamp};
+
+	fn price_response(state: &mut testing::OffchainState, url: &str, body: &[u8]) {
+		state.expect_request(testing::PendingRequest {
+			method: "GET".into(),
+			uri: url.into(),
+			response: Some(body.to_vec()),
+			sent: true,
+			..Default::default()
+		});
+	}
+
+	fn set_sources(sources: &[u8]) {
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ocw-urls", sources);
+	}
+
+	#[test]
+	fn parses_sources_with_weights() {
+		assert_eq!(
+			parse_sources(b"http://a/price/ 3, http://b/price/\nhttp://c/price/ 0,http://d/ 1 2,,"),
+			vec![
+				PriceSource { url: "http://a/price/".into(), weight: 3 },
+				PriceSource { url: "http://b/price/".into(), weight: 1 },
+			]
+		);
+		assert_eq!(parse_sources(&[0xff, 0xfe]), vec![]);
+	}
+
+	#[test]
+	fn weighted_median_works() {
+		assert_eq!(weighted_median(&[]), None);
+		assert_eq!(weighted_median(&[(5, 1)]), Some(5));
+		assert_eq!(weighted_median(&[(300, 1), (100, 1), (200, 1)]), Some(200));
+		assert_eq!(weighted_median(&[(100, 1), (200, 1), (300, 1), (400, 1)]), Some(250));
+		assert_eq!(weighted_median(&[(100, 3), (200, 1)]), Some(100));
+		assert_eq!(weighted_median(&[(100, 1), (200, 1), (300, 2)]), Some(250));
+		assert_eq!(weighted_median(&[(100, 0), (200, 1)]), Some(200));
+	}
+
+	#[test]
+	fn aggregate_discards_outliers() {
+		let answers = [(100, 1), (104, 1), (300, 1)];
+		assert_eq!(
+			aggregate(&answers, Some(Percent::from_percent(10))),
+			Some((102, vec![true, true, false]))
+		);
+		assert_eq!(aggregate(&answers, None), Some((104, vec![true, true, true])));
+		assert_eq!(aggregate(&[(100, 1), (300, 1)], Some(Percent::from_percent(10))), None);
+		assert_eq!(aggregate(&[], None), None);
+	}
+
+	#[test]
+	fn should_submit_median_of_agreeing_sources() {
+		let (mut t, _, _) = offchain_worker_env(|state| {
+			price_response(state, "http://a/price/0", br#"{"0": 100}"#);
+			price_response(state, "http://b/price/0", br#"{"0": 104}"#);
+			price_response(state, "http://c/price/0", br#"{"0": 300}"#);
+			price_response(state, "http://d/price/0", br#"{"0": "unavailable"}"#);
+		});
+
+		t.execute_with(|| {
+			assert_ok!(Oracle::add_asset_and_info(
+				RuntimeOrigin::signed(get_root_account()),
+				0,
+				Validated::new(Percent::from_percent(10)).unwrap(),
+				Validated::new(3).unwrap(),
+				Validated::new(5).unwrap(),
+				Validated::<BlockNumber, ValidBlockInterval<StalePrice>>::new(5).unwrap(),
+				5,
+				5,
+				false,
+			));
+			set_sources(b"http://a/price/,http://b/price/,http://c/price/,http://d/price/");
+
+			assert_eq!(Oracle::fetch_price(&0).unwrap(), 102);
+
+			let metrics = |url| {
+				let metrics = source_metrics(url);
+				(metrics.answers, metrics.failures, metrics.outliers)
+			};
+			assert_eq!(metrics("http://a/price/"), (1, 0, 0));
+			assert_eq!(metrics("http://b/price/"), (1, 0, 0));
+			assert_eq!(metrics("http://c/price/"), (0, 0, 1));
+			assert_eq!(metrics("http://d/price/"), (0, 1, 0));
+		});
+	}
+
+	#[test]
+	fn should_weight_sources() {
+		let (mut t, _, _) = offchain_worker_env(|state| {
+			price_response(state, "http://a/price/0", br#"{"0": 100}"#);
+			price_response(state, "http://b/price/0", br#"{"0": 200}"#);
+			price_response(state, "http://a/price/0", br#"{"0": 100}"#);
+			price_response(state, "http://b/price/0", br#"{"0": 200}"#);
+		});
+
+		t.execute_with(|| {
+			set_sources(b"http://a/price/ 3,http://b/price/");
+			assert_eq!(Oracle::fetch_price(&0).unwrap(), 100);
+
+			set_sources(b"http://a/price/,http://b/price/");
+			assert_eq!(Oracle::fetch_price(&0).unwrap(), 150);
+			assert_eq!(source_metrics("http://b/price/").answers, 2);
+		});
+	}
+
+	#[test]
+	fn parses_answer_timestamps() {
+		assert_eq!(Oracle::parse_timestamp(r#"{"0": 100, "timestamp": 1000}"#), Some(1000));
+		assert_eq!(
+			Oracle::parse_timestamp(
+				r#"{"0": 100, "sources": [{"feed": "Binance", "price": 100, "timestamp": 1010},
+				{"feed": "Composable", "price": 101, "timestamp": 1000}]}"#
+			),
+			Some(1000)
+		);
+		assert_eq!(Oracle::parse_timestamp(r#"{"0": 100}"#), None);
+		assert_eq!(Oracle::parse_timestamp(r#"{"0": 100, "timestamp": -1}"#), None);
+	}
+
+	#[test]
+	fn should_discard_stale_answers() {
+		let (mut t, _, _) = offchain_worker_env(|state| {
+			state.timestamp = OffchainTimestamp::from_unix_millis(1_100_000);
+			price_response(
+				state,
+				"http://a/price/0",
+				br#"{"0": 100, "sources": [{"feed": "Binance", "price": 100, "timestamp": 1090}]}"#,
+			);
+			price_response(state, "http://b/price/0", br#"{"0": 200, "timestamp": 1000}"#);
+			price_response(state, "http://c/price/0", br#"{"0": 104}"#);
+			price_response(state, "http://a/price/0", br#"{"0": 100, "timestamp": 1000}"#);
+			price_response(state, "http://b/price/0", br#"{"0": 200, "timestamp": 1000}"#);
+		});
+
+		t.execute_with(|| {
+			set_sources(b"http://a/price/,http://b/price/,http://c/price/");
+			assert_eq!(Oracle::fetch_price(&0).unwrap(), 102);
+			assert_eq!(source_metrics("http://a/price/").answers, 1);
+			assert_eq!(source_metrics("http://b/price/").failures, 1);
+
+			sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, b"ocw-max-age", b"100");
+			set_sources(b"http://a/price/,http://b/price/");
+			assert_eq!(Oracle::fetch_price(&0).unwrap(), 150);
+		});
+	}
+
+	#[test]
+	fn should_fail_without_any_answer() {
+		let (mut t, _, _) = offchain_worker_env(|state| {
+			price_response(state, "http://a/price/0", br#"{"1": 100}"#);
+		});
+
+		t.execute_with(|| {
+			set_sources(b"http://a/price/");
+			assert!(Oracle::fetch_price(&0).is_err());
+			assert_eq!(source_metrics("http://a/price/").failures, 1);
+		});
+	}
+
+	#[test]
+	fn should_fall_back_to_single_source() {
+		let (mut t, _, _) = offchain_worker_env(|state| {
+			price_response(state, "http://single/price/0", br#"{"0": 42}"#);
+		});
+
+		t.execute_with(|| {
+			set_sources(b" , ");
+			sp_io::offchain::local_storage_set(
+				StorageKind::PERSISTENT,
+				b"ocw-url",
+				b"http://single/price/",
+			);
+			assert_eq!(Oracle::fetch_price(&0).unwrap(), 42);
+		});
 	}
 }
 
@@ -17,6 +17,14 @@ pub trait WeightInfo {
 	fn submit_price(p: u32) -> Weight;
 	fn update_pre_prices(p: u32) -> Weight;
 	fn update_price(p: u32) -> Weight;
+	fn set_price_exponent() -> Weight;
+	fn set_circuit_breaker() -> Weight;
+	fn unfreeze_asset() -> Weight;
+	fn set_reveal_period() -> Weight;
+	fn commit_price() -> Weight;
+	fn reveal_price() -> Weight;
+	fn set_reputation_weighting() -> Weight;
+	fn set_derived_price() -> Weight;
 }
 
 /// Weights for pallet_oracle using the Substrate node and recommended hardware.
@@ -78,6 +86,46 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	fn set_price_exponent() -> Weight {
+		Weight::from_parts(25_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_circuit_breaker() -> Weight {
+		Weight::from_parts(25_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn unfreeze_asset() -> Weight {
+		Weight::from_parts(22_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn set_reveal_period() -> Weight {
+		Weight::from_parts(27_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn commit_price() -> Weight {
+		Weight::from_parts(60_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn reveal_price() -> Weight {
+		Weight::from_parts(55_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn set_reputation_weighting() -> Weight {
+		Weight::from_parts(24_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_derived_price() -> Weight {
+		Weight::from_parts(31_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -140,4 +188,44 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	fn set_price_exponent() -> Weight {
+		Weight::from_parts(25_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_circuit_breaker() -> Weight {
+		Weight::from_parts(25_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn unfreeze_asset() -> Weight {
+		Weight::from_parts(22_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_reveal_period() -> Weight {
+		Weight::from_parts(27_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn commit_price() -> Weight {
+		Weight::from_parts(60_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn reveal_price() -> Weight {
+		Weight::from_parts(55_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_reputation_weighting() -> Weight {
+		Weight::from_parts(24_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_derived_price() -> Weight {
+		Weight::from_parts(31_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -1,6 +1,6 @@
 use codec::Codec;
 use composable_support::rpc_helpers::SafeRpcWrapper;
-use composable_traits::dex::PriceAggregate;
+use composable_traits::{defi::Rate, dex::PriceAggregate};
 use core::{fmt::Display, str::FromStr};
 use jsonrpsee::{
 	core::{Error as RpcError, RpcResult},
@@ -58,6 +58,14 @@ where
 		min_expected_amounts: BTreeMap<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
 		at: Option<BlockHash>,
 	) -> RpcResult<BTreeMap<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>>;
+
+	#[method(name = "pablo_twap")]
+	fn twap(
+		&self,
+		pool_id: SafeRpcWrapper<PoolId>,
+		window: SafeRpcWrapper<u64>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BTreeMap<SafeRpcWrapper<AssetId>, Rate>>>;
 }
 
 pub struct Pablo<C, Block> {
@@ -176,4 +184,25 @@ where
 			)))
 		})
 	}
+
+	fn twap(
+		&self,
+		pool_id: SafeRpcWrapper<PoolId>,
+		window: SafeRpcWrapper<u64>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BTreeMap<SafeRpcWrapper<AssetId>, Rate>>> {
+		let api = self.client.runtime_api();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// calling ../../runtime-api
+		let runtime_api_result = api.twap(at, pool_id, window);
+		runtime_api_result.map_err(|e| {
+			RpcError::Call(CallError::Custom(ErrorObject::owned(
+				9876,
+				"Something wrong",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
 }
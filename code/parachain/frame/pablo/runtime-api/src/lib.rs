@@ -4,12 +4,13 @@
 
 use codec::Codec;
 use composable_support::rpc_helpers::SafeRpcWrapper;
-use composable_traits::dex::PriceAggregate;
+use composable_traits::{defi::Rate, dex::PriceAggregate};
 use sp_std::collections::btree_map::BTreeMap;
 
 // Pablo Runtime API declaration. Implemented for each runtime at
 // `runtime/<runtime-name>/src/lib.rs`.
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait PabloRuntimeApi<AccountId, PoolId, AssetId, Balance>
 	where
 		PoolId: Codec,
@@ -42,5 +43,13 @@ sp_api::decl_runtime_apis! {
 			lp_amount: SafeRpcWrapper<Balance>,
 			min_expected_amounts: BTreeMap<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>,
 		) -> BTreeMap<SafeRpcWrapper<AssetId>, SafeRpcWrapper<Balance>>;
+
+		/// Time weighted average price of each asset of the pool, over at least the last `window`
+		/// milliseconds. `None` if the pool has not been observed for long enough.
+		#[api_version(2)]
+		fn twap(
+			pool_id: SafeRpcWrapper<PoolId>,
+			window: SafeRpcWrapper<u64>,
+		) -> Option<BTreeMap<SafeRpcWrapper<AssetId>, Rate>>;
 	}
 }
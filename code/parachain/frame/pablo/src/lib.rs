@@ -53,8 +53,11 @@ pub use crate::weights::WeightInfo;
 pub mod pallet {
 	use crate::{
 		dual_asset_constant_product::DualAssetConstantProduct,
-		twap::{update_price_cumulative_state, update_twap_state},
-		types::{PriceCumulative, TimeWeightedAveragePrice},
+		twap::{
+			get_next_price_cumulative, get_twap_price, record_price_cumulative,
+			update_price_cumulative_state, update_twap_state,
+		},
+		types::{PriceCumulative, PriceCumulativeObservations, TimeWeightedAveragePrice},
 		WeightInfo,
 	};
 	use codec::FullCodec;
@@ -65,7 +68,7 @@ pub mod pallet {
 	use composable_traits::{
 		assets::CreateAsset,
 		defi::{CurrencyPair, Rate},
-		dex::{Amm, BasicPoolInfo, Fee, PriceAggregate},
+		dex::{Amm, AmmPools, AmmTwap, BasicPoolInfo, Fee, PriceAggregate},
 	};
 	use core::fmt::Debug;
 	use frame_support::{
@@ -95,7 +98,7 @@ pub mod pallet {
 	};
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 	use sp_runtime::{
-		traits::{AccountIdConversion, Convert, One, Zero},
+		traits::{AccountIdConversion, CheckedSub, Convert, One, Saturating, Zero},
 		ArithmeticError, FixedPointNumber, Permill, TransactionOutcome,
 	};
 	use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
@@ -130,6 +133,11 @@ pub mod pallet {
 	pub(crate) type TWAPStateOf<T> = TimeWeightedAveragePrice<MomentOf<T>, <T as Config>::Balance>;
 	pub(crate) type PriceCumulativeStateOf<T> =
 		PriceCumulative<MomentOf<T>, <T as Config>::Balance>;
+	pub(crate) type PriceCumulativeObservationsOf<T> = PriceCumulativeObservations<
+		MomentOf<T>,
+		<T as Config>::Balance,
+		<T as Config>::MaxTWAPObservations,
+	>;
 
 	// TODO (vim): Introduce a  new event for "buy" operation as swap is different.
 	#[pallet::event]
@@ -231,6 +239,10 @@ pub mod pallet {
 		/// Cannot buy an asset with itself.
 		CannotBuyAssetWithItself,
 		IncorrectPoolConfig,
+		/// TWAP windows must be longer than zero.
+		InvalidTwapWindow,
+		/// No observation of the pool is old enough to cover the requested TWAP window.
+		NotEnoughTwapObservations,
 	}
 
 	#[pallet::config]
@@ -296,6 +308,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type TWAPInterval: Get<MomentOf<Self>>;
 
+		/// Number of price cumulative observations kept per pool, one per `TWAPInterval` at most.
+		/// Bounds the longest window TWAPs can be read over.
+		#[pallet::constant]
+		type MaxTWAPObservations: Get<u32>;
+
 		type WeightInfo: WeightInfo;
 
 		type LPTokenExistentialDeposit: Get<Self::Balance>;
@@ -330,6 +347,12 @@ pub mod pallet {
 	pub type PriceCumulativeState<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, PriceCumulativeStateOf<T>, OptionQuery>;
 
+	/// Ring buffer of past price cumulatives of TWAP enabled pools, used to compute TWAPs over
+	/// arbitrary windows.
+	#[pallet::storage]
+	pub type TWAPObservations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PriceCumulativeObservationsOf<T>, OptionQuery>;
+
 	#[pallet::storage]
 	#[allow(clippy::disallowed_types)] // Allow for `ValueQuery` because of nonce
 	pub type LPTNonce<T: Config> = StorageValue<_, u64, ValueQuery, Nonce<OneInit, SafeIncrement>>;
@@ -386,7 +409,9 @@ pub mod pallet {
 		///
 		/// Emits `Swapped` event when successful.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::buy())]
+		#[pallet::weight(
+			T::WeightInfo::buy().saturating_add(Pallet::<T>::twap_observation_weight())
+		)]
 		pub fn buy(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -405,7 +430,9 @@ pub mod pallet {
 		///
 		/// Emits `Swapped` event when successful.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::swap())]
+		#[pallet::weight(
+			T::WeightInfo::swap().saturating_add(Pallet::<T>::twap_observation_weight())
+		)]
 		pub fn swap(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -422,7 +449,9 @@ pub mod pallet {
 		///
 		/// Emits `LiquidityAdded` event when successful.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		#[pallet::weight(
+			T::WeightInfo::add_liquidity().saturating_add(Pallet::<T>::twap_observation_weight())
+		)]
 		pub fn add_liquidity(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -439,7 +468,9 @@ pub mod pallet {
 		///
 		/// Emits `LiquidityRemoved` event when successful.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		#[pallet::weight(
+			T::WeightInfo::remove_liquidity().saturating_add(Pallet::<T>::twap_observation_weight())
+		)]
 		pub fn remove_liquidity(
 			origin: OriginFor<T>,
 			pool_id: T::PoolId,
@@ -477,14 +508,13 @@ pub mod pallet {
 					quote_twap: rate_quote,
 				},
 			);
-			PriceCumulativeState::<T>::insert(
-				pool_id,
-				PriceCumulative {
-					timestamp: current_timestamp,
-					base_price_cumulative,
-					quote_price_cumulative,
-				},
-			);
+			let price_cumulative = PriceCumulative {
+				timestamp: current_timestamp,
+				base_price_cumulative,
+				quote_price_cumulative,
+			};
+			record_price_cumulative::<T>(pool_id, &price_cumulative);
+			PriceCumulativeState::<T>::insert(pool_id, price_cumulative);
 			Ok(())
 		}
 	}
@@ -516,7 +546,12 @@ pub mod pallet {
 					},
 				);
 				if result.is_ok() {
-					weight = weight.saturating_add(Weight::from_parts(1, 0));
+					weight = weight
+						.saturating_add(Weight::from_parts(1, 0))
+						.saturating_add(Self::twap_observation_weight());
+					if let Some(price_cumulative) = PriceCumulativeState::<T>::get(pool_id) {
+						record_price_cumulative::<T>(pool_id, &price_cumulative);
+					}
 					if let Some(updated_twap) = TWAPState::<T>::get(pool_id) {
 						#[allow(deprecated)]
 						if let Ok(assets) = Self::pool_ordered_pair(pool_id) {
@@ -609,6 +644,15 @@ pub mod pallet {
 			.ok_or(ArithmeticError::Overflow)?)
 		}
 
+		/// Upper bound of the weight of storing a TWAP observation, which any trade updating the
+		/// TWAP of its pool may do.
+		pub(crate) fn twap_observation_weight() -> Weight {
+			T::DbWeight::get().reads_writes(1, 1).saturating_add(Weight::from_parts(
+				0,
+				PriceCumulativeObservationsOf::<T>::max_encoded_len() as u64,
+			))
+		}
+
 		fn update_twap(pool_id: T::PoolId) -> Result<(), DispatchError> {
 			#[allow(deprecated)]
 			let currency_pair = Self::pool_ordered_pair(pool_id)?; // update price cumulatives
@@ -635,6 +679,16 @@ pub mod pallet {
 					},
 				)?;
 				if let Some(updated_twap) = updated_twap {
+					// the TWAP is updated once per `TWAPInterval`, which is when an observation is
+					// due, so that other trades do not touch the observations
+					record_price_cumulative::<T>(
+						pool_id,
+						&PriceCumulative {
+							timestamp: updated_twap.timestamp,
+							base_price_cumulative,
+							quote_price_cumulative,
+						},
+					);
 					Self::deposit_event(Event::<T>::TwapUpdated {
 						pool_id,
 						timestamp: updated_twap.timestamp,
@@ -649,6 +703,37 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Average price of each asset of `pool_id` in the other asset, from the most recent
+		/// observation at least `window` old up to now.
+		pub fn do_twap(
+			pool_id: T::PoolId,
+			window: MomentOf<T>,
+		) -> Result<BTreeMap<T::AssetId, Rate>, DispatchError> {
+			ensure!(!window.is_zero(), Error::<T>::InvalidTwapWindow);
+			#[allow(deprecated)]
+			let currency_pair = Self::pool_ordered_pair(pool_id)?;
+			let latest = PriceCumulativeState::<T>::get(pool_id)
+				.ok_or(Error::<T>::NotEnoughTwapObservations)?;
+			let now = T::Time::now();
+			let (base_price_cumulative, quote_price_cumulative) = if now > latest.timestamp {
+				get_next_price_cumulative::<T>(pool_id, &latest)?
+			} else {
+				(latest.base_price_cumulative, latest.quote_price_cumulative)
+			};
+			let start = now.checked_sub(&window).and_then(|start| {
+				TWAPObservations::<T>::get(pool_id)?.at_or_before(&start).cloned()
+			});
+			let start = start.ok_or(Error::<T>::NotEnoughTwapObservations)?;
+			let (base_twap, quote_twap) = get_twap_price::<T>(
+				base_price_cumulative,
+				start.base_price_cumulative,
+				quote_price_cumulative,
+				start.quote_price_cumulative,
+				now.saturating_sub(start.timestamp),
+			)?;
+			Ok(BTreeMap::from([(currency_pair.base, base_twap), (currency_pair.quote, quote_twap)]))
+		}
+
 		#[transactional]
 		fn disburse_fees(
 			who: &T::AccountId,
@@ -732,6 +817,64 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> AmmTwap for Pallet<T> {
+		type Moment = MomentOf<T>;
+
+		fn twap(
+			pool_id: Self::PoolId,
+			window: Self::Moment,
+		) -> Result<BTreeMap<Self::AssetId, Rate>, DispatchError> {
+			Self::do_twap(pool_id, window)
+		}
+	}
+
+	impl<T: Config> AmmPools for Pallet<T> {
+		fn pools(limit: u32) -> Vec<Self::PoolId> {
+			Pools::<T>::iter_keys().take(limit as usize).collect()
+		}
+
+		fn balances(
+			pool_id: Self::PoolId,
+		) -> Result<BTreeMap<Self::AssetId, Self::Balance>, DispatchError> {
+			let pool = Self::get_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			match pool {
+				PoolConfiguration::DualAssetConstantProduct(info) => Ok(info
+					.assets_weights
+					.iter()
+					.map(|(asset_id, _)| (*asset_id, T::Assets::balance(*asset_id, &pool_account)))
+					.collect()),
+			}
+		}
+
+		fn buy_price(
+			pool_id: Self::PoolId,
+			in_asset_id: Self::AssetId,
+			out_asset: AssetAmount<Self::AssetId, Self::Balance>,
+			calculate_with_fees: bool,
+		) -> Result<SwapResult<Self::AssetId, Self::Balance>, DispatchError> {
+			ensure!(in_asset_id != out_asset.asset_id, Error::<T>::CannotBuyAssetWithItself);
+			let pool = Self::get_pool(pool_id)?;
+			let pool_account = Self::account_id(&pool_id);
+			match pool {
+				PoolConfiguration::DualAssetConstantProduct(info) => {
+					let (_amount_out, amount_sent, fee) = DualAssetConstantProduct::<T>::do_buy(
+						&info,
+						&pool_account,
+						out_asset,
+						in_asset_id,
+						calculate_with_fees,
+					)?;
+
+					Ok(SwapResult {
+						value: amount_sent,
+						fee: AssetAmount::new(amount_sent.asset_id, fee.fee),
+					})
+				},
+			}
+		}
+	}
+
 	impl<T: Config> Amm for Pallet<T> {
 		type AssetId = T::AssetId;
 		type Balance = T::Balance;
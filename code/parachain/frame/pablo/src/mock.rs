@@ -166,6 +166,7 @@ parameter_types! {
 	pub MaxInitialWeight: Permill = Permill::from_percent(95);
 	pub MinFinalWeight: Permill = Permill::from_percent(5);
 	pub const TWAPInterval: Moment = MILLISECS_PER_BLOCK * TWAP_INTERVAL_BLOCKS;
+	pub const MaxTWAPObservations: u32 = 16;
 }
 
 parameter_types! {
@@ -243,6 +244,7 @@ impl pablo::Config for Test {
 	type EnableTwapOrigin = EnsureRoot<AccountId>;
 	type Time = Timestamp;
 	type TWAPInterval = TWAPInterval;
+	type MaxTWAPObservations = MaxTWAPObservations;
 	type WeightInfo = ();
 	type LPTokenExistentialDeposit = LPTokenED;
 }
@@ -905,9 +905,9 @@ proptest! {
 
 mod twap {
 	use super::*;
-	use crate::types::TimeWeightedAveragePrice;
+	use crate::{types::TimeWeightedAveragePrice, TWAPObservations};
 	use composable_tests_helpers::test::block::process_and_progress_blocks;
-	use composable_traits::defi::Rate;
+	use composable_traits::{defi::Rate, dex::AmmTwap};
 	use sp_runtime::traits::One;
 
 	#[test]
@@ -1095,6 +1095,98 @@ mod twap {
 			assert!(twap.quote_twap < max_quote_price);
 		});
 	}
+	#[test]
+	fn twap_over_window_uses_past_observations() {
+		new_test_ext().execute_with(|| {
+			let unit = 1_000_000_000_000_u128;
+			let pool_id = create_pool(
+				BTC,
+				USDT,
+				100_u128 * unit,
+				100_u128 * unit,
+				LP_TOKEN_ID,
+				Permill::zero(),
+				Permill::zero(),
+			);
+			let interval = TWAPInterval::get();
+
+			System::set_block_number(0);
+			assert_noop!(
+				Pablo::do_twap(pool_id, interval),
+				Error::<Test>::NotEnoughTwapObservations
+			);
+			assert_ok!(Pablo::enable_twap(RuntimeOrigin::root(), pool_id));
+			assert_noop!(Pablo::do_twap(pool_id, 0), Error::<Test>::InvalidTwapWindow);
+			assert_noop!(
+				Pablo::do_twap(pool_id, interval),
+				Error::<Test>::NotEnoughTwapObservations
+			);
+
+			process_and_progress_blocks::<Pablo, Test>(2 * TWAP_INTERVAL_BLOCKS as usize);
+			assert_eq!(
+				<Pablo as AmmTwap>::twap(pool_id, interval),
+				Ok(BTreeMap::from([(BTC, Rate::one()), (USDT, Rate::one())]))
+			);
+
+			// move the price and let it settle
+			assert_ok!(Tokens::mint_into(USDT, &BOB, 50 * unit));
+			assert_ok!(Pablo::swap(
+				RuntimeOrigin::signed(BOB),
+				pool_id,
+				AssetAmount::new(USDT, 50 * unit),
+				AssetAmount::new(BTC, 0),
+				false
+			));
+			process_and_progress_blocks::<Pablo, Test>(2 * TWAP_INTERVAL_BLOCKS as usize);
+
+			// the longer window still averages the price from before the swap
+			let short_twaps = Pablo::do_twap(pool_id, interval).expect("short window is covered");
+			let long_twaps = Pablo::do_twap(pool_id, 4 * interval).expect("long window is covered");
+			for asset_id in [BTC, USDT] {
+				let (short_twap, long_twap) = (short_twaps[&asset_id], long_twaps[&asset_id]);
+				assert!(
+					(short_twap < long_twap && long_twap < Rate::one()) ||
+						(short_twap > long_twap && long_twap > Rate::one())
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn twap_observations_are_bounded() {
+		new_test_ext().execute_with(|| {
+			let unit = 1_000_000_000_000_u128;
+			let pool_id = create_pool(
+				BTC,
+				USDT,
+				100_u128 * unit,
+				100_u128 * unit,
+				LP_TOKEN_ID,
+				Permill::zero(),
+				Permill::zero(),
+			);
+			let interval = TWAPInterval::get();
+			let max_observations = MaxTWAPObservations::get();
+
+			System::set_block_number(0);
+			assert_ok!(Pablo::enable_twap(RuntimeOrigin::root(), pool_id));
+			process_and_progress_blocks::<Pablo, Test>(
+				(max_observations as usize + 2) * TWAP_INTERVAL_BLOCKS as usize,
+			);
+
+			let observations = TWAPObservations::<Test>::get(pool_id).expect("pool is observed");
+			assert_eq!(observations.iter().count(), max_observations as usize);
+			assert!(observations
+				.iter()
+				.zip(observations.iter().skip(1))
+				.all(|(older, newer)| newer.timestamp >= older.timestamp + interval));
+
+			// the first observations got overwritten
+			let now = (max_observations as u64 + 2) * interval;
+			assert_noop!(Pablo::do_twap(pool_id, now), Error::<Test>::NotEnoughTwapObservations);
+			assert_ok!(Pablo::do_twap(pool_id, (max_observations as u64 - 1) * interval));
+		});
+	}
 }
 
 #[test]
@@ -1,6 +1,6 @@
 use crate::{
 	types::{PriceCumulative, TimeWeightedAveragePrice},
-	Config, MomentOf, PriceCumulativeStateOf, PriceRatio, TWAPStateOf,
+	Config, MomentOf, PriceCumulativeStateOf, PriceRatio, TWAPObservations, TWAPStateOf,
 };
 use composable_maths::dex::price::{compute_next_price_cumulative, compute_twap};
 use composable_traits::defi::Rate;
@@ -106,3 +106,23 @@ pub(crate) fn update_twap_state<T: Config>(
 	}
 	Ok(())
 }
+
+/// Stores `price_cumulative` as an observation of `pool_id` if `TWAPInterval` passed since the
+/// last one.
+///
+/// Decodes the whole ring buffer, so it is only called when the TWAP of the pool was updated,
+/// which happens at the same pace as observations are due.
+pub(crate) fn record_price_cumulative<T: Config>(
+	pool_id: T::PoolId,
+	price_cumulative: &PriceCumulativeStateOf<T>,
+) {
+	TWAPObservations::<T>::mutate(pool_id, |observations| {
+		let observations = observations.get_or_insert_with(Default::default);
+		let is_due = observations.latest().map_or(true, |latest| {
+			price_cumulative.timestamp >= latest.timestamp.saturating_add(T::TWAPInterval::get())
+		});
+		if is_due {
+			observations.push(price_cumulative.clone());
+		}
+	});
+}
@@ -1,7 +1,11 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use composable_traits::defi::Rate;
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, DefaultNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
+use sp_std::fmt::Debug;
 
 #[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
 pub struct TimeWeightedAveragePrice<Timestamp, Balance> {
@@ -11,9 +15,67 @@ pub struct TimeWeightedAveragePrice<Timestamp, Balance> {
 	pub base_twap: Rate,
 	pub quote_twap: Rate,
 }
-#[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, PartialEq, Eq, RuntimeDebug)]
 pub struct PriceCumulative<Timestamp, Balance> {
 	pub timestamp: Timestamp,
 	pub base_price_cumulative: Balance,
 	pub quote_price_cumulative: Balance,
 }
+
+/// Ring buffer of the last `S` price cumulative observations of a pool.
+#[derive(
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	CloneNoBound,
+	DefaultNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+)]
+#[scale_info(skip_type_params(S))]
+pub struct PriceCumulativeObservations<
+	Timestamp: Clone + PartialEq + Debug,
+	Balance: Clone + PartialEq + Debug,
+	S: Get<u32>,
+> {
+	observations: BoundedVec<PriceCumulative<Timestamp, Balance>, S>,
+	/// Index of the oldest observation once the buffer is full.
+	oldest: u32,
+}
+
+impl<Timestamp, Balance, S> PriceCumulativeObservations<Timestamp, Balance, S>
+where
+	Timestamp: Clone + PartialEq + Debug + PartialOrd,
+	Balance: Clone + PartialEq + Debug,
+	S: Get<u32>,
+{
+	/// Appends `observation`, overwriting the oldest one if the buffer is full.
+	pub fn push(&mut self, observation: PriceCumulative<Timestamp, Balance>) {
+		if self.observations.try_push(observation.clone()).is_err() {
+			if let Some(oldest) = self.observations.get_mut(self.oldest as usize) {
+				*oldest = observation;
+				self.oldest = (self.oldest + 1) % S::get();
+			}
+		}
+	}
+
+	/// Most recent observation.
+	pub fn latest(&self) -> Option<&PriceCumulative<Timestamp, Balance>> {
+		self.iter().last()
+	}
+
+	/// Most recent observation made at or before `timestamp`.
+	pub fn at_or_before(
+		&self,
+		timestamp: &Timestamp,
+	) -> Option<&PriceCumulative<Timestamp, Balance>> {
+		self.iter().take_while(|observation| observation.timestamp <= *timestamp).last()
+	}
+
+	/// Observations from the oldest to the most recent.
+	pub fn iter(&self) -> impl Iterator<Item = &PriceCumulative<Timestamp, Balance>> {
+		let (newest, oldest) = self.observations.split_at(self.oldest as usize);
+		oldest.iter().chain(newest.iter())
+	}
+}
@@ -111,6 +111,9 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// Xcm fee and weight updated
 		XcmWeightFeeUpdated(XcmWeightFeeMisc<Weight, BalanceOf<T>>),
+		/// Xcm fee and weight removed, the call falls back to the default ones or can no longer
+		/// be sent
+		XcmWeightFeeRemoved(XcmCall),
 	}
 
 	#[pallet::storage]
@@ -139,6 +142,8 @@ pub mod pallet {
 		SendFailure,
 		/// Can not convert account success
 		ConvertAccountError,
+		/// The relaychain operation has no xcm fee and weight configured
+		XcmCallNotConfigured,
 	}
 
 	#[pallet::call]
@@ -161,6 +166,18 @@ pub mod pallet {
 			Self::deposit_event(Event::<T>::XcmWeightFeeUpdated(xcm_weight_fee_misc));
 			Ok(())
 		}
+
+		/// Remove the xcm fees and weight of a relaychain operation
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_xcm_weight_fee())]
+		#[transactional]
+		pub fn remove_xcm_weight_fee(origin: OriginFor<T>, xcm_call: XcmCall) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			XcmWeightFee::<T>::remove(&xcm_call);
+			Self::deposit_event(Event::<T>::XcmWeightFeeRemoved(xcm_call));
+			Ok(())
+		}
 	}
 }
 
@@ -213,6 +230,64 @@ pub trait XcmHelper<T: pallet_xcm::Config, Balance, TAccountId> {
 		index: u16,
 		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
 	) -> Result<QueryId, DispatchError>;
+
+	fn do_chill(
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	fn do_payout_stakers(
+		validator_stash: TAccountId,
+		era: u32,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	fn do_set_payee(
+		payee: RewardDestination<TAccountId>,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	fn do_join_pool(
+		amount: Balance,
+		pool_id: u32,
+		stash: TAccountId,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	fn do_unbond_from_pool(
+		unbonding_points: Balance,
+		stash: TAccountId,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	fn do_withdraw_from_pool(
+		num_slashing_spans: u32,
+		stash: TAccountId,
+		para_account_id: TAccountId,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	/// Add a proxy to the derivative account `index`, or to the sovereign account if `None`.
+	fn do_add_proxy(
+		delegate: TAccountId,
+		proxy_type: ProxyType,
+		delay: BlockNumber,
+		index: Option<u16>,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
+
+	/// Remove a proxy of the derivative account `index`, or of the sovereign account if `None`.
+	fn do_remove_proxy(
+		delegate: TAccountId,
+		proxy_type: ProxyType,
+		delay: BlockNumber,
+		index: Option<u16>,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError>;
 }
 
 impl<T: Config> Pallet<T> {
@@ -245,6 +320,36 @@ impl<T: Config> Pallet<T> {
 		Ok(query_id)
 	}
 
+	/// Weight and fee of `xcm_call`. Operations without a default weight and fee must have been
+	/// configured by `UpdateOrigin` to be sent.
+	pub fn configured_xcm_weight_fee(
+		xcm_call: XcmCall,
+	) -> Result<XcmWeightFeeMisc<Weight, BalanceOf<T>>, DispatchError> {
+		if xcm_call.has_default_weight_fee() {
+			return Ok(Self::xcm_weight_fee(xcm_call))
+		}
+		XcmWeightFee::<T>::try_get(xcm_call).map_err(|_| Error::<T>::XcmCallNotConfigured.into())
+	}
+
+	/// Send `message` to the relaychain, reporting its outcome to `notify`.
+	fn send_with_notify(
+		mut message: Xcm<()>,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let query_id = Self::report_outcome_notify(
+			&mut message,
+			MultiLocation::parent(),
+			notify,
+			T::NotifyTimeout::get(),
+		)?;
+
+		if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), message) {
+			return Err(Error::<T>::SendFailure.into())
+		}
+
+		Ok(query_id)
+	}
+
 	// Since xcm v3 doesn't support utility.batch_all
 	// instead, here append one more transact msg
 	//
@@ -561,4 +666,253 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 			query_id
 		}))
 	}
+
+	fn do_chill(
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::Chill)?;
+		switch_relay!({
+			let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+				UtilityAsDerivativeCall {
+					index,
+					call: RelaychainCall::Staking::<T>(StakingCall::Chill),
+				},
+			)));
+
+			let msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_payout_stakers(
+		validator_stash: AccountIdOf<T>,
+		era: u32,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::PayoutStakers)?;
+		switch_relay!({
+			// anyone can pay out, so no need to dispatch it from a derivative account
+			let call = RelaychainCall::Staking::<T>(StakingCall::PayoutStakers(
+				StakingPayoutStakersCall { validator_stash, era },
+			));
+
+			let msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_set_payee(
+		payee: RewardDestination<AccountIdOf<T>>,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::SetPayee)?;
+		switch_relay!({
+			let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+				UtilityAsDerivativeCall {
+					index,
+					call: RelaychainCall::Staking::<T>(StakingCall::SetPayee(
+						StakingSetPayeeCall { payee },
+					)),
+				},
+			)));
+
+			let msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_join_pool(
+		amount: BalanceOf<T>,
+		pool_id: u32,
+		stash: AccountIdOf<T>,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::JoinPool)?;
+		switch_relay!({
+			let call = RelaychainCall::<T>::Balances(BalancesCall::TransferKeepAlive(
+				BalancesTransferKeepAliveCall { dest: T::Lookup::unlookup(stash), value: amount },
+			));
+
+			let mut msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			let call = RelaychainCall::<T>::Utility(Box::new(UtilityCall::AsDerivative(
+				UtilityAsDerivativeCall {
+					index,
+					call: RelaychainCall::NominationPools::<T>(NominationPoolsCall::Join(
+						NominationPoolsJoinCall { amount, pool_id },
+					)),
+				},
+			)));
+			Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_unbond_from_pool(
+		unbonding_points: BalanceOf<T>,
+		stash: AccountIdOf<T>,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::UnbondFromPool)?;
+		switch_relay!({
+			let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+				UtilityAsDerivativeCall {
+					index,
+					call: RelaychainCall::NominationPools::<T>(NominationPoolsCall::Unbond(
+						NominationPoolsUnbondCall {
+							member_account: T::Lookup::unlookup(stash),
+							unbonding_points,
+						},
+					)),
+				},
+			)));
+
+			let msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_withdraw_from_pool(
+		num_slashing_spans: u32,
+		stash: AccountIdOf<T>,
+		para_account_id: AccountIdOf<T>,
+		index: u16,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::WithdrawFromPool)?;
+		switch_relay!({
+			let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+				UtilityAsDerivativeCall {
+					index,
+					call: RelaychainCall::NominationPools::<T>(
+						NominationPoolsCall::WithdrawUnbonded(
+							NominationPoolsWithdrawUnbondedCall {
+								member_account: T::Lookup::unlookup(stash),
+								num_slashing_spans,
+							},
+						),
+					),
+				},
+			)));
+
+			let mut msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+				UtilityAsDerivativeCall {
+					index,
+					call: RelaychainCall::Balances::<T>(BalancesCall::TransferAll(
+						BalancesTransferAllCall {
+							dest: T::Lookup::unlookup(para_account_id),
+							keep_alive: true,
+						},
+					)),
+				},
+			)));
+			Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_add_proxy(
+		delegate: AccountIdOf<T>,
+		proxy_type: ProxyType,
+		delay: BlockNumber,
+		index: Option<u16>,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::AddProxy)?;
+		switch_relay!({
+			let call =
+				RelaychainCall::<T>::Proxy(Box::new(ProxyCall::AddProxy(ProxyAddProxyCall {
+					delegate: T::Lookup::unlookup(delegate),
+					proxy_type,
+					delay,
+				})));
+			let call = match index {
+				Some(index) => RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+					UtilityAsDerivativeCall { index, call },
+				))),
+				None => call,
+			};
+
+			let msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
+
+	fn do_remove_proxy(
+		delegate: AccountIdOf<T>,
+		proxy_type: ProxyType,
+		delay: BlockNumber,
+		index: Option<u16>,
+		notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+	) -> Result<QueryId, DispatchError> {
+		let xcm_weight_fee_misc = Self::configured_xcm_weight_fee(XcmCall::RemoveProxy)?;
+		switch_relay!({
+			let call = RelaychainCall::<T>::Proxy(Box::new(ProxyCall::RemoveProxy(
+				ProxyRemoveProxyCall { delegate: T::Lookup::unlookup(delegate), proxy_type, delay },
+			)));
+			let call = match index {
+				Some(index) => RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+					UtilityAsDerivativeCall { index, call },
+				))),
+				None => call,
+			};
+
+			let msg = Self::do_ump_transact(
+				call.encode().into(),
+				xcm_weight_fee_misc.weight,
+				Self::refund_location(),
+				xcm_weight_fee_misc.fee,
+			)?;
+
+			Self::send_with_notify(msg, notify)
+		})
+	}
 }
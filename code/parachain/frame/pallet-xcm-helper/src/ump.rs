@@ -79,6 +79,13 @@ pub struct StakingNominateCall<T: Config> {
 	pub targets: Vec<<T::Lookup as StaticLookup>::Source>,
 }
 
+/// Relaychain staking.set_payee call arguments
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct StakingSetPayeeCall<T: Config> {
+	/// A destination account for payment.
+	pub payee: RewardDestination<T::AccountId>,
+}
+
 /// Relaychain staking.payout_stakers call arguments
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct StakingPayoutStakersCall<T: Config> {
@@ -100,12 +107,54 @@ pub enum StakingCall<T: Config> {
 	WithdrawUnbonded(StakingWithdrawUnbondedCall),
 	#[codec(index = 5)]
 	Nominate(StakingNominateCall<T>),
+	#[codec(index = 6)]
+	Chill,
+	#[codec(index = 7)]
+	SetPayee(StakingSetPayeeCall<T>),
 	#[codec(index = 18)]
 	PayoutStakers(StakingPayoutStakersCall<T>),
 	#[codec(index = 19)]
 	Rebond(StakingRebondCall),
 }
 
+/// Relaychain nomination_pools.join call arguments
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct NominationPoolsJoinCall {
+	/// Bond amount
+	#[codec(compact)]
+	pub amount: u128,
+	/// Pool to join
+	pub pool_id: u32,
+}
+
+/// Relaychain nomination_pools.unbond call arguments
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct NominationPoolsUnbondCall<T: Config> {
+	/// Member of the pool unbonding
+	pub member_account: <T::Lookup as StaticLookup>::Source,
+	/// Points of the member to unbond
+	#[codec(compact)]
+	pub unbonding_points: u128,
+}
+
+/// Relaychain nomination_pools.withdraw_unbonded call arguments
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct NominationPoolsWithdrawUnbondedCall<T: Config> {
+	/// Member of the pool withdrawing
+	pub member_account: <T::Lookup as StaticLookup>::Source,
+	pub num_slashing_spans: u32,
+}
+
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum NominationPoolsCall<T: Config> {
+	#[codec(index = 0)]
+	Join(NominationPoolsJoinCall),
+	#[codec(index = 3)]
+	Unbond(NominationPoolsUnbondCall<T>),
+	#[codec(index = 5)]
+	WithdrawUnbonded(NominationPoolsWithdrawUnbondedCall<T>),
+}
+
 /// Relaychain balances.transfer_keep_alive call arguments
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct BalancesTransferKeepAliveCall<T: Config> {
@@ -182,23 +231,23 @@ pub enum SystemCall {
 }
 
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct ProxyProxyCall<RelaychainCall> {
-	pub real: AccountId,
+pub struct ProxyProxyCall<T: Config, RelaychainCall> {
+	pub real: <T::Lookup as StaticLookup>::Source,
 	pub force_proxy_type: Option<ProxyType>,
 	pub call: RelaychainCall,
 }
 
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct ProxyAddProxyCall {
-	pub delegate: AccountId,
-	pub proxy_type: Option<ProxyType>,
+pub struct ProxyAddProxyCall<T: Config> {
+	pub delegate: <T::Lookup as StaticLookup>::Source,
+	pub proxy_type: ProxyType,
 	pub delay: BlockNumber,
 }
 
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct ProxyRemoveProxyCall {
-	pub delegate: AccountId,
-	pub proxy_type: Option<ProxyType>,
+pub struct ProxyRemoveProxyCall<T: Config> {
+	pub delegate: <T::Lookup as StaticLookup>::Source,
+	pub proxy_type: ProxyType,
 	pub delay: BlockNumber,
 }
 
@@ -230,13 +279,13 @@ impl Default for ProxyType {
 }
 
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
-pub enum ProxyCall<RelaychainCall> {
+pub enum ProxyCall<T: Config, RelaychainCall> {
 	#[codec(index = 0)]
-	Proxy(ProxyProxyCall<RelaychainCall>),
+	Proxy(ProxyProxyCall<T, RelaychainCall>),
 	#[codec(index = 1)]
-	AddProxy(ProxyAddProxyCall),
+	AddProxy(ProxyAddProxyCall<T>),
 	#[codec(index = 2)]
-	RemoveProxy(ProxyRemoveProxyCall),
+	RemoveProxy(ProxyRemoveProxyCall<T>),
 }
 
 /// Relaychain utility.as_derivative call arguments
@@ -272,9 +321,11 @@ pub enum KusamaCall<T: Config> {
 	#[codec(index = 6)]
 	Staking(StakingCall<T>),
 	#[codec(index = 22)]
-	Proxy(Box<ProxyCall<Self>>),
+	Proxy(Box<ProxyCall<T, Self>>),
 	#[codec(index = 24)]
 	Utility(Box<UtilityCall<Self>>),
+	#[codec(index = 41)]
+	NominationPools(NominationPoolsCall<T>),
 	#[codec(index = 73)]
 	Crowdloans(CrowdloansCall<T>),
 }
@@ -290,7 +341,9 @@ pub enum PolkadotCall<T: Config> {
 	#[codec(index = 26)]
 	Utility(Box<UtilityCall<Self>>),
 	#[codec(index = 29)]
-	Proxy(Box<ProxyCall<Self>>),
+	Proxy(Box<ProxyCall<T, Self>>),
+	#[codec(index = 39)]
+	NominationPools(NominationPoolsCall<T>),
 	#[codec(index = 73)]
 	Crowdloans(CrowdloansCall<T>),
 }
@@ -313,6 +366,8 @@ impl Default for XcmWeightFeeMisc<Weight, Balance> {
 	}
 }
 
+/// Relaychain operations, each with its own weight and fee in `XcmWeightFee`. Operations other
+/// than the staking ones used by liquid staking are only sent once `UpdateOrigin` configured them.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum XcmCall {
 	Bond,
@@ -321,6 +376,28 @@ pub enum XcmCall {
 	Rebond,
 	WithdrawUnbonded,
 	Nominate,
+	Chill,
+	PayoutStakers,
+	SetPayee,
+	JoinPool,
+	UnbondFromPool,
+	WithdrawFromPool,
+	AddProxy,
+	RemoveProxy,
+}
+
+impl XcmCall {
+	/// Whether the operation can be sent with the default weight and fee.
+	pub fn has_default_weight_fee(&self) -> bool {
+		matches!(
+			self,
+			XcmCall::Bond |
+				XcmCall::BondExtra |
+				XcmCall::Unbond | XcmCall::Rebond |
+				XcmCall::WithdrawUnbonded |
+				XcmCall::Nominate
+		)
+	}
 }
 
 #[macro_export]
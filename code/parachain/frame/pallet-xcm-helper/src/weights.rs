@@ -46,6 +46,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_xcm_helper.
 pub trait WeightInfo {
 	fn update_xcm_weight_fee() -> Weight;
+	fn remove_xcm_weight_fee() -> Weight;
 }
 
 /// Weights for pallet_xcm_helper using the Substrate node and recommended hardware.
@@ -58,6 +59,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2u64))
 			.saturating_add(T::DbWeight::get().writes(2u64))
 	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:0 w:1)
+	fn remove_xcm_weight_fee() -> Weight {
+		Weight::from_ref_time(31_240_000u64)
+			.saturating_add(T::DbWeight::get().reads(1u64))
+			.saturating_add(T::DbWeight::get().writes(2u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -69,4 +77,11 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2u64))
 			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:0 w:1)
+	fn remove_xcm_weight_fee() -> Weight {
+		Weight::from_ref_time(31_240_000u64)
+			.saturating_add(RocksDbWeight::get().reads(1u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
 }
@@ -1,5 +1,5 @@
 use sp_core::ConstU32;
-use sp_runtime::BoundedBTreeMap;
+use sp_runtime::{BoundedBTreeMap, Perbill};
 use subxt::utils::AccountId32;
 
 // raw types from subxt
@@ -17,6 +17,34 @@ pub struct VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance: HasC
 	pub per_period: Balance,
 	/// Amount already claimed
 	pub already_claimed: Balance,
+	/// Number of periods during which nothing is released
+	pub cliff: u32,
+	/// How the total amount is released over the periods
+	pub curve: VestingCurve,
+}
+
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+pub struct VestingBreakpoint {
+	pub period: u32,
+	pub released: Perbill,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+pub enum VestingCurve {
+	Linear,
+	PiecewiseLinear(Vec<VestingBreakpoint>),
 }
 
 #[derive(
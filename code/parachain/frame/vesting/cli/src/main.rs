@@ -73,6 +73,8 @@ async fn main() -> anyhow::Result<()> {
 							),
 							("period_count", Value::u128(record.period_count as u128)),
 							("per_period", Value::u128(record.per_period)),
+							("cliff", Value::u128(0)),
+							("curve", Value::unnamed_variant("Linear", vec![])),
 						]),
 					),
 				];
@@ -311,6 +313,37 @@ async fn main() -> anyhow::Result<()> {
 								client::VestingWindow::BlockNumberBased { start: _, period: _ } =>
 									todo!(),
 							};
+							let curve = match &item.curve {
+								client::VestingCurve::Linear =>
+									Value::unnamed_variant("Linear", vec![]),
+								client::VestingCurve::PiecewiseLinear(breakpoints) =>
+									Value::unnamed_variant(
+										"PiecewiseLinear",
+										vec![Value::unnamed_composite(
+											breakpoints
+												.iter()
+												.map(|breakpoint| {
+													Value::named_composite(vec![
+														(
+															"period",
+															Value::u128(breakpoint.period as u128),
+														),
+														(
+															"released",
+															Value::unnamed_composite(vec![
+																Value::u128(
+																	breakpoint
+																		.released
+																		.deconstruct() as u128,
+																),
+															]),
+														),
+													])
+												})
+												.collect::<Vec<_>>(),
+										)],
+									),
+							};
 
 							let old_total = item.per_period * item.period_count as u128;
 							let new_total = old_total - item.already_claimed;
@@ -329,6 +362,8 @@ async fn main() -> anyhow::Result<()> {
 								),
 								("period_count", Value::u128(item.period_count as u128)),
 								("per_period", Value::u128(new_per_period)),
+								("cliff", Value::u128(item.cliff as u128)),
+								("curve", curve),
 							]);
 							retained
 						})
@@ -3,7 +3,8 @@ use crate::Pallet as Vesting;
 
 use crate::{
 	types::{
-		VestingSchedule, VestingScheduleIdSet, VestingScheduleInfo, VestingWindow::BlockNumberBased,
+		VestingCurve, VestingSchedule, VestingScheduleIdSet, VestingScheduleInfo,
+		VestingWindow::BlockNumberBased,
 	},
 	AssetIdOf, BalanceOf, BlockNumberOf, Call, Config, Pallet, VestedTransfer,
 	VestingScheduleInfoOf, VestingScheduleNonce, VestingScheduleOf, Zero,
@@ -59,7 +60,13 @@ where
 	T: Config,
 	BalanceOf<T>: From<u64>,
 {
-	VestingScheduleInfo { window: BlockNumberBased { start, period }, period_count, per_period }
+	VestingScheduleInfo {
+		window: BlockNumberBased { start, period },
+		period_count,
+		per_period,
+		cliff: 0,
+		curve: VestingCurve::Linear,
+	}
 }
 
 fn vesting_schedule<T>(
@@ -78,6 +85,8 @@ where
 		period_count,
 		per_period,
 		already_claimed: Zero::zero(),
+		cliff: 0,
+		curve: VestingCurve::Linear,
 	}
 }
 
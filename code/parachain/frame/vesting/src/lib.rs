@@ -11,7 +11,9 @@
 //! The schedule of a vesting is described by data structure `VestingSchedule`:
 //! from the time of `window.start`, for every `window.period` amount of time,
 //! `per_period` amount of balance would unlocked, until number of periods
-//! `period_count` reached. The pallet supports measuring time windows in terms of absolute
+//! `period_count` reached. A `cliff` keeps everything locked for its first periods, and a
+//! piecewise-linear `curve` can replace the even release to express back-loaded or milestone
+//! releases. The pallet supports measuring time windows in terms of absolute
 //! timestamps as well as block numbers for vesting schedules. All `VestingSchedule`s under
 //! an account could be queried in chain state.
 //!
@@ -107,9 +109,14 @@ pub mod module {
 		BlockNumberOf<T>,
 		MomentOf<T>,
 		BalanceOf<T>,
+		<T as Config>::MaxCurveBreakpoints,
+	>;
+	pub(crate) type VestingScheduleInfoOf<T> = VestingScheduleInfo<
+		BlockNumberOf<T>,
+		MomentOf<T>,
+		BalanceOf<T>,
+		<T as Config>::MaxCurveBreakpoints,
 	>;
-	pub(crate) type VestingScheduleInfoOf<T> =
-		VestingScheduleInfo<BlockNumberOf<T>, MomentOf<T>, BalanceOf<T>>;
 	pub type ScheduledItem<T> = (
 		AssetIdOf<T>,
 		<T as frame_system::Config>::AccountId,
@@ -140,6 +147,10 @@ pub mod module {
 		/// The maximum vesting schedules
 		type MaxVestingSchedules: Get<u32>;
 
+		/// The maximum number of breakpoints of a piecewise-linear vesting curve.
+		#[pallet::constant]
+		type MaxCurveBreakpoints: Get<u32>;
+
 		/// Type of time
 		type Moment: AtLeast32Bit
 			+ Parameter
@@ -182,6 +193,10 @@ pub mod module {
 		TryingToSelfVest,
 		/// There is no vesting schedule with a given id
 		VestingScheduleNotFound,
+		/// The cliff is longer than the vesting schedule
+		InvalidVestingCliff,
+		/// The curve breakpoints are not ordered or not within the vesting schedule
+		InvalidVestingCurve,
 	}
 
 	#[pallet::event]
@@ -264,6 +279,8 @@ pub mod module {
 							period_count: *period_count,
 							per_period: *per_period,
 							already_claimed: BalanceOf::<T>::zero(),
+							cliff: 0,
+							curve: VestingCurve::Linear,
 						},
 					)
 					.expect("Max vesting schedules exceeded");
@@ -291,7 +308,10 @@ pub mod module {
 		}
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -417,6 +437,7 @@ impl<T: Config> VestedTransfer for Pallet<T> {
 	type Moment = MomentOf<T>;
 	type Balance = BalanceOf<T>;
 	type MinVestedTransfer = T::MinVestedTransfer;
+	type MaxCurveBreakpoints = T::MaxCurveBreakpoints;
 	type VestingScheduleId = T::VestingScheduleId;
 	type VestingScheduleNonce = VestingScheduleNonce<T>;
 
@@ -425,7 +446,7 @@ impl<T: Config> VestedTransfer for Pallet<T> {
 		asset: Self::AssetId,
 		from: &Self::AccountId,
 		to: &Self::AccountId,
-		schedule_info: VestingScheduleInfo<Self::BlockNumber, Self::Moment, Self::Balance>,
+		schedule_info: VestingScheduleInfoOf<T>,
 	) -> frame_support::dispatch::DispatchResult {
 		ensure!(from != to, Error::<T>::TryingToSelfVest);
 
@@ -682,6 +703,8 @@ fn ensure_valid_vesting_schedule<T: Config>(
 	ensure!(!schedule.is_zero_period(), Error::<T>::ZeroVestingPeriod);
 	ensure!(schedule.end().is_some(), ArithmeticError::Overflow);
 	ensure!(!schedule.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+	ensure!(schedule.cliff <= schedule.period_count, Error::<T>::InvalidVestingCliff);
+	ensure!(schedule.curve.is_valid(schedule.period_count), Error::<T>::InvalidVestingCurve);
 
 	let total_total = schedule.total_amount()?;
 
@@ -1,9 +1,52 @@
-use crate::{weights::WeightInfo, Config, Pallet, VestingSchedules, VestingWindow};
+use crate::{
+	weights::WeightInfo, AccountIdOf, AssetIdOf, BalanceOf, BlockNumberOf, Config, MomentOf,
+	Pallet, VestingCurve, VestingSchedule, VestingScheduleOf, VestingSchedules, VestingWindow,
+};
+use codec::{Decode, Encode, HasCompact};
 use frame_support::{
 	dispatch::GetStorageVersion,
 	traits::{OnRuntimeUpgrade, StorageVersion},
 	weights::Weight,
+	BoundedBTreeMap,
 };
+use sp_std::collections::btree_map::BTreeMap;
+
+/// `VestingSchedule` before `cliff` and `curve` were added.
+#[derive(Encode, Decode)]
+pub struct VestingScheduleV1<VestingScheduleId, BlockNumber, Moment, Balance: HasCompact> {
+	pub vesting_schedule_id: VestingScheduleId,
+	pub window: VestingWindow<BlockNumber, Moment>,
+	pub period_count: u32,
+	#[codec(compact)]
+	pub per_period: Balance,
+	pub already_claimed: Balance,
+}
+
+type VestingSchedulesV1Of<T> = BoundedBTreeMap<
+	<T as Config>::VestingScheduleId,
+	VestingScheduleV1<
+		<T as Config>::VestingScheduleId,
+		BlockNumberOf<T>,
+		MomentOf<T>,
+		BalanceOf<T>,
+	>,
+	<T as Config>::MaxVestingSchedules,
+>;
+
+mod v1 {
+	use super::*;
+	use frame_support::{storage_alias, Blake2_128Concat};
+
+	#[storage_alias]
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		VestingSchedulesV1Of<T>,
+	>;
+}
 
 pub struct VestingV0ToV1<T>(sp_std::marker::PhantomData<T>);
 
@@ -13,10 +56,9 @@ impl<T: Config> OnRuntimeUpgrade for VestingV0ToV1<T> {
 		let new = StorageVersion::new(1);
 		if current < new {
 			let mut total = 0;
-			for (account, asset) in VestingSchedules::<T>::iter_keys() {
-				VestingSchedules::<T>::mutate(account, asset, |schedules| {
-					let mut new_schedules = schedules.clone();
-					for (_id, schedule) in new_schedules.iter_mut() {
+			v1::VestingSchedules::<T>::translate_values::<VestingSchedulesV1Of<T>, _>(
+				|mut schedules| {
+					for (_id, schedule) in schedules.iter_mut() {
 						total += 1;
 						if let VestingWindow::MomentBased { start, period } =
 							schedule.window.clone()
@@ -27,9 +69,51 @@ impl<T: Config> OnRuntimeUpgrade for VestingV0ToV1<T> {
 								VestingWindow::MomentBased { start: start - period, period };
 						}
 					}
-					*schedules = new_schedules;
-				});
-			}
+					Some(schedules)
+				},
+			);
+			new.put::<Pallet<T>>();
+			<() as WeightInfo>::update_vesting_schedules(total)
+		} else {
+			Weight::zero()
+		}
+	}
+}
+
+/// Adds `cliff` and `curve` to existing vesting schedules, defaulting to no cliff and a linear
+/// release which keeps their behaviour unchanged.
+pub struct VestingV1ToV2<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for VestingV1ToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let current = Pallet::<T>::on_chain_storage_version();
+		let new = StorageVersion::new(2);
+		if current == StorageVersion::new(1) {
+			let mut total = 0;
+			VestingSchedules::<T>::translate::<VestingSchedulesV1Of<T>, _>(
+				|_account: AccountIdOf<T>, _asset: AssetIdOf<T>, schedules| {
+					total += schedules.len() as u32;
+					schedules
+						.into_iter()
+						.map(|(id, schedule)| {
+							(
+								id,
+								VestingSchedule {
+									vesting_schedule_id: schedule.vesting_schedule_id,
+									window: schedule.window,
+									period_count: schedule.period_count,
+									per_period: schedule.per_period,
+									already_claimed: schedule.already_claimed,
+									cliff: 0,
+									curve: VestingCurve::Linear,
+								},
+							)
+						})
+						.collect::<BTreeMap<_, VestingScheduleOf<T>>>()
+						.try_into()
+						.ok()
+				},
+			);
 			new.put::<Pallet<T>>();
 			<() as WeightInfo>::update_vesting_schedules(total)
 		} else {
@@ -162,6 +162,7 @@ impl orml_tokens::Config for Runtime {
 parameter_types! {
 	pub const MaxVestingSchedule: u32 = 3;
 	pub const MinVestedTransfer: u64 = 5;
+	pub const MaxCurveBreakpoints: u32 = 4;
 }
 
 impl Config for Runtime {
@@ -172,6 +173,7 @@ impl Config for Runtime {
 	type UpdateSchedulesOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
 	type MaxVestingSchedules = MaxVestingSchedule;
+	type MaxCurveBreakpoints = MaxCurveBreakpoints;
 	type Moment = Moment;
 	type Time = Timestamp;
 	type VestingScheduleId = u128;
@@ -3,15 +3,18 @@
 #![cfg(test)]
 
 use super::*;
-use crate::types::{VestingSchedule, VestingScheduleInfo, VestingWindow::*};
+use crate::types::{
+	VestingBreakpoint, VestingCurve, VestingSchedule, VestingScheduleInfo, VestingWindow::*,
+};
 use frame_support::{
-	assert_noop, assert_ok,
+	assert_noop, assert_ok, bounded_vec,
 	error::BadOrigin,
-	traits::{fungibles::Mutate, TryCollect},
+	traits::{fungibles::Mutate, GetStorageVersion, OnRuntimeUpgrade, StorageVersion, TryCollect},
 };
 use frame_system::EventRecord;
 use mock::{RuntimeEvent, *};
 use orml_tokens::BalanceLock;
+use sp_runtime::Perbill;
 
 #[test]
 fn production() {
@@ -21,6 +24,8 @@ fn production() {
 		window: MomentBased { start: start - 2592000000, period: 2592000000 },
 		period_count: 24,
 		per_period,
+		cliff: 0,
+		curve: VestingCurve::Linear,
 	};
 	let total = (schedule_info.period_count as u64) * schedule_info.per_period;
 	ExtBuilder::build().execute_with(|| {
@@ -100,6 +105,8 @@ fn vesting_from_chain_spec_works() {
 					period_count: 1_u32,
 					per_period: 5_u64,
 					already_claimed: 0_u64,
+					cliff: 0,
+					curve: VestingCurve::Linear,
 				},
 			),
 			(
@@ -122,6 +129,8 @@ fn vesting_from_chain_spec_works() {
 					period_count: 3_u32,
 					per_period: 5_u64,
 					already_claimed: 0_u64,
+					cliff: 0,
+					curve: VestingCurve::Linear,
 				},
 			),
 			(
@@ -148,6 +157,8 @@ fn vesting_from_chain_spec_works() {
 					period_count: 3_u32,
 					per_period: 5_u64,
 					already_claimed: 0_u64,
+					cliff: 0,
+					curve: VestingCurve::Linear,
 				},
 			),
 		]
@@ -218,6 +229,8 @@ fn vested_transfer_self_vest_ko() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -241,6 +254,8 @@ fn vested_transfer_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -275,6 +290,8 @@ fn vested_transfer_trait_emits_vesting_schedule_added_event() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 
 		let schedule = VestingSchedule::from_input(4_u128, schedule_input.clone());
@@ -307,6 +324,8 @@ fn vested_transfer_for_moment_based_schedule_works() {
 			window: MomentBased { start: 0_u64, period: 10_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		let schedule = VestingSchedule::from_input(4_u128, schedule_input.clone());
 		assert_ok!(Vesting::vested_transfer(
@@ -338,6 +357,8 @@ fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -354,6 +375,8 @@ fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 			window: BlockNumberBased { start: 10_u64, period: 13_u64 },
 			period_count: 1_u32,
 			per_period: 7_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -367,6 +390,8 @@ fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 			window: MomentBased { start: 72000_u64, period: 5000_u64 },
 			period_count: 2_u32,
 			per_period: 7_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -390,6 +415,8 @@ fn cannot_use_fund_if_not_claimed() {
 			window: BlockNumberBased { start: 10_u64, period: 10_u64 },
 			period_count: 1_u32,
 			per_period: 50_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -402,6 +429,8 @@ fn cannot_use_fund_if_not_claimed() {
 			window: MomentBased { start: 1000_u64, period: 5000_u64 },
 			period_count: 1_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -429,6 +458,8 @@ fn vested_transfer_fails_if_zero_period_count() {
 			window: BlockNumberBased { start: 1_u64, period: 1_u64 },
 			period_count: 0_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -450,6 +481,8 @@ fn vested_transfer_fails_if_zero_period() {
 			window: BlockNumberBased { start: 1_u64, period: 0_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -482,6 +515,8 @@ fn vested_transfer_fails_if_transfer_err() {
 			window: BlockNumberBased { start: 1_u64, period: 1_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -503,6 +538,8 @@ fn vested_transfer_fails_if_overflow() {
 			window: BlockNumberBased { start: 1_u64, period: 1_u64 },
 			period_count: 2_u32,
 			per_period: u64::MAX,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -519,6 +556,8 @@ fn vested_transfer_fails_if_overflow() {
 			window: BlockNumberBased { start: u64::MAX, period: 1_u64 },
 			period_count: 2_u32,
 			per_period: 1_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -535,6 +574,8 @@ fn vested_transfer_fails_if_overflow() {
 			window: MomentBased { start: u64::MAX, period: 1_u64 },
 			period_count: 2_u32,
 			per_period: 1_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -556,6 +597,8 @@ fn vested_transfer_fails_if_bad_origin() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 1_u32,
 			per_period: 100_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -577,6 +620,8 @@ fn claim_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -671,6 +716,8 @@ fn claim_nonexistent_schedules() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -733,6 +780,8 @@ fn claim_with_id_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -747,6 +796,8 @@ fn claim_with_id_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 15_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -761,6 +812,8 @@ fn claim_with_id_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 3_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -895,6 +948,8 @@ fn claim_with_multiple_ids_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -909,6 +964,8 @@ fn claim_with_multiple_ids_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 15_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -923,6 +980,8 @@ fn claim_with_multiple_ids_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 3_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1007,6 +1066,8 @@ fn claim_for_with_id_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1021,6 +1082,8 @@ fn claim_for_with_id_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 15_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1091,6 +1154,8 @@ fn claim_for_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1144,6 +1209,8 @@ fn claim_for_works_moment_based() {
 			window: MomentBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1189,6 +1256,8 @@ fn update_vesting_schedules_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1202,6 +1271,8 @@ fn update_vesting_schedules_works() {
 			window: MomentBased { start: 0_u64, period: 60000_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1215,11 +1286,15 @@ fn update_vesting_schedules_works() {
 			window: BlockNumberBased { start: 0_u64, period: 20_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		let updated_moment_based_schedule = VestingScheduleInfo {
 			window: MomentBased { start: 0_u64, period: 120000_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::update_vesting_schedules(
 			RuntimeOrigin::root(),
@@ -1278,6 +1353,8 @@ fn update_vesting_schedules_does_not_break_locking_amount() {
 			window: BlockNumberBased { start: 0_u64, period: 1_u64 },
 			period_count: 100_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 
 		assert_ok!(Vesting::vested_transfer(
@@ -1293,6 +1370,8 @@ fn update_vesting_schedules_does_not_break_locking_amount() {
 			window: BlockNumberBased { start: 0_u64, period: 1_u64 },
 			period_count: 50_u32,
 			per_period: 8_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 
 		assert_ok!(Vesting::vested_transfer(
@@ -1322,6 +1401,8 @@ fn update_vesting_schedules_does_not_break_locking_amount() {
 			window: BlockNumberBased { start: 30_u64, period: 1_u64 },
 			period_count: 60_u32,
 			per_period: 5_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 
 		// Locks 200 * 2 = 400
@@ -1329,6 +1410,8 @@ fn update_vesting_schedules_does_not_break_locking_amount() {
 			window: BlockNumberBased { start: 40_u64, period: 1_u64 },
 			period_count: 200_u32,
 			per_period: 2_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 
 		// Unlocks all and locks 300 + 400 = 700
@@ -1369,6 +1452,8 @@ fn vested_transfer_check_for_min() {
 			window: BlockNumberBased { start: 1_u64, period: 1_u64 },
 			period_count: 1_u32,
 			per_period: 3_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_noop!(
 			Vesting::vested_transfer(
@@ -1390,6 +1475,8 @@ fn multiple_vesting_schedule_claim_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		let schedule = VestingSchedule::from_input(4_u128, schedule_input.clone());
 		assert_ok!(Vesting::vested_transfer(
@@ -1404,6 +1491,8 @@ fn multiple_vesting_schedule_claim_works() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 3_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		let schedule2 = VestingSchedule::from_input(5_u128, schedule2_input.clone());
 
@@ -1455,11 +1544,15 @@ fn exceeding_maximum_schedules_should_fail() {
 			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		let moment_schedule_input = VestingScheduleInfo {
 			window: MomentBased { start: 0_u64, period: 10_u64 },
 			period_count: 2_u32,
 			per_period: 10_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::root(),
@@ -1511,3 +1604,201 @@ fn exceeding_maximum_schedules_should_fail() {
 		);
 	});
 }
+
+#[test]
+fn vested_transfer_with_cliff_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule_input = VestingScheduleInfo {
+			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
+			period_count: 4_u32,
+			per_period: 10_u64,
+			cliff: 2,
+			curve: VestingCurve::Linear,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::root(),
+			ALICE,
+			BOB,
+			MockCurrencyId::BTC,
+			schedule_input,
+		));
+
+		// nothing is released before the cliff
+		System::set_block_number(11);
+		assert_ok!(Vesting::claim(
+			RuntimeOrigin::signed(BOB),
+			MockCurrencyId::BTC,
+			VestingScheduleIdSet::All
+		));
+		assert_eq!(
+			Tokens::locks(&BOB, MockCurrencyId::BTC).get(0),
+			Some(&BalanceLock { id: VESTING_LOCK_ID, amount: 40_u64 })
+		);
+
+		// what accrued during the cliff is released at once
+		System::set_block_number(21);
+		assert_ok!(Vesting::claim(
+			RuntimeOrigin::signed(BOB),
+			MockCurrencyId::BTC,
+			VestingScheduleIdSet::All
+		));
+		assert_eq!(
+			Tokens::locks(&BOB, MockCurrencyId::BTC).get(0),
+			Some(&BalanceLock { id: VESTING_LOCK_ID, amount: 20_u64 })
+		);
+
+		System::set_block_number(41);
+		assert_ok!(Vesting::claim(
+			RuntimeOrigin::signed(BOB),
+			MockCurrencyId::BTC,
+			VestingScheduleIdSet::All
+		));
+		assert_eq!(Tokens::locks(&BOB, MockCurrencyId::BTC), vec![]);
+		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB, MockCurrencyId::BTC));
+	});
+}
+
+#[test]
+fn vested_transfer_with_piecewise_linear_curve_works() {
+	ExtBuilder::build().execute_with(|| {
+		// back-loaded: 10% after 2 periods, 20% after 3 periods, the rest at the end
+		let schedule_input = VestingScheduleInfo {
+			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
+			period_count: 4_u32,
+			per_period: 25_u64,
+			cliff: 0,
+			curve: VestingCurve::PiecewiseLinear(bounded_vec![
+				VestingBreakpoint { period: 2, released: Perbill::from_percent(10) },
+				VestingBreakpoint { period: 3, released: Perbill::from_percent(20) },
+			]),
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::root(),
+			ALICE,
+			BOB,
+			MockCurrencyId::BTC,
+			schedule_input,
+		));
+
+		for (block_number, locked) in [(11, 95_u64), (21, 90), (31, 80)] {
+			System::set_block_number(block_number);
+			assert_ok!(Vesting::claim(
+				RuntimeOrigin::signed(BOB),
+				MockCurrencyId::BTC,
+				VestingScheduleIdSet::All
+			));
+			assert_eq!(
+				Tokens::locks(&BOB, MockCurrencyId::BTC).get(0),
+				Some(&BalanceLock { id: VESTING_LOCK_ID, amount: locked })
+			);
+		}
+
+		System::set_block_number(41);
+		assert_ok!(Vesting::claim(
+			RuntimeOrigin::signed(BOB),
+			MockCurrencyId::BTC,
+			VestingScheduleIdSet::All
+		));
+		assert_eq!(Tokens::locks(&BOB, MockCurrencyId::BTC), vec![]);
+		assert_eq!(Tokens::free_balance(MockCurrencyId::BTC, &BOB), 100);
+	});
+}
+
+#[test]
+fn vested_transfer_fails_if_invalid_cliff_or_curve() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule_input = VestingScheduleInfo {
+			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
+			period_count: 8_u32,
+			per_period: 10_u64,
+			cliff: 9,
+			curve: VestingCurve::Linear,
+		};
+		assert_noop!(
+			Vesting::vested_transfer(
+				RuntimeOrigin::root(),
+				ALICE,
+				BOB,
+				MockCurrencyId::BTC,
+				schedule_input.clone(),
+			),
+			Error::<Runtime>::InvalidVestingCliff
+		);
+
+		let breakpoint = |period, percent| VestingBreakpoint {
+			period,
+			released: Perbill::from_percent(percent),
+		};
+		for breakpoints in [
+			// periods not increasing
+			bounded_vec![breakpoint(3, 10), breakpoint(2, 20)],
+			// shares decreasing
+			bounded_vec![breakpoint(2, 20), breakpoint(3, 10)],
+			// breakpoint at the end of the schedule
+			bounded_vec![breakpoint(8, 50)],
+		] {
+			assert_noop!(
+				Vesting::vested_transfer(
+					RuntimeOrigin::root(),
+					ALICE,
+					BOB,
+					MockCurrencyId::BTC,
+					VestingScheduleInfo {
+						cliff: 0,
+						curve: VestingCurve::PiecewiseLinear(breakpoints),
+						..schedule_input.clone()
+					},
+				),
+				Error::<Runtime>::InvalidVestingCurve
+			);
+		}
+		// more breakpoints than allowed can't be built, nor decoded from a call
+		assert!(BoundedVec::<_, MaxCurveBreakpoints>::try_from(
+			(1..=5).map(|period| breakpoint(period, 10)).collect::<Vec<_>>()
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn migration_v1_to_v2_keeps_existing_schedules_linear() {
+	ExtBuilder::build().execute_with(|| {
+		let old_schedules: BTreeMap<u128, migrations::VestingScheduleV1<u128, u64, u64, u64>> = [(
+			10_u128,
+			migrations::VestingScheduleV1 {
+				vesting_schedule_id: 10_u128,
+				window: BlockNumberBased { start: 0_u64, period: 10_u64 },
+				period_count: 2_u32,
+				per_period: 10_u64,
+				already_claimed: 5_u64,
+			},
+		)]
+		.into_iter()
+		.collect();
+		frame_support::storage::unhashed::put_raw(
+			&VestingSchedules::<Runtime>::hashed_key_for(BOB, MockCurrencyId::BTC),
+			&old_schedules.encode(),
+		);
+		StorageVersion::new(1).put::<Vesting>();
+
+		migrations::VestingV1ToV2::<Runtime>::on_runtime_upgrade();
+
+		let schedules: BoundedBTreeMap<_, _, MaxVestingSchedule> = [(
+			10_u128,
+			VestingSchedule {
+				vesting_schedule_id: 10_u128,
+				window: BlockNumberBased { start: 0_u64, period: 10_u64 },
+				period_count: 2_u32,
+				per_period: 10_u64,
+				already_claimed: 5_u64,
+				cliff: 0,
+				curve: VestingCurve::Linear,
+			},
+		)]
+		.into_iter()
+		.try_collect()
+		.unwrap();
+		assert_eq!(Vesting::vesting_schedules(&BOB, MockCurrencyId::BTC), schedules);
+		assert_eq!(Vesting::on_chain_storage_version(), StorageVersion::new(2));
+	});
+}
@@ -5,8 +5,8 @@ use composable_support::math::safe::SafeMul;
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32Bit, Zero},
-	ArithmeticError,
+	traits::{AtLeast32Bit, AtLeast32BitUnsigned, Zero},
+	ArithmeticError, Perbill,
 };
 use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
 
@@ -17,10 +17,11 @@ use serde::{Deserialize, Serialize};
 pub trait VestedTransfer {
 	type AccountId;
 	type AssetId;
-	type BlockNumber;
-	type Moment;
-	type Balance: HasCompact;
+	type BlockNumber: Clone + Eq + PartialEq + Debug;
+	type Moment: Clone + Eq + PartialEq + Debug;
+	type Balance: HasCompact + Clone + Eq + PartialEq + Debug;
 	type MinVestedTransfer: Get<Self::Balance>;
+	type MaxCurveBreakpoints: Get<u32>;
 	type VestingScheduleId;
 	type VestingScheduleNonce;
 
@@ -29,7 +30,12 @@ pub trait VestedTransfer {
 		asset: Self::AssetId,
 		from: &Self::AccountId,
 		to: &Self::AccountId,
-		schedule: VestingScheduleInfo<Self::BlockNumber, Self::Moment, Self::Balance>,
+		schedule: VestingScheduleInfo<
+			Self::BlockNumber,
+			Self::Moment,
+			Self::Balance,
+			Self::MaxCurveBreakpoints,
+		>,
 	) -> DispatchResult;
 }
 
@@ -51,6 +57,60 @@ pub enum VestingWindow<BlockNumber, Moment> {
 	},
 }
 
+/// A point of a piecewise-linear vesting curve: once `period` periods have passed, `released`
+/// of the total amount of the schedule is unlocked.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct VestingBreakpoint {
+	pub period: u32,
+	pub released: Perbill,
+}
+
+/// How the total amount of a vesting schedule is released over its periods.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxCurveBreakpoints))]
+pub enum VestingCurve<MaxCurveBreakpoints: Get<u32>> {
+	/// `per_period` is released every period.
+	Linear,
+	/// The released amount is interpolated linearly between breakpoints, starting from nothing
+	/// at period 0 and ending with the total amount at `period_count`.
+	///
+	/// Breakpoints must have strictly increasing periods within `1..period_count` and
+	/// non-decreasing shares. Back-loaded releases are expressed with a low share late in the
+	/// schedule, milestones with two breakpoints one period apart (e.g. `(11, 0%)` and
+	/// `(12, 25%)` releases a quarter at once after 12 periods).
+	PiecewiseLinear(BoundedVec<VestingBreakpoint, MaxCurveBreakpoints>),
+}
+
+impl<MaxCurveBreakpoints: Get<u32>> VestingCurve<MaxCurveBreakpoints> {
+	/// Checks the breakpoints of the curve against the number of periods of the schedule.
+	pub fn is_valid(&self, period_count: u32) -> bool {
+		match self {
+			VestingCurve::Linear => true,
+			VestingCurve::PiecewiseLinear(breakpoints) => {
+				let mut previous = VestingBreakpoint { period: 0, released: Perbill::zero() };
+				breakpoints.iter().all(|breakpoint| {
+					let valid = breakpoint.period > previous.period &&
+						breakpoint.period < period_count &&
+						breakpoint.released >= previous.released;
+					previous = *breakpoint;
+					valid
+				})
+			},
+		}
+	}
+}
+
 /// VestingScheduleId type for claiming.
 #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
 #[derive(
@@ -78,9 +138,17 @@ impl<Id: Clone + Copy + Eq + PartialEq + Debug, MaxVestingSchedules: Get<u32>>
 {
 	/// Returns a `Vec` containing all the ids of the schedules to be claimed. A reference to all
 	/// claimable schedules is passed in case `Self` is `All`.
-	pub fn into_all_ids<BlockNumber, Moment, Balance: HasCompact>(
+	pub fn into_all_ids<
+		BlockNumber: Clone + Eq + PartialEq + Debug,
+		Moment: Clone + Eq + PartialEq + Debug,
+		Balance: HasCompact + Clone + Eq + PartialEq + Debug,
+		MaxCurveBreakpoints: Get<u32>,
+	>(
 		self,
-		all_schedules: &BTreeMap<Id, VestingSchedule<Id, BlockNumber, Moment, Balance>>,
+		all_schedules: &BTreeMap<
+			Id,
+			VestingSchedule<Id, BlockNumber, Moment, Balance, MaxCurveBreakpoints>,
+		>,
 	) -> Vec<Id> {
 		match self {
 			VestingScheduleIdSet::All => all_schedules.keys().copied().collect(),
@@ -93,10 +161,19 @@ impl<Id: Clone + Copy + Eq + PartialEq + Debug, MaxVestingSchedules: Get<u32>>
 /// The vesting schedule.
 ///
 /// Benefits would be granted gradually, `per_period` amount every `window.period`
-/// of blocks after `window.start`.
-#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+/// of blocks after `window.start`, unless a `cliff` or a non-linear `curve` is set.
+#[derive(
+	CloneNoBound, Encode, Decode, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxCurveBreakpoints))]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance: HasCompact> {
+pub struct VestingSchedule<
+	VestingScheduleId: Clone + Eq + PartialEq + Debug,
+	BlockNumber: Clone + Eq + PartialEq + Debug,
+	Moment: Clone + Eq + PartialEq + Debug,
+	Balance: HasCompact + Clone + Eq + PartialEq + Debug,
+	MaxCurveBreakpoints: Get<u32>,
+> {
 	/// Vesting schedule id
 	pub vesting_schedule_id: VestingScheduleId,
 	pub window: VestingWindow<BlockNumber, Moment>,
@@ -107,20 +184,37 @@ pub struct VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance: HasC
 	pub per_period: Balance,
 	/// Amount already claimed
 	pub already_claimed: Balance,
+	/// Number of periods during which nothing is released; what the curve released in the
+	/// meantime is unlocked at once when the cliff ends
+	pub cliff: u32,
+	/// How the total amount is released over the periods
+	pub curve: VestingCurve<MaxCurveBreakpoints>,
 }
 
 /// Vesting schedule input, which is used to create a VestingSchedule.
 ///
 /// This is used for creating a VestingSchedule
-#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(
+	CloneNoBound, Encode, Decode, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxCurveBreakpoints))]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct VestingScheduleInfo<BlockNumber, Moment, Balance: HasCompact> {
+pub struct VestingScheduleInfo<
+	BlockNumber: Clone + Eq + PartialEq + Debug,
+	Moment: Clone + Eq + PartialEq + Debug,
+	Balance: HasCompact + Clone + Eq + PartialEq + Debug,
+	MaxCurveBreakpoints: Get<u32>,
+> {
 	pub window: VestingWindow<BlockNumber, Moment>,
 	/// Number of vest
 	pub period_count: u32,
 	/// Amount of tokens to release per vest
 	#[codec(compact)]
 	pub per_period: Balance,
+	/// Number of periods during which nothing is released
+	pub cliff: u32,
+	/// How the total amount is released over the periods
+	pub curve: VestingCurve<MaxCurveBreakpoints>,
 }
 
 pub enum VestingWindowResult<BlockNumber, Moment> {
@@ -129,11 +223,12 @@ pub enum VestingWindowResult<BlockNumber, Moment> {
 }
 
 impl<
-		VestingScheduleId,
-		BlockNumber: AtLeast32Bit + Copy,
-		Moment: AtLeast32Bit + Copy,
-		Balance: AtLeast32Bit + Copy,
-	> VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance>
+		VestingScheduleId: Clone + Eq + PartialEq + Debug,
+		BlockNumber: AtLeast32Bit + Copy + Debug,
+		Moment: AtLeast32Bit + Copy + Debug,
+		Balance: AtLeast32BitUnsigned + Copy + Debug,
+		MaxCurveBreakpoints: Get<u32>,
+	> VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance, MaxCurveBreakpoints>
 {
 	/// Check if the period is zero
 	pub fn is_zero_period(&self) -> bool {
@@ -165,43 +260,79 @@ impl<
 
 	/// Returns locked amount for a given schedule of VestingWindow.
 	///
-	/// Note this func assumes schedule is a valid one(non-zero period and
-	/// non-overflow total amount), and it should be guaranteed by callers.
+	/// Note this func assumes schedule is a valid one(non-zero period, non-overflow total amount
+	/// and valid cliff and curve), and it should be guaranteed by callers.
 	pub fn locked_amount(&self, block_number: BlockNumber, moment: Moment) -> Balance {
 		// full = (time - start) / period
-		// unrealized = period_count - full
-		// per_period * unrealized
-		let unrealized = match self.window {
-			VestingWindow::BlockNumberBased { start, period } => {
-				let full = block_number
-					.saturating_sub(start)
-					.checked_div(&period)
-					.expect("ensured non-zero period; qed");
-				self.period_count.saturating_sub(full.unique_saturated_into())
-			},
-			VestingWindow::MomentBased { start, period } => {
-				let full = moment
-					.saturating_sub(start)
-					.checked_div(&period)
-					.expect("ensured non-zero period; qed");
-				self.period_count.saturating_sub(full.unique_saturated_into())
-			},
+		// locked = total - released(full)
+		let full: u32 = match self.window {
+			VestingWindow::BlockNumberBased { start, period } => block_number
+				.saturating_sub(start)
+				.checked_div(&period)
+				.expect("ensured non-zero period; qed")
+				.unique_saturated_into(),
+			VestingWindow::MomentBased { start, period } => moment
+				.saturating_sub(start)
+				.checked_div(&period)
+				.expect("ensured non-zero period; qed")
+				.unique_saturated_into(),
 		};
-		self.per_period
-			.checked_mul(&unrealized.into())
-			.expect("ensured non-overflow total amount; qed")
+		let total = self.total_amount().expect("ensured non-overflow total amount; qed");
+		total.saturating_sub(self.released_amount(full, total))
+	}
+
+	/// Returns the amount released by the curve once `full` periods have passed, taking the
+	/// cliff into account.
+	fn released_amount(&self, full: u32, total: Balance) -> Balance {
+		if full < self.cliff {
+			return Zero::zero()
+		}
+		if full >= self.period_count {
+			return total
+		}
+		match &self.curve {
+			VestingCurve::Linear => self.per_period.saturating_mul(full.into()),
+			VestingCurve::PiecewiseLinear(breakpoints) => {
+				let mut from = VestingBreakpoint { period: 0, released: Perbill::zero() };
+				let mut to =
+					VestingBreakpoint { period: self.period_count, released: Perbill::one() };
+				for breakpoint in breakpoints {
+					if breakpoint.period <= full {
+						from = *breakpoint;
+					} else {
+						to = *breakpoint;
+						break
+					}
+				}
+				let from_amount = from.released.mul_floor(total);
+				let to_amount = to.released.mul_floor(total);
+				let progress = Perbill::from_rational(
+					full.saturating_sub(from.period),
+					to.period.saturating_sub(from.period),
+				);
+				from_amount
+					.saturating_add(progress.mul_floor(to_amount.saturating_sub(from_amount)))
+			},
+		}
 	}
 
 	pub fn from_input(
 		vesting_schedule_id: VestingScheduleId,
-		vesting_schedule_input: VestingScheduleInfo<BlockNumber, Moment, Balance>,
-	) -> VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance> {
+		vesting_schedule_input: VestingScheduleInfo<
+			BlockNumber,
+			Moment,
+			Balance,
+			MaxCurveBreakpoints,
+		>,
+	) -> VestingSchedule<VestingScheduleId, BlockNumber, Moment, Balance, MaxCurveBreakpoints> {
 		VestingSchedule {
 			vesting_schedule_id,
 			window: vesting_schedule_input.window,
 			per_period: vesting_schedule_input.per_period,
 			period_count: vesting_schedule_input.period_count,
 			already_claimed: Zero::zero(),
+			cliff: vesting_schedule_input.cliff,
+			curve: vesting_schedule_input.curve,
 		}
 	}
 }
@@ -210,27 +341,33 @@ impl<
 mod tests {
 	use super::*;
 	use crate::types::VestingWindow::*;
+	use frame_support::bounded_vec;
 
 	#[test]
 	fn test_is_zero_period() {
-		let mut vesting_schedule_time_based = VestingSchedule::<u128, u32, u64, u64> {
+		let mut vesting_schedule_time_based = VestingSchedule::<u128, u32, u64, u64, ConstU32<4>> {
 			vesting_schedule_id: 1_u128,
 			window: MomentBased { start: 1_u64, period: 10_u64 },
 			period_count: 100,
 			per_period: 1_u64,
 			already_claimed: 0_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert!(!vesting_schedule_time_based.is_zero_period());
 		vesting_schedule_time_based.window = MomentBased { start: 1, period: 0 };
 		assert!(vesting_schedule_time_based.is_zero_period());
 
-		let mut vesting_schedule_block_number_based = VestingSchedule::<u128, u64, u32, u64> {
-			vesting_schedule_id: 2_u128,
-			window: BlockNumberBased { start: 1_u64, period: 10_u64 },
-			period_count: 100,
-			per_period: 1_u64,
-			already_claimed: 0_u64,
-		};
+		let mut vesting_schedule_block_number_based =
+			VestingSchedule::<u128, u64, u32, u64, ConstU32<4>> {
+				vesting_schedule_id: 2_u128,
+				window: BlockNumberBased { start: 1_u64, period: 10_u64 },
+				period_count: 100,
+				per_period: 1_u64,
+				already_claimed: 0_u64,
+				cliff: 0,
+				curve: VestingCurve::Linear,
+			};
 		assert!(!vesting_schedule_block_number_based.is_zero_period());
 		vesting_schedule_block_number_based.window = BlockNumberBased { start: 1, period: 0 };
 		assert!(vesting_schedule_block_number_based.is_zero_period());
@@ -238,12 +375,14 @@ mod tests {
 
 	#[test]
 	fn test_end() {
-		let vesting_schedule_time_based = VestingSchedule::<u128, u32, u64, u64> {
+		let vesting_schedule_time_based = VestingSchedule::<u128, u32, u64, u64, ConstU32<4>> {
 			vesting_schedule_id: 3_u128,
 			window: MomentBased { start: 1_u64, period: 10_u64 },
 			period_count: 100,
 			per_period: 1_u64,
 			already_claimed: 0_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		match vesting_schedule_time_based.end() {
 			None => {},
@@ -252,13 +391,16 @@ mod tests {
 				VestingWindowResult::BlockNumberResult(_) => panic!("Unexpected BlockNumberResult"),
 			},
 		}
-		let vesting_schedule_block_number_based = VestingSchedule::<u128, u64, u32, u64> {
-			vesting_schedule_id: 4_u128,
-			window: BlockNumberBased { start: 1_u64, period: 10_u64 },
-			period_count: 100,
-			per_period: 1_u64,
-			already_claimed: 0_u64,
-		};
+		let vesting_schedule_block_number_based =
+			VestingSchedule::<u128, u64, u32, u64, ConstU32<4>> {
+				vesting_schedule_id: 4_u128,
+				window: BlockNumberBased { start: 1_u64, period: 10_u64 },
+				period_count: 100,
+				per_period: 1_u64,
+				already_claimed: 0_u64,
+				cliff: 0,
+				curve: VestingCurve::Linear,
+			};
 		match vesting_schedule_block_number_based.end() {
 			None => {},
 			Some(result) => match result {
@@ -270,12 +412,14 @@ mod tests {
 
 	#[test]
 	fn test_total_amount() {
-		let vesting_schedule = VestingSchedule::<u128, u64, u64, u64> {
+		let vesting_schedule = VestingSchedule::<u128, u64, u64, u64, ConstU32<4>> {
 			vesting_schedule_id: 5_u128,
 			window: BlockNumberBased { start: 1_u64, period: 10_u64 },
 			period_count: 100,
 			per_period: 1_u64,
 			already_claimed: 0_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_eq!(vesting_schedule.total_amount().unwrap(), 100)
 	}
@@ -283,26 +427,60 @@ mod tests {
 	/// TODO proptest for exhaustive tests
 	#[test]
 	fn test_locked_amount() {
-		let vesting_schedule_time_based = VestingSchedule::<u128, u32, u64, u64> {
+		let vesting_schedule_time_based = VestingSchedule::<u128, u32, u64, u64, ConstU32<4>> {
 			vesting_schedule_id: 6_u128,
 			window: MomentBased { start: 1_u64, period: 10_u64 },
 			period_count: 100,
 			per_period: 1_u64,
 			already_claimed: 0_u64,
+			cliff: 0,
+			curve: VestingCurve::Linear,
 		};
 		assert_eq!(vesting_schedule_time_based.locked_amount(1, 1), 100);
 		assert_eq!(vesting_schedule_time_based.locked_amount(1, 11), 99);
 		assert_eq!(vesting_schedule_time_based.locked_amount(1, 1001), 0);
 
-		let vesting_schedule_block_number_based = VestingSchedule::<u128, u64, u32, u64> {
-			vesting_schedule_id: 7_u128,
-			window: BlockNumberBased { start: 1_u64, period: 10_u64 },
-			period_count: 100,
-			per_period: 1_u64,
-			already_claimed: 0_u64,
-		};
+		let vesting_schedule_block_number_based =
+			VestingSchedule::<u128, u64, u32, u64, ConstU32<4>> {
+				vesting_schedule_id: 7_u128,
+				window: BlockNumberBased { start: 1_u64, period: 10_u64 },
+				period_count: 100,
+				per_period: 1_u64,
+				already_claimed: 0_u64,
+				cliff: 0,
+				curve: VestingCurve::Linear,
+			};
 		assert_eq!(vesting_schedule_block_number_based.locked_amount(1, 1), 100);
 		assert_eq!(vesting_schedule_block_number_based.locked_amount(11, 1), 99);
 		assert_eq!(vesting_schedule_block_number_based.locked_amount(1001, 1), 0);
 	}
+
+	#[test]
+	fn test_locked_amount_with_cliff_and_curve() {
+		let mut vesting_schedule = VestingSchedule::<u128, u64, u64, u64, ConstU32<4>> {
+			vesting_schedule_id: 8_u128,
+			window: BlockNumberBased { start: 0_u64, period: 10_u64 },
+			period_count: 10,
+			per_period: 10_u64,
+			already_claimed: 0_u64,
+			cliff: 3,
+			curve: VestingCurve::Linear,
+		};
+		assert_eq!(vesting_schedule.locked_amount(29, 0), 100);
+		assert_eq!(vesting_schedule.locked_amount(30, 0), 70);
+		assert_eq!(vesting_schedule.locked_amount(100, 0), 0);
+
+		vesting_schedule.cliff = 0;
+		vesting_schedule.curve = VestingCurve::PiecewiseLinear(bounded_vec![
+			VestingBreakpoint { period: 4, released: Perbill::from_percent(20) },
+			VestingBreakpoint { period: 5, released: Perbill::from_percent(60) },
+		]);
+		assert!(vesting_schedule.curve.is_valid(vesting_schedule.period_count));
+		assert_eq!(vesting_schedule.locked_amount(20, 0), 90);
+		assert_eq!(vesting_schedule.locked_amount(40, 0), 80);
+		assert_eq!(vesting_schedule.locked_amount(50, 0), 40);
+		assert_eq!(vesting_schedule.locked_amount(90, 0), 8);
+		assert_eq!(vesting_schedule.locked_amount(100, 0), 0);
+		assert!(!vesting_schedule.curve.is_valid(5));
+	}
 }
@@ -21,9 +21,11 @@ use crate::{
 	client::{FullBackend, FullClient},
 	runtime::{
 		assets::ExtendWithAssetsApi, cosmwasm::ExtendWithCosmwasmApi,
-		crowdloan_rewards::ExtendWithCrowdloanRewardsApi, farming::ExtendWithFarmingApi,
-		ibc::ExtendWithIbcApi, lending::ExtendWithLendingApi, pablo::ExtendWithPabloApi,
-		staking_rewards::ExtendWithStakingRewardsApi, BaseHostRuntimeApis,
+		crowdloan_rewards::ExtendWithCrowdloanRewardsApi, dex_router::ExtendWithDexRouterApi,
+		farming::ExtendWithFarmingApi, ibc::ExtendWithIbcApi, lending::ExtendWithLendingApi,
+		liquid_staking::ExtendWithLiquidStakingApi, oracle::ExtendWithOracleApi,
+		pablo::ExtendWithPabloApi, staking_rewards::ExtendWithStakingRewardsApi,
+		BaseHostRuntimeApis,
 	},
 };
 
@@ -67,6 +69,9 @@ where
 			+ ExtendWithAssetsApi<RuntimeApi, Executor>
 			+ ExtendWithCrowdloanRewardsApi<RuntimeApi, Executor>
 			+ ExtendWithPabloApi<RuntimeApi, Executor>
+			+ ExtendWithDexRouterApi<RuntimeApi, Executor>
+			+ ExtendWithOracleApi<RuntimeApi, Executor>
+			+ ExtendWithLiquidStakingApi<RuntimeApi, Executor>
 			+ ExtendWithFarmingApi<RuntimeApi, Executor>
 			+ ExtendWithLendingApi<RuntimeApi, Executor>
 			+ ExtendWithCosmwasmApi<RuntimeApi, Executor>
@@ -96,6 +101,21 @@ where
 		deps.clone(),
 	)?;
 
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_dex_router_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_oracle_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
+	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_liquid_staking_api(
+		&mut io,
+		deps.clone(),
+	)?;
+
 	<FullClient<RuntimeApi, Executor> as ProvideRuntimeApi<OpaqueBlock>>::Api::extend_with_farming_api(
 		&mut io,
 		deps.clone(),
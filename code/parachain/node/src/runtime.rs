@@ -3,7 +3,10 @@ use common::{AccountId, Balance, Index, OpaqueBlock};
 use cosmwasm_rpc::{Cosmwasm, CosmwasmApiServer};
 use crowdloan_rewards_rpc::{CrowdloanRewards, CrowdloanRewardsApiServer};
 use cumulus_primitives_core::CollectCollationInfo;
+use dex_router_rpc::{DexRouter, DexRouterApiServer};
 use ibc_rpc::{IbcApiServer, IbcRpcHandler};
+use liquid_staking_rpc::{LiquidStaking, LiquidStakingApiServer};
+use oracle_rpc::{Oracle, OracleApiServer};
 use pablo_rpc::{Pablo, PabloApiServer};
 use pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi;
 use reward_rpc::{Reward, RewardApiServer};
@@ -197,6 +200,52 @@ define_trait! {
 		}
 	}
 
+	mod dex_router {
+		pub trait ExtendWithDexRouterApi {
+			fn extend_with_dex_router_api(io, deps);
+		}
+
+		impl for composable_runtime {}
+
+		impl for picasso_runtime {
+			fn (io, deps) {
+				io.merge(DexRouter::new(deps.client).into_rpc())
+			}
+		}
+	}
+
+	mod oracle {
+		pub trait ExtendWithOracleApi {
+			fn extend_with_oracle_api(io, deps);
+		}
+
+		impl for composable_runtime {}
+
+		impl for picasso_runtime {
+			fn (io, deps) {
+				io.merge(Oracle::new(deps.client).into_rpc())
+			}
+		}
+	}
+
+	mod liquid_staking {
+		pub trait ExtendWithLiquidStakingApi {
+			fn extend_with_liquid_staking_api(io, deps);
+		}
+
+		impl for composable_runtime {
+			fn (io, deps) {
+				io.merge(LiquidStaking::new(deps.client).into_rpc())
+			}
+		}
+
+		impl for picasso_runtime {
+			fn (io, deps) {
+				io.merge(LiquidStaking::new(deps.client).into_rpc())
+			}
+		}
+	}
+
 	mod farming {
 		pub trait ExtendWithFarmingApi {
 			fn extend_with_farming_api(io, deps);
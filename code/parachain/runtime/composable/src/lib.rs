@@ -41,7 +41,13 @@ use common::{
 	NORMAL_DISPATCH_RATIO, SLOT_DURATION,
 };
 use composable_support::rpc_helpers::SafeRpcWrapper;
-use composable_traits::assets::Asset;
+use composable_traits::{
+	assets::Asset,
+	liquid_staking::{
+		DerivativeLedger, LiquidStakingPosition, PendingXcmRequest, StakeSimulation,
+		UnstakeSimulation,
+	},
+};
 use gates::*;
 use governance::*;
 use orml_traits::parameter_type_with_key;
@@ -589,6 +595,8 @@ parameter_types! {
 	pub const BondingDuration: pallet_liquid_staking::types::EraIndex = 28; // 28Days
 	pub const MinNominatorBond: Balance = 100_000_000_000; // 10DOT
 	pub const NumSlashingSpans: u32 = 0;
+	pub const MaxNominations: u32 = 16; // Polkadot MaxNominations
+	pub const MaxValidatorCandidates: u32 = 100;
 	pub const ElectionSolutionStoredOffset: BlockNumber = 12600;
 	pub LsdProtocolFeeAccount: sp_runtime::AccountId32 = sp_runtime::AccountId32::from(hex_literal::hex!("a3e194e1abc02bdd10ce919fe2c3d33b5c9291b3aaa3f75035c65fcdf1b8f129"));
 }
@@ -645,7 +653,11 @@ impl pallet_liquid_staking::Config for Runtime {
 	type DerivativeIndexList = DerivativeIndexList;
 	type XcmFees = XcmFees;
 	type MatchingPoolFastUnstakeFee = MatchingPoolFastUnstakeFee;
-	type DistributionStrategy = pallet_liquid_staking::distribution::MaxMinDistribution;
+	type DistributionStrategy = pallet_liquid_staking::SelectableDistribution<Runtime>;
+	type ValidatorSelection = pallet_liquid_staking::selection::LowestCommission;
+	type Dex = ();
+	type MaxNominations = MaxNominations;
+	type MaxValidatorCandidates = MaxValidatorCandidates;
 	type StakingCurrency = StakingCurrency;
 	type LiquidCurrency = LiquidCurrency;
 	type EraLength = EraLength;
@@ -825,6 +837,51 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
+	impl liquid_staking_runtime_api::LiquidStakingRuntimeApi<Block, AccountId, Balance> for Runtime {
+		fn exchange_rate() -> pallet_liquid_staking::types::Rate {
+			PalletLiquidStaking::exchange_rate()
+		}
+
+		fn current_era() -> pallet_liquid_staking::types::EraIndex {
+			PalletLiquidStaking::current_era()
+		}
+
+		fn position(who: AccountId) -> LiquidStakingPosition<SafeRpcWrapper<Balance>> {
+			PalletLiquidStaking::position(&who).map(SafeRpcWrapper)
+		}
+
+		fn staking_ledgers() -> Vec<DerivativeLedger<SafeRpcWrapper<Balance>>> {
+			PalletLiquidStaking::derivative_ledgers()
+				.into_iter()
+				.map(|ledger| ledger.map(SafeRpcWrapper))
+				.collect()
+		}
+
+		fn simulate_stake(
+			amount: SafeRpcWrapper<Balance>,
+		) -> Option<StakeSimulation<SafeRpcWrapper<Balance>>> {
+			PalletLiquidStaking::simulate_stake(amount.0)
+				.map(|simulation| simulation.map(SafeRpcWrapper))
+				.ok()
+		}
+
+		fn simulate_unstake(
+			liquid_amount: SafeRpcWrapper<Balance>,
+		) -> Option<UnstakeSimulation<SafeRpcWrapper<Balance>>> {
+			PalletLiquidStaking::simulate_unstake(liquid_amount.0)
+				.map(|simulation| simulation.map(SafeRpcWrapper))
+				.ok()
+		}
+
+		fn pending_xcm_requests() -> Vec<PendingXcmRequest<SafeRpcWrapper<Balance>, BlockNumber>> {
+			PalletLiquidStaking::pending_xcm_requests()
+				.into_iter()
+				.map(|request| request.map(SafeRpcWrapper))
+				.collect()
+		}
+	}
+
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
 			VERSION
@@ -33,6 +33,7 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				matches!(
 					c,
 					RuntimeCall::Pablo(..) |
+						RuntimeCall::DexRouter(..) |
 						RuntimeCall::FarmingRewards(..) |
 						RuntimeCall::Farming(..)
 				)
@@ -59,7 +59,13 @@ use common::{
 use composable_support::rpc_helpers::SafeRpcWrapper;
 use composable_traits::{
 	assets::Asset,
-	dex::{Amm, PriceAggregate},
+	defi::{CurrencyPair, Rate},
+	dex::{Amm, AmmTwap, AssetAmount, DexRouter as DexRouterTrait, PriceAggregate, RouteQuote},
+	liquid_staking::{
+		DerivativeLedger, LiquidStakingPosition, PendingXcmRequest, StakeSimulation,
+		UnstakeSimulation,
+	},
+	oracle::{OracleStatus, Price},
 };
 use pallet_ibc::ics20_fee::FlatFeeConverter;
 use primitives::currency::ForeignAssetId;
@@ -78,7 +84,7 @@ use sp_runtime::{
 		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Zero,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, Either, FixedI128,
+	ApplyExtrinsicResult, Either, FixedI128, Percent,
 };
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 // A few exports that help ease life for downstream crates.
@@ -270,6 +276,8 @@ impl revenue_ibc::Config for Runtime {
 parameter_types! {
 	pub PabloPalletId: PalletId = PalletId(*b"pal_pblo");
 	pub TWAPInterval: u64 = (MILLISECS_PER_BLOCK as u64) * 10;
+	// TWAPs can be read over the last 12 hours
+	pub const MaxTWAPObservations: u32 = 360;
 	pub LPTokenExistentialDeposit: Balance = 100;
 }
 
@@ -286,10 +294,35 @@ impl pablo::Config for Runtime {
 	type EnableTwapOrigin = EnsureRoot<AccountId>;
 	type Time = Timestamp;
 	type TWAPInterval = TWAPInterval;
+	type MaxTWAPObservations = MaxTWAPObservations;
 	type WeightInfo = weights::pablo::WeightInfo<Runtime>;
 	type LPTokenExistentialDeposit = LPTokenExistentialDeposit;
 }
 
+parameter_types! {
+	#[derive(TypeInfo, codec::MaxEncodedLen, codec::Encode)]
+	pub const MaxHopsInRoute: u32 = 4;
+	pub const MaxSplitParts: u32 = 8;
+	pub const MaxRoutingPools: u32 = 64;
+	pub const MaxRouteCandidates: u32 = 256;
+	pub DexRouterPalletId: PalletId = PalletId(*b"dex_rout");
+}
+
+impl dex_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+	type MaxHopsInRoute = MaxHopsInRoute;
+	type MaxSplitParts = MaxSplitParts;
+	type MaxRoutingPools = MaxRoutingPools;
+	type MaxRouteCandidates = MaxRouteCandidates;
+	type PoolId = PoolId;
+	type Pablo = Pablo;
+	type UpdateRouteOrigin = EnsureRoot<AccountId>;
+	type PalletId = DexRouterPalletId;
+	type WeightInfo = weights::dex_router::WeightInfo<Runtime>;
+}
+
 type FarmingRewardsInstance = reward::Instance1;
 
 impl reward::Config<FarmingRewardsInstance> for Runtime {
@@ -332,6 +365,7 @@ parameter_types! {
 	// cspell:disable-next
 	pub const OraclePalletId: PalletId = PalletId(*b"plt_orac");
 	pub const MsPerBlock: u64 = MILLISECS_PER_BLOCK as u64;
+	pub const ReputationSmoothing: Permill = Permill::from_percent(10);
 }
 
 impl oracle::Config for Runtime {
@@ -355,11 +389,15 @@ impl oracle::Config for Runtime {
 	type TwapWindow = TwapWindow;
 	type MaxPrePrices = MaxPrePrices;
 	type MsPerBlock = MsPerBlock;
+	type ReputationSmoothing = ReputationSmoothing;
 	type WeightInfo = weights::oracle::WeightInfo<Runtime>;
 	type LocalAssets = ();
 	type Moment = Moment;
 	type Time = Timestamp;
 	type PalletId = OraclePalletId;
+	type PoolId = PoolId;
+	type Amm = Pablo;
+	type ExchangeRates = PalletLiquidStaking;
 }
 
 parameter_types! {
@@ -681,12 +719,14 @@ impl crowdloan_rewards::Config for Runtime {
 parameter_types! {
 	  pub const MaxVestingSchedule: u32 = 128;
 	  pub MinVestedTransfer: u64 = CurrencyId::milli::<u64>();
+	  pub const MaxCurveBreakpoints: u32 = 32;
 }
 
 impl vesting::Config for Runtime {
 	type Currency = Assets;
 	type RuntimeEvent = RuntimeEvent;
 	type MaxVestingSchedules = MaxVestingSchedule;
+	type MaxCurveBreakpoints = MaxCurveBreakpoints;
 	type MinVestedTransfer = MinVestedTransfer;
 	type VestedTransferOrigin = EnsureRoot<Self::AccountId>;
 	type UpdateSchedulesOrigin = EnsureRoot<Self::AccountId>;
@@ -757,6 +797,8 @@ parameter_types! {
 	pub const BondingDuration: pallet_liquid_staking::types::EraIndex = 28; //28 era = 7 days (era 6 hours)
 	pub const MinNominatorBond: Balance = 100_000_000_000; // 0.1KSM
 	pub const NumSlashingSpans: u32 = 0;
+	pub const MaxNominations: u32 = 24; // Kusama MaxNominations
+	pub const MaxValidatorCandidates: u32 = 100;
 	pub const ElectionSolutionStoredOffset: BlockNumber = 3150;
 	pub LsdProtocolFeeAccount: sp_runtime::AccountId32 = sp_runtime::AccountId32::from(hex!("a3e194e1abc02bdd10ce919fe2c3d33b5c9291b3aaa3f75035c65fcdf1b8f129"));
 }
@@ -813,7 +855,11 @@ impl pallet_liquid_staking::Config for Runtime {
 	type DerivativeIndexList = DerivativeIndexList;
 	type XcmFees = XcmFees;
 	type MatchingPoolFastUnstakeFee = MatchingPoolFastUnstakeFee;
-	type DistributionStrategy = pallet_liquid_staking::distribution::MaxMinDistribution;
+	type DistributionStrategy = pallet_liquid_staking::SelectableDistribution<Runtime>;
+	type ValidatorSelection = pallet_liquid_staking::selection::LowestCommission;
+	type Dex = pallet_liquid_staking::dex::AmmDex<Pablo>;
+	type MaxNominations = MaxNominations;
+	type MaxValidatorCandidates = MaxValidatorCandidates;
 	type StakingCurrency = StakingCurrency;
 	type LiquidCurrency = LiquidCurrency;
 	type EraLength = EraLength;
@@ -891,6 +937,7 @@ construct_runtime!(
 		AssetsRegistry: assets_registry = 58,
 		Pablo: pablo = 59,
 		Oracle: oracle = 60,
+		DexRouter: dex_router = 61,
 		FarmingRewards: reward::<Instance1> = 62,
 		Farming: farming = 63,
 
@@ -970,6 +1017,7 @@ mod benches {
 		[vesting, Vesting]
 		[assets_registry, AssetsRegistry]
 		[oracle, Oracle]
+		[dex_router, DexRouter]
 		[pallet_ibc, Ibc]
 	);
 }
@@ -1038,6 +1086,52 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
+	impl liquid_staking_runtime_api::LiquidStakingRuntimeApi<Block, AccountId, Balance> for Runtime {
+		fn exchange_rate() -> pallet_liquid_staking::types::Rate {
+			PalletLiquidStaking::exchange_rate()
+		}
+
+		fn current_era() -> pallet_liquid_staking::types::EraIndex {
+			PalletLiquidStaking::current_era()
+		}
+
+		fn position(who: AccountId) -> LiquidStakingPosition<SafeRpcWrapper<Balance>> {
+			PalletLiquidStaking::position(&who).map(SafeRpcWrapper)
+		}
+
+		fn staking_ledgers() -> Vec<DerivativeLedger<SafeRpcWrapper<Balance>>> {
+			PalletLiquidStaking::derivative_ledgers()
+				.into_iter()
+				.map(|ledger| ledger.map(SafeRpcWrapper))
+				.collect()
+		}
+
+		fn simulate_stake(
+			amount: SafeRpcWrapper<Balance>,
+		) -> Option<StakeSimulation<SafeRpcWrapper<Balance>>> {
+			PalletLiquidStaking::simulate_stake(amount.0)
+				.map(|simulation| simulation.map(SafeRpcWrapper))
+				.ok()
+		}
+
+		fn simulate_unstake(
+			liquid_amount: SafeRpcWrapper<Balance>,
+		) -> Option<UnstakeSimulation<SafeRpcWrapper<Balance>>> {
+			PalletLiquidStaking::simulate_unstake(liquid_amount.0)
+				.map(|simulation| simulation.map(SafeRpcWrapper))
+				.ok()
+		}
+
+		fn pending_xcm_requests() -> Vec<PendingXcmRequest<SafeRpcWrapper<Balance>, BlockNumber>> {
+			PalletLiquidStaking::pending_xcm_requests()
+				.into_iter()
+				.map(|request| request.map(SafeRpcWrapper))
+				.collect()
+		}
+	}
+
+	#[api_version(2)]
 	impl pablo_runtime_api::PabloRuntimeApi<Block, AccountId, PoolId, CurrencyId, Balance> for Runtime {
 		fn prices_for(
 			pool_id: PoolId,
@@ -1113,6 +1207,113 @@ impl_runtime_apis! {
 				})
 				.unwrap_or_default()
 		}
+
+		fn twap(
+			pool_id: SafeRpcWrapper<PoolId>,
+			window: SafeRpcWrapper<u64>,
+		) -> Option<BTreeMap<SafeRpcWrapper<CurrencyId>, Rate>> {
+			<Pablo as AmmTwap>::twap(pool_id.0, window.0)
+				.map(|twaps| {
+					twaps
+						.into_iter()
+						.map(|(asset_id, twap)| (SafeRpcWrapper(asset_id), twap))
+						.collect()
+				})
+				.ok()
+		}
+	}
+
+	impl dex_router_runtime_api::DexRouterRuntimeApi<Block, PoolId, CurrencyId, Balance> for Runtime {
+		fn get_route(
+			asset_pair: CurrencyPair<SafeRpcWrapper<CurrencyId>>,
+		) -> Option<(Vec<SafeRpcWrapper<PoolId>>, bool)> {
+			<DexRouter as DexRouterTrait>::get_route(CurrencyPair::new(
+				asset_pair.base.0,
+				asset_pair.quote.0,
+			))
+			.map(|(route, reverse)| (route.into_iter().map(SafeRpcWrapper).collect(), reverse))
+		}
+
+		fn all_routes(
+		) -> Vec<(CurrencyPair<SafeRpcWrapper<CurrencyId>>, Vec<SafeRpcWrapper<PoolId>>)> {
+			DexRouter::all_routes()
+				.into_iter()
+				.map(|(pair, route)| {
+					(
+						CurrencyPair::new(SafeRpcWrapper(pair.base), SafeRpcWrapper(pair.quote)),
+						route.into_iter().map(SafeRpcWrapper).collect(),
+					)
+				})
+				.collect()
+		}
+
+		fn quote_swap(
+			in_asset: AssetAmount<SafeRpcWrapper<CurrencyId>, SafeRpcWrapper<Balance>>,
+			out_asset_id: SafeRpcWrapper<CurrencyId>,
+		) -> Option<
+			RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<CurrencyId>, SafeRpcWrapper<Balance>>,
+		> {
+			let in_asset = in_asset.map(|asset_id| asset_id.0, |amount| amount.0);
+			DexRouter::quote_swap(in_asset, out_asset_id.0)
+				.map(|quote| quote.map(SafeRpcWrapper, SafeRpcWrapper, SafeRpcWrapper))
+				.ok()
+		}
+
+		fn quote_buy(
+			in_asset_id: SafeRpcWrapper<CurrencyId>,
+			out_asset: AssetAmount<SafeRpcWrapper<CurrencyId>, SafeRpcWrapper<Balance>>,
+		) -> Option<
+			RouteQuote<SafeRpcWrapper<PoolId>, SafeRpcWrapper<CurrencyId>, SafeRpcWrapper<Balance>>,
+		> {
+			let out_asset = out_asset.map(|asset_id| asset_id.0, |amount| amount.0);
+			DexRouter::quote_buy(in_asset_id.0, out_asset)
+				.map(|quote| quote.map(SafeRpcWrapper, SafeRpcWrapper, SafeRpcWrapper))
+				.ok()
+		}
+	}
+
+	#[api_version(2)]
+	impl oracle_runtime_api::OracleRuntimeApi<Block, CurrencyId, Balance, BlockNumber, AccountId, Balance> for Runtime {
+		fn get_price(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+		) -> Option<Price<SafeRpcWrapper<Balance>, BlockNumber>> {
+			Oracle::latest_price(asset_id.0)
+				.map(|Price { price, block }| Price { price: SafeRpcWrapper(price), block })
+		}
+
+		fn get_twap(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+			weights: Vec<Percent>,
+		) -> Option<SafeRpcWrapper<Balance>> {
+			Oracle::get_weighted_twap(asset_id.0, weights).map(SafeRpcWrapper).ok()
+		}
+
+		fn price_history(
+			asset_id: SafeRpcWrapper<CurrencyId>,
+			count: u32,
+		) -> Vec<Price<SafeRpcWrapper<Balance>, BlockNumber>> {
+			Oracle::latest_history(asset_id.0, count)
+				.into_iter()
+				.map(|Price { price, block }| Price { price: SafeRpcWrapper(price), block })
+				.collect()
+		}
+
+		fn pending_requests() -> Vec<SafeRpcWrapper<CurrencyId>> {
+			Oracle::pending_requests().into_iter().map(SafeRpcWrapper).collect()
+		}
+
+		fn oracle_status(
+			signer: AccountId,
+		) -> Option<OracleStatus<AccountId, SafeRpcWrapper<Balance>, BlockNumber>> {
+			Oracle::oracle_status(&signer).map(|status| OracleStatus {
+				controller: status.controller,
+				stake: SafeRpcWrapper(status.stake),
+				answer_in_transit: SafeRpcWrapper(status.answer_in_transit),
+				withdrawal: status.withdrawal.map(|(stake, unlock_block)| (SafeRpcWrapper(stake), unlock_block)),
+				rewards: SafeRpcWrapper(status.rewards),
+				reputation: status.reputation,
+			})
+		}
 	}
 
 	impl cosmwasm_runtime_api::CosmwasmRuntimeApi<Block, AccountId, CurrencyId, Balance, Vec<u8>> for Runtime {
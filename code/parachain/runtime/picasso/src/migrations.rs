@@ -15,6 +15,11 @@ pub type Migrations = (
 	scheduler::migration::v3::MigrateToV4<Runtime>,
 	multisig::migrations::v1::MigrateToV1<Runtime>,
 	vesting::migrations::VestingV0ToV1<Runtime>,
+	vesting::migrations::VestingV1ToV2<Runtime>,
+	oracle::migrations::OracleV0ToV1<Runtime>,
+	oracle::migrations::OracleV1ToV2<Runtime>,
+	oracle::migrations::OracleV2ToV3<Runtime>,
+	pallet_liquid_staking::migrations::LiquidStakingV2ToV3<Runtime>,
 	frame_support::migrations::RemovePallet<
 		DemocracyPalletName,
 		<Runtime as frame_system::Config>::DbWeight,
@@ -0,0 +1,90 @@
+
+//! Weights for `dex_router`
+//!
+//! NOT GENERATED: conservative estimates, to be replaced by the output of the benchmark CLI
+//! (`nix run .#benchmarks-generate-picasso`) once `dex_router` has been benchmarked on the
+//! reference machine.
+//!
+//! Extrinsics are priced as routes of `MaxHopsInRoute` (4) hops, each hop costing as much as the
+//! generated `pablo` weight of the underlying operation. Route searches read every routed pool
+//! and price each candidate hop.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `dex_router`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> dex_router::WeightInfo for WeightInfo<T> {
+	/// Both directions of the pair and the assets of each of the 4 pools of the route.
+	fn update_route() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 16_000))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// 4 times `pablo::swap`.
+	fn swap() -> Weight {
+		Weight::from_parts(620_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 125_724))
+			.saturating_add(T::DbWeight::get().reads(40))
+			.saturating_add(T::DbWeight::get().writes(24))
+	}
+	/// `swap` for each of the `p` parts.
+	/// The range of component `p` is `[1, 8]`.
+	fn swap_split(p: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(620_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(0, 125_724).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads((40_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((24_u64).saturating_mul(p.into())))
+	}
+	/// 4 times `pablo::buy`, along with the pricing of each hop.
+	fn buy() -> Weight {
+		Weight::from_parts(640_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 125_724))
+			.saturating_add(T::DbWeight::get().reads(40))
+			.saturating_add(T::DbWeight::get().writes(24))
+	}
+	/// `pablo::add_liquidity` along with the route of the pair.
+	fn add_liquidity() -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 43_253))
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// `pablo::remove_liquidity` along with the route of the pair.
+	fn remove_liquidity() -> Weight {
+		Weight::from_parts(195_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 43_801))
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// `swap` to enter the pool, `pablo::swap` to the pool ratio and `pablo::add_liquidity`.
+	fn zap_in() -> Weight {
+		Weight::from_parts(1_061_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 195_190))
+			.saturating_add(T::DbWeight::get().reads(62))
+			.saturating_add(T::DbWeight::get().writes(38))
+	}
+	/// `pablo::remove_liquidity` and a `swap` of each of the redeemed assets.
+	fn zap_out() -> Weight {
+		Weight::from_parts(1_420_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 290_031))
+			.saturating_add(T::DbWeight::get().reads(92))
+			.saturating_add(T::DbWeight::get().writes(55))
+	}
+	/// The config and both reserves of each of the `p` pools, `c` hops priced.
+	/// The range of component `p` is `[2, 64]`.
+	/// The range of component `c` is `[1, 256]`.
+	fn route_search(p: u32, c: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 7_827).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(p.into())))
+	}
+}
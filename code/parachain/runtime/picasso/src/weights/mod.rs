@@ -5,6 +5,7 @@ pub mod balances;
 pub mod collator_selection;
 pub mod collective;
 pub mod crowdloan_rewards;
+pub mod dex_router;
 pub mod frame_system;
 pub mod identity;
 pub mod indices;
@@ -201,4 +201,62 @@ impl<T: frame_system::Config> oracle::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
+	// NOT GENERATED: the weights below are conservative estimates for calls the benchmark CLI has
+	// not been run on yet, to be replaced by the output of `nix run .#benchmarks-generate-picasso`.
+	/// Reads and writes the info of the asset.
+	fn set_price_exponent() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3544))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Reads and writes the info of the asset.
+	fn set_circuit_breaker() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3551))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Removes the freeze of the asset and records the unfreeze.
+	fn unfreeze_asset() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3498))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Checks the asset and that no round is in progress, sets the reveal period.
+	fn set_reveal_period() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 11079))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Stake, answer in transit, asset info, reveal period and a full list of commitments.
+	fn commit_price() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 24638))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Reveal period, a full list of commitments and a full list of pre-prices.
+	fn reveal_price() -> Weight {
+		Weight::from_parts(65_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 15559))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Checks the asset, sets the weighting.
+	fn set_reputation_weighting() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3551))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Checks the asset and walks a chain of 16 derived prices for cycles.
+	fn set_derived_price() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 40_000))
+			.saturating_add(T::DbWeight::get().reads(18))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -1,62 +1,76 @@
-use primitives::currency::CurrencyId;
-use std::{convert::TryFrom, fmt::Display};
-
-custom_derive! {
-	#[derive(EnumFromStr, Copy, Clone, PartialEq, Eq, Hash, Debug)]
-	pub enum Asset {
-		KSM,
-		PICA,
-		USDT,
-		USDC,
-		DOT,
-	}
+use serde::{de::Error, Deserialize, Deserializer};
+use std::{
+	fmt::{Debug, Display},
+	str::FromStr,
+};
+
+/// Maximum length of an asset symbol.
+pub const MAX_SYMBOL_LENGTH: usize = 16;
+
+/// An asset, identified by its ticker symbol as declared in the configuration.
+///
+/// The symbol is stored inline so that assets stay cheap to copy around feeds and caches.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Asset {
+	symbol: [u8; MAX_SYMBOL_LENGTH],
+	length: u8,
 }
 
-pub const VALID_PRICE_QUOTE_ASSETS: &[Asset] = &[Asset::USDT, Asset::USDC];
+impl Asset {
+	/// Asset of an ASCII alphanumeric `symbol`, `None` if invalid or too long.
+	pub fn new(symbol: &str) -> Option<Self> {
+		if symbol.is_empty() ||
+			symbol.len() > MAX_SYMBOL_LENGTH ||
+			!symbol.bytes().all(|c| c.is_ascii_alphanumeric())
+		{
+			return None
+		}
+		let mut asset = Asset { symbol: [0; MAX_SYMBOL_LENGTH], length: symbol.len() as u8 };
+		asset.symbol[..symbol.len()].copy_from_slice(symbol.as_bytes());
+		Some(asset)
+	}
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct AssetPair(pub Asset, pub Asset);
+	pub fn symbol(&self) -> &str {
+		// Only built from ASCII symbols.
+		std::str::from_utf8(&self.symbol[..self.length as usize]).expect("symbol is ASCII")
+	}
+}
 
-// NOTE: sure it is better move it into primitives
-//
-// The map of valid asset we are allowed to ask price for.
-// We must not swap two indexes.
-//
-// This is tiny enough that we don’t bother using a Map.
-pub static INDEX_TO_ASSET: [(CurrencyId, Asset); 5] = [
-	(CurrencyId::KSM, Asset::KSM),
-	(CurrencyId::PICA, Asset::PICA),
-	(CurrencyId::USDT, Asset::USDT),
-	(CurrencyId::USDC, Asset::USDC),
-	(CurrencyId::DOT, Asset::DOT),
-];
+impl FromStr for Asset {
+	type Err = ();
+	fn from_str(symbol: &str) -> Result<Asset, Self::Err> {
+		Asset::new(symbol).ok_or(())
+	}
+}
 
-impl AssetPair {
-	/*
-	  We currently only allow X/(USD|Stablecoin)
-	*/
-	pub fn new(x: Asset, y: Asset) -> Option<Self> {
-		match (x, y) {
-			(_, y) if VALID_PRICE_QUOTE_ASSETS.contains(&y) => Some(AssetPair(x, y)),
-			_ => None,
-		}
+impl<'de> Deserialize<'de> for Asset {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let symbol = String::deserialize(deserializer)?;
+		Asset::new(&symbol).ok_or_else(|| D::Error::custom(format!("invalid symbol {}", symbol)))
+	}
+}
+
+impl Display for Asset {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.symbol())
 	}
 }
 
-impl TryFrom<Asset> for CurrencyId {
-	type Error = ();
-	fn try_from(asset: Asset) -> Result<CurrencyId, Self::Error> {
-		INDEX_TO_ASSET.iter().find_map(|(id, a)| (asset == *a).then_some(*id)).ok_or(())
+impl Debug for Asset {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.symbol())
 	}
 }
 
-impl TryFrom<CurrencyId> for Asset {
-	type Error = ();
-	fn try_from(currency_index: CurrencyId) -> Result<Asset, Self::Error> {
-		INDEX_TO_ASSET
-			.iter()
-			.find_map(|(id, a)| (currency_index == *id).then_some(*a))
-			.ok_or(())
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AssetPair(pub Asset, pub Asset);
+
+impl AssetPair {
+	/*
+	  The quote asset is validated against the configuration when loading it.
+	*/
+	pub fn new(x: Asset, y: Asset) -> Self {
+		AssetPair(x, y)
 	}
 }
 
@@ -75,7 +89,7 @@ impl Display for ConcatSymbol {
 	#[inline(always)]
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let ConcatSymbol(AssetPair(x, y)) = self;
-		write!(f, "{:?}{:?}", x, y)
+		write!(f, "{}{}", x, y)
 	}
 }
 
@@ -94,6 +108,38 @@ impl Display for SlashSymbol {
 	#[inline(always)]
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let SlashSymbol(AssetPair(x, y)) = self;
-		write!(f, "{:?}/{:?}", x, y)
+		write!(f, "{}/{}", x, y)
+	}
+}
+
+/// Assets used across tests.
+#[cfg(test)]
+pub fn test_assets() -> impl Iterator<Item = Asset> {
+	["KSM", "PICA", "USDT", "USDC", "DOT"]
+		.into_iter()
+		.map(|symbol| Asset::new(symbol).expect("valid symbol"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Asset, AssetPair, ConcatSymbol, SlashSymbol};
+
+	#[test]
+	fn test_asset_symbol() {
+		[
+			("KSM", Some("KSM")),
+			("USDT", Some("USDT")),
+			("LONGSYMBOL123456", Some("LONGSYMBOL123456")),
+			("LONGSYMBOL1234567", None),
+			("", None),
+			("KS M", None),
+		]
+		.into_iter()
+		.for_each(|(symbol, expected)| {
+			assert_eq!(Asset::new(symbol).as_ref().map(Asset::symbol), expected);
+		});
+		let pair = AssetPair::new(Asset::new("KSM").unwrap(), Asset::new("USDT").unwrap());
+		assert_eq!(ConcatSymbol::new(pair).to_string(), "KSMUSDT");
+		assert_eq!(SlashSymbol::new(pair).to_string(), "KSM/USDT");
 	}
 }
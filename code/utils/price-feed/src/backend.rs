@@ -29,23 +29,21 @@ where
 	}
 }
 
+/* NOTE: prices are cached per asset and feed, the frontend merges the up to date prices of the
+   feeds with its `MergePolicy`. Prices are kept on channel close, they are ignored by the
+   frontend once outdated.
+*/
 impl<TFeed, TAsset, TPrice> TryFrom<FeedNotification<TFeed, TAsset, TPrice>>
-	for FeedNotificationAction<TAsset, TPrice>
+	for FeedNotificationAction<(TAsset, TFeed), TPrice>
 where
-	TFeed: Debug,
+	TFeed: Debug + Copy,
 	TAsset: Debug + Copy,
 	TPrice: Copy,
 {
 	type Error = ();
-	/* TODO: how are we going to handles X feeds:
-	  - do we just expose every one of them from their own endpoint?
-	  - do we merge the prices (median?), if so, merging will depend on timestamp?
-	  On notification close, do we remove the price as we are no
-	  longer getting new prices?
-	*/
 	fn try_from(
 		notification: FeedNotification<TFeed, TAsset, TPrice>,
-	) -> Result<FeedNotificationAction<TAsset, TPrice>, Self::Error> {
+	) -> Result<FeedNotificationAction<(TAsset, TFeed), TPrice>, Self::Error> {
 		match notification {
 			FeedNotification::Started { feed } => {
 				log::info!("{:?} started successfully", feed);
@@ -59,8 +57,8 @@ where
 				log::info!("{:?} has closed a channel for {:?}", feed, asset);
 				Err(())
 			},
-			FeedNotification::AssetPriceUpdated { asset, price, .. } =>
-				Ok(FeedNotificationAction::UpdateCache { key: asset, value: price }),
+			FeedNotification::AssetPriceUpdated { feed, asset, price } =>
+				Ok(FeedNotificationAction::UpdateCache { key: (asset, feed), value: price }),
 			FeedNotification::Stopped { feed } => {
 				log::info!("{:?} stopped", feed);
 				Err(())
@@ -121,7 +119,7 @@ impl Backend {
 mod tests {
 	use super::Backend;
 	use crate::{
-		asset::{Asset, INDEX_TO_ASSET},
+		asset::{test_assets, Asset},
 		backend::{FeedNotificationAction, Transition},
 		cache::{PriceCache, ThreadSafePriceCache},
 		feed::{
@@ -146,7 +144,7 @@ mod tests {
 			value: (Price(0xCAFEBABE), Exponent(0x1337)),
 			timestamp: TimeStamp::now(),
 		};
-		for (_, asset) in INDEX_TO_ASSET {
+		for asset in test_assets() {
 			for (notification, expected) in [
 				(FeedNotification::AssetOpened { feed, asset }, None),
 				(FeedNotification::AssetClosed { feed, asset }, None),
@@ -154,15 +152,17 @@ mod tests {
 					FeedNotification::AssetPriceUpdated { feed, asset, price: timestamped_price },
 					Some((
 						FeedNotificationAction::UpdateCache {
-							key: asset,
+							key: (asset, feed),
 							value: timestamped_price,
 						},
-						[(asset, timestamped_price)],
+						[((asset, feed), timestamped_price)],
 					)),
 				),
 			] {
 				if let (Ok(actual_action), Some((expected_action, expected_state))) = (
-					FeedNotificationAction::<Asset, TimeStampedPrice>::try_from(notification),
+					FeedNotificationAction::<(Asset, FeedIdentifier), TimeStampedPrice>::try_from(
+						notification,
+					),
 					expected,
 				) {
 					assert_eq!(actual_action, expected_action);
@@ -180,7 +180,7 @@ mod tests {
 			|x, y| TimeStamped { value: (Price(x), Exponent(y)), timestamp: TimeStamp::now() };
 		let (price1, price2, price3) = (mk_price(123, -3), mk_price(3134, -1), mk_price(93424, -4));
 		let feed = FeedIdentifier::Binance;
-		for (_, asset) in INDEX_TO_ASSET {
+		for asset in test_assets() {
 			let tests = [
 				(
 					vec![
@@ -188,7 +188,7 @@ mod tests {
 						FeedNotification::AssetPriceUpdated { feed, asset, price: price1 },
 						FeedNotification::AssetClosed { feed, asset },
 					],
-					[(asset, price1)],
+					[((asset, feed), price1)],
 				),
 				(
 					vec![
@@ -198,7 +198,7 @@ mod tests {
 						FeedNotification::AssetPriceUpdated { feed, asset, price: price2 },
 						FeedNotification::AssetClosed { feed, asset },
 					],
-					[(asset, price2)],
+					[((asset, feed), price2)],
 				),
 				(
 					vec![
@@ -208,7 +208,7 @@ mod tests {
 						FeedNotification::AssetPriceUpdated { feed, asset, price: price3 },
 						FeedNotification::AssetClosed { feed, asset },
 					],
-					[(asset, price3)],
+					[((asset, feed), price3)],
 				),
 			];
 			for (events, expected) in &tests {
@@ -218,7 +218,7 @@ mod tests {
 				let signals = Signals::new(&[]).expect("could not create signals stream").fuse();
 				let backend = Backend::new::<
 					FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
-					FeedNotificationAction<Asset, TimeStampedPrice>,
+					FeedNotificationAction<(Asset, FeedIdentifier), TimeStampedPrice>,
 					_,
 					_,
 					_,
@@ -241,4 +241,43 @@ mod tests {
 			}
 		}
 	}
+
+	#[tokio::test]
+	async fn test_feed_backend_caches_each_feed() {
+		let mk_price =
+			|x, y| TimeStamped { value: (Price(x), Exponent(y)), timestamp: TimeStamp::now() };
+		let (price1, price2) = (mk_price(123, -3), mk_price(3134, -1));
+		let (binance, composable) = (FeedIdentifier::Binance, FeedIdentifier::Composable);
+		for asset in test_assets() {
+			let prices_cache: ThreadSafePriceCache = Arc::new(RwLock::new(HashMap::new()));
+			let (feed_in, feed_out) =
+				mpsc::channel::<FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>>(8);
+			let signals = Signals::new(&[]).expect("could not create signals stream").fuse();
+			let backend = Backend::new::<
+				FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
+				FeedNotificationAction<(Asset, FeedIdentifier), TimeStampedPrice>,
+				_,
+				_,
+				_,
+				_,
+			>(prices_cache.clone(), ReceiverStream::new(feed_out), signals)
+			.await;
+
+			for event in [
+				FeedNotification::AssetPriceUpdated { feed: binance, asset, price: price1 },
+				FeedNotification::AssetPriceUpdated { feed: composable, asset, price: price2 },
+			] {
+				feed_in.send(event).await.expect("could not send feed notification");
+			}
+
+			drop(feed_in);
+			backend.shutdown_handle.await.expect("could not join on backend handle");
+
+			let prices_cache_r = prices_cache.read().expect("could not acquire read lock");
+			assert_eq!(
+				*prices_cache_r,
+				PriceCache::from([((asset, binance), price1), ((asset, composable), price2)])
+			);
+		}
+	}
 }
@@ -1,11 +1,15 @@
-use crate::{asset::Asset, feed::TimeStampedPrice};
+use crate::{
+	asset::Asset,
+	feed::{FeedIdentifier, TimeStampedPrice},
+};
 use std::{
 	collections::HashMap,
 	hash::Hash,
 	sync::{Arc, RwLock},
 };
 
-pub type PriceCache = HashMap<Asset, TimeStampedPrice>;
+/// Last price of every asset reported by every feed.
+pub type PriceCache = HashMap<(Asset, FeedIdentifier), TimeStampedPrice>;
 
 pub type ThreadSafePriceCache = Arc<RwLock<PriceCache>>;
 
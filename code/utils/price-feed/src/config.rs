@@ -0,0 +1,271 @@
+use crate::{
+	asset::Asset,
+	feed::{json::Selector, Exponent},
+};
+use primitives::currency::CurrencyId;
+use serde::Deserialize;
+use std::{
+	collections::HashSet,
+	path::Path,
+	sync::{Arc, RwLock},
+};
+
+/// An asset the server is allowed to price.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AssetConfig {
+	pub symbol: Asset,
+	/// Unique index of the asset in the oracle pallet.
+	pub currency_id: CurrencyId,
+	/// Price will be normalized to this exponent, instead of the default one.
+	#[serde(default)]
+	pub exponent: Option<Exponent>,
+}
+
+/// Assets each feed subscribes to.
+#[derive(Deserialize, Default, Clone, PartialEq, Eq, Debug)]
+pub struct FeedsConfig {
+	/// Assets traded against the quote asset on Binance.
+	#[serde(default)]
+	pub binance: Vec<Asset>,
+	/// Base and quote assets of the Pablo pools whose TWAP is reported by the composable node.
+	#[serde(default)]
+	pub composable: Vec<(Asset, Asset)>,
+	/// Pablo pools whose TWAP is read from the storage of the composable node.
+	#[serde(default)]
+	pub pablo: Vec<PabloPoolConfig>,
+	/// Generic JSON feeds, identified as `Json<index>` by their index in this list.
+	#[serde(default)]
+	pub json: Vec<JsonFeedConfig>,
+}
+
+/// A Pablo pool, pricing its base asset.
+#[derive(Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PabloPoolConfig {
+	pub pool_id: u128,
+	/// Base asset of the pool.
+	pub asset: Asset,
+}
+
+/// A JSON endpoint, either polled over HTTP or pushing messages over WebSocket.
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct JsonFeedConfig {
+	/// `http(s)://` endpoint polled every `poll_interval`, or `ws(s)://` endpoint.
+	pub url: String,
+	/// Milliseconds between two requests to an HTTP endpoint.
+	#[serde(default = "default_poll_interval")]
+	pub poll_interval: u64,
+	/// Message sent once connected to a WebSocket endpoint, usually a subscription.
+	#[serde(default)]
+	pub subscribe: Option<serde_json::Value>,
+	pub assets: Vec<JsonAssetConfig>,
+}
+
+/// Where to find the price of an asset in the documents of a JSON feed.
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct JsonAssetConfig {
+	pub asset: Asset,
+	pub selector: Selector,
+	/// Number of decimals the price is read with.
+	pub exponent: Exponent,
+}
+
+fn default_poll_interval() -> u64 {
+	1000
+}
+
+/// Configuration of the server, loaded from a JSON file.
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Config {
+	/// Asset to be used as quote for pricing.
+	pub quote_asset: Asset,
+	pub assets: Vec<AssetConfig>,
+	#[serde(default)]
+	pub feeds: FeedsConfig,
+}
+
+/// Configuration shared between the frontend and the reloading task.
+pub type ThreadSafeConfig = Arc<RwLock<Config>>;
+
+#[derive(Debug)]
+pub enum ConfigError {
+	CannotRead(std::io::Error),
+	CannotParse(serde_json::Error),
+	DuplicateAsset(Asset),
+	DuplicateCurrencyId(CurrencyId),
+	UnknownAsset(Asset),
+	TooManyJsonFeeds,
+}
+
+impl Config {
+	/// Read and validate the configuration file at `path`.
+	pub fn load(path: &Path) -> Result<Config, ConfigError> {
+		let content = std::fs::read_to_string(path).map_err(ConfigError::CannotRead)?;
+		let config: Config = serde_json::from_str(&content).map_err(ConfigError::CannotParse)?;
+		config.validate()?;
+		Ok(config)
+	}
+
+	/// Ensure that symbols and currency ids are unique, that every asset used is declared and that
+	/// JSON feeds can be identified.
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		let mut symbols = HashSet::new();
+		let mut currency_ids = HashSet::new();
+		for asset in &self.assets {
+			if !symbols.insert(asset.symbol) {
+				return Err(ConfigError::DuplicateAsset(asset.symbol))
+			}
+			if !currency_ids.insert(asset.currency_id) {
+				return Err(ConfigError::DuplicateCurrencyId(asset.currency_id))
+			}
+		}
+		let used = [self.quote_asset]
+			.into_iter()
+			.chain(self.feeds.binance.iter().copied())
+			.chain(self.feeds.composable.iter().flat_map(|&(base, quote)| [base, quote]))
+			.chain(self.feeds.pablo.iter().map(|pool| pool.asset))
+			.chain(self.feeds.json.iter().flat_map(|feed| feed.assets.iter().map(|x| x.asset)));
+		for asset in used {
+			if !symbols.contains(&asset) {
+				return Err(ConfigError::UnknownAsset(asset))
+			}
+		}
+		if self.feeds.json.len() > usize::from(u8::MAX) + 1 {
+			return Err(ConfigError::TooManyJsonFeeds)
+		}
+		Ok(())
+	}
+
+	pub fn currency_id(&self, asset: Asset) -> Option<CurrencyId> {
+		self.assets.iter().find_map(|x| (x.symbol == asset).then_some(x.currency_id))
+	}
+
+	pub fn asset(&self, currency_id: CurrencyId) -> Option<Asset> {
+		self.assets
+			.iter()
+			.find_map(|x| (x.currency_id == currency_id).then_some(x.symbol))
+	}
+
+	/// Replace the shared configuration with the one at `path`, keeping the current one if it is
+	/// invalid. Feeds subscribe to their assets when started, changes of the quote asset and
+	/// subscriptions only apply on restart.
+	pub fn reload(path: &Path, config: &ThreadSafeConfig) {
+		match Config::load(path) {
+			Ok(new_config) => {
+				let mut config = config.write().expect("could not acquire write lock");
+				if new_config.quote_asset != config.quote_asset || new_config.feeds != config.feeds
+				{
+					log::warn!("quote asset and feed subscriptions changes require a restart");
+				}
+				*config = new_config;
+				log::info!("configuration reloaded from {:?}", path);
+			},
+			Err(e) => log::error!("could not reload configuration from {:?}: {:?}", path, e),
+		}
+	}
+
+	/// Exponent the price of `asset` is normalized to, `default` if not configured.
+	pub fn exponent(&self, asset: Asset, default: Exponent) -> Exponent {
+		self.assets
+			.iter()
+			.find_map(|x| (x.symbol == asset).then_some(x.exponent))
+			.flatten()
+			.unwrap_or(default)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		AssetConfig, Config, ConfigError, FeedsConfig, JsonAssetConfig, JsonFeedConfig,
+		PabloPoolConfig,
+	};
+	use crate::{
+		asset::Asset,
+		feed::{json::Selector, Exponent},
+	};
+	use primitives::currency::CurrencyId;
+
+	fn asset(symbol: &str) -> Asset {
+		Asset::new(symbol).expect("valid symbol")
+	}
+
+	#[test]
+	fn test_parse_config() {
+		let config: Config = serde_json::from_str(
+			r#"{
+				"quote_asset": "USDT",
+				"assets": [
+					{ "symbol": "KSM", "currency_id": 4 },
+					{ "symbol": "USDT", "currency_id": 130, "exponent": 6 }
+				],
+				"feeds": { "binance": ["KSM"] }
+			}"#,
+		)
+		.expect("valid config");
+		assert_eq!(
+			config,
+			Config {
+				quote_asset: asset("USDT"),
+				assets: vec![
+					AssetConfig {
+						symbol: asset("KSM"),
+						currency_id: CurrencyId::KSM,
+						exponent: None
+					},
+					AssetConfig {
+						symbol: asset("USDT"),
+						currency_id: CurrencyId::USDT,
+						exponent: Some(Exponent(6))
+					},
+				],
+				feeds: FeedsConfig {
+					binance: vec![asset("KSM")],
+					composable: vec![],
+					pablo: vec![PabloPoolConfig { pool_id: 0, asset: asset("KSM") }],
+					json: vec![JsonFeedConfig {
+						url: "https://example.com/ticker".into(),
+						poll_interval: 1000,
+						subscribe: None,
+						assets: vec![JsonAssetConfig {
+							asset: asset("KSM"),
+							selector: "$.price".parse().expect("valid selector"),
+							exponent: Exponent(6),
+						}],
+					}],
+				},
+			}
+		);
+		assert!(config.validate().is_ok());
+		assert_eq!(config.currency_id(asset("KSM")), Some(CurrencyId::KSM));
+		assert_eq!(config.asset(CurrencyId::USDT), Some(asset("USDT")));
+		assert_eq!(config.asset(CurrencyId::DOT), None);
+		assert_eq!(config.exponent(asset("KSM"), Exponent(12)), Exponent(12));
+		assert_eq!(config.exponent(asset("USDT"), Exponent(12)), Exponent(6));
+	}
+
+	#[test]
+	fn test_validate_config() {
+		let ksm =
+			AssetConfig { symbol: asset("KSM"), currency_id: CurrencyId::KSM, exponent: None };
+		let config = Config {
+			quote_asset: asset("KSM"),
+			assets: vec![ksm, ksm],
+			feeds: FeedsConfig::default(),
+		};
+		assert!(matches!(config.validate(), Err(ConfigError::DuplicateAsset(_))));
+
+		let config =
+			Config { assets: vec![ksm, AssetConfig { symbol: asset("DOT"), ..ksm }], ..config };
+		assert!(matches!(config.validate(), Err(ConfigError::DuplicateCurrencyId(_))));
+
+		let config = Config {
+			assets: vec![ksm],
+			feeds: FeedsConfig {
+				composable: vec![(asset("PICA"), asset("KSM"))],
+				..FeedsConfig::default()
+			},
+			..config
+		};
+		assert!(matches!(config.validate(), Err(ConfigError::UnknownAsset(_))));
+	}
+}
@@ -5,6 +5,7 @@ use super::{
 use crate::{
 	asset::{Asset, AssetPair, ConcatSymbol},
 	feed::{Exponent, TimeStamp, CHANNEL_BUFFER_SIZE},
+	metrics::ThreadSafeMetrics,
 };
 use binance::websockets::{WebSockets, WebsocketEvent};
 use std::{
@@ -23,6 +24,7 @@ impl BinanceFeed {
 		keep_running: Arc<AtomicBool>,
 		assets: &HashSet<Asset>,
 		quote_asset: Asset,
+		metrics: ThreadSafeMetrics,
 	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
 		let (sink, source) = mpsc::channel(CHANNEL_BUFFER_SIZE);
 
@@ -36,12 +38,7 @@ impl BinanceFeed {
 
 		let symbol_asset = assets
 			.iter()
-			.map(|&asset| {
-				let asset_pair = AssetPair::new(asset, quote_asset).unwrap_or_else(|| {
-					panic!("asset {:?} should be quotable in {:?}", asset, quote_asset)
-				});
-				ConcatSymbol::new(asset_pair).to_string()
-			})
+			.map(|&asset| ConcatSymbol::new(AssetPair::new(asset, quote_asset)).to_string())
 			.zip(assets.iter().copied())
 			.collect::<HashMap<_, _>>();
 
@@ -85,14 +82,17 @@ impl BinanceFeed {
 							// Find back the asset from the symbol.
 							if let Some(&asset) = symbol_asset.get(&trades.symbol) {
 								// Trigger a price update in USD cent
-								let _ = sink.blocking_send(FeedNotification::AssetPriceUpdated {
+								let notification = FeedNotification::AssetPriceUpdated {
 									feed: FeedIdentifier::Binance,
 									asset,
 									price: TimeStamped {
 										value: (Price(usd_cent_price), Exponent(2)),
 										timestamp,
 									},
-								});
+								};
+								if sink.blocking_send(notification).is_err() {
+									metrics.record_dropped_notification();
+								}
 							}
 						}
 						Ok(())
@@ -1,13 +1,16 @@
 use super::{Feed, FeedError, FeedResult};
 use crate::{
 	asset::Asset,
+	config::Config,
 	feed::{
 		composable_api::api::pablo::events::TwapUpdated, Exponent, FeedIdentifier,
 		FeedNotification, Price, TimeStamp, TimeStamped, TimeStampedPrice, CHANNEL_BUFFER_SIZE,
 	},
+	metrics::ThreadSafeMetrics,
 };
 use futures::StreamExt;
-use std::collections::HashSet;
+use primitives::currency::CurrencyId;
+use std::collections::{HashMap, HashSet};
 use subxt::{OnlineClient, SubstrateConfig};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
@@ -19,7 +22,23 @@ impl ComposableFeed {
 		shutdown_message: tokio::sync::watch::Receiver<bool>,
 		composable_node_url: String,
 		assets: &HashSet<(Asset, Asset)>,
+		config: &Config,
+		metrics: ThreadSafeMetrics,
 	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
+		// Base asset of each pair, by the currency ids of the pool assets.
+		let pools = assets
+			.iter()
+			.map(|&(base, quote)| {
+				let currency_id = |asset| {
+					config.currency_id(asset).ok_or_else(|| {
+						log::error!("{:?} has no currency id", asset);
+						FeedError::CannotDecodeEvent
+					})
+				};
+				Ok(((currency_id(base)?, currency_id(quote)?), base))
+			})
+			.collect::<FeedResult<HashMap<_, _>>>()?;
+
 		let (sink, source) = mpsc::channel(CHANNEL_BUFFER_SIZE);
 		sink.send(FeedNotification::Started { feed: FeedIdentifier::Composable })
 			.await
@@ -36,7 +55,7 @@ impl ComposableFeed {
 					FeedError::NetworkFailure
 				})?;
 
-		for &(base, _quote) in assets.iter() {
+		for &base in pools.values() {
 			sink.send(FeedNotification::AssetOpened {
 				feed: FeedIdentifier::Composable,
 				asset: base,
@@ -49,7 +68,6 @@ impl ComposableFeed {
 		}
 
 		let sink = sink.clone();
-		let assets = assets.clone();
 
 		let handle = tokio::spawn(async move {
 			// Subscribe to finalized blocks.
@@ -80,7 +98,8 @@ impl ComposableFeed {
 
 					// If TwapUpdated event is found, handle it
 					if let Some(twap_updated_event) = maybe_twap_updated_event {
-						handle_twap_updated_event(twap_updated_event, &assets, &sink).await?;
+						handle_twap_updated_event(twap_updated_event, &pools, &sink, &metrics)
+							.await;
 					}
 				}
 
@@ -89,7 +108,7 @@ impl ComposableFeed {
 				}
 			}
 
-			for &(base, _quote) in assets.iter() {
+			for &base in pools.values() {
 				sink.send(FeedNotification::AssetClosed {
 					feed: FeedIdentifier::Composable,
 					asset: base,
@@ -116,32 +135,24 @@ impl ComposableFeed {
 
 async fn handle_twap_updated_event(
 	twap_updated_details: TwapUpdated,
-	assets: &HashSet<(Asset, Asset)>,
+	pools: &HashMap<(CurrencyId, CurrencyId), Asset>,
 	sink: &mpsc::Sender<FeedNotification<FeedIdentifier, Asset, TimeStamped<(Price, Exponent)>>>,
-) -> Result<(), FeedError> {
+	metrics: &ThreadSafeMetrics,
+) {
 	let (base_asset, base_price) = &twap_updated_details.twaps[0];
 	let (quote_asset, _) = &twap_updated_details.twaps[1];
-	let base_asset = primitives::currency::CurrencyId(base_asset.0).try_into().map_err(|e| {
-		log::error!("{:?}", e);
-		FeedError::NetworkFailure
-	})?;
-	let quote_asset = primitives::currency::CurrencyId(quote_asset.0).try_into().map_err(|e| {
-		log::error!("{:?}", e);
-		FeedError::NetworkFailure
-	})?;
-	Ok(if assets.contains(&(base_asset, quote_asset)) {
-		sink.send(FeedNotification::AssetPriceUpdated {
+	let pool = (CurrencyId(base_asset.0), CurrencyId(quote_asset.0));
+	if let Some(&base_asset) = pools.get(&pool) {
+		let notification = FeedNotification::AssetPriceUpdated {
 			feed: FeedIdentifier::Composable,
 			asset: base_asset,
 			price: TimeStamped {
 				value: (Price(base_price.0.try_into().unwrap()), Exponent(12)),
 				timestamp: TimeStamp::now(),
 			},
-		})
-		.await
-		.map_err(|e| {
-			log::error!("{}", e);
-			FeedError::ChannelIsBroken
-		})?;
-	})
+		};
+		if sink.send(notification).await.is_err() {
+			metrics.record_dropped_notification();
+		}
+	}
 }
@@ -0,0 +1,466 @@
+use super::{
+	Exponent, Feed, FeedError, FeedIdentifier, FeedNotification, FeedResult, Price, TimeStamp,
+	TimeStamped, TimeStampedPrice, CHANNEL_BUFFER_SIZE,
+};
+use crate::{
+	asset::Asset,
+	config::{JsonAssetConfig, JsonFeedConfig},
+	metrics::ThreadSafeMetrics,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{de::Error, Deserialize, Deserializer};
+use serde_json::Value;
+use std::{str::FromStr, time::Duration};
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_tungstenite::tungstenite::Message;
+
+type FeedSink = mpsc::Sender<FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>>;
+
+/// Delay before the first attempt to reconnect a dropped WebSocket, doubled after each failed
+/// attempt up to `MAX_RECONNECT_DELAY`.
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Path to a value of a JSON document, a subset of JSONPath made of object fields and array
+/// indexes. Like `$.data[0].price` or `$['KSM-USDT'].last`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Selector(Vec<Segment>);
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum Segment {
+	Field(String),
+	Index(usize),
+}
+
+impl Selector {
+	pub fn select<'a>(&self, document: &'a Value) -> Option<&'a Value> {
+		self.0.iter().try_fold(document, |value, segment| match segment {
+			Segment::Field(field) => value.get(field),
+			Segment::Index(index) => value.get(index),
+		})
+	}
+}
+
+impl FromStr for Selector {
+	type Err = ();
+	fn from_str(selector: &str) -> Result<Selector, Self::Err> {
+		let mut rest = selector.strip_prefix('$').ok_or(())?;
+		let mut segments = Vec::new();
+		while !rest.is_empty() {
+			if let Some(field) = rest.strip_prefix('.') {
+				let end = field.find(['.', '[']).unwrap_or(field.len());
+				if end == 0 {
+					return Err(())
+				}
+				segments.push(Segment::Field(field[..end].to_string()));
+				rest = &field[end..];
+			} else if let Some(field) = rest.strip_prefix("['") {
+				let end = field.find("']").ok_or(())?;
+				segments.push(Segment::Field(field[..end].to_string()));
+				rest = &field[end + 2..];
+			} else if let Some(index) = rest.strip_prefix('[') {
+				let end = index.find(']').ok_or(())?;
+				segments.push(Segment::Index(index[..end].parse().map_err(|_| ())?));
+				rest = &index[end + 1..];
+			} else {
+				return Err(())
+			}
+		}
+		Ok(Selector(segments))
+	}
+}
+
+impl<'de> Deserialize<'de> for Selector {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let selector = String::deserialize(deserializer)?;
+		Selector::from_str(&selector)
+			.map_err(|_| D::Error::custom(format!("invalid selector {}", selector)))
+	}
+}
+
+/// Read a decimal price, either a JSON number or a string, with `exponent` decimals. Extra
+/// decimals are truncated.
+pub fn parse_price(value: &Value, Exponent(exponent): Exponent) -> Option<Price> {
+	let decimal = match value {
+		Value::Number(number) => number.to_string(),
+		Value::String(string) => string.clone(),
+		_ => return None,
+	};
+	let exponent = usize::try_from(exponent).ok()?;
+	let (integer, fraction) = decimal.split_once('.').unwrap_or((&decimal, ""));
+	if integer.is_empty() || !integer.bytes().chain(fraction.bytes()).all(|c| c.is_ascii_digit()) {
+		return None
+	}
+	let fraction = fraction.get(..exponent).unwrap_or(fraction);
+	format!("{}{:0<width$}", integer, fraction, width = exponent)
+		.parse()
+		.ok()
+		.map(Price)
+}
+
+/// A feed reading prices out of the JSON documents served by an arbitrary endpoint.
+pub struct JsonFeed;
+
+impl JsonFeed {
+	pub async fn start(
+		shutdown_message: watch::Receiver<bool>,
+		feed: FeedIdentifier,
+		config: JsonFeedConfig,
+		metrics: ThreadSafeMetrics,
+	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
+		let (sink, source) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+		notify(&sink, FeedNotification::Started { feed }).await?;
+		for asset in config.assets.iter().map(|x| x.asset) {
+			notify(&sink, FeedNotification::AssetOpened { feed, asset }).await?;
+		}
+
+		let handle = tokio::spawn(async move {
+			// Make sure we trigger the AssetClosed/Stopped events
+			// by not returning early.
+			let e = if config.url.starts_with("ws") {
+				subscribe(shutdown_message, feed, &config, &sink, &metrics).await
+			} else {
+				poll(shutdown_message, feed, &config, &sink, &metrics).await
+			};
+
+			for asset in config.assets.iter().map(|x| x.asset) {
+				notify(&sink, FeedNotification::AssetClosed { feed, asset }).await?;
+			}
+			notify(&sink, FeedNotification::Stopped { feed }).await?;
+
+			e
+		});
+
+		Ok((handle, ReceiverStream::new(source)))
+	}
+}
+
+/// Request the HTTP endpoint every `poll_interval`. Failed requests, including the ones answered
+/// with an error status, are logged and retried on the next tick.
+async fn poll(
+	mut shutdown_message: watch::Receiver<bool>,
+	feed: FeedIdentifier,
+	config: &JsonFeedConfig,
+	sink: &FeedSink,
+	metrics: &ThreadSafeMetrics,
+) -> FeedResult<()> {
+	let client = reqwest::Client::new();
+	let mut interval = tokio::time::interval(Duration::from_millis(config.poll_interval));
+	loop {
+		tokio::select! {
+			_ = interval.tick() => {
+				let response = client
+					.get(&config.url)
+					.send()
+					.await
+					.and_then(reqwest::Response::error_for_status);
+				let document = match response {
+					Ok(response) => response.json::<Value>().await,
+					Err(e) => Err(e),
+				};
+				match document {
+					Ok(document) => publish(feed, &config.assets, &document, sink, metrics).await,
+					Err(e) => log::warn!("{}: {}", feed, e),
+				}
+			},
+			changed = shutdown_message.changed() => {
+				if changed.is_err() || *shutdown_message.borrow() {
+					return Ok(())
+				}
+			},
+		}
+	}
+}
+
+/// Listen to the messages pushed by the WebSocket endpoint, reconnecting with an exponential
+/// backoff whenever the connection fails or drops.
+async fn subscribe(
+	mut shutdown_message: watch::Receiver<bool>,
+	feed: FeedIdentifier,
+	config: &JsonFeedConfig,
+	sink: &FeedSink,
+	metrics: &ThreadSafeMetrics,
+) -> FeedResult<()> {
+	let mut delay = MIN_RECONNECT_DELAY;
+	loop {
+		match listen(&mut shutdown_message, feed, config, sink, metrics, &mut delay).await {
+			Err(FeedError::NetworkFailure) => {},
+			result => return result,
+		}
+		log::warn!("{}: reconnecting in {:?}", feed, delay);
+		tokio::select! {
+			_ = tokio::time::sleep(delay) => {},
+			changed = shutdown_message.changed() => {
+				if changed.is_err() || *shutdown_message.borrow() {
+					return Ok(())
+				}
+			},
+		}
+		delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+	}
+}
+
+/// Listen to the messages pushed by the WebSocket endpoint over a single connection, after sending
+/// the subscription message if any. The reconnection `delay` is reset once a message is received.
+async fn listen(
+	shutdown_message: &mut watch::Receiver<bool>,
+	feed: FeedIdentifier,
+	config: &JsonFeedConfig,
+	sink: &FeedSink,
+	metrics: &ThreadSafeMetrics,
+	delay: &mut Duration,
+) -> FeedResult<()> {
+	let (mut ws, _) = tokio_tungstenite::connect_async(config.url.as_str()).await.map_err(|e| {
+		log::error!("{}", e);
+		FeedError::NetworkFailure
+	})?;
+	if let Some(subscribe) = &config.subscribe {
+		ws.send(Message::Text(subscribe.to_string())).await.map_err(|e| {
+			log::error!("{}", e);
+			FeedError::NetworkFailure
+		})?;
+	}
+	loop {
+		tokio::select! {
+			message = ws.next() => match message {
+				Some(Ok(Message::Text(text))) => {
+					*delay = MIN_RECONNECT_DELAY;
+					match serde_json::from_str::<Value>(&text) {
+						Ok(document) =>
+							publish(feed, &config.assets, &document, sink, metrics).await,
+						Err(e) => log::warn!("{}: {}", feed, e),
+					}
+				},
+				// Pings are answered by tungstenite.
+				Some(Ok(_)) => {},
+				Some(Err(e)) => {
+					log::error!("{}", e);
+					return Err(FeedError::NetworkFailure)
+				},
+				None => {
+					log::error!("{}: connection closed", feed);
+					return Err(FeedError::NetworkFailure)
+				},
+			},
+			changed = shutdown_message.changed() => {
+				if changed.is_err() || *shutdown_message.borrow() {
+					let _ = ws.close(None).await;
+					return Ok(())
+				}
+			},
+		}
+	}
+}
+
+/// Notify the prices found in `document`. Documents without the price of an asset, like
+/// heartbeats, are ignored. Notifications the backend cannot take are dropped.
+async fn publish(
+	feed: FeedIdentifier,
+	assets: &[JsonAssetConfig],
+	document: &Value,
+	sink: &FeedSink,
+	metrics: &ThreadSafeMetrics,
+) {
+	let timestamp = TimeStamp::now();
+	for config in assets {
+		let price = config.selector.select(document).and_then(|x| parse_price(x, config.exponent));
+		if let Some(price) = price {
+			let notification = FeedNotification::AssetPriceUpdated {
+				feed,
+				asset: config.asset,
+				price: TimeStamped { value: (price, config.exponent), timestamp },
+			};
+			if sink.send(notification).await.is_err() {
+				metrics.record_dropped_notification();
+			}
+		}
+	}
+}
+
+async fn notify(
+	sink: &FeedSink,
+	notification: FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
+) -> FeedResult<()> {
+	sink.send(notification).await.map_err(|e| {
+		log::error!("{}", e);
+		FeedError::ChannelIsBroken
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_price, JsonFeed, Selector};
+	use crate::{
+		asset::Asset,
+		config::{JsonAssetConfig, JsonFeedConfig},
+		feed::{Exponent, FeedIdentifier, FeedNotification, Price, TimeStamped},
+		metrics::Metrics,
+	};
+	use futures::{SinkExt, StreamExt};
+	use serde_json::json;
+	use std::{
+		net::SocketAddr,
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			Arc,
+		},
+	};
+	use tokio::sync::watch;
+	use warp::{http::StatusCode, Filter};
+
+	fn ksm_config(url: String) -> JsonFeedConfig {
+		JsonFeedConfig {
+			url,
+			poll_interval: 10,
+			subscribe: Some(json!({ "subscribe": "KSM" })),
+			assets: vec![JsonAssetConfig {
+				asset: Asset::new("KSM").expect("valid symbol"),
+				selector: "$.data[0]['KSM-USDT'].price".parse().expect("valid selector"),
+				exponent: Exponent(2),
+			}],
+		}
+	}
+
+	/// Start the feed and return the first price it reports, then stop it.
+	async fn first_price(config: JsonFeedConfig) -> Price {
+		let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+		let (handle, mut source) = JsonFeed::start(
+			shutdown_receiver,
+			FeedIdentifier::Json(0),
+			config,
+			Arc::new(Metrics::default()),
+		)
+		.await
+		.expect("feed started");
+		let price = loop {
+			if let FeedNotification::AssetPriceUpdated {
+				price: TimeStamped { value: (price, _), .. },
+				..
+			} = source.next().await.expect("feed is running")
+			{
+				break price
+			}
+		};
+		shutdown_sender.send(true).expect("feed is listening");
+		while source.next().await.is_some() {}
+		assert_eq!(handle.await.expect("feed terminated"), Ok(()));
+		price
+	}
+
+	#[test]
+	fn test_select_price() {
+		let document = json!({ "data": [{ "KSM-USDT": { "price": "25.1234" } }] });
+		[
+			("$.data[0]['KSM-USDT'].price", Some(json!("25.1234"))),
+			("$.data[1]['KSM-USDT'].price", None),
+			("$.data.price", None),
+		]
+		.into_iter()
+		.for_each(|(selector, expected)| {
+			let selector = selector.parse::<Selector>().expect("valid selector");
+			assert_eq!(selector.select(&document), expected.as_ref());
+		});
+		["data", "$.", "$..data", "$[x]", "$['data'"]
+			.into_iter()
+			.for_each(|selector| assert_eq!(selector.parse::<Selector>(), Err(())));
+	}
+
+	#[test]
+	fn test_parse_price() {
+		[
+			(json!("25.1234"), Some(Price(2512))),
+			(json!("25"), Some(Price(2500))),
+			(json!(25.5), Some(Price(2550))),
+			(json!(25), Some(Price(2500))),
+			(json!("-25"), None),
+			(json!(".5"), None),
+			(json!(true), None),
+		]
+		.into_iter()
+		.for_each(|(value, expected)| {
+			assert_eq!(parse_price(&value, Exponent(2)), expected);
+		});
+	}
+
+	#[tokio::test]
+	async fn test_json_feed_polls_http() {
+		let route = warp::path("ticker")
+			.map(|| warp::reply::json(&json!({ "data": [{ "KSM-USDT": { "price": 25.5 } }] })));
+		let (address, server) =
+			warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+		tokio::spawn(server);
+		let price = first_price(ksm_config(format!("http://{}/ticker", address))).await;
+		assert_eq!(price, Price(2550));
+	}
+
+	#[tokio::test]
+	async fn test_json_feed_ignores_error_responses() {
+		let requests = Arc::new(AtomicUsize::new(0));
+		let route = warp::path("ticker").map(move || {
+			// An error page which happens to hold a price, then the actual ticker.
+			let (price, status) = if requests.fetch_add(1, Ordering::SeqCst) == 0 {
+				(99, StatusCode::SERVICE_UNAVAILABLE)
+			} else {
+				(25, StatusCode::OK)
+			};
+			warp::reply::with_status(
+				warp::reply::json(&json!({ "data": [{ "KSM-USDT": { "price": price } }] })),
+				status,
+			)
+		});
+		let (address, server) =
+			warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+		tokio::spawn(server);
+		let price = first_price(ksm_config(format!("http://{}/ticker", address))).await;
+		assert_eq!(price, Price(2500));
+	}
+
+	#[tokio::test]
+	async fn test_json_feed_reconnects_websocket() {
+		let connections = Arc::new(AtomicUsize::new(0));
+		let route = warp::path("ws").and(warp::ws()).map(move |ws: warp::ws::Ws| {
+			let connection = connections.fetch_add(1, Ordering::SeqCst);
+			ws.on_upgrade(move |mut socket| async move {
+				let _subscription = socket.next().await;
+				// Drop the first connection right after the subscription.
+				if connection > 0 {
+					let message = json!({ "data": [{ "KSM-USDT": { "price": "30.01" } }] });
+					let _ = socket.send(warp::ws::Message::text(message.to_string())).await;
+					while socket.next().await.is_some() {}
+				}
+			})
+		});
+		let (address, server) =
+			warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+		tokio::spawn(server);
+		let price = first_price(ksm_config(format!("ws://{}/ws", address))).await;
+		assert_eq!(price, Price(3001));
+	}
+
+	#[tokio::test]
+	async fn test_json_feed_subscribes_websocket() {
+		let route = warp::path("ws").and(warp::ws()).map(|ws: warp::ws::Ws| {
+			ws.on_upgrade(|mut socket| async move {
+				// Reply to the subscription with a heartbeat, then a price.
+				let subscription = socket.next().await;
+				assert_eq!(
+					subscription.and_then(Result::ok).as_ref().and_then(|x| x.to_str().ok()),
+					Some(r#"{"subscribe":"KSM"}"#)
+				);
+				for message in [
+					json!({ "heartbeat": true }),
+					json!({ "data": [{ "KSM-USDT": { "price": "30.01" } }] }),
+				] {
+					let _ = socket.send(warp::ws::Message::text(message.to_string())).await;
+				}
+				while socket.next().await.is_some() {}
+			})
+		});
+		let (address, server) =
+			warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+		tokio::spawn(server);
+		let price = first_price(ksm_config(format!("ws://{}/ws", address))).await;
+		assert_eq!(price, Price(3001));
+	}
+}
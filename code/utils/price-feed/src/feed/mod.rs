@@ -6,10 +6,13 @@ pub mod binance;
 pub mod composable;
 #[allow(clippy::all)]
 pub mod composable_api;
+pub mod json;
+pub mod pablo;
 pub mod pyth;
 
 use chrono::{Duration, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::{fmt::Display, str::FromStr};
 use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
 
@@ -63,6 +66,44 @@ pub enum FeedIdentifier {
 	Pyth,
 	Binance,
 	Composable,
+	Pablo,
+	/// A generic JSON feed, by its index in the configuration.
+	Json(u8),
+}
+
+impl FromStr for FeedIdentifier {
+	type Err = ();
+	fn from_str(feed: &str) -> Result<FeedIdentifier, Self::Err> {
+		match feed {
+			"Pyth" => Ok(FeedIdentifier::Pyth),
+			"Binance" => Ok(FeedIdentifier::Binance),
+			"Composable" => Ok(FeedIdentifier::Composable),
+			"Pablo" => Ok(FeedIdentifier::Pablo),
+			_ => feed
+				.strip_prefix("Json")
+				.and_then(|index| index.parse().ok())
+				.map(FeedIdentifier::Json)
+				.ok_or(()),
+		}
+	}
+}
+
+impl Display for FeedIdentifier {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			FeedIdentifier::Pyth => f.write_str("Pyth"),
+			FeedIdentifier::Binance => f.write_str("Binance"),
+			FeedIdentifier::Composable => f.write_str("Composable"),
+			FeedIdentifier::Pablo => f.write_str("Pablo"),
+			FeedIdentifier::Json(index) => write!(f, "Json{}", index),
+		}
+	}
+}
+
+impl Serialize for FeedIdentifier {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
 }
 
 /// The possible errors happening while feeds are running.
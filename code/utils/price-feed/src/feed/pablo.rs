@@ -0,0 +1,140 @@
+use super::{Feed, FeedError, FeedResult};
+use crate::{
+	asset::Asset,
+	config::PabloPoolConfig,
+	feed::{
+		composable_api, Exponent, FeedIdentifier, FeedNotification, Price, TimeStamp, TimeStamped,
+		TimeStampedPrice, CHANNEL_BUFFER_SIZE,
+	},
+	metrics::ThreadSafeMetrics,
+};
+use futures::StreamExt;
+use std::collections::HashMap;
+use subxt::{OnlineClient, SubstrateConfig};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// The TWAP is a `FixedU128` with 18 decimals, reported with 12 decimals to fit a `Price`.
+const TWAP_DIVISOR: u128 = 1_000_000;
+
+/// A feed reading the TWAP of Pablo pools out of the storage of the composable node, on each
+/// finalized block.
+pub struct PabloFeed;
+
+impl PabloFeed {
+	pub async fn start(
+		shutdown_message: tokio::sync::watch::Receiver<bool>,
+		composable_node_url: String,
+		pools: &[PabloPoolConfig],
+		metrics: ThreadSafeMetrics,
+	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
+		let pools = pools.to_vec();
+
+		let (sink, source) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+		sink.send(FeedNotification::Started { feed: FeedIdentifier::Pablo })
+			.await
+			.map_err(|e| {
+				log::error!("{}", e);
+				FeedError::ChannelIsBroken
+			})?;
+
+		let api =
+			OnlineClient::<SubstrateConfig>::from_url(composable_node_url)
+				.await
+				.map_err(|e| {
+					log::error!("{}", e);
+					FeedError::NetworkFailure
+				})?;
+
+		for pool in pools.iter() {
+			sink.send(FeedNotification::AssetOpened {
+				feed: FeedIdentifier::Pablo,
+				asset: pool.asset,
+			})
+			.await
+			.map_err(|e| {
+				log::error!("{}", e);
+				FeedError::ChannelIsBroken
+			})?;
+		}
+
+		let handle = tokio::spawn(async move {
+			// Timestamp of the last TWAP notified, by pool.
+			let mut last_updates = HashMap::new();
+
+			// Subscribe to finalized blocks.
+			let mut block_sub = api.blocks().subscribe_finalized().await.map_err(|e| {
+				log::error!("{}", e);
+				FeedError::NetworkFailure
+			})?;
+
+			// Read the TWAP of each pool as blocks arrive.
+			while let Some(block) = block_sub.next().await {
+				let block = block.map_err(|e| {
+					log::error!("{}", e);
+					FeedError::NetworkFailure
+				})?;
+
+				let storage = api.storage().at(Some(block.hash())).await.map_err(|e| {
+					log::error!("{}", e);
+					FeedError::NetworkFailure
+				})?;
+
+				for pool in pools.iter() {
+					let twap_state =
+						composable_api::api::storage().pablo().twap_state(pool.pool_id);
+					let twap = storage.fetch(&twap_state).await.map_err(|e| {
+						log::error!("{}", e);
+						FeedError::NetworkFailure
+					})?;
+
+					// The TWAP is only updated every TWAPInterval.
+					let Some(twap) = twap else { continue };
+					if last_updates.insert(pool.pool_id, twap.timestamp) == Some(twap.timestamp) {
+						continue
+					}
+
+					let price = u64::try_from(twap.base_twap.0 / TWAP_DIVISOR)
+						.map_err(|_| FeedError::CannotDecodeEvent)?;
+					let notification = FeedNotification::AssetPriceUpdated {
+						feed: FeedIdentifier::Pablo,
+						asset: pool.asset,
+						price: TimeStamped {
+							value: (Price(price), Exponent(12)),
+							timestamp: TimeStamp::now(),
+						},
+					};
+					if sink.send(notification).await.is_err() {
+						metrics.record_dropped_notification();
+					}
+				}
+
+				if *shutdown_message.borrow() {
+					break
+				}
+			}
+
+			for pool in pools.iter() {
+				sink.send(FeedNotification::AssetClosed {
+					feed: FeedIdentifier::Pablo,
+					asset: pool.asset,
+				})
+				.await
+				.map_err(|e| {
+					log::error!("{}", e);
+					FeedError::ChannelIsBroken
+				})?;
+			}
+
+			sink.send(FeedNotification::Stopped { feed: FeedIdentifier::Pablo })
+				.await
+				.map_err(|e| {
+					log::error!("{}", e);
+					FeedError::ChannelIsBroken
+				})?;
+
+			Ok(())
+		});
+		Ok((handle, ReceiverStream::new(source)))
+	}
+}
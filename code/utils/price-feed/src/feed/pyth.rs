@@ -223,6 +223,7 @@ impl PythFeed {
 		url: Url,
 		keep_running: Arc<AtomicBool>,
 		assets: &HashSet<Asset>,
+		quote_asset: Asset,
 	) -> FeedResult<Feed<FeedIdentifier, Asset, TimeStampedPrice>> {
 		let mut pyth = Pyth::new(&url).await.map_err(|_| FeedError::NetworkFailure)?;
 
@@ -233,11 +234,13 @@ impl PythFeed {
 			.map_err(|_| FeedError::ChannelIsBroken)?;
 
 		for &asset in assets.iter() {
-			if let Some(asset_pair) = AssetPair::new(asset, Asset::USDT) {
-				pyth.subscribe_to_asset(keep_running.clone(), &sink, &asset_pair)
-					.await
-					.expect("failed to subscribe to asset");
-			}
+			pyth.subscribe_to_asset(
+				keep_running.clone(),
+				&sink,
+				&AssetPair::new(asset, quote_asset),
+			)
+			.await
+			.expect("failed to subscribe to asset");
 		}
 
 		let handle = tokio::spawn(async move {
@@ -261,7 +264,7 @@ mod tests {
 		let product_price = PythProductPrice { account, price_exponent: Exponent(0x1337) };
 		let price = Price(0xCAFEBABE);
 		let timestamp = TimeStamp::now();
-		for (_, asset) in INDEX_TO_ASSET {
+		for asset in test_assets() {
 			let tests = [
 				(PythSymbolStatus::Halted, None),
 				(PythSymbolStatus::Unknown, None),
@@ -1,25 +1,38 @@
 use crate::{
 	asset::Asset,
-	cache::{Cache, PriceCache, ThreadSafePriceCache},
+	cache::{PriceCache, ThreadSafePriceCache},
+	config::ThreadSafeConfig,
 	feed::{Exponent, Price, TimeStamp, TimeStampedPrice},
+	merge::{MergePolicy, PriceSource},
+	metrics::ThreadSafeMetrics,
 };
 use chrono::Duration;
 use futures::channel::oneshot;
 use primitives::currency::CurrencyId;
+use scale_codec::Encode;
 use serde::Serialize;
+use sp_core::{sr25519, Pair};
 use std::{
 	collections::HashMap,
-	convert::TryFrom,
 	net::SocketAddr,
 	str::FromStr,
 	sync::{Arc, RwLock},
 };
 use tokio::task::JoinHandle;
-use warp::{hyper::StatusCode, reply, Filter, Rejection, Reply};
+use warp::{http::Response, hyper::StatusCode, reply, Filter, Rejection, Reply};
 
-#[derive(PartialEq, Eq, Serialize, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Serialize, Copy, Clone, Debug)]
 #[repr(transparent)]
-pub struct NormalizedPrice(u64);
+pub struct NormalizedPrice(pub(crate) u64);
+
+/// The oracle is expecting an object with the asset as key and it's price as value, the sources
+/// the price was merged from are listed under the `sources` key.
+#[derive(Serialize)]
+struct MergedPriceReply {
+	#[serde(flatten)]
+	price: HashMap<String, NormalizedPrice>,
+	sources: Vec<PriceSource>,
+}
 
 pub struct Frontend {
 	pub shutdown_trigger: oneshot::Sender<()>,
@@ -27,39 +40,65 @@ pub struct Frontend {
 }
 
 impl Frontend {
+	#[allow(clippy::too_many_arguments)]
 	pub async fn new(
 		listening_address: &str,
 		prices_cache: Arc<RwLock<PriceCache>>,
+		config: ThreadSafeConfig,
 		cache_duration: Duration,
 		expected_exponent: Exponent,
+		merge_policy: MergePolicy,
+		metrics: ThreadSafeMetrics,
+		signing_key: Option<sr25519::Pair>,
 	) -> Self {
-		let get_asset_id_endpoint = warp::path!("asset_id" / Asset)
-			.and(warp::get())
-			.and_then(move |asset_id| async move { get_asset_id(asset_id) });
+		let config_clone = config.clone();
+		let get_asset_id_endpoint =
+			warp::path!("asset_id" / Asset).and(warp::get()).and_then(move |asset_id| {
+				let config_clone = config_clone.clone();
+				async move { get_asset_id(config_clone, asset_id) }
+			});
 
 		let get_price_endpoint =
 			warp::path!("price" / CurrencyId)
 				.and(warp::get())
 				.and_then(move |currency_index| {
 					let prices_cache_clone = prices_cache.clone();
+					let config_clone = config.clone();
+					let merge_policy_clone = merge_policy.clone();
+					let metrics_clone = metrics.clone();
+					let signing_key_clone = signing_key.clone();
 					async move {
 						get_price(
 							prices_cache_clone,
+							config_clone,
 							currency_index,
 							cache_duration,
 							expected_exponent,
+							&merge_policy_clone,
+							&metrics_clone,
+							signing_key_clone.as_ref(),
 						)
 					}
 				});
 
+		let metrics_clone = metrics.clone();
+		let get_metrics_endpoint = warp::path!("metrics").and(warp::get()).map(move || {
+			reply::with_header(
+				metrics_clone.render(&TimeStamp::now()),
+				"content-type",
+				"text/plain; version=0.0.4",
+			)
+		});
+
 		let (shutdown_trigger, shutdown) = oneshot::channel::<()>();
-		let (_, server) = warp::serve(get_price_endpoint.or(get_asset_id_endpoint))
-			.bind_with_graceful_shutdown(
-				SocketAddr::from_str(listening_address).expect("invalid listening address."),
-				async {
-					shutdown.await.ok();
-				},
-			);
+		let (_, server) =
+			warp::serve(get_price_endpoint.or(get_asset_id_endpoint).or(get_metrics_endpoint))
+				.bind_with_graceful_shutdown(
+					SocketAddr::from_str(listening_address).expect("invalid listening address."),
+					async {
+						shutdown.await.ok();
+					},
+				);
 
 		let shutdown_handle = tokio::spawn(server);
 
@@ -67,47 +106,107 @@ impl Frontend {
 	}
 }
 
-fn get_asset_id(x: Asset) -> Result<impl Reply, Rejection> {
-	match CurrencyId::try_from(x) {
-		Ok(currency_index) => Ok(reply::with_status(reply::json(&currency_index), StatusCode::OK)),
-		Err(_) => Err(warp::reject::not_found()),
+fn get_asset_id(config: ThreadSafeConfig, x: Asset) -> Result<impl Reply, Rejection> {
+	match config.read().expect("could not acquire read lock").currency_id(x) {
+		Some(currency_index) =>
+			Ok(reply::with_status(reply::json(&currency_index), StatusCode::OK)),
+		None => Err(warp::reject::not_found()),
 	}
 }
 
+#[allow(clippy::too_many_arguments)]
 fn get_price(
 	prices: ThreadSafePriceCache,
+	config: ThreadSafeConfig,
 	currency_index: CurrencyId,
 	cache_duration: Duration,
-	expected_exponent: Exponent,
+	default_exponent: Exponent,
+	merge_policy: &MergePolicy,
+	metrics: &ThreadSafeMetrics,
+	signing_key: Option<&sr25519::Pair>,
 ) -> Result<impl Reply, Rejection> {
-	match Asset::try_from(currency_index).and_then(|asset| {
+	let asset_exponent = {
+		let config = config.read().expect("could not acquire read lock");
+		config
+			.asset(currency_index)
+			.map(|asset| (asset, config.exponent(asset, default_exponent)))
+	};
+	match asset_exponent.ok_or(()).and_then(|(asset, expected_exponent)| {
 		let now = TimeStamp::now();
 
-		prices
-			.get(&asset)
-			.and_then(|timestamped_price| {
-				ensure_uptodate_price(&cache_duration, &now, &timestamped_price)
+		let sources = prices
+			.read()
+			.expect("could not acquire read lock")
+			.iter()
+			.filter(|((cached_asset, _), _)| *cached_asset == asset)
+			.filter_map(|(&(_, feed), timestamped_price)| {
+				ensure_uptodate_price(&cache_duration, &now, timestamped_price).map(|(x, _)| {
+					PriceSource {
+						feed,
+						price: normalize_price(expected_exponent, x),
+						timestamp: timestamped_price.timestamp,
+					}
+				})
+			})
+			.collect::<Vec<_>>();
+
+		merge_policy
+			.merge(&sources)
+			.and_then(|(normalized_price, sources)| {
+				// The merged price is as old as the oldest price it was merged from.
+				let oldest = sources.iter().map(|source| source.timestamp).min_by_key(|t| t.0)?;
+				Some((normalized_price, sources, oldest, now.elapsed_since(&oldest)))
 			})
-			.map(|(x, elapsed)| (normalize_price(expected_exponent, x), elapsed))
 			.ok_or(())
 	}) {
-		// The oracle is expecting an object with the asset as key and it's price as value.
-		Ok((normalized_price, elapsed)) => Ok(reply::with_header(
-			reply::with_header(
-				reply::with_status(
-					reply::json(&HashMap::from([(currency_index.to_string(), normalized_price)])),
-					StatusCode::OK,
-				),
-				"x-composable-cache-elapsed",
-				elapsed.to_string(),
-			),
-			"x-composable-cache-duration",
-			cache_duration.to_string(),
-		)),
-		Err(_) => Err(warp::reject::not_found()),
+		Ok((normalized_price, sources, oldest, elapsed)) => {
+			metrics.record_cache_hit();
+			let body = serde_json::to_vec(&MergedPriceReply {
+				price: HashMap::from([(currency_index.to_string(), normalized_price)]),
+				sources,
+			})
+			.map_err(|e| {
+				log::error!("{}", e);
+				warp::reject::reject()
+			})?;
+			let mut response = Response::builder()
+				.status(StatusCode::OK)
+				.header("content-type", "application/json")
+				.header("x-composable-cache-elapsed", elapsed.to_string())
+				.header("x-composable-cache-duration", cache_duration.to_string());
+			if let Some(signing_key) = signing_key {
+				let (signer, signature) = sign_reply(signing_key, currency_index, oldest, &body);
+				response = response
+					.header("x-composable-timestamp", oldest.0.to_string())
+					.header("x-composable-signer", signer)
+					.header("x-composable-signature", signature);
+			}
+			response.body(body).map_err(|e| {
+				log::error!("{}", e);
+				warp::reject::reject()
+			})
+		},
+		Err(_) => {
+			metrics.record_cache_miss();
+			Err(warp::reject::not_found())
+		},
 	}
 }
 
+/// Sign a price reply, so that its provenance can be verified. The SCALE encoded tuple of the
+/// currency id, the timestamp of the oldest source and the body is signed, so that a reply cannot
+/// be replayed for another asset or passed off as fresher. Returns the hex encoded public key and
+/// signature.
+fn sign_reply(
+	signing_key: &sr25519::Pair,
+	currency_index: CurrencyId,
+	timestamp: TimeStamp,
+	body: &[u8],
+) -> (String, String) {
+	let signature = signing_key.sign(&(currency_index, timestamp.0, body).encode());
+	(format!("0x{}", hex::encode(signing_key.public())), format!("0x{}", hex::encode(signature)))
+}
+
 /// Ensure that the price is not outdated.
 fn ensure_uptodate_price(
 	&max_cache_duration: &Duration,
@@ -141,12 +240,15 @@ fn normalize_price(
 
 #[cfg(test)]
 mod tests {
-	use super::{normalize_price, NormalizedPrice};
+	use super::{normalize_price, sign_reply, NormalizedPrice};
 	use crate::{
 		feed::{Exponent, Price, TimeStamp, TimeStamped},
 		frontend::ensure_uptodate_price,
 	};
 	use chrono::Duration;
+	use primitives::currency::CurrencyId;
+	use scale_codec::Encode;
+	use sp_core::{sr25519, Pair};
 
 	#[test]
 	fn test_ensure_uptodate_price() {
@@ -209,4 +311,23 @@ mod tests {
 			assert_eq!(normalize_price(expected_exponent, price), expected_price);
 		});
 	}
+
+	#[test]
+	fn test_sign_reply() {
+		let signing_key = sr25519::Pair::from_string("//Alice", None).expect("valid secret");
+		let body = br#"{"4":25000000000000,"sources":[]}"#;
+		let (signer, signature) = sign_reply(&signing_key, CurrencyId(4), TimeStamp(10), body);
+		let decode = |x: &str| hex::decode(x.trim_start_matches("0x")).expect("hex encoded");
+		assert_eq!(decode(&signer), signing_key.public().0.to_vec());
+		let signature = sr25519::Signature::from_slice(&decode(&signature)).expect("signature");
+		let verify = |currency_index: u128, timestamp: i64, body: &[u8]| {
+			let message = (CurrencyId(currency_index), timestamp, body).encode();
+			sr25519::Pair::verify(&signature, message, &signing_key.public())
+		};
+		assert!(verify(4, 10, body));
+		assert!(!verify(4, 10, b"{}"));
+		assert!(!verify(5, 10, body));
+		assert!(!verify(4, 11, body));
+		assert!(!sr25519::Pair::verify(&signature, body, &signing_key.public()));
+	}
 }
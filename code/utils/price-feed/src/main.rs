@@ -1,24 +1,25 @@
 mod asset;
 mod backend;
 mod cache;
+mod config;
 mod feed;
 mod frontend;
+mod merge;
+mod metrics;
 mod opts;
 
-#[macro_use]
-extern crate custom_derive;
-#[macro_use]
-extern crate enum_derive;
-
 use crate::{
 	asset::Asset,
 	backend::{Backend, FeedNotificationAction},
 	cache::ThreadSafePriceCache,
+	config::{Config, ThreadSafeConfig},
 	feed::{
-		binance::BinanceFeed, composable::ComposableFeed, Exponent, FeedIdentifier,
-		FeedNotification, TimeStampedPrice,
+		binance::BinanceFeed, composable::ComposableFeed, json::JsonFeed, pablo::PabloFeed,
+		Exponent, FeedIdentifier, FeedNotification, TimeStampedPrice,
 	},
 	frontend::Frontend,
+	merge::MergePolicy,
+	metrics::{Metrics, ThreadSafeMetrics},
 	opts::Opts,
 };
 
@@ -30,23 +31,61 @@ use futures::{
 };
 use signal_hook::consts::signal::*;
 use signal_hook_tokio::{Signals, SignalsInfo};
+use sp_core::{sr25519, Pair};
 use tokio::sync::watch;
 
 use std::{
 	collections::HashMap,
-	str::FromStr,
+	path::PathBuf,
 	sync::{
 		atomic::{AtomicBool, Ordering},
 		Arc, RwLock,
 	},
 };
 
+/// Environment variable holding the secret URI of the signing key, when no key file is given.
+const SIGNING_KEY_VAR: &str = "PRICE_FEED_SIGNING_KEY";
+
 #[tokio::main]
 async fn main() {
 	env_logger::init();
 
 	let opts = Opts::parse();
 
+	let merge_policy =
+		MergePolicy::parse(&opts.merge_policy, &opts.feed_weights, &opts.feed_priority)
+			.expect("invalid merge policy");
+
+	let config_path = PathBuf::from(&opts.config);
+	let config = Config::load(&config_path)
+		.map_err(|e| {
+			log::error!("{:?}", e);
+			std::process::exit(1);
+		})
+		.unwrap();
+
+	let signing_key_suri = match &opts.signing_key_file {
+		Some(path) => Some(
+			std::fs::read_to_string(path)
+				.map_err(|e| {
+					log::error!("could not read the signing key file: {}", e);
+					std::process::exit(1);
+				})
+				.unwrap(),
+		),
+		None => std::env::var(SIGNING_KEY_VAR).ok(),
+	};
+	let signing_key = signing_key_suri.map(|suri| {
+		sr25519::Pair::from_string(suri.trim(), None)
+			.map_err(|e| {
+				log::error!("invalid signing key: {:?}", e);
+				std::process::exit(1);
+			})
+			.unwrap()
+	});
+
+	let metrics: ThreadSafeMetrics = Arc::new(Metrics::default());
+
 	let prices_cache: ThreadSafePriceCache = Arc::new(RwLock::new(HashMap::new()));
 
 	// watch instead of oneshot to allow for multiple feeds to listen at once, instead of creating
@@ -60,8 +99,9 @@ async fn main() {
 
 	let binance = BinanceFeed::start(
 		keep_running.clone(),
-		&[Asset::KSM, Asset::DOT].into_iter().collect(),
-		Asset::from_str(&opts.quote_asset).expect("invalid quote asset"),
+		&config.feeds.binance.iter().copied().collect(),
+		config.quote_asset,
+		metrics.clone(),
 	)
 	.await
 	.map_err(|e| {
@@ -71,9 +111,11 @@ async fn main() {
 	.unwrap();
 
 	let composable = ComposableFeed::start(
-		feed_shutdown_receiver,
-		opts.composable_node,
-		&[(Asset::PICA, Asset::USDC)].into_iter().collect(),
+		feed_shutdown_receiver.clone(),
+		opts.composable_node.clone(),
+		&config.feeds.composable.iter().copied().collect(),
+		&config,
+		metrics.clone(),
 	)
 	.await
 	.map_err(|e| {
@@ -82,6 +124,41 @@ async fn main() {
 	})
 	.unwrap();
 
+	let mut feeds = vec![binance, composable];
+
+	if !config.feeds.pablo.is_empty() {
+		let pablo = PabloFeed::start(
+			feed_shutdown_receiver.clone(),
+			opts.composable_node,
+			&config.feeds.pablo,
+			metrics.clone(),
+		)
+		.await
+		.map_err(|e| {
+			log::error!("{:?}", e);
+			std::process::exit(1);
+		})
+		.unwrap();
+		feeds.push(pablo);
+	}
+
+	// Validated by the configuration, there are at most 256 JSON feeds.
+	for (index, json) in (0..=u8::MAX).zip(config.feeds.json.iter()) {
+		let json = JsonFeed::start(
+			feed_shutdown_receiver.clone(),
+			FeedIdentifier::Json(index),
+			json.clone(),
+			metrics.clone(),
+		)
+		.await
+		.map_err(|e| {
+			log::error!("{:?}", e);
+			std::process::exit(1);
+		})
+		.unwrap();
+		feeds.push(json);
+	}
+
 	/* NOTE(hussein-aitlahcen):
 		 Introducing a new feed is a matter of merge it with the existing ones.
 		 A feed is a tuple of both a stream of notification along with joinable handle.
@@ -91,8 +168,13 @@ async fn main() {
 		 ... merge(vec![..., new_feed])
 	*/
 	let (feeds_handle, feeds_source) = {
-		let (handles, sources) = [binance, composable].into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
-		(join_all(handles), futures::stream::select_all(sources))
+		let (handles, sources) = feeds.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+		let metrics = metrics.clone();
+		(
+			join_all(handles),
+			futures::stream::select_all(sources)
+				.inspect(move |notification| metrics.record_notification(notification)),
+		)
 	};
 
 	let backend_shutdown_trigger: Fuse<SignalsInfo> = Signals::new([SIGTERM, SIGINT, SIGQUIT])
@@ -105,7 +187,7 @@ async fn main() {
 
 	let backend = Backend::new::<
 		FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
-		FeedNotificationAction<Asset, TimeStampedPrice>,
+		FeedNotificationAction<(Asset, FeedIdentifier), TimeStampedPrice>,
 		_,
 		_,
 		_,
@@ -113,11 +195,32 @@ async fn main() {
 	>(prices_cache.clone(), feeds_source, backend_shutdown_trigger)
 	.await;
 
+	let config: ThreadSafeConfig = Arc::new(RwLock::new(config));
+
+	let mut reload_trigger = Signals::new([SIGHUP])
+		.map_err(|e| {
+			log::error!("{:?}", e);
+			std::process::exit(1);
+		})
+		.unwrap();
+	let reload_trigger_handle = reload_trigger.handle();
+	let reload_config = config.clone();
+	let reload_handle = tokio::spawn(async move {
+		while reload_trigger.next().await.is_some() {
+			log::info!("reloading configuration...");
+			Config::reload(&config_path, &reload_config);
+		}
+	});
+
 	let frontend = Frontend::new(
 		&opts.listening_address,
 		prices_cache,
+		config,
 		Duration::seconds(opts.cache_duration.into()),
 		Exponent(opts.expected_exponent),
+		merge_policy,
+		metrics,
+		signing_key,
 	)
 	.await;
 
@@ -137,5 +240,8 @@ async fn main() {
 	log::info!("waiting for warp to terminate...");
 	frontend.shutdown_handle.await.expect("oops, something went wrong");
 
+	reload_trigger_handle.close();
+	reload_handle.await.expect("oops, something went wrong");
+
 	log::info!("farewell.");
 }
@@ -0,0 +1,197 @@
+use crate::{
+	feed::{FeedIdentifier, TimeStamp},
+	frontend::NormalizedPrice,
+};
+use serde::Serialize;
+use std::{collections::HashMap, str::FromStr};
+
+/// An up to date price of an asset reported by a feed.
+#[derive(Serialize, PartialEq, Eq, Copy, Clone, Debug)]
+pub struct PriceSource {
+	pub feed: FeedIdentifier,
+	pub price: NormalizedPrice,
+	pub timestamp: TimeStamp,
+}
+
+/// How the prices of an asset reported by several feeds are merged into one.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum MergePolicy {
+	/// Median of the prices, the mean of the two middle ones for an even count.
+	Median,
+	/// Mean of the prices weighted by feed, feeds without weight are ignored.
+	Weighted(HashMap<FeedIdentifier, u64>),
+	/// Price of the first feed in the list, falling back to the next ones while a feed has no up
+	/// to date price.
+	Priority(Vec<FeedIdentifier>),
+}
+
+impl MergePolicy {
+	/// Parse a policy from its name, `median`, `weighted` or `priority`, along with the feed
+	/// weights (e.g. `Binance=2,Composable=1`) and the feeds by decreasing priority (e.g.
+	/// `Binance,Composable`).
+	pub fn parse(policy: &str, weights: &str, priority: &str) -> Option<Self> {
+		match policy {
+			"median" => Some(MergePolicy::Median),
+			"weighted" => weights
+				.split(',')
+				.map(|weight| {
+					let (feed, weight) = weight.split_once('=')?;
+					Some((FeedIdentifier::from_str(feed.trim()).ok()?, weight.trim().parse().ok()?))
+				})
+				.collect::<Option<_>>()
+				.map(MergePolicy::Weighted),
+			"priority" => priority
+				.split(',')
+				.map(|feed| FeedIdentifier::from_str(feed.trim()).ok())
+				.collect::<Option<_>>()
+				.map(MergePolicy::Priority),
+			_ => None,
+		}
+	}
+
+	/// Merge the up to date prices of an asset. Returns the merged price along with the sources it
+	/// was computed from, `None` if no source is usable.
+	pub fn merge(&self, sources: &[PriceSource]) -> Option<(NormalizedPrice, Vec<PriceSource>)> {
+		match self {
+			MergePolicy::Median => {
+				let mut used = sources.to_vec();
+				used.sort_by_key(|source| source.price);
+				let mid = used.len() / 2;
+				let price = match used.len() {
+					0 => return None,
+					len if len % 2 == 0 => {
+						let (NormalizedPrice(x), NormalizedPrice(y)) =
+							(used[mid - 1].price, used[mid].price);
+						NormalizedPrice(x / 2 + y / 2 + (x % 2 + y % 2) / 2)
+					},
+					_ => used[mid].price,
+				};
+				Some((price, used))
+			},
+			MergePolicy::Weighted(weights) => {
+				let used = sources
+					.iter()
+					.filter(|source| weights.get(&source.feed).copied().unwrap_or(0) > 0)
+					.copied()
+					.collect::<Vec<_>>();
+				let (weighted_sum, total_weight) =
+					used.iter().fold((0_u128, 0_u128), |(sum, total), source| {
+						let weight = u128::from(weights[&source.feed]);
+						(sum + u128::from(source.price.0) * weight, total + weight)
+					});
+				if total_weight == 0 {
+					return None
+				}
+				let price = u64::try_from(weighted_sum / total_weight).ok()?;
+				Some((NormalizedPrice(price), used))
+			},
+			MergePolicy::Priority(feeds) => feeds.iter().find_map(|feed| {
+				let source = sources.iter().find(|source| source.feed == *feed)?;
+				Some((source.price, vec![*source]))
+			}),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{MergePolicy, PriceSource};
+	use crate::{
+		feed::{FeedIdentifier, TimeStamp},
+		frontend::NormalizedPrice,
+	};
+	use std::collections::HashMap;
+
+	fn source(feed: FeedIdentifier, price: u64) -> PriceSource {
+		PriceSource { feed, price: NormalizedPrice(price), timestamp: TimeStamp(0) }
+	}
+
+	#[test]
+	fn test_parse_merge_policy() {
+		[
+			(("median", "", ""), Some(MergePolicy::Median)),
+			(
+				("weighted", "Binance=2, Composable=1", ""),
+				Some(MergePolicy::Weighted(HashMap::from([
+					(FeedIdentifier::Binance, 2),
+					(FeedIdentifier::Composable, 1),
+				]))),
+			),
+			(("weighted", "Binance", ""), None),
+			(
+				("priority", "", "Composable,Binance"),
+				Some(MergePolicy::Priority(vec![
+					FeedIdentifier::Composable,
+					FeedIdentifier::Binance,
+				])),
+			),
+			(
+				("priority", "", "Json1,Pablo"),
+				Some(MergePolicy::Priority(vec![FeedIdentifier::Json(1), FeedIdentifier::Pablo])),
+			),
+			(("priority", "", "Kraken"), None),
+			(("priority", "", "Json256"), None),
+			(("mean", "", ""), None),
+		]
+		.into_iter()
+		.for_each(|((policy, weights, priority), expected)| {
+			assert_eq!(MergePolicy::parse(policy, weights, priority), expected);
+		});
+	}
+
+	#[test]
+	fn test_merge_median() {
+		let (pyth, binance, composable) = (
+			source(FeedIdentifier::Pyth, 103),
+			source(FeedIdentifier::Binance, 100),
+			source(FeedIdentifier::Composable, 110),
+		);
+		assert_eq!(MergePolicy::Median.merge(&[]), None);
+		assert_eq!(
+			MergePolicy::Median.merge(&[pyth, binance, composable]),
+			Some((NormalizedPrice(103), vec![binance, pyth, composable]))
+		);
+		assert_eq!(
+			MergePolicy::Median.merge(&[composable, pyth]),
+			Some((NormalizedPrice(106), vec![pyth, composable]))
+		);
+	}
+
+	#[test]
+	fn test_merge_weighted() {
+		let policy = MergePolicy::Weighted(HashMap::from([
+			(FeedIdentifier::Binance, 3),
+			(FeedIdentifier::Composable, 1),
+		]));
+		let (pyth, binance, composable) = (
+			source(FeedIdentifier::Pyth, 1000),
+			source(FeedIdentifier::Binance, 100),
+			source(FeedIdentifier::Composable, 120),
+		);
+		assert_eq!(
+			policy.merge(&[pyth, binance, composable]),
+			Some((NormalizedPrice(105), vec![binance, composable]))
+		);
+		assert_eq!(policy.merge(&[pyth]), None);
+	}
+
+	#[test]
+	fn test_merge_priority() {
+		let policy =
+			MergePolicy::Priority(vec![FeedIdentifier::Binance, FeedIdentifier::Composable]);
+		let (pyth, binance, composable) = (
+			source(FeedIdentifier::Pyth, 1000),
+			source(FeedIdentifier::Binance, 100),
+			source(FeedIdentifier::Composable, 120),
+		);
+		assert_eq!(
+			policy.merge(&[pyth, composable, binance]),
+			Some((NormalizedPrice(100), vec![binance]))
+		);
+		assert_eq!(
+			policy.merge(&[pyth, composable]),
+			Some((NormalizedPrice(120), vec![composable]))
+		);
+		assert_eq!(policy.merge(&[pyth]), None);
+	}
+}
@@ -0,0 +1,182 @@
+use crate::{
+	asset::Asset,
+	feed::{FeedIdentifier, FeedNotification, TimeStamp, TimeStampedPrice},
+};
+use std::{
+	collections::HashMap,
+	fmt::Write,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+};
+
+#[derive(Copy, Clone, Default, Debug)]
+struct FeedMetrics {
+	updates: u64,
+	last_update: Option<TimeStamp>,
+}
+
+/// Counters of the server, exposed in the Prometheus text format.
+#[derive(Default, Debug)]
+pub struct Metrics {
+	feeds: Mutex<HashMap<FeedIdentifier, FeedMetrics>>,
+	cache_hits: AtomicU64,
+	cache_misses: AtomicU64,
+	dropped_notifications: AtomicU64,
+}
+
+pub type ThreadSafeMetrics = Arc<Metrics>;
+
+impl Metrics {
+	/// Count the price updates of the feeds, before they reach the backend.
+	pub fn record_notification(
+		&self,
+		notification: &FeedNotification<FeedIdentifier, Asset, TimeStampedPrice>,
+	) {
+		if let FeedNotification::AssetPriceUpdated { feed, price, .. } = notification {
+			let mut feeds = self.feeds.lock().expect("could not acquire lock");
+			let metrics = feeds.entry(*feed).or_default();
+			metrics.updates += 1;
+			metrics.last_update = Some(price.timestamp);
+		}
+	}
+
+	/// A price request was answered with an up to date price.
+	pub fn record_cache_hit(&self) {
+		self.cache_hits.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// A price request found no up to date price.
+	pub fn record_cache_miss(&self) {
+		self.cache_misses.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// A feed could not deliver a notification to the backend.
+	pub fn record_dropped_notification(&self) {
+		self.dropped_notifications.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Render the metrics in the Prometheus text format. Update rates are derived from the
+	/// `price_feed_updates_total` counters, e.g. `rate(price_feed_updates_total[1m])`.
+	pub fn render(&self, now: &TimeStamp) -> String {
+		let mut feeds = self
+			.feeds
+			.lock()
+			.expect("could not acquire lock")
+			.iter()
+			.map(|(feed, metrics)| (feed.to_string(), *metrics))
+			.collect::<Vec<_>>();
+		feeds.sort_by(|(x, _), (y, _)| x.cmp(y));
+
+		let mut output = String::new();
+		// Writing to a String never fails.
+		let _ = writeln!(
+			output,
+			"# HELP price_feed_updates_total Price updates received from the feed.\n\
+			 # TYPE price_feed_updates_total counter"
+		);
+		for (feed, metrics) in feeds.iter() {
+			let _ = writeln!(
+				output,
+				"price_feed_updates_total{{feed=\"{}\"}} {}",
+				feed, metrics.updates
+			);
+		}
+		let _ = writeln!(
+			output,
+			"# HELP price_feed_last_update_age_seconds Seconds since the last price update of the feed.\n\
+			 # TYPE price_feed_last_update_age_seconds gauge"
+		);
+		for (feed, metrics) in feeds.iter() {
+			if let Some(last_update) = metrics.last_update {
+				let _ = writeln!(
+					output,
+					"price_feed_last_update_age_seconds{{feed=\"{}\"}} {}",
+					feed,
+					now.elapsed_since(&last_update).num_seconds()
+				);
+			}
+		}
+		for (name, help, counter) in [
+			(
+				"price_feed_cache_hits_total",
+				"Price requests answered with an up to date price.",
+				&self.cache_hits,
+			),
+			(
+				"price_feed_cache_misses_total",
+				"Price requests without up to date price.",
+				&self.cache_misses,
+			),
+			(
+				"price_feed_dropped_notifications_total",
+				"Notifications the feeds could not deliver.",
+				&self.dropped_notifications,
+			),
+		] {
+			let _ = writeln!(
+				output,
+				"# HELP {name} {help}\n# TYPE {name} counter\n{name} {}",
+				counter.load(Ordering::Relaxed)
+			);
+		}
+		output
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Metrics;
+	use crate::{
+		asset::Asset,
+		feed::{Exponent, FeedIdentifier, FeedNotification, Price, TimeStamp, TimeStamped},
+	};
+
+	#[test]
+	fn test_render_metrics() {
+		let metrics = Metrics::default();
+		let asset = Asset::new("KSM").expect("valid symbol");
+		[
+			(FeedIdentifier::Binance, 10),
+			(FeedIdentifier::Json(0), 12),
+			(FeedIdentifier::Binance, 15),
+		]
+		.into_iter()
+		.for_each(|(feed, timestamp)| {
+			metrics.record_notification(&FeedNotification::AssetPriceUpdated {
+				feed,
+				asset,
+				price: TimeStamped {
+					value: (Price(1), Exponent(0)),
+					timestamp: TimeStamp(timestamp),
+				},
+			})
+		});
+		metrics.record_notification(&FeedNotification::Started { feed: FeedIdentifier::Pablo });
+		metrics.record_cache_hit();
+		metrics.record_cache_miss();
+		metrics.record_cache_miss();
+
+		assert_eq!(
+			metrics.render(&TimeStamp(20)),
+			"# HELP price_feed_updates_total Price updates received from the feed.\n\
+			 # TYPE price_feed_updates_total counter\n\
+			 price_feed_updates_total{feed=\"Binance\"} 2\n\
+			 price_feed_updates_total{feed=\"Json0\"} 1\n\
+			 # HELP price_feed_last_update_age_seconds Seconds since the last price update of the feed.\n\
+			 # TYPE price_feed_last_update_age_seconds gauge\n\
+			 price_feed_last_update_age_seconds{feed=\"Binance\"} 5\n\
+			 price_feed_last_update_age_seconds{feed=\"Json0\"} 8\n\
+			 # HELP price_feed_cache_hits_total Price requests answered with an up to date price.\n\
+			 # TYPE price_feed_cache_hits_total counter\n\
+			 price_feed_cache_hits_total 1\n\
+			 # HELP price_feed_cache_misses_total Price requests without up to date price.\n\
+			 # TYPE price_feed_cache_misses_total counter\n\
+			 price_feed_cache_misses_total 2\n\
+			 # HELP price_feed_dropped_notifications_total Notifications the feeds could not deliver.\n\
+			 # TYPE price_feed_dropped_notifications_total counter\n\
+			 price_feed_dropped_notifications_total 0\n"
+		);
+	}
+}
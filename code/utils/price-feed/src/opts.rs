@@ -15,15 +15,34 @@ pub struct Opts {
 	#[clap(short, long, default_value = "127.0.0.1:3001")]
 	pub listening_address: String,
 
-	/// Asset to be used as quote for pricing.
-	#[clap(short, long, default_value = "USDT")]
-	pub quote_asset: String,
+	/// JSON configuration of the assets and feeds, reloaded on SIGHUP.
+	#[clap(long, default_value = "price-feed.json")]
+	pub config: String,
 
-	/// Price will be normalized to this exponent.
+	/// Price will be normalized to this exponent, unless configured for the asset.
 	#[clap(short, long, default_value = "12")]
 	pub expected_exponent: i32,
 
 	/// Duration, in seconds, before a price is evicted from the cache.
 	#[clap(short, long, default_value = "10")]
 	pub cache_duration: u32,
+
+	/// How the prices of an asset reported by several feeds are merged: median, weighted or
+	/// priority.
+	#[clap(long, default_value = "median")]
+	pub merge_policy: String,
+
+	/// Weights of the feeds for the weighted merge policy, feeds without weight are ignored.
+	#[clap(long, default_value = "Binance=1,Composable=1")]
+	pub feed_weights: String,
+
+	/// Feeds by decreasing priority for the priority merge policy, falling back to the next feed
+	/// while a feed has no up to date price.
+	#[clap(long, default_value = "Binance,Composable")]
+	pub feed_priority: String,
+
+	/// File holding the secret URI of the sr25519 key signing the price replies. Falls back to the
+	/// `PRICE_FEED_SIGNING_KEY` environment variable, replies are unsigned if neither is set.
+	#[clap(long)]
+	pub signing_key_file: Option<String>,
 }
@@ -26,6 +26,7 @@
           all-outputs
           cargo-clippy-check
           cargo-deny-check
+          cargo-dex-router-tests
           cargo-fmt-check
           cargo-no-std-core-check
           cargo-no-std-xcm-ibc
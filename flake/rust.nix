@@ -13,6 +13,15 @@
           '';
           installPhase = "mkdir --parents $out";
         });
+      cargo-package-tests = pname:
+        crane.nightly.cargoBuild (systemCommonRust.common-attrs // {
+          SKIP_WASM_BUILD = "1";
+          pnameSuffix = "-${pname}-tests";
+          doInstallCargoArtifacts = false;
+          cargoArtifacts = self'.packages.common-test-deps;
+          buildPhase = "cargo test --release --locked --package ${pname}";
+          installPhase = "mkdir --parents $out";
+        });
 
     in {
       _module.args.crane = rec {
@@ -59,6 +68,8 @@
         cargo-no-std-cosmwasm = cargo-no-std-check "pallet-cosmwasm";
         cargo-no-std-xcm-ibc = cargo-no-std-check "pallet-multihop-xcm-ibc";
 
+        cargo-dex-router-tests = cargo-package-tests "pallet-dex-router";
+
         benchmarks-check = crane.nightly.cargoBuild
           (systemCommonRust.common-attrs // {
             cargoArtifacts = self'.packages.common-deps-nightly;